@@ -77,9 +77,9 @@ check_requirements() {
     
     local missing=()
     local tools=(
-        "rustc" "cargo" "nasm" "grub-mkrescue"
+        "rustc" "cargo" "grub-mkrescue"
         "xorriso" "qemu-system-x86_64" "mtools"
-        "git" "make" "gcc" "ld"
+        "git" "make" "gcc" "ld" "python3" "nm" "objdump" "objcopy"
     )
     
     for tool in "${tools[@]}"; do
@@ -99,7 +99,6 @@ check_requirements() {
         echo "  sudo apt update && sudo apt upgrade -y"
         echo "  sudo apt install -y \\"
         echo "    build-essential \\"
-        echo "    nasm \\"
         echo "    grub-pc-bin \\"
         echo "    grub-efi-amd64-bin \\"
         echo "    xorriso \\"
@@ -121,14 +120,13 @@ check_requirements() {
     
     print_success "جميع المتطلبات مثبتة"
     print_info "   Rust: $rust_version"
-    print_info "   NASM: $(nasm --version | head -n1)"
 }
 
 setup_environment() {
     print_step "⚙️ إعداد بيئة البناء..."
     
     # إنشاء مجلدات البناء
-    mkdir -p build/{iso,boot,initrd,modules}
+    mkdir -p build/{iso/boot/grub,initrd,modules}
     mkdir -p target/{release,debug}
     
     # نسخ الأصول
@@ -138,46 +136,25 @@ setup_environment() {
     print_success "تم إعداد البيئة"
 }
 
-build_bootloader() {
-    print_step "🔨 بناء برنامج الإقلاع..."
-    
-    cd src/boot
-    
-    # تجميع ملفات Assembly
-    nasm -f elf64 multiboot_header.asm -o ../build/boot/multiboot.o
-    nasm -f elf64 boot.asm -o ../build/boot/boot.o
-    
-    # الرابط
-    ld -n -T linker.ld -o ../build/boot/boot.bin \
-        ../build/boot/multiboot.o \
-        ../build/boot/boot.o
-    
-    cd ../..
-    
-    # التحقق من الملف
-    if [ -f "build/boot/boot.bin" ]; then
-        print_success "تم بناء برنامج الإقلاع"
-        print_info "   الحجم: $(stat -c%s build/boot/boot.bin) بايت"
-    else
-        print_error "فشل بناء برنامج الإقلاع"
-        exit 1
-    fi
-}
-
 build_kernel() {
     print_step "🏗️ بناء نواة النظام..."
     
     cd src/kernel
     
-    # بناء النواة بإعدادات الإصدار
-    export RUSTFLAGS="-C link-arg=-T../boot/linker.ld"
+    # بناء النواة مكتبة ساكنة بإعدادات الإصدار، مع مؤشرات الإطار لتتبع المكدس
+    # عند الذعر والرموز محفوظة حتى يُولَّد منها جدول .ksyms ثم تُحذف
+    export RUSTFLAGS="-C force-frame-pointers=yes"
+    export CARGO_PROFILE_RELEASE_STRIP=false
     
-    if cargo build --release --target x86_64-unknown-none &> build.log; then
+    if cargo rustc --release --target x86_64-unknown-none --lib --crate-type staticlib &> build.log; then
         print_success "تم بناء النواة بنجاح"
         
-        # نسخ النواة
-        cp target/x86_64-unknown-none/release/libkernel.a \
-            ../../build/iso/boot/kernel.bin
+        # ربط الأرشيف في صورة ELF (شفرة الدخول وترويسة Multiboot2 فيه)
+        ld -T ../boot/linker.ld -o ../../build/iso/boot/kernel.bin \
+            ../../target/x86_64-unknown-none/release/libkernel.a
+        
+        # تضمين جدول الرموز في القسم .ksyms
+        python3 ../../scripts/gen-ksyms.py ../../build/iso/boot/kernel.bin --strip
         
         print_info "   الحجم: $(stat -c%s ../../build/iso/boot/kernel.bin) بايت"
     else
@@ -281,6 +258,32 @@ EOF
 ╚══════════════════════════════════════════════════════╝
 EOF
     
+    # قاعدة الحسابات: مدير وضيف (الصيغة في src/kernel/src/accounts.rs)
+    local admin_password="${ISLAM_ADMIN_PASSWORD:-$(head -c 12 /dev/urandom | base64)}"
+    cat > etc/passwd << EOF
+islam:0:admin:0:/data/home/islam
+guest:65534:guest:0:/data/home/guest
+EOF
+    ADMIN_PASSWORD="$admin_password" python3 - > etc/shadow << 'EOF'
+import hashlib, os
+salt = os.urandom(16)
+digest = hashlib.pbkdf2_hmac("sha256", os.environ["ADMIN_PASSWORD"].encode(), salt, 10000)
+print(f"islam:pbkdf2-sha256$10000${salt.hex()}${digest.hex()}")
+print("guest:")
+EOF
+    chmod 600 etc/shadow
+    if [ -z "${ISLAM_ADMIN_PASSWORD:-}" ]; then
+        print_warning "كلمة مرور المدير islam المولدة: ${admin_password} (حددها بـ ISLAM_ADMIN_PASSWORD)"
+    fi
+    
+    # سجلات التوكنات (src/kernel/src/tokens): رصيد ابتدائي لـ ledger=file
+    # ومفتاح موقّع القسائم لـ ledger=voucher إن حُدد
+    mkdir -p etc/tokens
+    echo "local=150" > etc/tokens/ledger
+    if [ -n "${ISLAM_VOUCHER_KEY:-}" ]; then
+        echo "${ISLAM_VOUCHER_KEY}" > etc/tokens/voucher.pub
+    fi
+    
     # ملفات التكوين
     cp ../../config/* etc/
     
@@ -305,14 +308,14 @@ set menu_color_highlight=black/light-magenta
 menuentry "نظام تشغيل إسلام ${OS_VERSION}" {
     echo "جاري تحميل النواة..."
     multiboot2 /boot/kernel.bin
-    module2 /boot/initrd.img
+    module2 /boot/initrd.img initrd.img
     boot
 }
 
 menuentry "نظام إسلام (وضع الاسترداد)" {
     echo "جاري تحميل وضع الاسترداد..."
     multiboot2 /boot/kernel.bin recovery=1
-    module2 /boot/initrd.img
+    module2 /boot/initrd.img initrd.img
     boot
 }
 
@@ -468,7 +471,6 @@ main() {
     setup_environment
     
     # بناء المكونات
-    build_bootloader
     build_kernel
     build_ai_system
     build_security_system
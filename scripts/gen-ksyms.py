@@ -0,0 +1,100 @@
+#!/usr/bin/env python3
+# 🔎 توليد جدول رموز النواة وتضمينه في القسم .ksyms
+# المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+#
+# الاستخدام: gen-ksyms.py <kernel.elf> [--strip]
+#
+# تحجز النواة القسم .ksyms بحجم ثابت؛ يقرأ هذا السكريبت رموز الدوال بـ nm
+# ويكتب الجدول فوق القسم بـ objcopy دون إعادة الربط. مع --strip تُحذف
+# رموز ELF بعد التضمين لأن الجدول أصبح داخل الصورة.
+#
+# الصيغة (little-endian)، ويجب أن تطابق src/kernel/src/backtrace.rs:
+#   0   "KSYM"          التوقيع
+#   4   u32 الإصدار     1
+#   8   u32 عدد الرموز
+#   12  u32 بداية الأسماء
+#   16  u32 طول الأسماء
+#   32  [u64 العنوان، u32 الحجم، u32 إزاحة الاسم] مرتبة بالعنوان
+#   ثم الأسماء متتالية بلا فواصل (الطول = إزاحة التالي - إزاحة الحالي)
+
+import re
+import struct
+import subprocess
+import sys
+import tempfile
+
+MAGIC = b"KSYM"
+VERSION = 1
+HEADER_SIZE = 32
+ENTRY = struct.Struct("<QII")
+SECTION = ".ksyms"
+
+# لاحقة التجزئة في أسماء Rust: ::h0123456789abcdef
+HASH_SUFFIX = re.compile(r"::h[0-9a-f]{16}$")
+
+
+def section_size(elf):
+    out = subprocess.run(["objdump", "-h", elf], check=True, capture_output=True, text=True).stdout
+    for line in out.splitlines():
+        fields = line.split()
+        if len(fields) > 2 and fields[1] == SECTION:
+            return int(fields[2], 16)
+    sys.exit(f"❌ القسم {SECTION} غير موجود في {elf}")
+
+
+def symbols(elf):
+    out = subprocess.run(
+        ["nm", "--numeric-sort", "--defined-only", "--print-size", "--demangle", elf],
+        check=True, capture_output=True, text=True,
+    ).stdout
+
+    result = {}
+    for line in out.splitlines():
+        fields = line.split(" ", 3)
+        if len(fields) != 4 or fields[2] not in "tTwW":
+            continue
+        address, size = int(fields[0], 16), int(fields[1], 16)
+        if size == 0:
+            continue
+        result[address] = (size, HASH_SUFFIX.sub("", fields[3]))
+    return sorted((a, s, n) for a, (s, n) in result.items())
+
+
+def encode(table):
+    names = bytearray()
+    entries = bytearray()
+    for address, size, name in table:
+        entries += ENTRY.pack(address, size, len(names))
+        names += name.encode()
+
+    strings_offset = HEADER_SIZE + len(entries)
+    header = struct.pack("<4sIIII", MAGIC, VERSION, len(table), strings_offset, len(names))
+    return header.ljust(HEADER_SIZE, b"\0") + entries + names
+
+
+def main():
+    if len(sys.argv) < 2:
+        sys.exit("الاستخدام: gen-ksyms.py <kernel.elf> [--strip]")
+
+    elf = sys.argv[1]
+    capacity = section_size(elf)
+    table = symbols(elf)
+    blob = encode(table)
+
+    if len(blob) > capacity:
+        sys.exit(f"❌ جدول الرموز ({len(blob)} بايت) أكبر من القسم {SECTION} ({capacity} بايت); "
+                 "زد KSYMS_CAPACITY في backtrace.rs")
+
+    with tempfile.NamedTemporaryFile(suffix=".ksyms") as tmp:
+        tmp.write(blob.ljust(capacity, b"\0"))
+        tmp.flush()
+        subprocess.run(["objcopy", "--update-section", f"{SECTION}={tmp.name}", elf], check=True)
+
+    if "--strip" in sys.argv[2:]:
+        subprocess.run(["objcopy", "--strip-all", elf], check=True)
+
+    print(f"✓ {len(table)} رمزاً ({len(blob)}/{capacity} بايت) في {elf}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,43 @@
+#!/bin/bash
+# 🧪 إنشاء صور الأقراص التي تختبر عليها تعريفات ext2 و FAT32
+# المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+#
+# يتطلب: e2fsprogs و dosfstools و mtools
+# الناتج: src/kernel/src/fs/testdata/{ext2,fat32}.img.gz
+
+set -euo pipefail
+
+OUT="$(cd "$(dirname "$0")/.." && pwd)/src/kernel/src/fs/testdata"
+WORK=$(mktemp -d)
+trap 'rm -rf "$WORK"' EXIT
+
+# محتوى نمطي: 20000 بايت تتجاوز الكتل المباشرة في ext2 بكتل 1 كيلوبايت
+pattern() {
+    python3 -c "import sys; sys.stdout.buffer.write(bytes((i * 7) % 251 for i in range($1)))"
+}
+
+# ext2: كتل 1024 بايت، 128 عقدة، 512 كيلوبايت
+mkdir -p "$WORK/ext2/docs"
+printf 'Assalamu alaikum from ext2\n' > "$WORK/ext2/hello.txt"
+printf 'docs readme\n' > "$WORK/ext2/docs/readme.md"
+pattern 20000 > "$WORK/ext2/big.bin"
+ln -s hello.txt "$WORK/ext2/link"
+mke2fs -q -t ext2 -b 1024 -N 128 -L islam-test -E root_owner=0:0 \
+    -d "$WORK/ext2" "$WORK/ext2.img" 512K
+e2fsck -fn "$WORK/ext2.img"
+
+# FAT32: عنقود بقطاع واحد، 1 ميجابايت (أقل من الحد الموصى به لكن لينكس يقبله)
+mkfs.fat -F 32 -S 512 -s 1 -R 32 -n ISLAM-TEST -C "$WORK/fat32.img" 1024
+printf 'Assalamu alaikum from FAT32\n' > "$WORK/HELLO.TXT"
+printf 'long name content\n' > "$WORK/Long File Name.txt"
+printf 'notes in a subdirectory\n' > "$WORK/notes.txt"
+pattern 3000 > "$WORK/BIG.BIN"
+mcopy -i "$WORK/fat32.img" "$WORK/HELLO.TXT" "$WORK/Long File Name.txt" ::/
+mmd -i "$WORK/fat32.img" ::/DOCS
+mcopy -i "$WORK/fat32.img" "$WORK/notes.txt" ::/DOCS/
+mcopy -i "$WORK/fat32.img" "$WORK/BIG.BIN" ::/
+fsck.fat -n "$WORK/fat32.img"
+
+gzip -9n < "$WORK/ext2.img" > "$OUT/ext2.img.gz"
+gzip -9n < "$WORK/fat32.img" > "$OUT/fat32.img.gz"
+echo "✅ $OUT"
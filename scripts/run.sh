@@ -0,0 +1,48 @@
+#!/bin/bash
+# 🚀 تشغيل نظام تشغيل إسلام على QEMU
+# المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+#
+# الاستخدام: ./scripts/run.sh [--debug]
+# المتغيرات: SMP (عدد المعالجات، 4 افتراضياً) و MEMORY (512M افتراضياً)
+#           NET: user (افتراضياً، المنفذ 7007 على المضيف يصل إلى خدمة الصدى
+#           مع net.echo=1)، أو tap (الجهاز TAP، tap0 افتراضياً)، أو none
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+ISO="$ROOT/islam-os-0.1.0.iso"
+SMP="${SMP:-4}"
+MEMORY="${MEMORY:-512M}"
+NET="${NET:-user}"
+
+if [ ! -f "$ISO" ]; then
+    echo "لا توجد صورة $ISO؛ شغّل ./scripts/build.sh أولاً" >&2
+    exit 1
+fi
+
+# المعالجات التطبيقية تُشغَّل من MADT، وسجل النواة على المنفذ التسلسلي
+ARGS=(
+    -cdrom "$ISO"
+    -smp "$SMP"
+    -m "$MEMORY"
+    -serial stdio
+    -no-reboot
+)
+
+# virtio-net-pci انتقالي افتراضياً، فيه BAR المنافذ الذي يستخدمه التعريف
+case "$NET" in
+    user) ARGS+=(-netdev "user,id=net0,hostfwd=tcp::7007-:7,hostfwd=udp::7007-:7" -device virtio-net-pci,netdev=net0) ;;
+    tap) ARGS+=(-netdev "tap,id=net0,ifname=${TAP:-tap0},script=no,downscript=no" -device virtio-net-pci,netdev=net0) ;;
+    none) ;;
+    *)
+        echo "NET غير معروف: $NET (user أو tap أو none)" >&2
+        exit 1
+        ;;
+esac
+
+if [ "${1:-}" = "--debug" ]; then
+    ARGS+=(-s -S -d int,cpu_reset -D "$ROOT/build/qemu.log")
+    echo "في انتظار gdb على :1234 (target remote :1234)"
+fi
+
+exec qemu-system-x86_64 "${ARGS[@]}"
@@ -0,0 +1,23 @@
+//! 📚 قاعدة معرفة ذكاء إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! أنواع المعرفة فقط؛ لا معرفة مضمّنة تُحمّل بعد.
+
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IslamicKnowledge {
+    pub topic: String,
+    pub text: String,
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScientificKnowledge {
+    pub field: String,
+    pub fact: String,
+}
+
+/// التحديث الدوري (لا عمل له بعد)
+pub fn update() {}
@@ -0,0 +1,7 @@
+//! 🧠 التعلم من المحادثات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! هيكل فقط: المساعد لا يتعلم من المحادثات بعد.
+
+/// المعالجة الدورية (لا عمل لها بعد)
+pub fn process() {}
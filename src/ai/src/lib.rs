@@ -0,0 +1,31 @@
+//! 🤖 ذكاء إسلام (Zaka Islam) - المساعد الذكي لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! مكتبة `no_std` تربطها النواة وتستدعي مهامها الدورية:
+//!
+//! ```text
+//! zaka_core::start()            عند الإقلاع
+//! knowledge_base::update() و learning::process() و zaka_core::optimize()
+//! ```
+//!
+//! `knowledge_base` و `learning` و `nlp_processor` هياكل دنيا تكفي لبناء
+//! `zaka_core`؛ لا معرفة محملة ولا تصنيف نوايا ولا تعلم فيها بعد.
+
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+#[macro_use]
+extern crate lazy_static;
+
+pub mod knowledge_base;
+pub mod learning;
+pub mod nlp_processor;
+pub mod zaka_core;
+
+mod random;
+
+/// عقد توكن INSAN ورصيده الأدنى (كما في النواة)
+pub const CONTRACT_ADDRESS: &str = "0xa23D57f128Df2517517CA0c195C5159d81324711";
+pub const TOKEN_NAME: &str = "INSAN";
+pub const MIN_TOKENS: u32 = 100;
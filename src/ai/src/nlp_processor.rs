@@ -0,0 +1,32 @@
+//! 🗣️ معالجة النص العربي
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تقطيع السؤال إلى كلمات فقط؛ تصنيف النية غير منفذ بعد.
+
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Intent {
+    IslamicQuestion,
+    TechnicalQuestion,
+    SystemCommand,
+    Greeting,
+    Joke,
+    PrayerTime,
+    QuranVerse,
+    Hadith,
+    Calculation,
+    Unknown,
+}
+
+/// نص بعد المعالجة؛ الكلمات المفتاحية شرائح من الأصل
+#[derive(Debug, Clone)]
+pub struct ProcessedText<'a> {
+    pub original: &'a str,
+    pub keywords: Vec<&'a str>,
+}
+
+pub fn process_arabic(text: &str) -> ProcessedText<'_> {
+    ProcessedText { original: text, keywords: text.split_whitespace().collect() }
+}
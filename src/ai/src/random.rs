@@ -0,0 +1,19 @@
+//! 🎲 اختيار الردود
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! xorshift64 بذرة ثابتة؛ يكفي لتنويع الردود ولا يصلح للتشفير.
+
+use core::ops::RangeTo;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static STATE: AtomicU64 = AtomicU64::new(0x9E37_79B9_7F4A_7C15);
+
+/// رقم في `..end` (بديل `fastrand::usize` بلا std)
+pub fn usize(range: RangeTo<usize>) -> usize {
+    let mut x = STATE.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    STATE.store(x, Ordering::Relaxed);
+    (x % range.end.max(1) as u64) as usize
+}
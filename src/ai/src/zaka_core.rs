@@ -7,12 +7,21 @@ use alloc::vec::Vec;
 use serde::{Serialize, Deserialize};
 use spin::Mutex;
 use crate::knowledge_base::{IslamicKnowledge, ScientificKnowledge};
-use crate::nlp_processor::{process_arabic, Intent};
+use crate::nlp_processor::{process_arabic, Intent, ProcessedText};
+use crate::random;
 
 lazy_static! {
     pub static ref ZAKA_CORE: Mutex<ZakaAI> = Mutex::new(ZakaAI::new());
 }
 
+/// تشغيل المساعد عند الإقلاع
+pub fn start() {
+    ZAKA_CORE.lock().initialize();
+}
+
+/// التحسين الدوري (لا عمل له بعد)
+pub fn optimize() {}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ZakaAI {
     pub name: String,
@@ -25,6 +34,12 @@ pub struct ZakaAI {
     pub user_profiles: BTreeMap<String, UserProfile>,
 }
 
+impl Default for ZakaAI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ZakaAI {
     pub fn new() -> Self {
         Self {
@@ -52,6 +67,13 @@ impl ZakaAI {
         log::info!("📚 قاعدة المعرفة: {} معلومة", self.knowledge_base.total_facts());
     }
     
+    /// الإيقاف عند الإغلاق: لا تعلم بعده، والمحادثات لا تُحفظ فتُنسى
+    pub fn stop(&mut self) {
+        self.is_learning = false;
+        self.conversation_history.clear();
+        log::info!("🤖 أُوقف Zaka Islam");
+    }
+    
     pub fn process_query(&mut self, query: &str, user_id: &str) -> AIResponse {
         // معالجة اللغة الطبيعية
         let processed = process_arabic(query);
@@ -82,8 +104,55 @@ impl ZakaAI {
         response
     }
     
+    /// تصنيف النية غير منفذ بعد
+    fn analyze_intent(&self, _processed: &ProcessedText) -> Intent {
+        Intent::Unknown
+    }
+    
+    fn generate_greeting(&self, _user_id: &str) -> AIResponse {
+        AIResponse {
+            text: "وعليكم السلام ورحمة الله وبركاته\n\n🤖 Zaka Islam".to_string(),
+            intent: Intent::Greeting,
+            confidence: 0.99,
+            sources: vec![],
+        }
+    }
+    
+    fn generate_joke(&self) -> AIResponse {
+        AIResponse {
+            text: "😄 لا نكات محفوظة بعد".to_string(),
+            intent: Intent::Joke,
+            confidence: 0.50,
+            sources: vec![],
+        }
+    }
+    
+    fn generate_default_response(&self, _query: &ProcessedText) -> AIResponse {
+        AIResponse {
+            text: "لم أفهم السؤال تماماً، هل يمكنك توضيحه؟\n\n🤖 Zaka Islam".to_string(),
+            intent: Intent::Unknown,
+            confidence: 0.30,
+            sources: vec![],
+        }
+    }
+    
+    /// لا معرفة محملة بعد
+    fn get_related_knowledge(&self, _query: &ProcessedText) -> String {
+        String::new()
+    }
+    
+    fn get_tafsir(&self, _surah: &str, _ayah: u32) -> String {
+        "راجع كتب التفسير المعتمدة".to_string()
+    }
+    
+    /// سجل المحادثات غير مفعل بعد
+    fn save_conversation(&mut self, _user_id: &str, _query: &str, _response: &str) {}
+    
+    /// التعلم غير منفذ بعد
+    fn learn_from_interaction(&mut self, _query: &str, _response: &str) {}
+    
     fn generate_islamic_response(&self, query: &ProcessedText) -> AIResponse {
-        let responses = vec![
+        let responses = [
             "بسم الله الرحمن الرحيم، الحمد لله رب العالمين".to_string(),
             "قال تعالى: {وَقُلِ اعْمَلُوا فَسَيَرَى اللَّهُ عَمَلَكُمْ وَرَسُولُهُ وَالْمُؤْمِنُونَ}".to_string(),
             "قال رسول الله صلى الله عليه وسلم: {خيركم من تعلم القرآن وعلمه}".to_string(),
@@ -91,7 +160,7 @@ impl ZakaAI {
             "الصلاة عماد الدين، فحافظ عليها يا عبد الله".to_string(),
         ];
         
-        let base = responses[fastrand::usize(..responses.len())].clone();
+        let base = responses[random::usize(..responses.len())].clone();
         
         AIResponse {
             text: format!("{}\n\n{}\n\n🤖 Zaka Islam\n📞 للاستفسارات: +201556328989", base, self.get_related_knowledge(query)),
@@ -106,7 +175,7 @@ impl ZakaAI {
     }
     
     fn generate_technical_response(&self, query: &ProcessedText) -> AIResponse {
-        let response = match query.keywords.get(0) {
+        let response = match query.keywords.first().copied() {
             Some("نظام") | Some("تشغيل") => {
                 "نظام تشغيل إسلام هو نظام تشغيل سيادي مبني من الصفر بلغة Rust.\n\
                  يدعم الذكاء الاصطناعي والأمن المتقدم والدفع الإلكتروني الإسلامي.".to_string()
@@ -168,15 +237,15 @@ impl ZakaAI {
         }
     }
     
-    fn get_quran_verse(&self, query: &ProcessedText) -> AIResponse {
-        let verses = vec![
+    fn get_quran_verse(&self, _query: &ProcessedText) -> AIResponse {
+        let verses = [
             ("البقرة", 255, "اللَّهُ لَا إِلَٰهَ إِلَّا هُوَ الْحَيُّ الْقَيُّومُ..."),
             ("الفاتحة", 1, "بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ"),
             ("العلق", 1, "اقْرَأْ بِاسْمِ رَبِّكَ الَّذِي خَلَقَ"),
             ("النور", 35, "اللَّهُ نُورُ السَّمَاوَاتِ وَالْأَرْضِ..."),
         ];
         
-        let (surah, ayah, text) = verses[fastrand::usize(..verses.len())];
+        let (surah, ayah, text) = verses[random::usize(..verses.len())];
         
         AIResponse {
             text: format!("📖 سورة {} - الآية {}\n{}\n\nتفسير موجز: {}", 
@@ -187,15 +256,15 @@ impl ZakaAI {
         }
     }
     
-    fn get_hadith(&self, query: &ProcessedText) -> AIResponse {
-        let hadiths = vec![
+    fn get_hadith(&self, _query: &ProcessedText) -> AIResponse {
+        let hadiths = [
             ("البخاري", "إنما الأعمال بالنيات..."),
             ("مسلم", "من حسن إسلام المرء تركه ما لا يعنيه"),
             ("الترمذي", "اطلبوا العلم من المهد إلى اللحد"),
             ("أبو داود", "خيركم من تعلم القرآن وعلمه"),
         ];
         
-        let (source, text) = hadiths[fastrand::usize(..hadiths.len())];
+        let (source, text) = hadiths[random::usize(..hadiths.len())];
         
         AIResponse {
             text: format!("📜 حديث {}:\n{}\n\nدرجة الحديث: صحيح", source, text),
@@ -205,7 +274,7 @@ impl ZakaAI {
         }
     }
     
-    fn calculate(&self, query: &ProcessedText) -> AIResponse {
+    fn calculate(&self, _query: &ProcessedText) -> AIResponse {
         // معالجة رياضية بسيطة
         let result = "42"; // نتيجة افتراضية
         
@@ -354,4 +423,28 @@ pub struct LearningPattern {
     pub learning_speed: f32,
     pub retention_rate: f32,
     pub last_learned: u64,
-}
\ No newline at end of file
+}
+
+/// المعرفة المحملة (فارغة حتى تُضمّن معرفة)
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct KnowledgeBase {
+    pub islamic: Vec<IslamicKnowledge>,
+    pub scientific: Vec<ScientificKnowledge>,
+    pub technical: Vec<String>,
+}
+
+impl KnowledgeBase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn load_islamic_knowledge(&mut self) {}
+    
+    pub fn load_scientific_knowledge(&mut self) {}
+    
+    pub fn load_technical_knowledge(&mut self) {}
+    
+    pub fn total_facts(&self) -> usize {
+        self.islamic.len() + self.scientific.len() + self.technical.len()
+    }
+}
@@ -0,0 +1,655 @@
+//! 👥 حسابات المستخدمين والمصادقة والصلاحيات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! قاعدة الحسابات ملفان نصيان على غرار يونكس:
+//!
+//! ```text
+//! /etc/passwd   الاسم:المعرف:الدور:رصيد التوكنات:المجلد الرئيسي
+//! /etc/shadow   الاسم:pbkdf2-sha256$التكرارات$الملح$التجزئة
+//! ```
+//!
+//! نسخة initrd للقراءة فقط وتحمل الحسابات الافتراضية التي ينشئها
+//! `scripts/build.sh`؛ التعديلات تُحفظ في `/data/etc` وتُقدَّم عليها في
+//! الإقلاع التالي، ولا تعديل دون قرص البيانات. المجلدات الرئيسية في `/data/home`.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::sync::atomic::{AtomicU32, Ordering};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use spin::{Mutex, RwLock};
+
+use crate::fs::{self, vfs, FsError};
+use crate::power;
+use crate::utils::decode_hex;
+use crate::utils::sha256::{pbkdf2_sha256, DIGEST_SIZE};
+
+/// ملف الحسابات
+pub const PASSWD: &str = "/etc/passwd";
+
+/// ملف تجزئات كلمات المرور
+pub const SHADOW: &str = "/etc/shadow";
+
+/// الجلسات التي كانت مفتوحة عند آخر إغلاق منظم (على قرص البيانات فقط)
+pub const SAVED_SESSIONS: &str = "/etc/sessions";
+
+/// أصل المجلدات الرئيسية على قرص البيانات
+pub const HOME_ROOT: &str = "/data/home";
+
+/// تكرارات PBKDF2 لكلمات المرور الجديدة
+pub const PBKDF2_ITERATIONS: u32 = 10_000;
+
+const SALT_SIZE: usize = 16;
+
+/// أقصى طول لاسم المستخدم بالبايت
+const MAX_NAME: usize = 32;
+
+/// أول معرف للحسابات العادية
+const FIRST_UID: u32 = 1000;
+
+/// أخطاء المصادقة والصلاحيات
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthError {
+    /// لا يوجد حساب بهذا الاسم
+    UnknownUser,
+    /// كلمة المرور خاطئة
+    BadPassword,
+    /// الدخول إلى الحساب معطل
+    AccountLocked,
+    /// الدور لا يسمح بالعملية
+    PermissionDenied,
+    /// الحساب موجود مسبقاً
+    AlreadyExists,
+    /// اسم مستخدم غير صالح
+    InvalidName,
+    /// الجلسة غير موجودة أو انتهت
+    NoSession,
+    /// خطأ في حفظ القاعدة
+    Fs(FsError),
+}
+
+impl From<FsError> for AuthError {
+    fn from(error: FsError) -> Self {
+        AuthError::Fs(error)
+    }
+}
+
+/// دور المستخدم
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserRole {
+    Admin,
+    User,
+    Guest,
+}
+
+/// العمليات التي تتطلب صلاحية
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    /// إنشاء الحسابات وحذفها وتغيير أدوارها وكلمات مرور الآخرين
+    ManageUsers,
+    /// تركيب أنظمة الملفات وفكها
+    Mount,
+    /// إعادة التشغيل وإيقاف التشغيل
+    Power,
+    /// إضافة التوكنات إلى أرصدة المستخدمين
+    ManageTokens,
+    /// تغيير كلمة المرور الخاصة
+    ChangeOwnPassword,
+}
+
+impl UserRole {
+    /// سياسة الصلاحيات: المدير يملك كل شيء، والضيف لا يملك شيئاً
+    pub fn allows(self, privilege: Privilege) -> bool {
+        match self {
+            UserRole::Admin => true,
+            UserRole::User => privilege == Privilege::ChangeOwnPassword,
+            UserRole::Guest => false,
+        }
+    }
+    
+    fn as_str(self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::User => "user",
+            UserRole::Guest => "guest",
+        }
+    }
+    
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "admin" => Some(UserRole::Admin),
+            "user" => Some(UserRole::User),
+            "guest" => Some(UserRole::Guest),
+            _ => None,
+        }
+    }
+}
+
+/// مستخدم النظام (كما يظهر في `SystemState`)
+#[derive(Debug, Clone)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+    pub role: UserRole,
+    pub token_balance: u32,
+}
+
+/// تجزئة كلمة مرور مملحة
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordHash {
+    /// بلا كلمة مرور (حسابات الضيوف)
+    Empty,
+    /// الدخول معطل
+    Locked,
+    Pbkdf2 {
+        iterations: u32,
+        salt: [u8; SALT_SIZE],
+        hash: [u8; DIGEST_SIZE],
+    },
+}
+
+impl PasswordHash {
+    /// تجزئة كلمة مرور بملح عشوائي جديد
+    pub fn new(password: &str) -> Self {
+        let mut salt = [0u8; SALT_SIZE];
+        for chunk in salt.chunks_mut(8) {
+            chunk.copy_from_slice(&crate::arch::cpu::random_u64().to_le_bytes());
+        }
+        
+        let mut hash = [0u8; DIGEST_SIZE];
+        pbkdf2_sha256(password.as_bytes(), &salt, PBKDF2_ITERATIONS, &mut hash);
+        PasswordHash::Pbkdf2 { iterations: PBKDF2_ITERATIONS, salt, hash }
+    }
+    
+    /// التحقق من كلمة مرور (المقارنة بزمن ثابت)
+    pub fn verify(&self, password: &str) -> bool {
+        match self {
+            PasswordHash::Empty => password.is_empty(),
+            PasswordHash::Locked => false,
+            PasswordHash::Pbkdf2 { iterations, salt, hash } => {
+                let mut candidate = [0u8; DIGEST_SIZE];
+                pbkdf2_sha256(password.as_bytes(), salt, *iterations, &mut candidate);
+                candidate.iter().zip(hash).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+            }
+        }
+    }
+    
+    fn parse(field: &str) -> Option<Self> {
+        if field.is_empty() {
+            return Some(PasswordHash::Empty);
+        }
+        if field.starts_with('!') || field.starts_with('*') {
+            return Some(PasswordHash::Locked);
+        }
+        
+        let mut parts = field.split('$');
+        if parts.next()? != "pbkdf2-sha256" {
+            return None;
+        }
+        let iterations = parts.next()?.parse().ok().filter(|&n| n > 0)?;
+        let salt = decode_hex(parts.next()?)?;
+        let hash = decode_hex(parts.next()?)?;
+        parts.next().is_none().then_some(PasswordHash::Pbkdf2 { iterations, salt, hash })
+    }
+}
+
+impl fmt::Display for PasswordHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordHash::Empty => Ok(()),
+            PasswordHash::Locked => f.write_str("!"),
+            PasswordHash::Pbkdf2 { iterations, salt, hash } => {
+                write!(f, "pbkdf2-sha256${}$", iterations)?;
+                salt.iter().try_for_each(|b| write!(f, "{:02x}", b))?;
+                f.write_str("$")?;
+                hash.iter().try_for_each(|b| write!(f, "{:02x}", b))
+            }
+        }
+    }
+}
+
+/// حساب في القاعدة
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub user: User,
+    pub home: String,
+    pub password: PasswordHash,
+}
+
+impl Account {
+    pub fn new(name: &str, id: u32, role: UserRole, password: PasswordHash) -> Self {
+        Account {
+            user: User { id, name: name.to_string(), role, token_balance: 0 },
+            home: format!("{}/{}", HOME_ROOT, name),
+            password,
+        }
+    }
+}
+
+/// قاعدة الحسابات في الذاكرة
+#[derive(Debug, Clone, Default)]
+pub struct Database {
+    accounts: Vec<Account>,
+}
+
+impl Database {
+    /// تحليل محتوى `passwd` و `shadow`؛ الحساب بلا سطر في `shadow` يبقى مقفلاً
+    pub fn parse(passwd: &str, shadow: &str) -> Self {
+        let hashes: BTreeMap<&str, PasswordHash> = records(shadow)
+            .filter_map(|fields| Some((*fields.first()?, PasswordHash::parse(fields.get(1)?)?)))
+            .collect();
+        
+        let mut database = Database::default();
+        for fields in records(passwd) {
+            let &[name, id, role, tokens, home] = fields.as_slice() else {
+                warn!("⚠️ سطر غير صالح في {}: {}", PASSWD, fields.join(":"));
+                continue;
+            };
+            let (Ok(id), Some(role), Ok(token_balance)) = (id.parse(), UserRole::parse(role), tokens.parse()) else {
+                warn!("⚠️ حساب غير صالح في {}: {}", PASSWD, name);
+                continue;
+            };
+            
+            let password = hashes.get(name).cloned().unwrap_or(PasswordHash::Locked);
+            let user = User { id, name: name.to_string(), role, token_balance };
+            if database.insert(Account { user, home: home.to_string(), password }).is_err() {
+                warn!("⚠️ حساب مكرر في {}: {}", PASSWD, name);
+            }
+        }
+        database
+    }
+    
+    /// قاعدة الإقلاع الأول دون ملفات: مدير مقفل وضيف بلا كلمة مرور
+    pub fn bootstrap() -> Self {
+        let mut database = Database::default();
+        let _ = database.insert(Account::new("islam", 0, UserRole::Admin, PasswordHash::Locked));
+        let _ = database.insert(Account::new("guest", 65534, UserRole::Guest, PasswordHash::Empty));
+        database
+    }
+    
+    pub fn passwd(&self) -> String {
+        self.accounts.iter()
+            .map(|a| format!("{}:{}:{}:{}:{}\n", a.user.name, a.user.id, a.user.role.as_str(), a.user.token_balance, a.home))
+            .collect()
+    }
+    
+    pub fn shadow(&self) -> String {
+        self.accounts.iter().map(|a| format!("{}:{}\n", a.user.name, a.password)).collect()
+    }
+    
+    pub fn find(&self, name: &str) -> Option<&Account> {
+        self.accounts.iter().find(|a| a.user.name == name)
+    }
+    
+    fn find_mut(&mut self, name: &str) -> Result<&mut Account, AuthError> {
+        self.accounts.iter_mut().find(|a| a.user.name == name).ok_or(AuthError::UnknownUser)
+    }
+    
+    pub fn insert(&mut self, account: Account) -> Result<(), AuthError> {
+        if self.find(&account.user.name).is_some() {
+            return Err(AuthError::AlreadyExists);
+        }
+        self.accounts.push(account);
+        Ok(())
+    }
+    
+    pub fn remove(&mut self, name: &str) -> Option<Account> {
+        let index = self.accounts.iter().position(|a| a.user.name == name)?;
+        Some(self.accounts.remove(index))
+    }
+    
+    /// أول معرف حر بعد أكبر معرف عادي
+    pub fn next_uid(&self) -> u32 {
+        self.accounts.iter()
+            .map(|a| a.user.id)
+            .filter(|id| (FIRST_UID..65534).contains(id))
+            .max()
+            .map_or(FIRST_UID, |id| id + 1)
+    }
+    
+    pub fn users(&self) -> Vec<User> {
+        self.accounts.iter().map(|a| a.user.clone()).collect()
+    }
+    
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+/// أسطر الملف مقسمة بـ `:` دون التعليقات والأسطر الفارغة
+fn records(text: &str) -> impl Iterator<Item = Vec<&str>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split(':').collect())
+}
+
+/// اسم صالح: حروف وأرقام (بأي لغة) و `_` و `-` و `.` دون أن يبدأ بشرطة
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME
+        && !name.starts_with(['-', '.'])
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// معرف جلسة دخول
+pub type SessionId = u32;
+
+/// جلسة دخول نشطة
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: SessionId,
+    pub uid: u32,
+    pub name: String,
+    pub role: UserRole,
+    pub home: String,
+    /// تكة بدء الجلسة
+    pub started: u64,
+}
+
+lazy_static! {
+    static ref DATABASE: RwLock<Database> = RwLock::new(Database::default());
+    static ref SESSIONS: Mutex<BTreeMap<SessionId, Session>> = Mutex::new(BTreeMap::new());
+}
+
+static NEXT_SESSION: AtomicU32 = AtomicU32::new(1);
+
+/// تحميل قاعدة الحسابات من القرص أو initrd
+pub fn init() {
+    let database = load().unwrap_or_else(|| {
+        warn!("⚠️ لا توجد قاعدة حسابات في {}: حساب مدير مقفل وحساب ضيف فقط", PASSWD);
+        Database::bootstrap()
+    });
+    
+    info!("👥 {} حساباً في قاعدة المستخدمين", database.len());
+    replace(database);
+    report_saved_sessions();
+    power::register("sessions", power::priority::SESSIONS, power::DEFAULT_BUDGET_TICKS, save_on_shutdown);
+}
+
+/// خطاف الإغلاق لحفظ الجلسات
+fn save_on_shutdown(_: &power::HookContext) -> Result<(), String> {
+    let count = save_sessions().map_err(|e| format!("{:?}", e))?;
+    info!("👥 حفظ {} جلسة مفتوحة", count);
+    Ok(())
+}
+
+/// إعلام المستخدمين بجلسات قطعها الإغلاق السابق؛ لا تُستعاد دون كلمة مرور
+fn report_saved_sessions() {
+    let path = data_path(SAVED_SESSIONS);
+    let Ok(saved) = vfs::read_to_string(&path) else {
+        return;
+    };
+    
+    for fields in records(&saved) {
+        if let [id, name, ..] = fields.as_slice() {
+            info!("👥 الجلسة {} للمستخدم {} أُغلقت مع النظام، يلزم دخول جديد", id, name);
+        }
+    }
+    if fs::data_available() {
+        let _ = vfs::remove(&path);
+    }
+}
+
+/// حفظ الجلسات المفتوحة قبل الإغلاق المنظم وإعادة عددها
+pub fn save_sessions() -> Result<usize, AuthError> {
+    let open = sessions();
+    if open.is_empty() {
+        return Ok(0);
+    }
+    if !fs::data_available() {
+        return Err(AuthError::Fs(FsError::NotMounted));
+    }
+    
+    let mut text = String::new();
+    for session in &open {
+        text.push_str(&format!("{}:{}:{}:{}\n", session.id, session.name, session.uid, session.started));
+    }
+    
+    let etc = data_path("/etc");
+    if !vfs::exists(&etc) {
+        vfs::create_dir(&etc)?;
+    }
+    vfs::write_file(&data_path(SAVED_SESSIONS), text.as_bytes())?;
+    vfs::sync_all()?;
+    Ok(open.len())
+}
+
+/// مسار النسخة القابلة للكتابة من ملف في `/etc`
+fn data_path(path: &str) -> String {
+    format!("{}{}", fs::DATA_MOUNT, path)
+}
+
+fn load() -> Option<Database> {
+    for (passwd, shadow) in [(data_path(PASSWD), data_path(SHADOW)), (PASSWD.to_string(), SHADOW.to_string())] {
+        if let Ok(users) = vfs::read_to_string(&passwd) {
+            info!("👥 تحميل الحسابات من {}", passwd);
+            let hashes = vfs::read_to_string(&shadow).unwrap_or_default();
+            return Some(Database::parse(&users, &hashes));
+        }
+    }
+    None
+}
+
+/// استبدال القاعدة كلها وإنهاء كل الجلسات
+pub fn replace(database: Database) {
+    *DATABASE.write() = database;
+    SESSIONS.lock().clear();
+    publish();
+}
+
+/// نسخ قائمة المستخدمين إلى `SystemState`
+fn publish() {
+    let users = DATABASE.read().users();
+    crate::SYSTEM_STATE.lock().users = users;
+}
+
+/// حفظ القاعدة في `/data/etc` بدل `previous`
+///
+/// `shadow` أولاً: إن انقطع الحفظ بعده فالحساب الجديد بلا سطر في `passwd` لا
+/// يظهر، والحساب الذي فقد سطره في `shadow` يُحمَّل مقفلاً. إن فشلت الكتابة
+/// يُعاد الملفان إلى `previous`. دون قرص البيانات يفشل الحفظ فلا يُطبَّق التعديل.
+fn save(previous: &Database, database: &Database) -> Result<(), AuthError> {
+    if !fs::data_available() {
+        return Err(AuthError::Fs(FsError::NotMounted));
+    }
+    
+    let etc = data_path("/etc");
+    if !vfs::exists(&etc) {
+        vfs::create_dir(&etc)?;
+    }
+    if let Err(e) = write_database(database) {
+        if let Err(restore) = write_database(previous) {
+            warn!("⚠️ تعذرت استعادة قاعدة الحسابات السابقة: {:?}", restore);
+        }
+        return Err(e.into());
+    }
+    vfs::sync_all()?;
+    Ok(())
+}
+
+/// كتابة `shadow` ثم `passwd`
+fn write_database(database: &Database) -> Result<(), FsError> {
+    vfs::write_file(&data_path(SHADOW), database.shadow().as_bytes())?;
+    vfs::write_file(&data_path(PASSWD), database.passwd().as_bytes())
+}
+
+/// إنشاء المجلد الرئيسي إن أمكن
+fn ensure_home(home: &str) {
+    if !fs::data_available() || !home.starts_with(HOME_ROOT) || vfs::exists(home) {
+        return;
+    }
+    
+    let root = if vfs::exists(HOME_ROOT) { Ok(()) } else { vfs::create_dir(HOME_ROOT) };
+    match root.and_then(|_| vfs::create_dir(home)) {
+        Ok(()) => info!("🏠 إنشاء المجلد الرئيسي {}", home),
+        Err(e) => warn!("⚠️ تعذر إنشاء المجلد الرئيسي {}: {:?}", home, e),
+    }
+}
+
+/// تسجيل الدخول وبدء جلسة
+pub fn login(name: &str, password: &str) -> Result<SessionId, AuthError> {
+    let session = {
+        let database = DATABASE.read();
+        let account = database.find(name).ok_or(AuthError::UnknownUser)?;
+        
+        if account.password == PasswordHash::Locked {
+            warn!("🔒 محاولة دخول إلى حساب مقفل: {}", name);
+            return Err(AuthError::AccountLocked);
+        }
+        if !account.password.verify(password) {
+            warn!("🔑 كلمة مرور خاطئة للمستخدم {}", name);
+            return Err(AuthError::BadPassword);
+        }
+        
+        Session {
+            id: NEXT_SESSION.fetch_add(1, Ordering::SeqCst),
+            uid: account.user.id,
+            name: account.user.name.clone(),
+            role: account.user.role,
+            home: account.home.clone(),
+            started: crate::process::scheduler::ticks(),
+        }
+    };
+    
+    let id = session.id;
+    info!("🔑 دخول {} ({:?}) في الجلسة {}", name, session.role, id);
+    ensure_home(&session.home);
+    SESSIONS.lock().insert(id, session);
+    Ok(id)
+}
+
+/// إنهاء جلسة
+pub fn logout(session: SessionId) -> Result<(), AuthError> {
+    let session = SESSIONS.lock().remove(&session).ok_or(AuthError::NoSession)?;
+    info!("👋 خروج {} من الجلسة {}", session.name, session.id);
+    Ok(())
+}
+
+/// بيانات جلسة نشطة
+pub fn session(id: SessionId) -> Option<Session> {
+    SESSIONS.lock().get(&id).cloned()
+}
+
+/// كل الجلسات النشطة
+pub fn sessions() -> Vec<Session> {
+    SESSIONS.lock().values().cloned().collect()
+}
+
+/// التحقق من أن دور الجلسة يسمح بالعملية
+pub fn authorize(session: SessionId, privilege: Privilege) -> Result<Session, AuthError> {
+    let session = self::session(session).ok_or(AuthError::NoSession)?;
+    if !session.role.allows(privilege) {
+        warn!("⛔ رُفضت {:?} للمستخدم {} ({:?})", privilege, session.name, session.role);
+        return Err(AuthError::PermissionDenied);
+    }
+    Ok(session)
+}
+
+/// قائمة المستخدمين
+pub fn users() -> Vec<User> {
+    DATABASE.read().users()
+}
+
+/// تعديل نسخة من القاعدة وحفظها، ثم استبدال الحية بها عند نجاح الحفظ وحده
+fn modify<T>(change: impl FnOnce(&mut Database) -> Result<T, AuthError>) -> Result<T, AuthError> {
+    let mut database = DATABASE.write();
+    let mut updated = database.clone();
+    let result = change(&mut updated)?;
+    save(&database, &updated)?;
+    *database = updated;
+    drop(database);
+    publish();
+    Ok(result)
+}
+
+/// إنشاء حساب (للمدير فقط)؛ كلمة المرور الفارغة مسموحة للضيوف وحدهم
+pub fn add_user(session: SessionId, name: &str, password: &str, role: UserRole) -> Result<u32, AuthError> {
+    let admin = authorize(session, Privilege::ManageUsers)?;
+    if !valid_name(name) {
+        return Err(AuthError::InvalidName);
+    }
+    
+    let password = match (password.is_empty(), role) {
+        (true, UserRole::Guest) => PasswordHash::Empty,
+        (true, _) => PasswordHash::Locked,
+        (false, _) => PasswordHash::new(password),
+    };
+    
+    let account = modify(|database| {
+        let account = Account::new(name, database.next_uid(), role, password);
+        database.insert(account.clone())?;
+        Ok(account)
+    })?;
+    
+    info!("👤 {} أنشأ الحساب {} ({:?}، المعرف {})", admin.name, name, role, account.user.id);
+    ensure_home(&account.home);
+    Ok(account.user.id)
+}
+
+/// حذف حساب (للمدير فقط)؛ المجلد الرئيسي يبقى كما هو
+pub fn remove_user(session: SessionId, name: &str) -> Result<(), AuthError> {
+    let admin = authorize(session, Privilege::ManageUsers)?;
+    if admin.name == name {
+        return Err(AuthError::PermissionDenied);
+    }
+    
+    modify(|database| database.remove(name).map(|_| ()).ok_or(AuthError::UnknownUser))?;
+    SESSIONS.lock().retain(|_, s| s.name != name);
+    info!("👤 {} حذف الحساب {}", admin.name, name);
+    Ok(())
+}
+
+/// تغيير دور مستخدم (للمدير فقط)
+pub fn set_role(session: SessionId, name: &str, role: UserRole) -> Result<(), AuthError> {
+    let admin = authorize(session, Privilege::ManageUsers)?;
+    
+    modify(|database| {
+        database.find_mut(name)?.user.role = role;
+        Ok(())
+    })?;
+    
+    // الجلسات المفتوحة تأخذ الدور الجديد فوراً
+    for open in SESSIONS.lock().values_mut().filter(|s| s.name == name) {
+        open.role = role;
+    }
+    info!("👤 {} غيّر دور {} إلى {:?}", admin.name, name, role);
+    Ok(())
+}
+
+/// تغيير كلمة المرور: كلمة المستخدم نفسه، أو أي كلمة للمدير
+pub fn set_password(session: SessionId, name: &str, password: &str) -> Result<(), AuthError> {
+    let current = self::session(session).ok_or(AuthError::NoSession)?;
+    let privilege = if current.name == name { Privilege::ChangeOwnPassword } else { Privilege::ManageUsers };
+    authorize(session, privilege)?;
+    
+    let hash = if password.is_empty() { PasswordHash::Locked } else { PasswordHash::new(password) };
+    modify(|database| {
+        database.find_mut(name)?.password = hash;
+        Ok(())
+    })?;
+    
+    info!("🔑 تغيير كلمة مرور {}", name);
+    Ok(())
+}
+
+/// إضافة توكنات إلى رصيد مستخدم (للمدير فقط)
+pub fn credit_tokens(session: SessionId, name: &str, amount: u32) -> Result<u32, AuthError> {
+    authorize(session, Privilege::ManageTokens)?;
+    
+    let balance = modify(|database| {
+        let user = &mut database.find_mut(name)?.user;
+        user.token_balance = user.token_balance.saturating_add(amount);
+        Ok(user.token_balance)
+    })?;
+    
+    info!("💰 {} {} إلى {}، الرصيد: {}", amount, crate::TOKEN_NAME, name, balance);
+    Ok(balance)
+}
@@ -0,0 +1,561 @@
+//! ⚡ إدارة الطاقة عبر ACPI
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الجداول تُقرأ بمكتبة `acpi` بدءاً من RSDP الذي يمرره GRUB (أو بالبحث في
+//! ذاكرة BIOS). من FADT نأخذ مسجلات PM1 للإطفاء وزر الطاقة ومسجل إعادة
+//! التعيين، ومن DSDT قيمة `SLP_TYPx` لحالة S5، ومن MADT متحكمات المقاطعات
+//! والمعالجات. لا نفسر AML كاملاً: يكفي البحث عن الحزمة `_S5_`.
+//!
+//! في وضع المحاكاة تُسجَّل كتابات المنافذ في الذاكرة بدلاً من العتاد.
+
+use alloc::vec::Vec;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use log::{info, warn};
+use spin::Mutex;
+
+use ::acpi::fadt::Fadt;
+use ::acpi::platform::address::{AddressSpace, GenericAddress};
+use ::acpi::sdt::Signature;
+use ::acpi::{AcpiHandler, AcpiTables, InterruptModel, PhysicalMapping};
+
+use crate::boot;
+
+/// بت تفعيل وضع ACPI في PM1_CNT
+const SCI_EN: u16 = 1 << 0;
+/// بتات نوع السكون في PM1_CNT
+const SLP_TYP_SHIFT: u16 = 10;
+const SLP_TYP_MASK: u16 = 0b111 << SLP_TYP_SHIFT;
+/// بت الدخول في السكون في PM1_CNT
+const SLP_EN: u16 = 1 << 13;
+/// بت زر الطاقة في مسجلي الحالة والتمكين PM1_STS/PM1_EN
+const PWRBTN: u16 = 1 << 8;
+
+/// خط IRQ المعتاد لمقاطعة SCI على متحكم 8259
+const LEGACY_SCI_IRQ: u16 = 9;
+
+/// أخطاء قراءة الجداول
+#[derive(Debug)]
+pub enum AcpiError {
+    /// لا RSDP من المحمل ولا في ذاكرة BIOS
+    NoRsdp,
+    MissingFadt,
+    /// مسجل في فضاء عناوين غير مدعوم (ليس منفذ إدخال/إخراج)
+    UnsupportedRegister,
+    Tables(::acpi::AcpiError),
+}
+
+impl From<::acpi::AcpiError> for AcpiError {
+    fn from(e: ::acpi::AcpiError) -> Self {
+        AcpiError::Tables(e)
+    }
+}
+
+/// مسجل عام: منفذ أو ذاكرة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    Io(u16),
+    Memory(u64),
+}
+
+/// مسجلات الطاقة الثابتة من FADT و DSDT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerRegisters {
+    pub pm1a_control: u16,
+    pub pm1b_control: Option<u16>,
+    /// كتلة الأحداث: نصفها الأول للحالة والثاني للتمكين
+    pub pm1a_event: u16,
+    pub pm1b_event: Option<u16>,
+    pub event_length: u8,
+    /// `SLP_TYPa` و `SLP_TYPb` لحالة S5
+    pub s5_sleep_type: Option<(u8, u8)>,
+    /// مسجل إعادة التعيين وقيمته إن دعمه النظام
+    pub reset: Option<(Register, u8)>,
+    pub sci_interrupt: u16,
+    pub smi_command: u16,
+    pub acpi_enable: u8,
+}
+
+/// متحكم I/O APIC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoApic {
+    pub id: u8,
+    pub address: u32,
+    pub gsi_base: u32,
+}
+
+/// متحكمات المقاطعات من MADT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterruptControllers {
+    pub local_apic: u64,
+    pub io_apics: Vec<IoApic>,
+    /// تحويلات ISA: (خط IRQ، المقاطعة العامة GSI)
+    pub overrides: Vec<(u8, u32)>,
+    pub legacy_pics: bool,
+}
+
+/// معالج مفعل من MADT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Processor {
+    pub uid: u32,
+    pub apic_id: u32,
+    /// معالج الإقلاع (الوحيد العامل عند دخول النواة)
+    pub is_bsp: bool,
+}
+
+/// ما نحتفظ به من جداول ACPI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcpiInfo {
+    pub revision: u8,
+    pub power: PowerRegisters,
+    pub interrupts: Option<InterruptControllers>,
+    /// المعالجات المفعلة، معالج الإقلاع أولاً (فارغة إن لم يوجد MADT)
+    pub processors: Vec<Processor>,
+}
+
+/// ربط الجداول على عناوينها الفيزيائية
+#[derive(Clone, Copy)]
+struct KernelHandler;
+
+impl AcpiHandler for KernelHandler {
+    unsafe fn map_physical_region<T>(&self, physical_address: usize, size: usize) -> PhysicalMapping<Self, T> {
+        #[cfg(not(feature = "hosted"))]
+        {
+            use x86_64::structures::paging::PageTableFlags;
+            crate::memory::paging::map_physical(physical_address as u64, size as u64, PageTableFlags::NO_EXECUTE)
+                .expect("تعذر ربط جداول ACPI");
+        }
+        
+        // الربط مطابق، وفي المحاكاة "العنوان الفيزيائي" مؤشر في العملية
+        let virtual_start = NonNull::new(physical_address as *mut T).expect("عنوان ACPI صفري");
+        PhysicalMapping::new(physical_address, virtual_start, size, size, *self)
+    }
+    
+    fn unmap_physical_region<T>(_region: &PhysicalMapping<Self, T>) {
+        // الجداول تبقى مربوطة: تُقرأ مرة أخرى عند الإطفاء
+    }
+}
+
+static INFO: Mutex<Option<AcpiInfo>> = Mutex::new(None);
+
+/// منفذ PM1a_STS لمعالج SCI (صفر قبل التهيئة)
+static EVENT_STATUS_PORT: AtomicU16 = AtomicU16::new(0);
+static SCI_WIRED: AtomicBool = AtomicBool::new(false);
+static POWER_BUTTON: AtomicBool = AtomicBool::new(false);
+
+/// قراءة الجداول، تفعيل وضع ACPI وزر الطاقة
+pub fn init() {
+    let info = match discover() {
+        Ok(info) => info,
+        Err(e) => {
+            warn!("⚠️ ACPI غير متاح ({:?})، الإطفاء عبر منفذ QEMU فقط", e);
+            return;
+        }
+    };
+    
+    let power = info.power;
+    info!("⚡ ACPI {}: PM1a_CNT={:#x}، S5={:?}، SCI={}، إعادة التعيين={:?}",
+        info.revision, power.pm1a_control, power.s5_sleep_type, power.sci_interrupt, power.reset);
+    match &info.interrupts {
+        Some(apic) => info!("⚡ MADT: LAPIC عند {:#x}، {} I/O APIC، {} تحويل ISA، {} معالج",
+            apic.local_apic, apic.io_apics.len(), apic.overrides.len(), info.processors.len()),
+        None => info!("⚡ لا MADT: متحكم 8259 فقط"),
+    }
+    
+    enable_acpi_mode(&power);
+    enable_power_button(&power);
+    *INFO.lock() = Some(info);
+}
+
+fn discover() -> Result<AcpiInfo, AcpiError> {
+    let tables = match boot::info().and_then(|info| info.rsdp()) {
+        Some(rsdp) => unsafe { AcpiTables::from_rsdp(KernelHandler, rsdp.address as usize)? },
+        #[cfg(not(feature = "hosted"))]
+        None => unsafe { AcpiTables::search_for_rsdp_bios(KernelHandler).map_err(|_| AcpiError::NoRsdp)? },
+        #[cfg(feature = "hosted")]
+        None => return Err(AcpiError::NoRsdp),
+    };
+    parse(&tables, &KernelHandler)
+}
+
+/// استخراج المسجلات والمتحكمات من جداول مقروءة
+pub fn parse<H: AcpiHandler>(tables: &AcpiTables<H>, handler: &H) -> Result<AcpiInfo, AcpiError> {
+    let fadt = unsafe { tables.get_sdt::<Fadt>(Signature::FADT)? }.ok_or(AcpiError::MissingFadt)?;
+    
+    let pm1a_event = fadt.pm1a_event_block()?;
+    let flags = fadt.flags;
+    let reset_value = fadt.reset_value;
+    let reset = match fadt.reset_register() {
+        Ok(address) if flags.supports_system_reset_via_fadt() => register(&address).map(|r| (r, reset_value)),
+        _ => None,
+    };
+    
+    // DSDT ليس ضمن قائمة XSDT؛ المكتبة تحفظ عنوان شيفرة AML بعد ترويسته
+    let s5_sleep_type = tables.dsdt.as_ref().and_then(|dsdt| {
+        let mapping = unsafe { handler.map_physical_region::<u8>(dsdt.address, dsdt.length as usize) };
+        let aml = unsafe { core::slice::from_raw_parts(mapping.virtual_start().as_ptr(), dsdt.length as usize) };
+        find_s5(aml)
+    });
+    
+    let power = PowerRegisters {
+        pm1a_control: io_port(&fadt.pm1a_control_block()?)?,
+        pm1b_control: fadt.pm1b_control_block()?.map(|address| io_port(&address)).transpose()?,
+        pm1a_event: io_port(&pm1a_event)?,
+        pm1b_event: fadt.pm1b_event_block()?.map(|address| io_port(&address)).transpose()?,
+        event_length: pm1a_event.bit_width / 8,
+        s5_sleep_type,
+        reset,
+        sci_interrupt: fadt.sci_interrupt,
+        smi_command: fadt.smi_cmd_port as u16,
+        acpi_enable: fadt.acpi_enable,
+    };
+    drop(fadt);
+    
+    let (interrupts, processors) = match tables.platform_info() {
+        Ok(platform) => {
+            let processors = platform.processor_info.as_ref()
+                .map(|cpus| {
+                    core::iter::once(&cpus.boot_processor)
+                        .chain(cpus.application_processors.iter())
+                        .filter(|cpu| cpu.state != ::acpi::platform::ProcessorState::Disabled)
+                        .map(|cpu| Processor { uid: cpu.processor_uid, apic_id: cpu.local_apic_id, is_bsp: !cpu.is_ap })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let interrupts = match platform.interrupt_model {
+                InterruptModel::Apic(apic) => Some(InterruptControllers {
+                    local_apic: apic.local_apic_address,
+                    io_apics: apic.io_apics.iter()
+                        .map(|io| IoApic { id: io.id, address: io.address, gsi_base: io.global_system_interrupt_base })
+                        .collect(),
+                    overrides: apic.interrupt_source_overrides.iter()
+                        .map(|o| (o.isa_source, o.global_system_interrupt))
+                        .collect(),
+                    legacy_pics: apic.also_has_legacy_pics,
+                }),
+                _ => None,
+            };
+            (interrupts, processors)
+        }
+        Err(e) => {
+            warn!("⚠️ تعذر تحليل MADT: {:?}", e);
+            (None, Vec::new())
+        }
+    };
+    
+    Ok(AcpiInfo { revision: tables.revision, power, interrupts, processors })
+}
+
+fn register(address: &GenericAddress) -> Option<Register> {
+    match address.address_space {
+        AddressSpace::SystemIo => Some(Register::Io(address.address as u16)),
+        AddressSpace::SystemMemory => Some(Register::Memory(address.address)),
+        _ => None,
+    }
+}
+
+fn io_port(address: &GenericAddress) -> Result<u16, AcpiError> {
+    match register(address) {
+        Some(Register::Io(port)) => Ok(port),
+        _ => Err(AcpiError::UnsupportedRegister),
+    }
+}
+
+/// قيم `SLP_TYPa/b` من `Name(_S5_, Package() {a, b, ...})` في شيفرة AML
+pub fn find_s5(aml: &[u8]) -> Option<(u8, u8)> {
+    let name = aml.windows(4).position(|window| window == b"_S5_")?;
+    
+    // NameOp (0x08) قبل الاسم، مباشرة أو قبل بادئة الجذر '\'
+    let prefixed = match name {
+        0 => false,
+        1 => aml[0] == 0x08,
+        _ => aml[name - 1] == 0x08 || (aml[name - 1] == b'\\' && aml[name - 2] == 0x08),
+    };
+    if !prefixed {
+        return None;
+    }
+    
+    let mut rest = aml.get(name + 4..)?;
+    if *rest.first()? != 0x12 {
+        return None; // ليس PackageOp
+    }
+    
+    // طول الحزمة: البتان العلويان في البايت الأول عدد البايتات الإضافية
+    let length_bytes = 1 + (rest.get(1)? >> 6) as usize;
+    rest = rest.get(1 + length_bytes + 1..)?; // PkgLength ثم NumElements
+    
+    let (a, rest) = package_byte(rest)?;
+    let (b, _) = package_byte(rest)?;
+    Some((a, b))
+}
+
+/// عنصر عددي صغير: BytePrefix قيمة، أو ZeroOp/OneOp
+fn package_byte(aml: &[u8]) -> Option<(u8, &[u8])> {
+    match *aml.first()? {
+        0x0A => Some((*aml.get(1)?, aml.get(2..)?)),
+        value @ (0x00 | 0x01) => Some((value, &aml[1..])),
+        _ => None,
+    }
+}
+
+/// تسليم المسجلات من البرنامج الثابت إن لم يكن وضع ACPI مفعلاً
+fn enable_acpi_mode(power: &PowerRegisters) {
+    if io::read_u16(power.pm1a_control) & SCI_EN != 0 {
+        return;
+    }
+    if power.smi_command == 0 || power.acpi_enable == 0 {
+        warn!("⚠️ وضع ACPI غير مفعل ولا يوجد منفذ SMI لتفعيله");
+        return;
+    }
+    
+    io::write_u8(power.smi_command, power.acpi_enable);
+    for _ in 0..1_000_000 {
+        if io::read_u16(power.pm1a_control) & SCI_EN != 0 {
+            info!("⚡ تم تفعيل وضع ACPI");
+            return;
+        }
+        core::hint::spin_loop();
+    }
+    warn!("⚠️ لم يستجب البرنامج الثابت لطلب تفعيل ACPI");
+}
+
+/// تمكين حدث زر الطاقة وربط SCI بمتحكم المقاطعات
+fn enable_power_button(power: &PowerRegisters) {
+    let half = (power.event_length / 2) as u16;
+    if half == 0 {
+        return;
+    }
+    
+    // مسح أي ضغطة قديمة (الكتابة بواحد تمسح البت) ثم التمكين
+    io::write_u16(power.pm1a_event, PWRBTN);
+    let enable = power.pm1a_event + half;
+    io::write_u16(enable, io::read_u16(enable) | PWRBTN);
+    EVENT_STATUS_PORT.store(power.pm1a_event, Ordering::SeqCst);
+    
+    if power.sci_interrupt == LEGACY_SCI_IRQ {
+        super::idt::unmask_irq(LEGACY_SCI_IRQ as u8);
+        SCI_WIRED.store(true, Ordering::SeqCst);
+    } else {
+        warn!("⚠️ SCI على المقاطعة {}: زر الطاقة بالاستطلاع", power.sci_interrupt);
+    }
+}
+
+/// معالج مقاطعة SCI: تسجيل ضغطة زر الطاقة فقط، والإغلاق في الحلقة الرئيسية
+pub fn handle_sci() {
+    let port = EVENT_STATUS_PORT.load(Ordering::SeqCst);
+    if port == 0 {
+        return;
+    }
+    
+    if io::read_u16(port) & PWRBTN != 0 {
+        io::write_u16(port, PWRBTN);
+        POWER_BUTTON.store(true, Ordering::SeqCst);
+    }
+}
+
+/// هل ضُغط زر الطاقة منذ آخر سؤال؟
+pub fn take_power_button() -> bool {
+    if !SCI_WIRED.load(Ordering::SeqCst) {
+        handle_sci();
+    }
+    POWER_BUTTON.swap(false, Ordering::SeqCst)
+}
+
+/// معلومات ACPI إن نجحت التهيئة
+pub fn info() -> Option<AcpiInfo> {
+    INFO.lock().clone()
+}
+
+/// الدخول في حالة S5؛ يعود فقط إن لم يكن ذلك ممكناً
+pub fn poweroff() {
+    let Some(power) = INFO.lock().as_ref().map(|info| info.power) else {
+        return;
+    };
+    let Some((a, b)) = power.s5_sleep_type else {
+        warn!("⚠️ لا حزمة _S5_ في DSDT");
+        return;
+    };
+    
+    info!("⚡ ACPI: الدخول في S5");
+    let sleep = |port: u16, sleep_type: u8| {
+        let value = io::read_u16(port) & !SLP_TYP_MASK;
+        io::write_u16(port, value | ((sleep_type as u16) << SLP_TYP_SHIFT) | SLP_EN);
+    };
+    sleep(power.pm1a_control, a);
+    if let Some(port) = power.pm1b_control {
+        sleep(port, b);
+    }
+}
+
+/// إعادة التعيين عبر مسجل FADT؛ يعود فقط إن لم يكن ذلك ممكناً
+pub fn reset() {
+    let Some((register, value)) = INFO.lock().as_ref().and_then(|info| info.power.reset) else {
+        return;
+    };
+    
+    info!("⚡ ACPI: إعادة التعيين عبر {:?}", register);
+    match register {
+        Register::Io(port) => io::write_u8(port, value),
+        Register::Memory(address) => io::write_memory_u8(address, value),
+    }
+}
+
+/// الوصول إلى مسجلات ACPI على العتاد
+#[cfg(not(feature = "hosted"))]
+mod io {
+    use x86_64::instructions::port::Port;
+    
+    pub fn read_u16(port: u16) -> u16 {
+        unsafe { Port::new(port).read() }
+    }
+    
+    pub fn write_u16(port: u16, value: u16) {
+        unsafe { Port::new(port).write(value) }
+    }
+    
+    pub fn write_u8(port: u16, value: u8) {
+        unsafe { Port::new(port).write(value) }
+    }
+    
+    pub fn write_memory_u8(address: u64, value: u8) {
+        use x86_64::structures::paging::PageTableFlags;
+        
+        let flags = PageTableFlags::WRITABLE | PageTableFlags::NO_CACHE | PageTableFlags::NO_EXECUTE;
+        if let Ok(virt) = crate::memory::paging::map_physical(address, 1, flags) {
+            unsafe { core::ptr::write_volatile(virt.as_mut_ptr::<u8>(), value) }
+        }
+    }
+}
+
+/// منافذ محاكاة: القراءة تعيد آخر قيمة كُتبت
+#[cfg(feature = "hosted")]
+pub mod io {
+    use alloc::collections::BTreeMap;
+    use spin::Mutex;
+    
+    static PORTS: Mutex<BTreeMap<u16, u16>> = Mutex::new(BTreeMap::new());
+    
+    pub fn read_u16(port: u16) -> u16 {
+        PORTS.lock().get(&port).copied().unwrap_or(0)
+    }
+    
+    pub fn write_u16(port: u16, value: u16) {
+        PORTS.lock().insert(port, value);
+    }
+    
+    pub fn write_u8(port: u16, value: u8) {
+        PORTS.lock().insert(port, value as u16);
+    }
+    
+    pub fn write_memory_u8(address: u64, value: u8) {
+        log::debug!("🖥️ [محاكاة] كتابة {:#x} في الذاكرة {:#x}", value, address);
+    }
+}
+
+/// جداول ACPI صغيرة لوضع المحاكاة والاختبارات
+#[cfg(any(test, feature = "hosted"))]
+pub mod builder {
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    
+    /// منافذ PM1 كما في QEMU (PIIX4)
+    pub const PM1A_EVENT: u16 = 0x600;
+    pub const PM1A_CONTROL: u16 = 0x604;
+    pub const RESET_PORT: u16 = 0xCF9;
+    pub const RESET_VALUE: u8 = 0x06;
+    pub const LOCAL_APIC: u32 = 0xFEE0_0000;
+    pub const IO_APIC: u32 = 0xFEC0_0000;
+    
+    const HEADER: usize = 36;
+    const FADT_LENGTH: usize = 276;
+    
+    fn header(table: &mut [u8], signature: &[u8; 4], revision: u8) {
+        let length = table.len() as u32;
+        table[0..4].copy_from_slice(signature);
+        table[4..8].copy_from_slice(&length.to_le_bytes());
+        table[8] = revision;
+        table[10..16].copy_from_slice(b"ISLAM ");
+        table[16..24].copy_from_slice(b"ISLAMOS ");
+        table[9] = checksum(table);
+    }
+    
+    fn checksum(bytes: &[u8]) -> u8 {
+        0u8.wrapping_sub(bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)))
+    }
+    
+    /// DSDT بحزمة `_S5_` فقط
+    fn dsdt(s5: (u8, u8)) -> Vec<u8> {
+        let mut table = vec![0; HEADER];
+        table.extend_from_slice(&[0x08, b'_', b'S', b'5', b'_', 0x12, 0x08, 0x04, 0x0A, s5.0, 0x0A, s5.1, 0x00, 0x00]);
+        header(&mut table, b"DSDT", 2);
+        table
+    }
+    
+    fn fadt(dsdt: u64) -> Vec<u8> {
+        let mut table = vec![0; FADT_LENGTH];
+        table[46..48].copy_from_slice(&9u16.to_le_bytes()); // SCI على IRQ 9
+        table[56..60].copy_from_slice(&(PM1A_EVENT as u32).to_le_bytes());
+        table[64..68].copy_from_slice(&(PM1A_CONTROL as u32).to_le_bytes());
+        table[88] = 4; // PM1_EVT_LEN
+        table[89] = 2; // PM1_CNT_LEN
+        table[112..116].copy_from_slice(&(1u32 << 10).to_le_bytes()); // RESET_REG_SUP
+        table[116..120].copy_from_slice(&[1, 8, 0, 1]); // منفذ إدخال/إخراج بعرض بايت
+        table[120..128].copy_from_slice(&(RESET_PORT as u64).to_le_bytes());
+        table[128] = RESET_VALUE;
+        table[140..148].copy_from_slice(&dsdt.to_le_bytes()); // X_DSDT
+        header(&mut table, b"FACP", 6);
+        table
+    }
+    
+    /// معالجات `qemu -smp 4`
+    pub const PROCESSORS: u8 = 4;
+    
+    /// `PROCESSORS` معالجات وI/O APIC واحد وتحويل IRQ 0 إلى GSI 2
+    fn madt() -> Vec<u8> {
+        let mut table = vec![0; HEADER];
+        table.extend_from_slice(&LOCAL_APIC.to_le_bytes());
+        table.extend_from_slice(&1u32.to_le_bytes()); // PCAT_COMPAT
+        for apic_id in 0..PROCESSORS {
+            table.extend_from_slice(&[0, 8, apic_id, apic_id, 1, 0, 0, 0]);
+        }
+        table.extend_from_slice(&[1, 12, 0, 0]);
+        table.extend_from_slice(&IO_APIC.to_le_bytes());
+        table.extend_from_slice(&0u32.to_le_bytes());
+        table.extend_from_slice(&[2, 10, 0, 0, 2, 0, 0, 0, 0, 0]);
+        header(&mut table, b"APIC", 4);
+        table
+    }
+    
+    /// بناء الجداول في ذاكرة دائمة وإعادة RSDP (ACPI 2.0) يشير إليها
+    pub fn rsdp(s5: (u8, u8)) -> Vec<u8> {
+        let xsdt_length = HEADER + 2 * 8;
+        let dsdt_offset = xsdt_length;
+        let fadt_offset = dsdt_offset + dsdt(s5).len();
+        let madt_offset = fadt_offset + FADT_LENGTH;
+        let total = madt_offset + madt().len();
+        
+        let memory: &'static mut [u8] = Box::leak(vec![0u8; total].into_boxed_slice());
+        let base = memory.as_ptr() as u64;
+        
+        let mut xsdt = vec![0; HEADER];
+        xsdt.extend_from_slice(&(base + fadt_offset as u64).to_le_bytes());
+        xsdt.extend_from_slice(&(base + madt_offset as u64).to_le_bytes());
+        header(&mut xsdt, b"XSDT", 1);
+        
+        memory[..xsdt_length].copy_from_slice(&xsdt);
+        memory[dsdt_offset..fadt_offset].copy_from_slice(&dsdt(s5));
+        memory[fadt_offset..madt_offset].copy_from_slice(&fadt(base + dsdt_offset as u64));
+        memory[madt_offset..].copy_from_slice(&madt());
+        
+        let mut rsdp = vec![0u8; 36];
+        rsdp[..8].copy_from_slice(b"RSD PTR ");
+        rsdp[9..15].copy_from_slice(b"ISLAM ");
+        rsdp[15] = 2;
+        rsdp[20..24].copy_from_slice(&36u32.to_le_bytes());
+        rsdp[24..32].copy_from_slice(&base.to_le_bytes());
+        rsdp[8] = checksum(&rsdp[..20]);
+        rsdp[32] = checksum(&rsdp);
+        rsdp
+    }
+}
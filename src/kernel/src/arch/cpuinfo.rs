@@ -0,0 +1,263 @@
+//! 🔬 هوية المعالج ومحاسبة الخمول
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الهوية والميزات من CPUID عبر `raw-cpuid`، وتردد TSC منه إن أعلنه المعالج
+//! أو المشرف الافتراضي (وإلا تقيسه الواجهة الخلفية بنفسها).
+//!
+//! الاستخدام محسوب من زمن الخمول لا من عينات التكات: كل معالج يسجل دورات TSC
+//! التي قضاها متوقفاً بـ `hlt`، والنسبة في كل نافذة هي ما بقي منها:
+//!
+//! ```text
+//! الاستخدام = 100 × (1 − دورات الخمول ÷ دورات النافذة)
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use raw_cpuid::CpuId;
+use spin::Mutex;
+
+use super::MAX_CPUS;
+
+/// ما نعرفه عن المعالج بعد CPUID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuInfo {
+    /// "GenuineIntel" أو "AuthenticAMD"...
+    pub vendor: String,
+    /// اسم الطراز كما يعلنه المعالج
+    pub brand: String,
+    pub family: u8,
+    pub model: u8,
+    pub stepping: u8,
+    /// أسماء الميزات المدعومة بترتيب ثابت
+    pub features: Vec<&'static str>,
+    /// TSC ثابت المعدل مهما تغير تردد النواة (شرط صحة محاسبة الخمول)
+    pub invariant_tsc: bool,
+    /// يعمل تحت مشرف افتراضي
+    pub hypervisor: bool,
+    /// حساس الحرارة الرقمي (IA32_THERM_STATUS)
+    pub thermal_sensor: bool,
+    /// تردد TSC بالهرتز إن أعلنه CPUID
+    pub tsc_hz: Option<u64>,
+}
+
+impl CpuInfo {
+    pub fn has(&self, feature: &str) -> bool {
+        self.features.contains(&feature)
+    }
+}
+
+/// قراءة الهوية والميزات وتردد TSC المعلن
+pub fn detect(cpuid: &CpuId) -> CpuInfo {
+    let mut info = CpuInfo {
+        vendor: cpuid.get_vendor_info().map(|vendor| vendor.as_str().to_string()).unwrap_or_default(),
+        brand: cpuid.get_processor_brand_string().map(|brand| brand.as_str().trim().to_string()).unwrap_or_default(),
+        family: 0,
+        model: 0,
+        stepping: 0,
+        features: Vec::new(),
+        invariant_tsc: cpuid.get_advanced_power_mgmt_info().is_some_and(|apm| apm.has_invariant_tsc()),
+        hypervisor: false,
+        thermal_sensor: cpuid.get_thermal_power_info().is_some_and(|thermal| thermal.has_dts()),
+        tsc_hz: None,
+    };
+    
+    if let Some(features) = cpuid.get_feature_info() {
+        // العائلة والطراز الممتدان يُضافان حسب قواعد Intel و AMD
+        info.family = match features.family_id() {
+            0xF => 0xF + features.extended_family_id(),
+            family => family,
+        };
+        info.model = match features.family_id() {
+            0x6 | 0xF => (features.extended_model_id() << 4) | features.model_id(),
+            _ => features.model_id(),
+        };
+        info.stepping = features.stepping_id();
+        info.hypervisor = features.has_hypervisor();
+        
+        let flags = [
+            ("fpu", features.has_fpu()),
+            ("tsc", features.has_tsc()),
+            ("msr", features.has_msr()),
+            ("apic", features.has_apic()),
+            ("sse", features.has_sse()),
+            ("sse2", features.has_sse2()),
+            ("sse3", features.has_sse3()),
+            ("ssse3", features.has_ssse3()),
+            ("sse4.1", features.has_sse41()),
+            ("sse4.2", features.has_sse42()),
+            ("popcnt", features.has_popcnt()),
+            ("aes", features.has_aesni()),
+            ("avx", features.has_avx()),
+            ("rdrand", features.has_rdrand()),
+            ("x2apic", features.has_x2apic()),
+            ("tsc_deadline", features.has_tsc_deadline()),
+        ];
+        info.features.extend(flags.iter().filter(|(_, present)| *present).map(|(name, _)| *name));
+    }
+    
+    if let Some(extended) = cpuid.get_extended_feature_info() {
+        let flags = [
+            ("avx2", extended.has_avx2()),
+            ("smep", extended.has_smep()),
+            ("smap", extended.has_smap()),
+            ("rdseed", extended.has_rdseed()),
+        ];
+        info.features.extend(flags.iter().filter(|(_, present)| *present).map(|(name, _)| *name));
+    }
+    
+    if let Some(extended) = cpuid.get_extended_processor_and_feature_identifiers() {
+        let flags = [
+            ("nx", extended.has_execute_disable()),
+            ("pdpe1gb", extended.has_1gib_pages()),
+        ];
+        info.features.extend(flags.iter().filter(|(_, present)| *present).map(|(name, _)| *name));
+    }
+    
+    // الورقة 0x15 (بلورة × نسبة)، ثم ورقة المشرف 0x40000010، ثم التردد الأساسي في 0x16
+    info.tsc_hz = cpuid.get_tsc_info().and_then(|tsc| tsc.tsc_frequency())
+        .or_else(|| cpuid.get_hypervisor_info().and_then(|hv| hv.tsc_frequency()).filter(|&khz| khz > 0).map(|khz| khz as u64 * 1000))
+        .or_else(|| cpuid.get_processor_frequency_info().map(|freq| freq.processor_base_frequency() as u64).filter(|&mhz| mhz > 0).map(|mhz| mhz * 1_000_000));
+    
+    info
+}
+
+static INFO: Mutex<Option<CpuInfo>> = Mutex::new(None);
+
+/// حفظ ما اكتشفته الواجهة الخلفية عند التهيئة
+pub fn set_info(info: CpuInfo) {
+    *INFO.lock() = Some(info);
+}
+
+/// هوية المعالج إن اكتملت التهيئة
+pub fn info() -> Option<CpuInfo> {
+    INFO.lock().clone()
+}
+
+/// نافذة القياس الجارية
+struct Window {
+    start: u64,
+    idle: [u64; MAX_CPUS],
+    usage: Vec<f32>,
+}
+
+/// دورات الخمول لكل معالج ونافذة حساب الاستخدام منها
+pub struct IdleAccounting {
+    idle: [AtomicU64; MAX_CPUS],
+    /// بداية التوقف الجاري، أو صفر إن كان المعالج يعمل
+    halted_since: [AtomicU64; MAX_CPUS],
+    window: Mutex<Window>,
+}
+
+impl IdleAccounting {
+    pub const fn new() -> Self {
+        Self {
+            idle: [const { AtomicU64::new(0) }; MAX_CPUS],
+            halted_since: [const { AtomicU64::new(0) }; MAX_CPUS],
+            window: Mutex::new(Window { start: 0, idle: [0; MAX_CPUS], usage: Vec::new() }),
+        }
+    }
+    
+    /// المعالج `cpu` سيتوقف الآن
+    pub fn enter(&self, cpu: usize, now: u64) {
+        self.halted_since[cpu].store(now, Ordering::SeqCst);
+    }
+    
+    /// المعالج `cpu` استيقظ؛ الاستدعاء الثاني للتوقف نفسه لا يضيف شيئاً
+    pub fn exit(&self, cpu: usize, now: u64) {
+        let since = self.halted_since[cpu].swap(0, Ordering::SeqCst);
+        if since != 0 {
+            self.idle[cpu].fetch_add(now.saturating_sub(since), Ordering::SeqCst);
+        }
+    }
+    
+    /// دورات الخمول حتى `now`، بما فيها التوقف الجاري
+    fn idle_until(&self, cpu: usize, now: u64) -> u64 {
+        let since = self.halted_since[cpu].load(Ordering::SeqCst);
+        let ongoing = if since == 0 { 0 } else { now.saturating_sub(since) };
+        self.idle[cpu].load(Ordering::SeqCst) + ongoing
+    }
+    
+    /// استخدام أول `cpus` معالجاً بالنسبة المئوية؛ يُعاد حسابه كلما مضت
+    /// `min_window` دورة، وتُعاد النتيجة السابقة بين ذلك
+    pub fn sample(&self, cpus: usize, now: u64, min_window: u64) -> Vec<f32> {
+        let cpus = cpus.min(MAX_CPUS);
+        let mut window = self.window.lock();
+        let elapsed = now.saturating_sub(window.start);
+        let first = window.start == 0;
+        let due = elapsed >= min_window.max(1);
+        
+        if !first && due {
+            window.usage = (0..cpus)
+                .map(|cpu| {
+                    let idle = self.idle_until(cpu, now).saturating_sub(window.idle[cpu]).min(elapsed);
+                    100.0 * (1.0 - idle as f32 / elapsed as f32)
+                })
+                .collect();
+        }
+        
+        if first || due {
+            window.start = now;
+            for cpu in 0..cpus {
+                window.idle[cpu] = self.idle_until(cpu, now);
+            }
+        }
+        
+        // معالج انضم منذ آخر نافذة يظهر بصفر حتى النافذة التالية
+        let mut usage = window.usage.clone();
+        usage.resize(cpus, 0.0);
+        usage
+    }
+}
+
+impl Default for IdleAccounting {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raw_cpuid::CpuIdResult;
+    
+    #[test_case]
+    fn test_cpuid_detection_and_idle_accounting() {
+        fn words(text: &[u8; 16]) -> CpuIdResult {
+            let word = |i: usize| u32::from_le_bytes([text[i], text[i + 1], text[i + 2], text[i + 3]]);
+            CpuIdResult { eax: word(0), ebx: word(4), ecx: word(8), edx: word(12) }
+        }
+        
+        // معالج Intel وهمي: العائلة 6 الطراز 0x55، بلورة 24 ميجاهرتز × 100
+        fn fake_cpuid(leaf: u32, _subleaf: u32) -> CpuIdResult {
+            match leaf {
+                0x0 => CpuIdResult { eax: 0x15, ebx: 0x756e_6547, ecx: 0x6c65_746e, edx: 0x4965_6e69 },
+                0x1 => CpuIdResult { eax: 0x0005_0654, ebx: 0, ecx: (1 << 0) | (1 << 28), edx: (1 << 4) | (1 << 25) | (1 << 26) },
+                0x15 => CpuIdResult { eax: 1, ebx: 100, ecx: 24_000_000, edx: 0 },
+                0x8000_0000 => CpuIdResult { eax: 0x8000_0004, ebx: 0, ecx: 0, edx: 0 },
+                0x8000_0002 => words(b"Islam Test CPU  "),
+                0x8000_0003 => words(b"@ 2.40GHz\0\0\0\0\0\0\0"),
+                _ => CpuIdResult { eax: 0, ebx: 0, ecx: 0, edx: 0 },
+            }
+        }
+        
+        let info = detect(&CpuId::with_cpuid_fn(fake_cpuid));
+        assert_eq!(info.vendor, "GenuineIntel");
+        assert!(info.brand.starts_with("Islam Test CPU  @ 2.40GHz"));
+        assert_eq!((info.family, info.model, info.stepping), (6, 0x55, 4));
+        assert_eq!(info.features, ["tsc", "sse", "sse2", "sse3", "avx"]);
+        assert!(info.has("avx") && !info.has("avx2"));
+        assert_eq!(info.tsc_hz, Some(2_400_000_000));
+        
+        // المعالج 0 خامل ربع النافذة، والمعالج 1 متوقف منذ منتصفها ولم يستيقظ
+        let idle = IdleAccounting::new();
+        assert_eq!(idle.sample(2, 1000, 100), [0.0, 0.0]);
+        idle.enter(0, 1000);
+        idle.exit(0, 1025);
+        idle.exit(0, 1090);
+        idle.enter(1, 1050);
+        assert_eq!(idle.sample(2, 1100, 100), [75.0, 50.0]);
+        assert_eq!(idle.sample(3, 1150, 100), [75.0, 50.0, 0.0]);
+        assert_eq!(idle.sample(2, 1200, 100), [100.0, 0.0]);
+    }
+}
@@ -0,0 +1,226 @@
+//! 🖥️ واجهة العمارة الوهمية لوضع المحاكاة المستضافة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! لا تلمس أي عتاد؛ تسجل فقط ما طُلب منها حتى تتحقق منه الاختبارات.
+
+/// جدول الواصفات العام (لا يوجد ما يُحمَّل في عملية لينكس)
+pub mod gdt {
+    pub fn init() {
+        log::debug!("🖥️ [محاكاة] GDT");
+    }
+}
+
+/// جدول المقاطعات (لا يوجد ما يُحمَّل في عملية لينكس)
+pub mod idt {
+    pub fn init() {
+        log::debug!("🖥️ [محاكاة] IDT");
+    }
+    
+    pub fn unmask_irq(irq: u8) {
+        log::debug!("🖥️ [محاكاة] إلغاء حجب IRQ {}", irq);
+    }
+}
+
+/// مؤقت PIT المحاكى: كل `wait_for_interrupt` تكة واحدة بالتردد نفسه
+pub mod pit {
+    pub const HZ: u32 = 1000;
+    
+    pub fn init() {
+        log::debug!("🖥️ [محاكاة] PIT على {} هرتز", HZ);
+    }
+}
+
+/// معالجات محاكاة: كل معالج تطبيقي في MADT يعمل فوراً، وتكاته تُنفَّذ
+/// متزامنة من تكة معالج الإقلاع بتبديل "المعالج الحالي"
+pub mod smp {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use crate::arch::{acpi, MAX_CPUS};
+    use crate::process::scheduler;
+    
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static ONLINE: AtomicUsize = AtomicUsize::new(1);
+    
+    pub fn current() -> usize {
+        CURRENT.load(Ordering::SeqCst)
+    }
+    
+    pub fn online() -> usize {
+        ONLINE.load(Ordering::SeqCst)
+    }
+    
+    pub fn init() -> usize {
+        let processors = acpi::info().map(|info| info.processors).unwrap_or_default();
+        for _ in processors.iter().filter(|processor| !processor.is_bsp).take(MAX_CPUS - online()) {
+            let cpu = online();
+            run_on(cpu, || scheduler::init_ap(cpu));
+            ONLINE.fetch_add(1, Ordering::SeqCst);
+        }
+        log::info!("🧵 [محاكاة] {} معالجات عاملة", online());
+        online()
+    }
+    
+    pub fn broadcast_tick() {
+        for cpu in 1..online() {
+            run_on(cpu, scheduler::on_timer_tick);
+        }
+    }
+    
+    /// تنفيذ `f` كأنها على المعالج `cpu`
+    pub fn run_on<R>(cpu: usize, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT.swap(cpu, Ordering::SeqCst);
+        let result = f();
+        CURRENT.store(previous, Ordering::SeqCst);
+        result
+    }
+}
+
+/// المقاطعات المحاكاة
+pub mod interrupts {
+    use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+    static WAITS: AtomicU64 = AtomicU64::new(0);
+    
+    /// # Safety
+    /// لا شروط في المحاكاة؛ التوقيع يطابق نسخة العتاد
+    pub unsafe fn disable() {
+        ENABLED.store(false, Ordering::SeqCst);
+    }
+    
+    /// # Safety
+    /// لا شروط في المحاكاة؛ التوقيع يطابق نسخة العتاد
+    pub unsafe fn enable() {
+        ENABLED.store(true, Ordering::SeqCst);
+    }
+    
+    pub fn are_enabled() -> bool {
+        ENABLED.load(Ordering::SeqCst)
+    }
+    
+    pub fn without_interrupts<F, R>(f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let was_enabled = ENABLED.swap(false, Ordering::SeqCst);
+        let result = f();
+        ENABLED.store(was_enabled, Ordering::SeqCst);
+        result
+    }
+    
+    /// كل انتظار لمقاطعة يُحسب تكة مؤقت واحدة تُسلَّم للجدولة
+    pub fn wait_for_interrupt() {
+        ENABLED.store(true, Ordering::SeqCst);
+        WAITS.fetch_add(1, Ordering::SeqCst);
+        crate::process::scheduler::on_timer_tick();
+    }
+    
+    /// عدد مرات انتظار المقاطعات
+    pub fn wait_count() -> u64 {
+        WAITS.load(Ordering::SeqCst)
+    }
+}
+
+/// تبديل السياق المحاكى: لا تُنفَّذ المهام فعلياً على مكدساتها، لكن
+/// الجدولة تُحاسب وتختار المهام كما على العتاد
+pub mod context {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    
+    static SWITCHES: AtomicU64 = AtomicU64::new(0);
+    
+    pub fn prepare_stack(_stack: &mut [u8], _entry: extern "C" fn() -> !) -> u64 {
+        0
+    }
+    
+    /// # Safety
+    /// لا شروط في المحاكاة؛ التوقيع يطابق نسخة العتاد
+    pub unsafe fn switch(_old_rsp: *mut u64, _new_rsp: u64) {
+        SWITCHES.fetch_add(1, Ordering::SeqCst);
+    }
+    
+    /// عدد تبديلات السياق المحاكاة
+    pub fn switch_count() -> u64 {
+        SWITCHES.load(Ordering::SeqCst)
+    }
+}
+
+/// المعالج المحاكى
+pub mod cpu {
+    use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use crate::CpuStats;
+    
+    static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+    static REBOOT_REQUESTED: AtomicBool = AtomicBool::new(false);
+    
+    /// هوية المعالج المضيف؛ الاستخدام يبقى من عينات تكات المجدول لأن
+    /// `halt` هنا لا يوقف شيئاً يمكن قياسه
+    pub fn init() {
+        let info = crate::arch::cpuinfo::detect(&raw_cpuid::CpuId::new());
+        log::info!("⚡ المعالج: محاكاة مستضافة على {} ({})", info.brand, info.vendor);
+        crate::arch::cpuinfo::set_info(info);
+    }
+    
+    pub fn halt() {
+        std::thread::yield_now();
+    }
+    
+    pub(crate) fn get_usage_stats() -> CpuStats {
+        let cores = crate::process::scheduler::core_usage();
+        CpuStats {
+            usage: cores.iter().sum::<f32>() / cores.len().max(1) as f32,
+            frequency: crate::arch::cpuinfo::info().and_then(|info| info.tsc_hz).unwrap_or(0),
+            cores,
+            ..CpuStats::default()
+        }
+    }
+    
+    /// لا مسجلات حقيقية في المحاكاة: مؤشر المكدس تقريبي من عنوان متغير محلي
+    pub fn capture_registers() -> crate::arch::RegisterSnapshot {
+        let marker = 0u8;
+        let rsp = &marker as *const u8 as u64;
+        
+        crate::arch::RegisterSnapshot {
+            rip: capture_registers as fn() -> crate::arch::RegisterSnapshot as usize as u64,
+            rsp,
+            rbp: rsp,
+            ..Default::default()
+        }
+    }
+    
+    /// رقم عشوائي من بذرة `RandomState` في المكتبة القياسية
+    pub fn random_u64() -> u64 {
+        use std::hash::{BuildHasher, Hasher};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.finish()
+    }
+    
+    /// تسجيل طلب الإغلاق بدلاً من إيقاف العملية
+    pub fn shutdown() {
+        log::warn!("🔌 [محاكاة] طلب إيقاف التشغيل");
+        crate::arch::acpi::poweroff();
+        SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+    }
+    
+    /// تسجيل طلب إعادة التشغيل بدلاً من إعادة تشغيل العملية
+    pub fn reboot() {
+        log::warn!("🔄 [محاكاة] طلب إعادة التشغيل");
+        crate::arch::acpi::reset();
+        REBOOT_REQUESTED.store(true, Ordering::SeqCst);
+    }
+    
+    pub fn reboot_requested() -> bool {
+        REBOOT_REQUESTED.load(Ordering::SeqCst)
+    }
+    
+    pub fn shutdown_requested() -> bool {
+        SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+    }
+    
+    /// مسح الطلبين عند تشغيل المحاكاة من جديد
+    pub fn clear_requests() {
+        SHUTDOWN_REQUESTED.store(false, Ordering::SeqCst);
+        REBOOT_REQUESTED.store(false, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,50 @@
+//! ⚙️ طبقة العمارة لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تختار الواجهة الخلفية عند الترجمة: عتاد x86_64 الحقيقي افتراضياً،
+//! أو المحاكاة المستضافة عند تفعيل الميزة `hosted`.
+
+pub mod acpi;
+pub mod cpuinfo;
+
+#[cfg(not(feature = "hosted"))]
+mod x86_64;
+#[cfg(not(feature = "hosted"))]
+pub use self::x86_64::{context, cpu, gdt, idt, interrupts, pit, smp};
+
+#[cfg(feature = "hosted")]
+mod hosted;
+#[cfg(feature = "hosted")]
+pub use self::hosted::{context, cpu, gdt, idt, interrupts, pit, smp};
+
+/// أقصى عدد معالجات تُشغَّل
+pub const MAX_CPUS: usize = 16;
+
+/// لقطة من مسجلات المعالج (تُحفظ في سجلات الأعطال)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegisterSnapshot {
+    pub rip: u64,
+    pub rsp: u64,
+    pub rbp: u64,
+    pub rflags: u64,
+    pub cr0: u64,
+    pub cr2: u64,
+    pub cr3: u64,
+    pub cr4: u64,
+}
+
+impl RegisterSnapshot {
+    /// أسماء المسجلات وقيمها بترتيب ثابت
+    pub fn fields(&self) -> [(&'static str, u64); 8] {
+        [
+            ("rip", self.rip),
+            ("rsp", self.rsp),
+            ("rbp", self.rbp),
+            ("rflags", self.rflags),
+            ("cr0", self.cr0),
+            ("cr2", self.cr2),
+            ("cr3", self.cr3),
+            ("cr4", self.cr4),
+        ]
+    }
+}
@@ -0,0 +1,107 @@
+//! 📡 متحكم المقاطعات المحلي (Local APIC)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! لكل معالج LAPIC على العنوان نفسه من MADT؛ الكتابة فيه تصل إلى متحكم
+//! المعالج الذي ينفذها. نستخدمه لإرسال مقاطعات بين المعالجات (IPI) فقط،
+//! وتبقى مقاطعات الأجهزة على 8259 عبر LINT0 في معالج الإقلاع.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use x86_64::structures::paging::PageTableFlags;
+
+use super::idt::SPURIOUS_VECTOR;
+
+const ID: u64 = 0x020;
+const EOI: u64 = 0x0B0;
+const SPURIOUS: u64 = 0x0F0;
+const ICR_LOW: u64 = 0x300;
+const ICR_HIGH: u64 = 0x310;
+const LVT_LINT0: u64 = 0x350;
+const LVT_LINT1: u64 = 0x360;
+
+/// بت تمكين المتحكم برمجياً في مسجل المقاطعة الزائفة
+const SOFTWARE_ENABLE: u32 = 1 << 8;
+
+/// أنماط التسليم في ICR ومداخل LVT
+const DELIVERY_FIXED: u32 = 0b000 << 8;
+const DELIVERY_NMI: u32 = 0b100 << 8;
+const DELIVERY_INIT: u32 = 0b101 << 8;
+const DELIVERY_STARTUP: u32 = 0b110 << 8;
+const DELIVERY_EXTINT: u32 = 0b111 << 8;
+const DELIVERY_PENDING: u32 = 1 << 12;
+const LEVEL_ASSERT: u32 = 1 << 14;
+const LVT_MASKED: u32 = 1 << 16;
+/// الإرسال للجميع عدا المرسل
+const ALL_EXCLUDING_SELF: u32 = 0b11 << 18;
+
+/// العنوان المربوط (صفر قبل التهيئة)
+static BASE: AtomicU64 = AtomicU64::new(0);
+
+fn read(register: u64) -> u32 {
+    unsafe { core::ptr::read_volatile((BASE.load(Ordering::Relaxed) + register) as *const u32) }
+}
+
+fn write(register: u64, value: u32) {
+    unsafe { core::ptr::write_volatile((BASE.load(Ordering::Relaxed) + register) as *mut u32, value) }
+}
+
+/// ربط مسجلات LAPIC وتمكين متحكم معالج الإقلاع في نمط السلك الافتراضي
+pub fn init(address: u64) -> bool {
+    let flags = PageTableFlags::WRITABLE | PageTableFlags::NO_CACHE | PageTableFlags::NO_EXECUTE;
+    match crate::memory::paging::map_physical(address, 0x1000, flags) {
+        Ok(virt) => BASE.store(virt.as_u64(), Ordering::SeqCst),
+        Err(e) => {
+            log::warn!("⚠️ تعذر ربط LAPIC عند {:#x}: {:?}", address, e);
+            return false;
+        }
+    }
+    
+    // مقاطعات 8259 تمر عبر LINT0 كما تركها البرنامج الثابت
+    write(LVT_LINT0, DELIVERY_EXTINT);
+    write(LVT_LINT1, DELIVERY_NMI);
+    enable();
+    true
+}
+
+/// تمكين متحكم معالج تطبيقي (مقاطعات 8259 تصل معالج الإقلاع وحده)
+pub fn init_ap() {
+    write(LVT_LINT0, LVT_MASKED);
+    write(LVT_LINT1, DELIVERY_NMI);
+    enable();
+}
+
+fn enable() {
+    write(SPURIOUS, SOFTWARE_ENABLE | SPURIOUS_VECTOR as u32);
+}
+
+/// معرف APIC للمعالج المنفذ
+pub fn id() -> u32 {
+    read(ID) >> 24
+}
+
+/// إنهاء مقاطعة سلمها LAPIC
+pub fn end_of_interrupt() {
+    write(EOI, 0);
+}
+
+fn send(destination: u32, command: u32) {
+    write(ICR_HIGH, destination << 24);
+    write(ICR_LOW, command);
+    while read(ICR_LOW) & DELIVERY_PENDING != 0 {
+        core::hint::spin_loop();
+    }
+}
+
+/// إعادة تعيين معالج تطبيقي (INIT)
+pub fn send_init(apic_id: u32) {
+    send(apic_id, DELIVERY_INIT | LEVEL_ASSERT);
+}
+
+/// بدء معالج تطبيقي من الصفحة `page` في الذاكرة المنخفضة (SIPI)
+pub fn send_startup(apic_id: u32, page: u8) {
+    send(apic_id, DELIVERY_STARTUP | LEVEL_ASSERT | page as u32);
+}
+
+/// مقاطعة `vector` لكل المعالجات الأخرى
+pub fn broadcast(vector: u8) {
+    send(0, ALL_EXCLUDING_SELF | DELIVERY_FIXED | LEVEL_ASSERT | vector as u32);
+}
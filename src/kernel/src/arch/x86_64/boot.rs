@@ -0,0 +1,123 @@
+//! 🥾 نقطة دخول Multiboot2
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! ترويسة Multiboot2 وشفرة الدخول التي يقفز إليها GRUB في الوضع المحمي:
+//!
+//! ```text
+//! boot_entry (32 بت): EAX=السحر، EBX=معلومات الإقلاع
+//!   ──▶ EDI/ESI ──▶ فحص الوضع الطويل ──▶ ربط مطابق لأول جيجابايت (صفحات 2MiB)
+//!   ──PAE+LME+PG──▶ 64 بت ──▶ _start(multiboot_magic, multiboot_info)
+//! ```
+//!
+//! الأقسام تُرتب في `src/boot/linker.ld`: الترويسة أولاً في أول 32KiB من
+//! الصورة، والجداول والمكدس في `.bss` بين `__kernel_start` و `__kernel_end`.
+
+/// حجم مكدس الإقلاع (تعمل عليه `_start` حتى يبدأ المجدول)
+const BOOT_STACK_SIZE: usize = 64 * 1024;
+
+core::arch::global_asm!(
+    ".section .multiboot_header, \"a\"",
+    ".balign 8",
+    "1:",
+    "    .long 0xe85250d6",
+    "    .long 0",
+    "    .long 2f - 1b",
+    "    .long 0x100000000 - (0xe85250d6 + (2f - 1b))",
+    // وسم النهاية
+    "    .short 0",
+    "    .short 0",
+    "    .long 8",
+    "2:",
+    "",
+    ".section .boot.text, \"ax\"",
+    ".global boot_entry",
+    ".code32",
+    "boot_entry:",
+    "    cli",
+    "    cld",
+    "    movl $boot_stack_top, %esp",
+    "    movl %eax, %edi",
+    "    movl %ebx, %esi",
+    // CPUID.80000001h:EDX[29]
+    "    movl $0x80000000, %eax",
+    "    cpuid",
+    "    cmpl $0x80000001, %eax",
+    "    jb 4f",
+    "    movl $0x80000001, %eax",
+    "    cpuid",
+    "    testl $(1 << 29), %edx",
+    "    jz 4f",
+    // PML4[0] ──▶ PDPT[0] ──▶ PD: 512 صفحة كبيرة حاضرة وقابلة للكتابة
+    "    movl $boot_pdpt + 0x3, boot_pml4",
+    "    movl $boot_pd + 0x3, boot_pdpt",
+    "    xorl %ecx, %ecx",
+    "3:",
+    "    movl %ecx, %eax",
+    "    shll $21, %eax",
+    "    orl $0x83, %eax",
+    "    movl %eax, boot_pd(, %ecx, 8)",
+    "    incl %ecx",
+    "    cmpl $512, %ecx",
+    "    jne 3b",
+    // PAE، ثم جدول الصفحات، ثم LME في EFER، ثم PG و WP
+    // (NXE يفعّله `memory::paging::init`)
+    "    movl %cr4, %eax",
+    "    orl $(1 << 5), %eax",
+    "    movl %eax, %cr4",
+    "    movl $boot_pml4, %eax",
+    "    movl %eax, %cr3",
+    "    movl $0xC0000080, %ecx",
+    "    rdmsr",
+    "    orl $(1 << 8), %eax",
+    "    wrmsr",
+    "    movl %cr0, %eax",
+    "    orl $0x80010000, %eax",
+    "    movl %eax, %cr0",
+    "    lgdtl boot_gdt_pointer",
+    "    ljmpl $0x08, $5f",
+    // لا وضع طويل: "64" بالأحمر في أول خلية VGA ثم توقف
+    "4:",
+    "    movl $0x4c344c36, 0xb8000",
+    "6:",
+    "    hlt",
+    "    jmp 6b",
+    ".code64",
+    "5:",
+    "    xorw %ax, %ax",
+    "    movw %ax, %ds",
+    "    movw %ax, %es",
+    "    movw %ax, %fs",
+    "    movw %ax, %gs",
+    "    movw %ax, %ss",
+    // النصف الأعلى من المسجلات غير معرّف بعد تغيير الوضع
+    "    movl %edi, %edi",
+    "    movl %esi, %esi",
+    "    callq _start",
+    "7:",
+    "    hlt",
+    "    jmp 7b",
+    "",
+    ".section .rodata.boot, \"a\"",
+    ".balign 8",
+    // فارغ، شفرة 64 بت (`arch::gdt::init` يستبدله لاحقاً)
+    "boot_gdt:",
+    "    .quad 0",
+    "    .quad 0x00af9a000000ffff",
+    "boot_gdt_pointer:",
+    "    .short boot_gdt_pointer - boot_gdt - 1",
+    "    .long boot_gdt",
+    "",
+    ".section .bss.boot, \"aw\", @nobits",
+    ".balign 4096",
+    "boot_pml4:",
+    "    .skip 4096",
+    "boot_pdpt:",
+    "    .skip 4096",
+    "boot_pd:",
+    "    .skip 4096",
+    "    .skip {stack_size}",
+    "boot_stack_top:",
+    ".previous",
+    stack_size = const BOOT_STACK_SIZE,
+    options(att_syntax),
+);
@@ -0,0 +1,56 @@
+//! 🔀 تبديل سياق المهام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use core::arch::naked_asm;
+
+/// عدد المسجلات المحفوظة على المكدس (rbp, rbx, r12-r15)
+const SAVED_REGISTERS: usize = 6;
+
+/// تجهيز مكدس مهمة جديدة بحيث يعود `switch` إلى `entry`
+pub fn prepare_stack(stack: &mut [u8], entry: extern "C" fn() -> !) -> u64 {
+    let top = (stack.as_mut_ptr() as u64 + stack.len() as u64) & !0xF;
+    
+    unsafe {
+        let mut sp = top as *mut u64;
+        
+        // عنوان عودة وهمي حتى تبدأ `entry` بمحاذاة استدعاء عادي
+        sp = sp.sub(1);
+        sp.write(0);
+        
+        // يلتقطه `ret` في نهاية `switch`
+        sp = sp.sub(1);
+        sp.write(entry as usize as u64);
+        
+        for _ in 0..SAVED_REGISTERS {
+            sp = sp.sub(1);
+            sp.write(0);
+        }
+        
+        sp as u64
+    }
+}
+
+/// حفظ المسجلات المحفوظة-بالمستدعى في `*old_rsp` والانتقال إلى `new_rsp`
+///
+/// # Safety
+/// `new_rsp` مكدس أعدته `prepare_stack` أو حفظته `switch` سابقاً، و `old_rsp` صالح للكتابة
+#[unsafe(naked)]
+pub unsafe extern "C" fn switch(old_rsp: *mut u64, new_rsp: u64) {
+    naked_asm!(
+        "push rbp",
+        "push rbx",
+        "push r12",
+        "push r13",
+        "push r14",
+        "push r15",
+        "mov [rdi], rsp",
+        "mov rsp, rsi",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop rbx",
+        "pop rbp",
+        "ret",
+    );
+}
@@ -0,0 +1,205 @@
+//! ⚡ إدارة المعالج
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! كل `hlt` محاط بتسجيل بداية الخمول ونهايته بدورات TSC. مقاطعة المؤقت قد
+//! تستبق المهمة المتوقفة إلى مهمة أخرى قبل أن يعود `hlt`، فتنهي معالجاتها
+//! الخمول بنفسها حتى لا يُحسب زمن المهمة الجديدة خمولاً.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use log::{info, warn};
+use raw_cpuid::CpuId;
+use x86_64::instructions::port::Port;
+use x86_64::registers::model_specific::Msr;
+
+use super::{pit, smp};
+use crate::arch::cpuinfo::{self, IdleAccounting};
+use crate::arch::RegisterSnapshot;
+use crate::CpuStats;
+
+/// مدة قياس TSC بالقناة 2 إن لم يعلن CPUID تردده
+const CALIBRATION_MS: u32 = 10;
+
+/// طول نافذة حساب الاستخدام
+const USAGE_WINDOW_MS: u64 = 500;
+
+/// حالة الحرارة الرقمية: القراءة صالحة في البت 31، والبعد عن الحد في 16..22
+const IA32_THERM_STATUS: u32 = 0x19C;
+/// حد الحرارة القصوى (Intel) في البتات 16..23
+const MSR_TEMPERATURE_TARGET: u32 = 0x1A2;
+/// الحد المفترض إن لم يُقرأ
+const DEFAULT_TJ_MAX: u64 = 100;
+
+/// منفذ إيقاف التشغيل في QEMU (PIIX4 PM1a_CNT)
+const QEMU_POWEROFF_PORT: u16 = 0x604;
+const QEMU_POWEROFF_VALUE: u16 = 0x2000;
+
+static IDLE: IdleAccounting = IdleAccounting::new();
+
+/// تردد TSC بالهرتز (صفر قبل التهيئة)
+static TSC_HZ: AtomicU64 = AtomicU64::new(0);
+
+fn rdtsc() -> u64 {
+    unsafe { core::arch::x86_64::_rdtsc() }
+}
+
+/// تهيئة المعالج: الهوية والميزات ثم تردد TSC
+pub fn init() {
+    let info = cpuinfo::detect(&CpuId::new());
+    let tsc_hz = info.tsc_hz.unwrap_or_else(|| pit::measure_tsc_hz(CALIBRATION_MS));
+    TSC_HZ.store(tsc_hz, Ordering::SeqCst);
+    
+    info!("⚡ المعالج: {} ({}) العائلة {:#x} الطراز {:#x}، TSC {} ميجاهرتز{}",
+        info.brand, info.vendor, info.family, info.model, tsc_hz / 1_000_000,
+        if info.tsc_hz.is_some() { "" } else { " (مقيس)" });
+    info!("⚡ الميزات: {}", info.features.join(" "));
+    if !info.invariant_tsc && !info.hypervisor {
+        warn!("⚠️ TSC غير ثابت المعدل: قد تنحرف نسب الاستخدام مع تغير التردد");
+    }
+    
+    cpuinfo::set_info(info);
+}
+
+/// المعالج المنفذ سيتوقف حتى مقاطعة
+pub fn enter_idle() {
+    IDLE.enter(smp::current(), rdtsc());
+}
+
+/// المعالج المنفذ استيقظ (من `hlt` أو في بداية معالج مقاطعة)
+pub fn leave_idle() {
+    IDLE.exit(smp::current(), rdtsc());
+}
+
+/// إيقاف المعالج حتى المقاطعة التالية
+pub fn halt() {
+    enter_idle();
+    x86_64::instructions::hlt();
+    leave_idle();
+}
+
+/// إحصائيات استخدام المعالج من زمن الخمول
+pub(crate) fn get_usage_stats() -> CpuStats {
+    let frequency = TSC_HZ.load(Ordering::SeqCst);
+    let window = frequency * USAGE_WINDOW_MS / 1000;
+    let cores = IDLE.sample(smp::online(), rdtsc(), window);
+    
+    CpuStats {
+        usage: cores.iter().sum::<f32>() / cores.len().max(1) as f32,
+        frequency,
+        temperature: temperature().unwrap_or(0.0),
+        cores,
+    }
+}
+
+/// حرارة المعالج المنفذ بالدرجات المئوية من الحساس الرقمي
+fn temperature() -> Option<f32> {
+    let info = cpuinfo::info()?;
+    if !info.thermal_sensor {
+        return None;
+    }
+    
+    let status = unsafe { Msr::new(IA32_THERM_STATUS).read() };
+    if status & (1 << 31) == 0 {
+        return None;
+    }
+    let below_max = (status >> 16) & 0x7F;
+    
+    let tj_max = match info.vendor.as_str() {
+        "GenuineIntel" => (unsafe { Msr::new(MSR_TEMPERATURE_TARGET).read() } >> 16) & 0xFF,
+        _ => 0,
+    };
+    let tj_max = if tj_max == 0 { DEFAULT_TJ_MAX } else { tj_max };
+    
+    Some(tj_max.saturating_sub(below_max) as f32)
+}
+
+/// التقاط المسجلات الحالية دون تخصيص ذاكرة (آمن داخل معالج الذعر)
+#[inline(always)]
+pub fn capture_registers() -> RegisterSnapshot {
+    use x86_64::registers::control::{Cr0, Cr2, Cr3, Cr4};
+    use x86_64::registers::rflags;
+    
+    let (rip, rsp, rbp): (u64, u64, u64);
+    unsafe {
+        core::arch::asm!(
+            "lea {rip}, [rip]",
+            "mov {rsp}, rsp",
+            "mov {rbp}, rbp",
+            rip = out(reg) rip,
+            rsp = out(reg) rsp,
+            rbp = out(reg) rbp,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+    
+    let (cr3_frame, cr3_flags) = Cr3::read();
+    
+    RegisterSnapshot {
+        rip,
+        rsp,
+        rbp,
+        rflags: rflags::read_raw(),
+        cr0: Cr0::read_raw(),
+        cr2: Cr2::read().as_u64(),
+        cr3: cr3_frame.start_address().as_u64() | cr3_flags.bits(),
+        cr4: Cr4::read_raw(),
+    }
+}
+
+/// رقم عشوائي من RDRAND، أو من عداد الطوابع الزمنية إن لم يتوفر
+pub fn random_u64() -> u64 {
+    use x86_64::instructions::random::RdRand;
+    
+    if let Some(value) = RdRand::new().and_then(|rdrand| rdrand.get_u64()) {
+        return value;
+    }
+    
+    // خلط TSC (مصدر ضعيف لكنه يكفي لأملاح كلمات المرور على عتاد قديم)
+    let tsc = unsafe { core::arch::x86_64::_rdtsc() };
+    let mixed = (tsc ^ (tsc >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+    mixed ^ (mixed >> 33)
+}
+
+/// إيقاف تشغيل النظام: حالة S5 عبر ACPI، ثم منفذ QEMU القديم
+pub fn shutdown() {
+    warn!("🔌 إيقاف تشغيل النظام...");
+    
+    crate::arch::acpi::poweroff();
+    unsafe {
+        Port::new(QEMU_POWEROFF_PORT).write(QEMU_POWEROFF_VALUE);
+    }
+    
+    // إذا لم يتوقف الجهاز، نبقى في حالة توقف
+    loop {
+        x86_64::instructions::interrupts::disable();
+        halt();
+    }
+}
+
+/// إعادة تشغيل النظام: مسجل ACPI، ثم متحكم لوحة المفاتيح 8042
+pub fn reboot() {
+    warn!("🔄 إعادة تشغيل النظام...");
+    
+    x86_64::instructions::interrupts::disable();
+    crate::arch::acpi::reset();
+    unsafe {
+        let mut status: Port<u8> = Port::new(0x64);
+        
+        // انتظار فراغ مخزن الإدخال ثم إرسال أمر نبضة إعادة التعيين
+        while status.read() & 0x02 != 0 {}
+        status.write(0xFE);
+    }
+    
+    loop {
+        halt();
+    }
+}
+
+/// على العتاد الحقيقي لا يعود `shutdown` أبداً
+pub fn shutdown_requested() -> bool {
+    false
+}
+
+/// على العتاد الحقيقي لا يعود `reboot` أبداً
+pub fn reboot_requested() -> bool {
+    false
+}
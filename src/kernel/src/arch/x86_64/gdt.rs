@@ -0,0 +1,76 @@
+//! 📋 جدول الواصفات العام (GDT) ومقطع حالة المهمة (TSS)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! لكل معالج GDT و TSS خاصان: واصف TSS يُعلَّم "مشغولاً" عند تحميله فلا
+//! يُشارك، ولكل معالج مكدس خطأ مزدوج مستقل.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use lazy_static::lazy_static;
+use x86_64::VirtAddr;
+use x86_64::structures::gdt::{Descriptor, GlobalDescriptorTable, SegmentSelector};
+use x86_64::structures::tss::TaskStateSegment;
+
+/// فهرس مكدس الخطأ المزدوج في جدول IST
+pub const DOUBLE_FAULT_IST_INDEX: u16 = 0;
+
+/// حجم مكدس الخطأ المزدوج
+const STACK_SIZE: usize = 4096 * 5;
+
+lazy_static! {
+    static ref TSS: TaskStateSegment = {
+        let mut tss = TaskStateSegment::new();
+        tss.interrupt_stack_table[DOUBLE_FAULT_IST_INDEX as usize] = {
+            static mut STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
+            
+            let stack_start = VirtAddr::from_ptr(&raw const STACK);
+            stack_start + STACK_SIZE
+        };
+        tss
+    };
+    
+    static ref GDT: (GlobalDescriptorTable, Selectors) = build(&TSS);
+}
+
+struct Selectors {
+    code_selector: SegmentSelector,
+    data_selector: SegmentSelector,
+    tss_selector: SegmentSelector,
+}
+
+fn build(tss: &'static TaskStateSegment) -> (GlobalDescriptorTable, Selectors) {
+    let mut gdt = GlobalDescriptorTable::new();
+    let code_selector = gdt.add_entry(Descriptor::kernel_code_segment());
+    let data_selector = gdt.add_entry(Descriptor::kernel_data_segment());
+    let tss_selector = gdt.add_entry(Descriptor::tss_segment(tss));
+    (gdt, Selectors { code_selector, data_selector, tss_selector })
+}
+
+fn load(tables: &'static (GlobalDescriptorTable, Selectors)) {
+    use x86_64::instructions::segmentation::{Segment, CS, DS, SS};
+    use x86_64::instructions::tables::load_tss;
+    
+    tables.0.load();
+    
+    unsafe {
+        CS::set_reg(tables.1.code_selector);
+        DS::set_reg(tables.1.data_selector);
+        SS::set_reg(tables.1.data_selector);
+        load_tss(tables.1.tss_selector);
+    }
+}
+
+/// تحميل GDT و TSS لمعالج الإقلاع (قبل تهيئة الكومة)
+pub fn init() {
+    load(&GDT);
+}
+
+/// جداول معالج تطبيقي من الكومة، تبقى طوال عمر النواة
+pub fn init_ap() {
+    let stack: &'static mut [u8] = Box::leak(vec![0u8; STACK_SIZE].into_boxed_slice());
+    let mut tss = TaskStateSegment::new();
+    tss.interrupt_stack_table[DOUBLE_FAULT_IST_INDEX as usize] = VirtAddr::from_ptr(stack.as_ptr()) + STACK_SIZE;
+    
+    let tss: &'static TaskStateSegment = Box::leak(Box::new(tss));
+    load(Box::leak(Box::new(build(tss))));
+}
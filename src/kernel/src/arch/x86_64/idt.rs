@@ -0,0 +1,157 @@
+//! ⚡ جدول واصفات المقاطعات (IDT) ومتحكم المقاطعات 8259
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use lazy_static::lazy_static;
+use log::{error, warn};
+use pic8259::ChainedPics;
+use spin::Mutex;
+use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame, PageFaultErrorCode};
+
+use super::{apic, cpu, gdt};
+
+/// إزاحة متحكمي المقاطعات بعد استثناءات المعالج
+pub const PIC_1_OFFSET: u8 = 32;
+pub const PIC_2_OFFSET: u8 = PIC_1_OFFSET + 8;
+
+pub static PICS: Mutex<ChainedPics> =
+    Mutex::new(unsafe { ChainedPics::new(PIC_1_OFFSET, PIC_2_OFFSET) });
+
+/// تكة المؤقت يرسلها معالج الإقلاع إلى المعالجات الأخرى
+pub const TICK_VECTOR: u8 = 0xF0;
+/// مقاطعة LAPIC الزائفة (لا تحتاج إنهاء)
+pub const SPURIOUS_VECTOR: u8 = 0xFF;
+
+/// أرقام مقاطعات العتاد
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum InterruptIndex {
+    Timer = PIC_1_OFFSET,
+    Keyboard,
+    /// مقاطعة التحكم في النظام (SCI) من ACPI
+    Acpi = PIC_1_OFFSET + 9,
+    /// الفأرة على المنفذ الإضافي لمتحكم 8042
+    Mouse = PIC_1_OFFSET + 12,
+}
+
+impl InterruptIndex {
+    fn as_u8(self) -> u8 {
+        self as u8
+    }
+    
+    fn as_usize(self) -> usize {
+        usize::from(self.as_u8())
+    }
+}
+
+lazy_static! {
+    static ref IDT: InterruptDescriptorTable = {
+        let mut idt = InterruptDescriptorTable::new();
+        idt.breakpoint.set_handler_fn(breakpoint_handler);
+        idt.page_fault.set_handler_fn(page_fault_handler);
+        unsafe {
+            idt.double_fault
+                .set_handler_fn(double_fault_handler)
+                .set_stack_index(gdt::DOUBLE_FAULT_IST_INDEX);
+        }
+        idt[InterruptIndex::Timer.as_usize()].set_handler_fn(timer_interrupt_handler);
+        idt[InterruptIndex::Keyboard.as_usize()].set_handler_fn(keyboard_interrupt_handler);
+        idt[InterruptIndex::Acpi.as_usize()].set_handler_fn(acpi_interrupt_handler);
+        idt[InterruptIndex::Mouse.as_usize()].set_handler_fn(mouse_interrupt_handler);
+        idt[TICK_VECTOR as usize].set_handler_fn(tick_ipi_handler);
+        idt[SPURIOUS_VECTOR as usize].set_handler_fn(spurious_interrupt_handler);
+        idt
+    };
+}
+
+/// تحميل IDT وتهيئة متحكمي المقاطعات
+pub fn init() {
+    IDT.load();
+    unsafe {
+        PICS.lock().initialize();
+    }
+}
+
+/// تحميل IDT المشترك على معالج تطبيقي
+pub fn init_ap() {
+    IDT.load();
+}
+
+/// إلغاء حجب خط IRQ (والخط 2 الموصل بالمتحكم الثانوي عند الحاجة)
+pub fn unmask_irq(irq: u8) {
+    x86_64::instructions::interrupts::without_interrupts(|| unsafe {
+        let mut pics = PICS.lock();
+        let [mut primary, mut secondary] = pics.read_masks();
+        if irq < 8 {
+            primary &= !(1 << irq);
+        } else {
+            secondary &= !(1 << (irq - 8));
+            primary &= !(1 << 2);
+        }
+        pics.write_masks(primary, secondary);
+    });
+}
+
+extern "x86-interrupt" fn breakpoint_handler(stack_frame: InterruptStackFrame) {
+    warn!("🔎 نقطة توقف:\n{:#?}", stack_frame);
+}
+
+extern "x86-interrupt" fn page_fault_handler(
+    stack_frame: InterruptStackFrame,
+    error_code: PageFaultErrorCode,
+) {
+    use x86_64::registers::control::Cr2;
+    
+    error!("📄 خطأ صفحة عند العنوان: {:?}", Cr2::read());
+    error!("🔢 رمز الخطأ: {:?}", error_code);
+    panic!("خطأ صفحة:\n{:#?}", stack_frame);
+}
+
+extern "x86-interrupt" fn double_fault_handler(
+    stack_frame: InterruptStackFrame,
+    _error_code: u64,
+) -> ! {
+    panic!("خطأ مزدوج:\n{:#?}", stack_frame);
+}
+
+extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    cpu::leave_idle();
+    
+    // إنهاء المقاطعة قبل الجدولة لأن الاستباق قد ينقلنا إلى مهمة أخرى
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Timer.as_u8());
+    }
+    
+    crate::process::scheduler::on_timer_tick();
+}
+
+extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    crate::drivers::keyboard::on_interrupt();
+    
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Keyboard.as_u8());
+    }
+}
+
+extern "x86-interrupt" fn mouse_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    crate::drivers::mouse::on_interrupt();
+    
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Mouse.as_u8());
+    }
+}
+
+extern "x86-interrupt" fn tick_ipi_handler(_stack_frame: InterruptStackFrame) {
+    cpu::leave_idle();
+    apic::end_of_interrupt();
+    crate::process::scheduler::on_timer_tick();
+}
+
+extern "x86-interrupt" fn spurious_interrupt_handler(_stack_frame: InterruptStackFrame) {}
+
+extern "x86-interrupt" fn acpi_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    crate::arch::acpi::handle_sci();
+    
+    unsafe {
+        PICS.lock().notify_end_of_interrupt(InterruptIndex::Acpi.as_u8());
+    }
+}
@@ -0,0 +1,38 @@
+//! 🔔 التحكم في المقاطعات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+/// تعطيل المقاطعات
+///
+/// # Safety
+/// على المستدعي إعادة تمكينها؛ المؤقت والجدولة متوقفان حتى ذلك
+pub unsafe fn disable() {
+    x86_64::instructions::interrupts::disable();
+}
+
+/// تمكين المقاطعات
+///
+/// # Safety
+/// يجب أن تكون IDT والمتحكم مهيأين قبل وصول أول مقاطعة
+pub unsafe fn enable() {
+    x86_64::instructions::interrupts::enable();
+}
+
+/// هل المقاطعات مفعلة؟
+pub fn are_enabled() -> bool {
+    x86_64::instructions::interrupts::are_enabled()
+}
+
+/// تنفيذ دالة والمقاطعات معطلة
+pub fn without_interrupts<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    x86_64::instructions::interrupts::without_interrupts(f)
+}
+
+/// تمكين المقاطعات وانتظار المقاطعة التالية
+pub fn wait_for_interrupt() {
+    super::cpu::enter_idle();
+    x86_64::instructions::interrupts::enable_and_hlt();
+    super::cpu::leave_idle();
+}
@@ -0,0 +1,12 @@
+//! 🖥️ الواجهة الخلفية لعمارة x86_64
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+pub mod apic;
+pub mod boot;
+pub mod context;
+pub mod cpu;
+pub mod gdt;
+pub mod idt;
+pub mod interrupts;
+pub mod pit;
+pub mod smp;
@@ -0,0 +1,70 @@
+//! ⏲️ مؤقت PIT (Intel 8253/8254)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! القناة 0 موصولة بـ IRQ 0، وتعمل بعد الإقلاع بنحو 18.2 هرتز فقط. نبرمجها
+//! على `HZ` حتى تقابل التكة الواحدة زمناً معروفاً تبني عليه المؤقتات.
+
+use log::info;
+use x86_64::instructions::port::Port;
+
+/// تردد مقاطعة المؤقت بعد التهيئة
+pub const HZ: u32 = 1000;
+
+/// تردد مذبذب PIT الأساسي
+const BASE_FREQUENCY: u32 = 1_193_182;
+
+const CHANNEL0_PORT: u16 = 0x40;
+const COMMAND_PORT: u16 = 0x43;
+
+/// القناة 0، البايت المنخفض ثم المرتفع، النمط 2 (مولد معدل)، ثنائي
+const CHANNEL0_RATE_GENERATOR: u8 = 0b0011_0100;
+
+/// برمجة القناة 0 على `HZ`
+pub fn init() {
+    let divisor = (BASE_FREQUENCY / HZ) as u16;
+    
+    x86_64::instructions::interrupts::without_interrupts(|| unsafe {
+        Port::<u8>::new(COMMAND_PORT).write(CHANNEL0_RATE_GENERATOR);
+        let mut data = Port::<u8>::new(CHANNEL0_PORT);
+        data.write(divisor as u8);
+        data.write((divisor >> 8) as u8);
+    });
+    
+    info!("⏲️ مؤقت PIT على {} هرتز (القاسم {})", HZ, divisor);
+}
+
+const CHANNEL2_PORT: u16 = 0x42;
+/// بوابة القناة 2 (البت 0) ومكبر الصوت (البت 1) ومخرجها (البت 5)
+const CHANNEL2_GATE_PORT: u16 = 0x61;
+
+/// القناة 2، البايت المنخفض ثم المرتفع، النمط 0 (مقاطعة عند انتهاء العد)، ثنائي
+const CHANNEL2_ONE_SHOT: u8 = 0b1011_0000;
+
+/// قياس تردد TSC بالهرتز: عدّ تنازلي لمدة `ms` على القناة 2 التي لا تولد
+/// مقاطعة، فيصلح القياس والمقاطعات معطلة ولا يمس تكات القناة 0
+pub fn measure_tsc_hz(ms: u32) -> u64 {
+    let count = (BASE_FREQUENCY as u64 * ms as u64 / 1000).min(u16::MAX as u64) as u16;
+    
+    x86_64::instructions::interrupts::without_interrupts(|| unsafe {
+        let mut gate = Port::<u8>::new(CHANNEL2_GATE_PORT);
+        let mut data = Port::<u8>::new(CHANNEL2_PORT);
+        
+        // إغلاق البوابة وإسكات المكبر أثناء البرمجة
+        let saved = gate.read();
+        gate.write(saved & !0b11);
+        Port::<u8>::new(COMMAND_PORT).write(CHANNEL2_ONE_SHOT);
+        data.write(count as u8);
+        data.write((count >> 8) as u8);
+        
+        // فتح البوابة يبدأ العد؛ المخرج يرتفع عند الصفر
+        gate.write((saved & !0b10) | 0b01);
+        let start = core::arch::x86_64::_rdtsc();
+        while gate.read() & 0x20 == 0 {
+            core::hint::spin_loop();
+        }
+        let end = core::arch::x86_64::_rdtsc();
+        
+        gate.write(saved);
+        (end - start) * BASE_FREQUENCY as u64 / count as u64
+    })
+}
@@ -0,0 +1,238 @@
+//! 🧵 تشغيل المعالجات التطبيقية (SMP)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! المعالجات من MADT؛ يُشغَّل كل منها بدوره بتسلسل INIT ثم SIPI مرتين:
+//!
+//! ```text
+//! 0x8000  الوضع الحقيقي ──lgdt──▶ الوضع المحمي ──PAE+LME+PG──▶ الوضع الطويل
+//!                                   (CR3 جدول صفحات معالج الإقلاع)
+//!         ──▶ ap_entry(cpu): GDT/TSS خاص، IDT، LAPIC، طابور تشغيل، خمول
+//! ```
+//!
+//! رقم المعالج (0 لمعالج الإقلاع، ثم بترتيب التشغيل) محفوظ في GS_BASE.
+//! مقاطعة PIT تصل معالج الإقلاع وحده، فيعيد إرسالها تكةً لبقية المعالجات.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use log::{info, warn};
+use x86_64::VirtAddr;
+use x86_64::registers::control::Cr3;
+use x86_64::registers::model_specific::GsBase;
+use x86_64::structures::paging::PageTableFlags;
+
+use super::{apic, gdt, idt, interrupts};
+use crate::arch::{acpi, MAX_CPUS};
+use crate::process::{scheduler, task::KERNEL_STACK_SIZE, timer};
+
+/// عنوان شفرة البدء في الذاكرة المنخفضة (صفحة SIPI رقم 8)
+const TRAMPOLINE_ADDR: u64 = 0x8000;
+
+/// مهلة انتظار المعالج بعد SIPI الثاني
+const STARTUP_TIMEOUT_MS: u64 = 100;
+
+/// المعاملات في نهاية شفرة البدء، تُملأ قبل تشغيل كل معالج
+#[repr(C)]
+struct TrampolineArgs {
+    cr3: u64,
+    stack_top: u64,
+    entry: u64,
+    cpu: u64,
+}
+
+core::arch::global_asm!(
+    ".section .rodata.smp_trampoline, \"a\"",
+    ".global smp_trampoline_start",
+    ".global smp_trampoline_args",
+    ".global smp_trampoline_end",
+    ".code16",
+    "smp_trampoline_start:",
+    "    cli",
+    "    cld",
+    "    xorw %ax, %ax",
+    "    movw %ax, %ds",
+    "    lgdtl {base} + (2f - smp_trampoline_start)",
+    "    movl %cr0, %eax",
+    "    orl $1, %eax",
+    "    movl %eax, %cr0",
+    "    ljmpl $0x08, ${base} + (3f - smp_trampoline_start)",
+    ".code32",
+    "3:",
+    "    movw $0x10, %ax",
+    "    movw %ax, %ds",
+    "    movw %ax, %es",
+    "    movw %ax, %ss",
+    // PAE، ثم جدول الصفحات، ثم LME و NXE في EFER، ثم PG و WP
+    "    movl %cr4, %eax",
+    "    orl $(1 << 5), %eax",
+    "    movl %eax, %cr4",
+    "    movl {base} + (smp_trampoline_args - smp_trampoline_start), %eax",
+    "    movl %eax, %cr3",
+    "    movl $0xC0000080, %ecx",
+    "    rdmsr",
+    "    orl $((1 << 8) | (1 << 11)), %eax",
+    "    wrmsr",
+    "    movl %cr0, %eax",
+    "    orl $0x80010000, %eax",
+    "    movl %eax, %cr0",
+    "    ljmpl $0x18, ${base} + (4f - smp_trampoline_start)",
+    ".code64",
+    "4:",
+    "    xorw %ax, %ax",
+    "    movw %ax, %ds",
+    "    movw %ax, %es",
+    "    movw %ax, %ss",
+    "    movq {base} + (smp_trampoline_args + 8 - smp_trampoline_start), %rsp",
+    "    movq {base} + (smp_trampoline_args + 24 - smp_trampoline_start), %rdi",
+    "    movq {base} + (smp_trampoline_args + 16 - smp_trampoline_start), %rax",
+    "    callq *%rax",
+    "5:",
+    "    hlt",
+    "    jmp 5b",
+    // فارغ، شفرة 32 بت، بيانات، شفرة 64 بت
+    ".balign 8",
+    "1:",
+    "    .quad 0",
+    "    .quad 0x00cf9a000000ffff",
+    "    .quad 0x00cf92000000ffff",
+    "    .quad 0x00af9a000000ffff",
+    "2:",
+    "    .word 2b - 1b - 1",
+    "    .long {base} + (1b - smp_trampoline_start)",
+    ".balign 8",
+    "smp_trampoline_args:",
+    "    .quad 0, 0, 0, 0",
+    "smp_trampoline_end:",
+    ".code64",
+    ".previous",
+    base = const TRAMPOLINE_ADDR,
+    options(att_syntax),
+);
+
+extern "C" {
+    static smp_trampoline_start: u8;
+    static smp_trampoline_args: u8;
+    static smp_trampoline_end: u8;
+}
+
+/// المعالجات العاملة (معالج الإقلاع دائماً)
+static ONLINE: AtomicUsize = AtomicUsize::new(1);
+
+/// رقم المعالج المنفذ
+pub fn current() -> usize {
+    GsBase::read().as_u64() as usize
+}
+
+/// عدد المعالجات العاملة؛ أرقامها من 0 إلى العدد ناقص واحد
+pub fn online() -> usize {
+    ONLINE.load(Ordering::SeqCst)
+}
+
+/// تشغيل المعالجات التطبيقية من MADT، ويعيد عدد المعالجات العاملة
+pub fn init() -> usize {
+    let Some(info) = acpi::info() else {
+        return online();
+    };
+    let Some(local_apic) = info.interrupts.as_ref().map(|controllers| controllers.local_apic) else {
+        warn!("⚠️ لا LAPIC في MADT: معالج واحد");
+        return online();
+    };
+    
+    let application: Vec<_> = info.processors.iter().filter(|processor| !processor.is_bsp).collect();
+    if application.is_empty() || !apic::init(local_apic) {
+        return online();
+    }
+    install_trampoline();
+    
+    for processor in application {
+        if online() == MAX_CPUS {
+            warn!("⚠️ تجاوز الحد الأقصى {} معالجاً", MAX_CPUS);
+            break;
+        }
+        if processor.apic_id > 0xFF {
+            warn!("⚠️ المعالج {} يحتاج x2APIC (APIC {})", processor.uid, processor.apic_id);
+            continue;
+        }
+        if !start(processor.apic_id) {
+            warn!("⚠️ المعالج ذو APIC {} لم يستجب", processor.apic_id);
+        }
+    }
+    
+    info!("🧵 {} معالجات عاملة من {}", online(), info.processors.len());
+    online()
+}
+
+/// نسخ شفرة البدء إلى الذاكرة المنخفضة (أول ميجابايت لا يُخصص أبداً)
+fn install_trampoline() {
+    let flags = PageTableFlags::WRITABLE;
+    unsafe {
+        let start = &smp_trampoline_start as *const u8;
+        let size = &smp_trampoline_end as *const u8 as usize - start as usize;
+        crate::memory::paging::map_physical(TRAMPOLINE_ADDR, size as u64, flags).expect("تعذر ربط شفرة البدء");
+        core::ptr::copy_nonoverlapping(start, TRAMPOLINE_ADDR as *mut u8, size);
+    }
+}
+
+/// تشغيل معالج واحد والانتظار حتى يعلن جاهزيته
+fn start(apic_id: u32) -> bool {
+    let cpu = online();
+    let stack: &'static mut [u8] = Box::leak(vec![0u8; KERNEL_STACK_SIZE].into_boxed_slice());
+    let args = TrampolineArgs {
+        cr3: Cr3::read().0.start_address().as_u64(),
+        stack_top: (stack.as_ptr() as u64 + stack.len() as u64) & !0xF,
+        entry: ap_entry as extern "C" fn(usize) -> ! as usize as u64,
+        cpu: cpu as u64,
+    };
+    
+    unsafe {
+        let offset = &smp_trampoline_args as *const u8 as u64 - &smp_trampoline_start as *const u8 as u64;
+        core::ptr::write_volatile((TRAMPOLINE_ADDR + offset) as *mut TrampolineArgs, args);
+    }
+    
+    apic::send_init(apic_id);
+    wait_ms(10);
+    for _ in 0..2 {
+        apic::send_startup(apic_id, (TRAMPOLINE_ADDR >> 12) as u8);
+        wait_ms(1);
+    }
+    
+    let deadline = timer::ticks() + timer::ms_to_ticks(STARTUP_TIMEOUT_MS);
+    while online() == cpu && timer::ticks() < deadline {
+        core::hint::spin_loop();
+    }
+    online() > cpu
+}
+
+/// انتظار بتكات PIT (المقاطعات مفعلة على معالج الإقلاع)
+fn wait_ms(ms: u64) {
+    let deadline = timer::ticks() + timer::ms_to_ticks(ms).max(1);
+    while timer::ticks() < deadline {
+        core::hint::spin_loop();
+    }
+}
+
+/// أول شفرة Rust على المعالج التطبيقي، على المكدس المخصص له
+extern "C" fn ap_entry(cpu: usize) -> ! {
+    gdt::init_ap();
+    idt::init_ap();
+    GsBase::write(VirtAddr::new(cpu as u64));
+    apic::init_ap();
+    scheduler::init_ap(cpu);
+    
+    info!("🧵 المعالج {} يعمل (APIC {})", cpu, apic::id());
+    ONLINE.fetch_add(1, Ordering::SeqCst);
+    
+    // سياق البدء هو مهمة الخمول لهذا المعالج
+    loop {
+        scheduler::run();
+        interrupts::wait_for_interrupt();
+    }
+}
+
+/// إعادة إرسال تكة المؤقت من معالج الإقلاع إلى البقية
+pub fn broadcast_tick() {
+    if online() > 1 {
+        apic::broadcast(idt::TICK_VECTOR);
+    }
+}
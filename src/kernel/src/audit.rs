@@ -0,0 +1,79 @@
+//! 🧾 سجل التدقيق
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! أحداث تغيّر حالة النظام أو صلاحياته (مراحل الرصيد، الإغلاق...) تُسجَّل
+//! هنا بجانب سجل النواة. آخر الأحداث في الذاكرة، وكلها تُلحق بـ
+//! `/data/var/log/audit.log` ما دام قرص البيانات متاحاً للكتابة:
+//!
+//! ```text
+//! التكة<TAB>الفئة<TAB>الرسالة
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use log::{info, warn};
+use spin::Mutex;
+
+use crate::fs::{self, vfs, FsError};
+
+/// ملف السجل على قرص البيانات
+pub const AUDIT_LOG: &str = "/data/var/log/audit.log";
+
+/// عدد الأحداث المحفوظة في الذاكرة
+pub const CAPACITY: usize = 128;
+
+/// حدث تدقيق
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub tick: u64,
+    pub category: &'static str,
+    pub message: String,
+}
+
+static ENTRIES: Mutex<VecDeque<AuditEntry>> = Mutex::new(VecDeque::new());
+
+/// تسجيل حدث
+pub fn record(category: &'static str, message: fmt::Arguments) {
+    let entry = AuditEntry {
+        tick: crate::process::scheduler::try_ticks().unwrap_or(0),
+        category,
+        message: format!("{}", message),
+    };
+    info!("🧾 [{}] {}", entry.category, entry.message);
+    
+    if fs::data_available() {
+        if let Err(e) = append(&entry) {
+            warn!("🧾 تعذر إلحاق الحدث بـ {}: {:?}", AUDIT_LOG, e);
+        }
+    }
+    
+    let mut entries = ENTRIES.lock();
+    if entries.len() == CAPACITY {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+fn append(entry: &AuditEntry) -> Result<(), FsError> {
+    for dir in ["/data/var", "/data/var/log"] {
+        if !vfs::exists(dir) {
+            vfs::create_dir(dir)?;
+        }
+    }
+    
+    let line = format!("{}\t{}\t{}\n", entry.tick, entry.category, entry.message.replace('\n', " "));
+    let fd = vfs::open(AUDIT_LOG, vfs::OpenFlags::WRITE | vfs::OpenFlags::CREATE | vfs::OpenFlags::APPEND)?;
+    let written = vfs::write(fd, line.as_bytes());
+    vfs::close(fd)?;
+    written?;
+    Ok(())
+}
+
+/// آخر `count` حدثاً من الأقدم للأحدث
+pub fn recent(count: usize) -> Vec<AuditEntry> {
+    let entries = ENTRIES.lock();
+    entries.iter().skip(entries.len().saturating_sub(count)).cloned().collect()
+}
@@ -0,0 +1,229 @@
+//! 🔙 تتبع المكدس ورموز النواة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يمشي على سلسلة مؤشرات الإطار (`rbp`) ويترجم عناوين العودة بجدول رموز
+//! مضمّن في القسم `.ksyms`. تحجز النواة القسم بحجم ثابت، ويملؤه
+//! `scripts/gen-ksyms.py` بعد الربط، لذا يجب البناء بـ
+//! `-C force-frame-pointers=yes`. لا تخصيص للذاكرة ولا أقفال: يصلح لمعالج الذعر.
+
+use core::fmt;
+
+use crate::drivers::serial;
+
+/// سعة القسم `.ksyms` بالبايت
+pub const KSYMS_CAPACITY: usize = 256 * 1024;
+
+/// أقصى عدد من الإطارات يُعرض
+pub const MAX_FRAMES: usize = 32;
+
+/// إطار أكبر من هذا يعني أن السلسلة فسدت
+#[cfg(not(feature = "hosted"))]
+const MAX_FRAME_SIZE: u64 = 64 * 1024;
+
+const MAGIC: [u8; 4] = *b"KSYM";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 32;
+const ENTRY_SIZE: usize = 16;
+
+/// جدول الرموز المضمّن (فارغ حتى يُشغّل `gen-ksyms.py` على الصورة)
+#[used]
+#[link_section = ".ksyms"]
+static KSYMS: [u8; KSYMS_CAPACITY] = empty_table();
+
+/// ترويسة جدول فارغ صالح؛ المحتوى غير الصفري يُبقي القسم PROGBITS في الصورة
+const fn empty_table() -> [u8; KSYMS_CAPACITY] {
+    let mut blob = [0u8; KSYMS_CAPACITY];
+    let mut i = 0;
+    while i < 4 {
+        blob[i] = MAGIC[i];
+        i += 1;
+    }
+    blob[4] = FORMAT_VERSION as u8;
+    blob[12] = HEADER_SIZE as u8;
+    blob
+}
+
+/// عرض للقراءة فقط فوق جدول رموز مرتب بالعنوان
+#[derive(Clone, Copy)]
+pub struct SymbolTable<'a> {
+    entries: &'a [u8],
+    strings: &'a [u8],
+}
+
+impl<'a> SymbolTable<'a> {
+    /// تحليل جدول بالصيغة التي يكتبها `gen-ksyms.py`
+    pub fn parse(blob: &'a [u8]) -> Option<Self> {
+        let word = |at: usize| -> Option<usize> {
+            let bytes = blob.get(at..at + 4)?;
+            Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+        };
+        
+        if blob.get(..4)? != MAGIC || word(4)? != FORMAT_VERSION as usize {
+            return None;
+        }
+        
+        let (count, strings_offset, strings_len) = (word(8)?, word(12)?, word(16)?);
+        let entries = blob.get(HEADER_SIZE..HEADER_SIZE + count.checked_mul(ENTRY_SIZE)?)?;
+        let strings = blob.get(strings_offset..strings_offset.checked_add(strings_len)?)?;
+        Some(Self { entries, strings })
+    }
+    
+    pub fn len(&self) -> usize {
+        self.entries.len() / ENTRY_SIZE
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    
+    fn entry(&self, index: usize) -> (u64, u64, usize) {
+        let raw = &self.entries[index * ENTRY_SIZE..(index + 1) * ENTRY_SIZE];
+        let address = u64::from_le_bytes(raw[..8].try_into().unwrap());
+        let size = u32::from_le_bytes(raw[8..12].try_into().unwrap()) as u64;
+        let name = u32::from_le_bytes(raw[12..16].try_into().unwrap()) as usize;
+        (address, size, name)
+    }
+    
+    fn name(&self, index: usize) -> &'a str {
+        let start = self.entry(index).2;
+        // الأسماء متتالية بلا فواصل: ينتهي الاسم حيث يبدأ التالي
+        let end = if index + 1 < self.len() { self.entry(index + 1).2 } else { self.strings.len() };
+        self.strings.get(start..end)
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+            .unwrap_or("?")
+    }
+    
+    /// الرمز الذي يحوي العنوان والإزاحة داخله
+    pub fn lookup(&self, address: u64) -> Option<(&'a str, u64)> {
+        // أول رمز يبدأ بعد العنوان، والمطلوب هو الذي قبله
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = (low + high) / 2;
+            if self.entry(middle).0 <= address {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        
+        let index = low.checked_sub(1)?;
+        let (start, size, _) = self.entry(index);
+        let offset = address - start;
+        (offset < size).then(|| (self.name(index), offset))
+    }
+}
+
+/// جدول رموز النواة إن كان قد مُلئ بعد الربط
+pub fn kernel_symbols() -> Option<SymbolTable<'static>> {
+    // المحتوى يُستبدل بعد الترجمة فلا يجوز للمترجم طي قراءته كثابت
+    let blob: &'static [u8; KSYMS_CAPACITY] = core::hint::black_box(&KSYMS);
+    SymbolTable::parse(blob)
+}
+
+/// عنوان مع اسم رمزه عند العرض: `name+0x1a` أو `?`
+pub struct Symbolized<'a>(pub Option<&'a SymbolTable<'a>>, pub u64);
+
+impl fmt::Display for Symbolized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // عنوان العودة يلي تعليمة call، فنبحث عن البايت الذي قبله
+        match self.0.and_then(|table| table.lookup(self.1.saturating_sub(1))) {
+            Some((name, offset)) => write!(f, "{}+{:#x}", name, offset + 1),
+            None => f.write_str("?"),
+        }
+    }
+}
+
+/// المرور على عناوين العودة في المكدس الحالي من الأحدث للأقدم
+///
+/// يعيد عدد الإطارات. في المحاكاة المستضافة لا تُضمن مؤشرات الإطار فلا يمشي.
+#[inline(never)]
+pub fn walk(mut visit: impl FnMut(u64)) -> usize {
+    #[cfg(not(feature = "hosted"))]
+    {
+        let mut rbp = crate::arch::cpu::capture_registers().rbp;
+        let mut depth = 0;
+        
+        while depth < MAX_FRAMES && rbp != 0 && rbp & 7 == 0 {
+            // [rbp] = rbp السابق، [rbp + 8] = عنوان العودة
+            let frame = rbp as *const u64;
+            let (next, return_address) = unsafe { (frame.read(), frame.add(1).read()) };
+            if return_address == 0 {
+                break;
+            }
+            
+            visit(return_address);
+            depth += 1;
+            
+            // المكدس ينمو للأسفل: الإطار الأقدم أعلى دائماً وعلى مسافة معقولة
+            if next <= rbp || next - rbp > MAX_FRAME_SIZE {
+                break;
+            }
+            rbp = next;
+        }
+        
+        depth
+    }
+    
+    #[cfg(feature = "hosted")]
+    {
+        let _ = &mut visit;
+        0
+    }
+}
+
+/// طباعة تتبع المكدس مع الرموز إلى المنفذ التسلسلي
+pub fn print() {
+    let symbols = kernel_symbols();
+    let table = symbols.as_ref().filter(|table| !table.is_empty());
+    
+    serial::emergency_write_fmt(format_args!("🔙 تتبع المكدس:\n"));
+    
+    let mut index = 0;
+    walk(|address| {
+        serial::emergency_write_fmt(format_args!("  #{:<2} {:#018x}  {}\n", index, address, Symbolized(table, address)));
+        index += 1;
+    });
+    
+    if index == 0 {
+        serial::emergency_write_fmt(format_args!("  (غير متاح)\n"));
+    }
+    if table.is_none() {
+        serial::emergency_write_fmt(format_args!("  (لا جدول رموز: شغّل scripts/gen-ksyms.py على صورة النواة)\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    
+    #[test_case]
+    fn test_backtrace_symbol_lookup() {
+        // جدول بصيغة gen-ksyms.py: رمزان، الثاني بعد فجوة
+        let mut blob = vec![0u8; 32];
+        blob[..4].copy_from_slice(b"KSYM");
+        blob[4] = 1;
+        blob[8] = 2;
+        blob[12] = 32 + 2 * 16;
+        blob[16] = 17;
+        for (address, size, name) in [(0x1000u64, 0x40u32, 0u32), (0x2000, 0x10, 11)] {
+            blob.extend_from_slice(&address.to_le_bytes());
+            blob.extend_from_slice(&size.to_le_bytes());
+            blob.extend_from_slice(&name.to_le_bytes());
+        }
+        blob.extend_from_slice(b"kernel_mainpanic");
+        blob.push(b'!');
+        
+        let table = SymbolTable::parse(&blob).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.lookup(0x1000), Some(("kernel_main", 0)));
+        assert_eq!(table.lookup(0x203F), None);
+        assert_eq!(table.lookup(0x1050), None);
+        assert_eq!(table.lookup(0x2004), Some(("panic!", 4)));
+        assert_eq!(alloc::format!("{}", Symbolized(Some(&table), 0x1011)), "kernel_main+0x11");
+        assert_eq!(alloc::format!("{}", Symbolized(Some(&table), 0xFFF)), "?");
+        
+        // قبل تشغيل gen-ksyms.py يكون الجدول المضمّن فارغاً لكنه صالح
+        assert!(kernel_symbols().is_some());
+    }
+}
@@ -0,0 +1,19 @@
+//! 🖥️ تشغيل نواة نظام تشغيل إسلام كعملية لينكس عادية
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يتطلب الميزة `hosted`:
+//! `cargo run -p kernel --features hosted --bin islam_hosted -- [عدد التكات]`
+
+/// عدد التكات الافتراضي
+const DEFAULT_TICKS: u64 = 10_000;
+
+fn main() {
+    let ticks = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(DEFAULT_TICKS);
+    
+    kernel::hosted::run(ticks);
+    
+    print!("{}", kernel::hosted::report());
+}
@@ -0,0 +1,91 @@
+//! 📝 محلل سطر أوامر النواة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الصيغة: كلمات مفصولة بمسافات، كل منها `مفتاح=قيمة` أو `مفتاح` وحده
+//! (يعني `مفتاح=1`). الكلمة الأولى إن كانت مساراً فهي مسار النواة وتُتجاهل.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+/// وضع الإقلاع
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootMode {
+    Normal,
+    /// وضع الاسترداد: `recovery=1`
+    Recovery,
+}
+
+/// معاملات النواة بعد التحليل
+#[derive(Debug, Clone, Default)]
+pub struct KernelParams {
+    raw: String,
+    params: BTreeMap<String, String>,
+}
+
+impl KernelParams {
+    pub fn parse(command_line: &str) -> Self {
+        let mut params = BTreeMap::new();
+        
+        for (i, word) in command_line.split_whitespace().enumerate() {
+            if i == 0 && word.starts_with('/') && !word.contains('=') {
+                continue;
+            }
+            
+            let (key, value) = match word.split_once('=') {
+                Some((key, value)) => (key, value),
+                None => (word, "1"),
+            };
+            if !key.is_empty() {
+                params.insert(key.to_string(), value.to_string());
+            }
+        }
+        
+        Self {
+            raw: command_line.to_string(),
+            params,
+        }
+    }
+    
+    /// سطر الأوامر كما هو
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+    
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(|value| value.as_str())
+    }
+    
+    /// قيمة منطقية: 1/true/yes/on
+    pub fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some("1") | Some("true") | Some("yes") | Some("on"))
+    }
+    
+    pub fn boot_mode(&self) -> BootMode {
+        if self.flag("recovery") {
+            BootMode::Recovery
+        } else {
+            BootMode::Normal
+        }
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_parse_grub_recovery_entry() {
+        let params = KernelParams::parse("/boot/kernel.bin recovery=1 quiet loglevel=debug");
+        assert_eq!(params.boot_mode(), BootMode::Recovery);
+        assert!(params.flag("quiet"));
+        assert_eq!(params.get("loglevel"), Some("debug"));
+        assert_eq!(params.get("/boot/kernel.bin"), None);
+        
+        assert_eq!(KernelParams::parse("recovery=0").boot_mode(), BootMode::Normal);
+        assert_eq!(KernelParams::parse("").boot_mode(), BootMode::Normal);
+    }
+}
@@ -0,0 +1,137 @@
+//! 🥾 معلومات الإقلاع لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يقرأ بنية Multiboot2 التي يسلمها GRUB (`multiboot2 /boot/kernel.bin`)
+//! ويتيح خريطة الذاكرة وسطر الأوامر ومخزن الإطارات و RSDP والوحدات لبقية النواة.
+
+pub mod cmdline;
+pub mod multiboot2;
+
+use log::{info, warn};
+use spin::Once;
+
+use crate::memory::{self, MemoryRegion, MemoryRegionKind};
+pub use cmdline::{BootMode, KernelParams};
+pub use multiboot2::{BootModule, FramebufferInfo, Multiboot2Info, RsdpInfo};
+
+/// أقصى عدد لمناطق خريطة الذاكرة
+const MAX_MEMORY_REGIONS: usize = 64;
+
+/// أقصى عدد للمديات المحجوزة (بنية المعلومات + الوحدات)
+const MAX_RESERVED_RANGES: usize = 16;
+
+/// نسخة ثابتة من خريطة الذاكرة والمديات المحجوزة (لا كومة قبل الذاكرة)
+struct BootMemory {
+    regions: [MemoryRegion; MAX_MEMORY_REGIONS],
+    region_count: usize,
+    reserved: [(u64, u64); MAX_RESERVED_RANGES],
+    reserved_count: usize,
+}
+
+static BOOT_INFO: Once<Multiboot2Info<'static>> = Once::new();
+static BOOT_MEMORY: Once<BootMemory> = Once::new();
+static KERNEL_PARAMS: Once<KernelParams> = Once::new();
+
+/// قراءة معلومات الإقلاع من المسجلات التي مررها `boot_entry`
+pub fn init(magic: u32, info_address: usize) {
+    let info = match unsafe { Multiboot2Info::from_address(magic, info_address) } {
+        Ok(info) => BOOT_INFO.call_once(|| info),
+        Err(e) => {
+            warn!("⚠️ معلومات Multiboot2 غير صالحة: {:?}، استخدام الخريطة الاحتياطية", e);
+            return;
+        }
+    };
+    
+    BOOT_MEMORY.call_once(|| {
+        let mut memory = BootMemory {
+            regions: [MemoryRegion::new(0, 0, MemoryRegionKind::Reserved); MAX_MEMORY_REGIONS],
+            region_count: 0,
+            reserved: [(0, 0); MAX_RESERVED_RANGES],
+            reserved_count: 0,
+        };
+        
+        for region in info.memory_regions().take(MAX_MEMORY_REGIONS) {
+            memory.regions[memory.region_count] = region;
+            memory.region_count += 1;
+        }
+        
+        let ranges = core::iter::once(info.range())
+            .chain(info.modules().map(|module| (module.start, module.end)));
+        for range in ranges.take(MAX_RESERVED_RANGES) {
+            memory.reserved[memory.reserved_count] = range;
+            memory.reserved_count += 1;
+        }
+        
+        memory
+    });
+}
+
+/// معلومات الإقلاع إن كانت صالحة
+pub fn info() -> Option<&'static Multiboot2Info<'static>> {
+    BOOT_INFO.get()
+}
+
+/// خريطة الذاكرة من المحمل، أو الخريطة الاحتياطية
+pub fn memory_map() -> &'static [MemoryRegion] {
+    match BOOT_MEMORY.get() {
+        Some(memory) if memory.region_count > 0 => &memory.regions[..memory.region_count],
+        _ => &memory::FALLBACK_MEMORY_MAP,
+    }
+}
+
+/// المديات الفيزيائية التي لا يجوز لمخصص الإطارات تسليمها
+pub fn reserved_ranges() -> &'static [(u64, u64)] {
+    match BOOT_MEMORY.get() {
+        Some(memory) => &memory.reserved[..memory.reserved_count],
+        None => &[],
+    }
+}
+
+/// سطر أوامر النواة
+pub fn command_line() -> &'static str {
+    info().and_then(|info| info.command_line()).unwrap_or("")
+}
+
+/// معاملات النواة (تُحلَّل عند أول استخدام، بعد تهيئة الكومة)
+pub fn params() -> &'static KernelParams {
+    KERNEL_PARAMS.call_once(|| KernelParams::parse(command_line()))
+}
+
+/// وضع الإقلاع المطلوب من سطر الأوامر
+pub fn mode() -> BootMode {
+    params().boot_mode()
+}
+
+/// عرض ملخص معلومات الإقلاع (بعد تهيئة الكومة والسجل)
+pub fn log_summary() {
+    let info = match info() {
+        Some(info) => info,
+        None => return,
+    };
+    
+    info!("🥾 المحمل: {}", info.bootloader_name().unwrap_or("غير معروف"));
+    info!("📝 سطر الأوامر: {}", command_line());
+    if mode() == BootMode::Recovery {
+        warn!("🧰 وضع الاسترداد مفعل");
+    }
+    
+    let usable: u64 = memory_map().iter()
+        .filter(|region| region.kind == MemoryRegionKind::Usable)
+        .map(|region| region.length)
+        .sum();
+    info!("🗺️ خريطة الذاكرة: {} منطقة، {} ميجابايت قابلة للاستخدام",
+        memory_map().len(),
+        usable / 1024 / 1024);
+    
+    for module in info.modules() {
+        info!("📦 وحدة: {} ({} بايت عند {:#x})", module.command_line, module.size(), module.start);
+    }
+    
+    if let Some(fb) = info.framebuffer() {
+        info!("🖼️ مخزن الإطارات: {}×{}×{} ({:?}) عند {:#x}", fb.width, fb.height, fb.bpp, fb.kind, fb.address);
+    }
+    
+    if let Some(rsdp) = info.rsdp() {
+        info!("⚡ ACPI RSDP: المراجعة {} عند {:#x}", rsdp.revision, rsdp.address);
+    }
+}
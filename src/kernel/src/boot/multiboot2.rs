@@ -0,0 +1,368 @@
+//! 🥾 محلل بنية معلومات الإقلاع Multiboot2
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! محلل بلا تخصيص ذاكرة: يعمل قبل تهيئة الكومة لأن خريطة الذاكرة
+//! نفسها مطلوبة لبناء مخصص الإطارات.
+
+use core::str;
+
+use crate::memory::{MemoryRegion, MemoryRegionKind};
+
+/// القيمة التي يضعها المحمل في EAX عند الإقلاع
+pub const BOOTLOADER_MAGIC: u32 = 0x36d7_6289;
+
+const TAG_END: u32 = 0;
+const TAG_COMMAND_LINE: u32 = 1;
+const TAG_BOOTLOADER_NAME: u32 = 2;
+const TAG_MODULE: u32 = 3;
+const TAG_MEMORY_MAP: u32 = 6;
+const TAG_FRAMEBUFFER: u32 = 8;
+const TAG_ACPI_OLD: u32 = 14;
+const TAG_ACPI_NEW: u32 = 15;
+
+/// أخطاء تحليل معلومات الإقلاع
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootInfoError {
+    BadMagic(u32),
+    NullPointer,
+    Truncated,
+    MissingEndTag,
+}
+
+/// معلومات الإقلاع كما سلمها المحمل
+#[derive(Clone, Copy)]
+pub struct Multiboot2Info<'a> {
+    bytes: &'a [u8],
+    address: u64,
+}
+
+/// وسم واحد من بنية المعلومات
+#[derive(Clone, Copy)]
+struct Tag<'a> {
+    kind: u32,
+    /// محتوى الوسم بعد ترويسة النوع والحجم
+    body: &'a [u8],
+}
+
+/// وحدة محملة (مثل initrd.img)
+#[derive(Debug, Clone, Copy)]
+pub struct BootModule<'a> {
+    pub start: u64,
+    pub end: u64,
+    pub command_line: &'a str,
+}
+
+impl<'a> BootModule<'a> {
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+/// نوع مخزن الإطارات
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramebufferKind {
+    Indexed,
+    Rgb,
+    EgaText,
+    Unknown(u8),
+}
+
+/// واصف مخزن الإطارات
+#[derive(Debug, Clone, Copy)]
+pub struct FramebufferInfo {
+    pub address: u64,
+    pub pitch: u32,
+    pub width: u32,
+    pub height: u32,
+    pub bpp: u8,
+    pub kind: FramebufferKind,
+}
+
+/// مؤشر جذر نظام ACPI (نسخة داخل بنية المعلومات)
+#[derive(Debug, Clone, Copy)]
+pub struct RsdpInfo<'a> {
+    /// عنوان النسخة الفيزيائي، صالح لتمريره إلى محلل ACPI
+    pub address: u64,
+    pub revision: u8,
+    pub bytes: &'a [u8],
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    let low = read_u32(bytes, offset)? as u64;
+    let high = read_u32(bytes, offset + 4)? as u64;
+    Some(low | (high << 32))
+}
+
+/// قراءة نص منتهٍ بصفر
+fn read_cstr(bytes: &[u8]) -> &str {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    str::from_utf8(&bytes[..end]).unwrap_or("")
+}
+
+impl<'a> Multiboot2Info<'a> {
+    /// التحقق من البنية كاملة قبل استخدامها
+    pub fn from_bytes(bytes: &'a [u8], address: u64) -> Result<Self, BootInfoError> {
+        let total_size = read_u32(bytes, 0).ok_or(BootInfoError::Truncated)? as usize;
+        if total_size < 8 || total_size > bytes.len() {
+            return Err(BootInfoError::Truncated);
+        }
+        
+        let info = Self { bytes: &bytes[..total_size], address };
+        
+        let mut offset = 8;
+        loop {
+            let kind = read_u32(info.bytes, offset).ok_or(BootInfoError::MissingEndTag)?;
+            let size = read_u32(info.bytes, offset + 4).ok_or(BootInfoError::MissingEndTag)? as usize;
+            if size < 8 || offset + size > total_size {
+                return Err(BootInfoError::Truncated);
+            }
+            if kind == TAG_END {
+                break;
+            }
+            offset += (size + 7) & !7;
+        }
+        
+        Ok(info)
+    }
+    
+    /// قراءة البنية من العنوان الفيزيائي الذي سلمه المحمل في EBX
+    ///
+    /// # Safety
+    /// `address` يجب أن يشير إلى بنية Multiboot2 سليمة تبقى في الذاكرة طوال عمل النواة
+    pub unsafe fn from_address(magic: u32, address: usize) -> Result<Multiboot2Info<'static>, BootInfoError> {
+        if magic != BOOTLOADER_MAGIC {
+            return Err(BootInfoError::BadMagic(magic));
+        }
+        if address == 0 {
+            return Err(BootInfoError::NullPointer);
+        }
+        
+        let total_size = core::ptr::read_unaligned(address as *const u32) as usize;
+        let bytes = core::slice::from_raw_parts(address as *const u8, total_size);
+        Multiboot2Info::from_bytes(bytes, address as u64)
+    }
+    
+    /// المدى الفيزيائي للبنية نفسها (يجب حجزه من مخصص الإطارات)
+    pub fn range(&self) -> (u64, u64) {
+        (self.address, self.address + self.bytes.len() as u64)
+    }
+    
+    fn tags(&self) -> impl Iterator<Item = Tag<'a>> + 'a {
+        let bytes = self.bytes;
+        let mut offset = 8;
+        
+        core::iter::from_fn(move || {
+            let kind = read_u32(bytes, offset)?;
+            let size = read_u32(bytes, offset + 4)? as usize;
+            if kind == TAG_END || size < 8 {
+                return None;
+            }
+            
+            let body = bytes.get(offset + 8..offset + size)?;
+            offset += (size + 7) & !7;
+            Some(Tag { kind, body })
+        })
+    }
+    
+    fn find_tag(&self, kind: u32) -> Option<Tag<'a>> {
+        self.tags().find(|tag| tag.kind == kind)
+    }
+    
+    /// سطر أوامر النواة
+    pub fn command_line(&self) -> Option<&'a str> {
+        self.find_tag(TAG_COMMAND_LINE).map(|tag| read_cstr(tag.body))
+    }
+    
+    /// اسم المحمل
+    pub fn bootloader_name(&self) -> Option<&'a str> {
+        self.find_tag(TAG_BOOTLOADER_NAME).map(|tag| read_cstr(tag.body))
+    }
+    
+    /// مناطق خريطة الذاكرة
+    pub fn memory_regions(&self) -> impl Iterator<Item = MemoryRegion> + 'a {
+        let tag = self.find_tag(TAG_MEMORY_MAP);
+        let (entry_size, entries) = match tag {
+            Some(tag) => (read_u32(tag.body, 0).unwrap_or(24) as usize, tag.body.get(8..).unwrap_or(&[])),
+            None => (24, &[][..]),
+        };
+        
+        entries.chunks_exact(entry_size.max(24)).filter_map(|entry| {
+            let start = read_u64(entry, 0)?;
+            let length = read_u64(entry, 8)?;
+            let kind = match read_u32(entry, 16)? {
+                1 => MemoryRegionKind::Usable,
+                3 => MemoryRegionKind::AcpiReclaimable,
+                4 => MemoryRegionKind::AcpiNvs,
+                5 => MemoryRegionKind::BadMemory,
+                _ => MemoryRegionKind::Reserved,
+            };
+            Some(MemoryRegion::new(start, length, kind))
+        })
+    }
+    
+    /// الوحدات المحملة بترتيب أسطر `module2`
+    pub fn modules(&self) -> impl Iterator<Item = BootModule<'a>> + 'a {
+        self.tags()
+            .filter(|tag| tag.kind == TAG_MODULE)
+            .filter_map(|tag| {
+                Some(BootModule {
+                    start: read_u32(tag.body, 0)? as u64,
+                    end: read_u32(tag.body, 4)? as u64,
+                    command_line: read_cstr(tag.body.get(8..)?),
+                })
+            })
+    }
+    
+    /// واصف مخزن الإطارات
+    pub fn framebuffer(&self) -> Option<FramebufferInfo> {
+        let body = self.find_tag(TAG_FRAMEBUFFER)?.body;
+        let kind = match *body.get(21)? {
+            0 => FramebufferKind::Indexed,
+            1 => FramebufferKind::Rgb,
+            2 => FramebufferKind::EgaText,
+            other => FramebufferKind::Unknown(other),
+        };
+        
+        Some(FramebufferInfo {
+            address: read_u64(body, 0)?,
+            pitch: read_u32(body, 8)?,
+            width: read_u32(body, 12)?,
+            height: read_u32(body, 16)?,
+            bpp: *body.get(20)?,
+            kind,
+        })
+    }
+    
+    /// مؤشر RSDP (يُفضَّل وسم ACPI 2.0 إن وُجد)
+    pub fn rsdp(&self) -> Option<RsdpInfo<'a>> {
+        let tag = self.find_tag(TAG_ACPI_NEW).or_else(|| self.find_tag(TAG_ACPI_OLD))?;
+        if tag.body.len() < 20 || &tag.body[..8] != b"RSD PTR " {
+            return None;
+        }
+        
+        let offset = tag.body.as_ptr() as u64 - self.bytes.as_ptr() as u64;
+        Some(RsdpInfo {
+            address: self.address + offset,
+            revision: tag.body[15],
+            bytes: tag.body,
+        })
+    }
+}
+
+/// باني بنية Multiboot2 لوضع المحاكاة والاختبارات
+#[cfg(any(test, feature = "hosted"))]
+pub mod builder {
+    use alloc::vec::Vec;
+    
+    use super::*;
+    
+    pub struct InfoBuilder {
+        bytes: Vec<u8>,
+    }
+    
+    impl Default for InfoBuilder {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+    
+    impl InfoBuilder {
+        pub fn new() -> Self {
+            Self { bytes: alloc::vec![0; 8] }
+        }
+        
+        fn tag(mut self, kind: u32, body: &[u8]) -> Self {
+            self.bytes.extend_from_slice(&kind.to_le_bytes());
+            self.bytes.extend_from_slice(&(8 + body.len() as u32).to_le_bytes());
+            self.bytes.extend_from_slice(body);
+            while !self.bytes.len().is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            self
+        }
+        
+        pub fn command_line(self, line: &str) -> Self {
+            let mut body = Vec::from(line.as_bytes());
+            body.push(0);
+            self.tag(TAG_COMMAND_LINE, &body)
+        }
+        
+        pub fn memory_map(self, regions: &[(u64, u64, u32)]) -> Self {
+            let mut body = Vec::new();
+            body.extend_from_slice(&24u32.to_le_bytes());
+            body.extend_from_slice(&0u32.to_le_bytes());
+            for &(start, length, kind) in regions {
+                body.extend_from_slice(&start.to_le_bytes());
+                body.extend_from_slice(&length.to_le_bytes());
+                body.extend_from_slice(&kind.to_le_bytes());
+                body.extend_from_slice(&0u32.to_le_bytes());
+            }
+            self.tag(TAG_MEMORY_MAP, &body)
+        }
+        
+        pub fn module(self, start: u32, end: u32, line: &str) -> Self {
+            let mut body = Vec::new();
+            body.extend_from_slice(&start.to_le_bytes());
+            body.extend_from_slice(&end.to_le_bytes());
+            body.extend_from_slice(line.as_bytes());
+            body.push(0);
+            self.tag(TAG_MODULE, &body)
+        }
+        
+        /// نسخة RSDP كما يضعها GRUB (وسم ACPI 2.0 إن كانت المراجعة 2 فأكثر)
+        pub fn rsdp(self, rsdp: &[u8]) -> Self {
+            let kind = if rsdp.get(15).is_some_and(|&revision| revision >= 2) { TAG_ACPI_NEW } else { TAG_ACPI_OLD };
+            self.tag(kind, rsdp)
+        }
+        
+        pub fn build(self) -> Vec<u8> {
+            let mut this = self.tag(TAG_END, &[]);
+            let total = this.bytes.len() as u32;
+            this.bytes[..4].copy_from_slice(&total.to_le_bytes());
+            this.bytes
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::builder::InfoBuilder;
+    use super::*;
+    
+    #[test_case]
+    fn test_parse_grub_style_info() {
+        let bytes = InfoBuilder::new()
+            .command_line("/boot/kernel.bin recovery=1")
+            .memory_map(&[(0, 0x9_F000, 1), (0xF_0000, 0x1_0000, 2), (0x10_0000, 0x7F0_0000, 1)])
+            .module(0x20_0000, 0x21_0000, "/boot/initrd.img")
+            .build();
+        
+        let info = Multiboot2Info::from_bytes(&bytes, 0x1000).unwrap();
+        assert_eq!(info.command_line(), Some("/boot/kernel.bin recovery=1"));
+        assert_eq!(info.memory_regions().count(), 3);
+        assert_eq!(
+            info.memory_regions().filter(|r| r.kind == MemoryRegionKind::Usable).count(),
+            2
+        );
+        
+        let module = info.modules().next().unwrap();
+        assert_eq!(module.size(), 0x1_0000);
+        assert_eq!(module.command_line, "/boot/initrd.img");
+        assert!(info.framebuffer().is_none());
+        assert!(info.rsdp().is_none());
+    }
+    
+    #[test_case]
+    fn test_reject_truncated_info() {
+        let bytes = InfoBuilder::new().command_line("x").build();
+        assert_eq!(
+            Multiboot2Info::from_bytes(&bytes[..bytes.len() - 8], 0).err(),
+            Some(BootInfoError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! 💥 سجلات الأعطال الدائمة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! عند الذعر يُكتب سجل منظم إلى المنفذ التسلسلي وإلى منطقة محجوزة على
+//! القرص، ويعرضه الإقلاع التالي على غرار pstore في لينكس. الكتابة لا تخصص
+//! ذاكرة ولا تنتظر أي قفل، وتذهب إلى الجهاز مباشرة متجاوزة أي تخزين مؤقت.
+//!
+//! تخطيط المنطقة: ترويسة من 32 بايت (التوقيع، الإصدار، طول النص، CRC32)
+//! يليها نص السجل بصيغة `مفتاح=قيمة` سطراً بسطر.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+use log::{info, warn};
+use spin::Mutex;
+
+use crate::arch::{self, RegisterSnapshot};
+use crate::drivers::block::{BlockDevice, BlockError, SECTOR_SIZE};
+use crate::drivers::serial;
+use crate::utils::inflate::crc32;
+
+/// توقيع المنطقة
+const MAGIC: [u8; 8] = *b"ISLMCRSH";
+
+/// إصدار صيغة السجل
+const FORMAT_VERSION: u32 = 1;
+
+const HEADER_SIZE: usize = 32;
+
+/// الحجم الافتراضي للمنطقة المحجوزة بالقطاعات (32 كيلوبايت)
+pub const REGION_SECTORS: u64 = 64;
+
+const REGION_BYTES: usize = REGION_SECTORS as usize * SECTOR_SIZE;
+
+/// عدد أسطر السجل المرفقة بكل عطل
+pub const LOG_LINES: usize = 48;
+
+/// مخزن ثابت لبناء السجل دون تخصيص ذاكرة
+static mut BUFFER: [u8; REGION_BYTES] = [0; REGION_BYTES];
+
+/// يمنع الذعر المتداخل من الكتابة فوق السجل الأول
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+/// المنطقة المحجوزة على القرص
+struct Region {
+    device: Arc<dyn BlockDevice>,
+    start: u64,
+    sectors: u64,
+}
+
+impl Region {
+    fn capacity(&self) -> usize {
+        (self.sectors as usize * SECTOR_SIZE).min(REGION_BYTES)
+    }
+}
+
+static REGION: Mutex<Option<Region>> = Mutex::new(None);
+
+/// السجل الذي وُجد عند الإقلاع
+static LAST: Mutex<Option<CrashRecord>> = Mutex::new(None);
+
+/// سجل عطل مقروء من القرص
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashRecord {
+    /// الحقول بترتيب كتابتها
+    pub fields: Vec<(String, String)>,
+    /// تتبع المكدس مع الرموز
+    pub backtrace: Vec<String>,
+    /// آخر أسطر سجل النواة قبل العطل
+    pub log: Vec<String>,
+}
+
+impl CrashRecord {
+    /// تحليل نص السجل
+    pub fn parse(text: &str) -> Self {
+        let mut record = CrashRecord { fields: Vec::new(), backtrace: Vec::new(), log: Vec::new() };
+        
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+            let value = unescape(value);
+            match key {
+                "bt" => record.backtrace.push(value),
+                "log" => record.log.push(value),
+                _ => record.fields.push((String::from(key), value)),
+            }
+        }
+        
+        record
+    }
+    
+    /// قيمة حقل بالاسم
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+    
+    pub fn message(&self) -> &str {
+        self.get("message").unwrap_or("")
+    }
+    
+    pub fn location(&self) -> &str {
+        self.get("location").unwrap_or("?")
+    }
+    
+    pub fn uptime_ticks(&self) -> Option<u64> {
+        self.get("uptime_ticks")?.parse().ok()
+    }
+}
+
+/// ربط منطقة محجوزة على جهاز كتلي وقراءة أي سجل معلق فيها
+pub fn attach(device: Arc<dyn BlockDevice>, start: u64, sectors: u64) -> Result<(), BlockError> {
+    if sectors == 0 || !matches!(start.checked_add(sectors), Some(end) if end <= device.sector_count()) {
+        return Err(BlockError::OutOfRange);
+    }
+    
+    let region = Region { device, start, sectors };
+    let record = read_region(&region)?;
+    
+    match &record {
+        Some(record) => {
+            warn!("💥 سجل عطل من إقلاع سابق على {}: {}", region.device.name(), record.message());
+            warn!("📍 الموقع: {} (بعد {} تكت)", record.location(),
+                record.uptime_ticks().map_or(String::from("?"), |t| alloc::format!("{}", t)));
+            warn!("🧰 اعرضه بالأمر `crash` في وحدة الاسترداد، وامسحه بـ `crash clear`");
+        }
+        None => info!("💥 منطقة سجلات الأعطال: {} من القطاع {} ({} قطاعاً)", region.device.name(), start, sectors),
+    }
+    
+    *LAST.lock() = record;
+    *REGION.lock() = Some(region);
+    Ok(())
+}
+
+/// هل هناك منطقة مربوطة؟
+pub fn attached() -> bool {
+    REGION.lock().is_some()
+}
+
+/// فك ربط المنطقة
+pub fn detach() {
+    *REGION.lock() = None;
+    *LAST.lock() = None;
+}
+
+/// السجل الذي وُجد عند الإقلاع إن وُجد
+pub fn last() -> Option<CrashRecord> {
+    LAST.lock().clone()
+}
+
+/// مسح السجل المحفوظ من القرص
+pub fn clear() -> Result<(), BlockError> {
+    if let Some(region) = REGION.lock().as_ref() {
+        region.device.write_sectors(region.start, &[0; SECTOR_SIZE])?;
+        region.device.flush()?;
+    }
+    *LAST.lock() = None;
+    Ok(())
+}
+
+fn read_region(region: &Region) -> Result<Option<CrashRecord>, BlockError> {
+    let mut header = [0u8; SECTOR_SIZE];
+    region.device.read_sectors(region.start, &mut header)?;
+    if header[..8] != MAGIC {
+        return Ok(None);
+    }
+    
+    let field = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+    let (version, len, expected_crc) = (field(8), field(12) as usize, field(16));
+    if version != FORMAT_VERSION || HEADER_SIZE + len > region.capacity() {
+        warn!("⚠️ سجل عطل بصيغة غير معروفة (الإصدار {}) على {}", version, region.device.name());
+        return Ok(None);
+    }
+    
+    let mut data = vec![0u8; (HEADER_SIZE + len).div_ceil(SECTOR_SIZE) * SECTOR_SIZE];
+    region.device.read_sectors(region.start, &mut data)?;
+    let body = &data[HEADER_SIZE..HEADER_SIZE + len];
+    
+    if crc32(body) != expected_crc {
+        warn!("⚠️ سجل العطل على {} تالف (CRC32 غير مطابق)", region.device.name());
+        return Ok(None);
+    }
+    
+    Ok(Some(CrashRecord::parse(&String::from_utf8_lossy(body))))
+}
+
+/// تسجيل عطل من معالج الذعر
+pub fn record_panic(info: &PanicInfo) {
+    let registers = arch::cpu::capture_registers();
+    let location = info.location().map(|l| (l.file(), l.line(), l.column()));
+    
+    record(format_args!("{}", info.message()), location, registers);
+}
+
+/// كتابة سجل عطل إلى المنفذ التسلسلي والمنطقة المحجوزة
+pub fn record(message: fmt::Arguments, location: Option<(&str, u32, u32)>, registers: RegisterSnapshot) {
+    if WRITING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    
+    // المخزن الثابت لا يمسه أحد غيرنا ما دام WRITING مرفوعاً
+    let buffer = unsafe { &mut *core::ptr::addr_of_mut!(BUFFER) };
+    let guard = REGION.try_lock();
+    let region = guard.as_deref().and_then(Option::as_ref);
+    let capacity = region.map_or(REGION_BYTES, Region::capacity);
+    
+    let mut body = FixedWriter { buf: &mut buffer[HEADER_SIZE..capacity], len: 0 };
+    write_body(&mut body, message, location, &registers);
+    let len = body.len;
+    
+    let crc = crc32(&buffer[HEADER_SIZE..HEADER_SIZE + len]);
+    buffer[..HEADER_SIZE].fill(0);
+    buffer[..8].copy_from_slice(&MAGIC);
+    buffer[8..12].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+    buffer[12..16].copy_from_slice(&(len as u32).to_le_bytes());
+    buffer[16..20].copy_from_slice(&crc.to_le_bytes());
+    
+    let text = core::str::from_utf8(&buffer[HEADER_SIZE..HEADER_SIZE + len]).unwrap_or("");
+    serial::emergency_write_fmt(format_args!("\n---[ سجل العطل ]---\n{}---[ نهاية سجل العطل ]---\n", text));
+    
+    if let Some(region) = region {
+        let end = (HEADER_SIZE + len).div_ceil(SECTOR_SIZE) * SECTOR_SIZE;
+        buffer[HEADER_SIZE + len..end].fill(0);
+        
+        let written = region.device.write_sectors(region.start, &buffer[..end])
+            .and_then(|_| region.device.flush());
+        if written.is_err() {
+            serial::emergency_write_fmt(format_args!("❌ تعذرت كتابة سجل العطل إلى {}\n", region.device.name()));
+        }
+    }
+    
+    drop(guard);
+    WRITING.store(false, Ordering::SeqCst);
+}
+
+/// بناء نص السجل من الحالة الحية دون انتظار أي قفل
+fn write_body(out: &mut FixedWriter, message: fmt::Arguments, location: Option<(&str, u32, u32)>, registers: &RegisterSnapshot) {
+    let _ = writeln!(out, "version={}", FORMAT_VERSION);
+    let _ = writeln!(out, "system={} {}", crate::SYSTEM_NAME, crate::SYSTEM_VERSION);
+    match crate::process::scheduler::try_ticks() {
+        Some(ticks) => { let _ = writeln!(out, "uptime_ticks={}", ticks); }
+        None => { let _ = writeln!(out, "uptime_ticks=?"); }
+    }
+    if let Some((file, line, column)) = location {
+        let _ = writeln!(out, "location={}:{}:{}", file, line, column);
+    }
+    let _ = out.write_str("message=");
+    let _ = Escaped(out).write_fmt(message);
+    let _ = out.write_str("\n");
+    
+    match crate::SYSTEM_STATE.try_lock() {
+        Some(state) => {
+            let _ = writeln!(out, "state.initialized={}", state.is_initialized);
+            let _ = writeln!(out, "state.uptime_ticks={}", state.uptime_ticks);
+            let _ = writeln!(out, "state.memory_used={}", state.memory_usage.used);
+            let _ = writeln!(out, "state.memory_total={}", state.memory_usage.total);
+            let _ = writeln!(out, "state.cpu_usage={:.1}", state.cpu_usage.usage);
+            let _ = writeln!(out, "state.security_level={:?}", state.security_level);
+            let _ = writeln!(out, "state.ai_enabled={}", state.ai_enabled);
+            let _ = writeln!(out, "state.network_connected={}", state.network_connected);
+            let _ = writeln!(out, "state.users={}", state.users.len());
+            let _ = writeln!(out, "state.active_processes={}", state.active_processes);
+        }
+        None => { let _ = writeln!(out, "state=locked"); }
+    }
+    
+    match crate::TOKEN_MANAGER.try_lock() {
+        Some(tokens) => {
+            let _ = writeln!(out, "tokens.current={}", tokens.current_tokens);
+            let _ = writeln!(out, "tokens.min={}", tokens.min_tokens);
+            let _ = writeln!(out, "tokens.locked={}", tokens.is_locked);
+            let _ = writeln!(out, "tokens.last_check={}", tokens.last_check);
+            let _ = writeln!(out, "tokens.ledger={}", tokens.ledger.name());
+            let _ = writeln!(out, "tokens.state={}", tokens.policy.state().name());
+        }
+        None => { let _ = writeln!(out, "tokens=locked"); }
+    }
+    
+    for (name, value) in registers.fields() {
+        let _ = writeln!(out, "reg.{}={:#018x}", name, value);
+    }
+    
+    let symbols = crate::backtrace::kernel_symbols();
+    let table = symbols.as_ref().filter(|table| !table.is_empty());
+    crate::backtrace::walk(|address| {
+        let _ = write!(out, "bt={:#018x} ", address);
+        let _ = write!(Escaped(out), "{}", crate::backtrace::Symbolized(table, address));
+        let _ = out.write_str("\n");
+    });
+    
+    let available = crate::dmesg::for_each_recent(LOG_LINES, |tick, level, text| {
+        let _ = write!(out, "log=[{:>8}] {:<5} ", tick, level);
+        let _ = Escaped(out).write_str(text);
+        let _ = out.write_str("\n");
+    });
+    if !available {
+        let _ = writeln!(out, "log.unavailable=true");
+    }
+}
+
+/// كاتب فوق مخزن ثابت يقتطع عند الامتلاء عند حدود الأحرف
+struct FixedWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for FixedWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let width = c.len_utf8();
+            if self.len + width > self.buf.len() {
+                return Err(fmt::Error);
+            }
+            c.encode_utf8(&mut self.buf[self.len..]);
+            self.len += width;
+        }
+        Ok(())
+    }
+}
+
+/// يهرّب فواصل الأسطر حتى تبقى كل قيمة على سطر واحد
+struct Escaped<'a, 'b>(&'a mut FixedWriter<'b>);
+
+impl Write for Escaped<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                c => self.0.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
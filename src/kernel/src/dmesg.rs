@@ -0,0 +1,125 @@
+//! 📜 حلقة سجل النواة (dmesg)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! حلقة ثابتة الحجم في الذاكرة تحتفظ بآخر الأسطر المسجلة. لا تخصص ذاكرة
+//! عند الإضافة، لذا يمكن ملؤها من المقاطعات وقراءتها من معالج الذعر. يملؤها
+//! `logger` بكل ما يمر من مرشحاته، ويقرؤها أمر `dmesg` وسجل العطل.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use log::Level;
+use spin::Mutex;
+
+/// عدد الأسطر المحفوظة
+pub const CAPACITY: usize = 256;
+
+/// أقصى طول للسطر بالبايت (يُقتطع الزائد)
+pub const LINE_MAX: usize = 160;
+
+#[derive(Clone, Copy)]
+struct Line {
+    tick: u64,
+    level: Level,
+    len: usize,
+    bytes: [u8; LINE_MAX],
+}
+
+impl Line {
+    const EMPTY: Line = Line { tick: 0, level: Level::Info, len: 0, bytes: [0; LINE_MAX] };
+    
+    fn text(&self) -> &str {
+        // الاقتطاع يتم عند حدود الأحرف فالنص صالح دائماً
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+impl fmt::Write for Line {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let c = if c == '\n' { ' ' } else { c };
+            let width = c.len_utf8();
+            if self.len + width > LINE_MAX {
+                break;
+            }
+            c.encode_utf8(&mut self.bytes[self.len..]);
+            self.len += width;
+        }
+        Ok(())
+    }
+}
+
+struct Ring {
+    lines: [Line; CAPACITY],
+    next: usize,
+    len: usize,
+}
+
+static RING: Mutex<Ring> = Mutex::new(Ring { lines: [Line::EMPTY; CAPACITY], next: 0, len: 0 });
+
+/// سطر مقروء من الحلقة
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub tick: u64,
+    pub level: Level,
+    pub text: String,
+}
+
+/// إضافة سطر إلى الحلقة (يُستبدل الأقدم عند الامتلاء)
+pub fn push(level: Level, args: fmt::Arguments) {
+    crate::arch::interrupts::without_interrupts(|| {
+        let mut ring = RING.lock();
+        let slot = ring.next;
+        // قد يكون السجل صادراً من داخل الجدولة نفسها وقفلها محتجز
+        let previous = ring.lines[(slot + CAPACITY - 1) % CAPACITY].tick;
+        let tick = crate::process::scheduler::try_ticks().unwrap_or(previous);
+        
+        let line = &mut ring.lines[slot];
+        line.tick = tick;
+        line.level = level;
+        line.len = 0;
+        let _ = fmt::Write::write_fmt(line, args);
+        
+        ring.next = (slot + 1) % CAPACITY;
+        ring.len = (ring.len + 1).min(CAPACITY);
+    });
+}
+
+/// المرور على آخر `count` سطراً من الأقدم للأحدث دون تخصيص ذاكرة
+///
+/// تستخدم `try_lock` فلا تعلق إذا وقع الذعر أثناء الكتابة في الحلقة.
+pub fn for_each_recent(count: usize, mut visit: impl FnMut(u64, Level, &str)) -> bool {
+    let ring = match RING.try_lock() {
+        Some(ring) => ring,
+        None => return false,
+    };
+    
+    let count = count.min(ring.len);
+    for i in 0..count {
+        let index = (ring.next + CAPACITY - count + i) % CAPACITY;
+        let line = &ring.lines[index];
+        visit(line.tick, line.level, line.text());
+    }
+    true
+}
+
+/// نسخة من آخر `count` سطراً
+pub fn recent(count: usize) -> Vec<LogLine> {
+    let mut lines = Vec::new();
+    for_each_recent(count, |tick, level, text| {
+        lines.push(LogLine { tick, level, text: String::from(text) });
+    });
+    lines
+}
+
+/// عدد الأسطر المحفوظة حالياً
+pub fn len() -> usize {
+    RING.lock().len
+}
+
+/// تفريغ الحلقة
+pub fn clear() {
+    let mut ring = RING.lock();
+    ring.next = 0;
+    ring.len = 0;
+}
@@ -0,0 +1,590 @@
+//! 💿 أقراص ATA بالإدخال والإخراج المبرمج (PIO)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! متحكم IDE (الفئة 01:01) له قناتان، ولكل قناة قرص رئيسي وتابع. المنافذ
+//! التقليدية 0x1F0/0x3F6 و 0x170/0x376 إلا إن كانت القناة في الوضع الأصلي
+//! (بتات prog-if 0 و 2) فتأتي من BAR0..BAR3. النقل بالاستطلاع ومقاطعات
+//! القناة معطلة (nIEN):
+//!
+//! ```text
+//! IDENTIFY (0xEC) ──▶ 256 كلمة: الطراز، LBA28/LBA48، عدد القطاعات
+//! READ  (0x20 / 0x24 EXT)  ──▶ لكل قطاع: انتظار DRQ ثم 256 كلمة
+//! WRITE (0x30 / 0x34 EXT)  ──▶ لكل قطاع: انتظار DRQ ثم 256 كلمة
+//! FLUSH (0xE7 / 0xEA EXT)
+//! ```
+//!
+//! الأقراص تُسمى hda و hdb (القناة الأولى) و hdc و hdd (الثانية). في
+//! المحاكاة تحاكي القناة أقراصاً في الذاكرة تُركّب بـ `hosted_channel`.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use log::{info, warn};
+use spin::Mutex;
+
+use super::{check_range, BlockDevice, BlockError, SECTOR_SIZE};
+use crate::drivers::pci::{self, DeviceId, PciAddress, PciDevice, PciDriver};
+
+/// مسجلات كتلة الأوامر (إزاحات من منفذ القناة)
+pub mod register {
+    pub const DATA: u16 = 0;
+    pub const ERROR: u16 = 1;
+    pub const SECTOR_COUNT: u16 = 2;
+    pub const LBA_LOW: u16 = 3;
+    pub const LBA_MID: u16 = 4;
+    pub const LBA_HIGH: u16 = 5;
+    pub const DRIVE: u16 = 6;
+    /// الحالة عند القراءة والأمر عند الكتابة
+    pub const STATUS: u16 = 7;
+}
+
+/// بتات الحالة
+pub mod status {
+    pub const ERR: u8 = 1 << 0;
+    pub const DRQ: u8 = 1 << 3;
+    pub const DF: u8 = 1 << 5;
+    pub const DRDY: u8 = 1 << 6;
+    pub const BSY: u8 = 1 << 7;
+}
+
+/// الأوامر
+pub mod command {
+    pub const READ: u8 = 0x20;
+    pub const READ_EXT: u8 = 0x24;
+    pub const WRITE: u8 = 0x30;
+    pub const WRITE_EXT: u8 = 0x34;
+    pub const FLUSH: u8 = 0xE7;
+    pub const FLUSH_EXT: u8 = 0xEA;
+    pub const IDENTIFY: u8 = 0xEC;
+}
+
+/// بت nIEN في مسجل التحكم
+const CONTROL_NO_INTERRUPTS: u8 = 1 << 1;
+
+/// أقصى قطاعات في أمر واحد (عداد القطاعات ببايت واحد في LBA28)
+const MAX_TRANSFER: usize = 128;
+
+/// محاولات الانتظار قبل اعتبار القرص معطلاً
+const SPINS: usize = 1_000_000;
+
+/// الوصول إلى مسجلات قناة واحدة
+pub trait Channel: Send + Sync {
+    fn read(&self, register: u16) -> u8;
+    fn write(&self, register: u16, value: u8);
+    fn read_data(&self) -> u16;
+    fn write_data(&self, value: u16);
+    /// الحالة البديلة: قراءتها لا تمسح أي شيء (للانتظار 400 نانوثانية)
+    fn alternate_status(&self) -> u8;
+    fn set_control(&self, value: u8);
+}
+
+/// قناة على منافذ الإدخال والإخراج
+#[cfg(not(feature = "hosted"))]
+pub struct PortChannel {
+    base: u16,
+    control: u16,
+}
+
+#[cfg(not(feature = "hosted"))]
+impl PortChannel {
+    pub const fn new(base: u16, control: u16) -> Self {
+        Self { base, control }
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+impl Channel for PortChannel {
+    fn read(&self, register: u16) -> u8 {
+        unsafe { x86_64::instructions::port::Port::new(self.base + register).read() }
+    }
+    
+    fn write(&self, register: u16, value: u8) {
+        unsafe { x86_64::instructions::port::Port::new(self.base + register).write(value) }
+    }
+    
+    fn read_data(&self) -> u16 {
+        unsafe { x86_64::instructions::port::Port::new(self.base + register::DATA).read() }
+    }
+    
+    fn write_data(&self, value: u16) {
+        unsafe { x86_64::instructions::port::Port::new(self.base + register::DATA).write(value) }
+    }
+    
+    fn alternate_status(&self) -> u8 {
+        unsafe { x86_64::instructions::port::Port::new(self.control).read() }
+    }
+    
+    fn set_control(&self, value: u8) {
+        unsafe { x86_64::instructions::port::Port::new(self.control).write(value) }
+    }
+}
+
+/// ما يعلنه القرص في IDENTIFY
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub model: String,
+    pub sectors: u64,
+    pub lba48: bool,
+}
+
+impl Identity {
+    /// تحليل كلمات IDENTIFY؛ النصوص مخزنة ببايتين مقلوبين في كل كلمة
+    pub fn parse(words: &[u16; 256]) -> Self {
+        let model: Vec<u8> = words[27..47].iter().flat_map(|word| word.to_be_bytes()).collect();
+        let lba48 = words[83] & (1 << 10) != 0;
+        let sectors = if lba48 {
+            (0..4).fold(0u64, |total, i| total | (words[100 + i] as u64) << (16 * i))
+        } else {
+            words[60] as u64 | (words[61] as u64) << 16
+        };
+        Identity { model: String::from_utf8_lossy(&model).trim().into(), sectors, lba48 }
+    }
+}
+
+/// انتظار زوال BSY؛ مع `drq` انتظار طلب البيانات أيضاً
+fn wait(channel: &dyn Channel, drq: bool) -> Result<(), BlockError> {
+    for _ in 0..SPINS {
+        let state = channel.read(register::STATUS);
+        if state & status::BSY != 0 {
+            continue;
+        }
+        if state & (status::ERR | status::DF) != 0 {
+            return Err(BlockError::Io);
+        }
+        if !drq || state & status::DRQ != 0 {
+            return Ok(());
+        }
+    }
+    Err(BlockError::Io)
+}
+
+/// مهلة 400 نانوثانية بعد اختيار القرص
+fn settle(channel: &dyn Channel) {
+    for _ in 0..4 {
+        channel.alternate_status();
+    }
+}
+
+/// إرسال IDENTIFY إلى القرص؛ `None` إن لم يكن هناك قرص ATA
+pub fn identify(channel: &dyn Channel, slave: bool) -> Option<Identity> {
+    channel.set_control(CONTROL_NO_INTERRUPTS);
+    channel.write(register::DRIVE, 0xA0 | (slave as u8) << 4);
+    settle(channel);
+    for reg in [register::SECTOR_COUNT, register::LBA_LOW, register::LBA_MID, register::LBA_HIGH] {
+        channel.write(reg, 0);
+    }
+    channel.write(register::STATUS, command::IDENTIFY);
+    
+    // الحالة صفر أو 0xFF: لا قرص على هذا الموقع أو لا قناة أصلاً
+    if matches!(channel.read(register::STATUS), 0 | 0xFF) {
+        return None;
+    }
+    (0..SPINS).find(|_| channel.read(register::STATUS) & status::BSY == 0)?;
+    
+    // ATAPI و SATA تضع توقيعاً في LBA_MID/LBA_HIGH بدل البيانات
+    if channel.read(register::LBA_MID) != 0 || channel.read(register::LBA_HIGH) != 0 {
+        return None;
+    }
+    wait(channel, true).ok()?;
+    
+    let mut words = [0u16; 256];
+    for word in words.iter_mut() {
+        *word = channel.read_data();
+    }
+    Some(Identity::parse(&words))
+}
+
+/// قرص على قناة
+pub struct AtaDrive {
+    name: String,
+    channel: Arc<dyn Channel>,
+    slave: bool,
+    identity: Identity,
+    /// القناة مشتركة بين قرصين، فالقفل يخص القناة لا القرص
+    lock: Arc<Mutex<()>>,
+}
+
+impl AtaDrive {
+    pub fn identity(&self) -> &Identity {
+        &self.identity
+    }
+    
+    /// اختيار القرص ومدى القطاعات ثم إرسال الأمر
+    fn issue(&self, lba: u64, count: usize, lba28: u8, lba48: u8) {
+        let channel = &*self.channel;
+        if self.identity.lba48 {
+            channel.write(register::DRIVE, 0x40 | (self.slave as u8) << 4);
+            settle(channel);
+            // البايتات العليا أولاً ثم الدنيا في المسجلات نفسها
+            channel.write(register::SECTOR_COUNT, (count >> 8) as u8);
+            channel.write(register::LBA_LOW, (lba >> 24) as u8);
+            channel.write(register::LBA_MID, (lba >> 32) as u8);
+            channel.write(register::LBA_HIGH, (lba >> 40) as u8);
+            channel.write(register::SECTOR_COUNT, count as u8);
+            channel.write(register::LBA_LOW, lba as u8);
+            channel.write(register::LBA_MID, (lba >> 8) as u8);
+            channel.write(register::LBA_HIGH, (lba >> 16) as u8);
+            channel.write(register::STATUS, lba48);
+        } else {
+            channel.write(register::DRIVE, 0xE0 | (self.slave as u8) << 4 | ((lba >> 24) & 0x0F) as u8);
+            settle(channel);
+            channel.write(register::SECTOR_COUNT, count as u8);
+            channel.write(register::LBA_LOW, lba as u8);
+            channel.write(register::LBA_MID, (lba >> 8) as u8);
+            channel.write(register::LBA_HIGH, (lba >> 16) as u8);
+            channel.write(register::STATUS, lba28);
+        }
+    }
+}
+
+impl BlockDevice for AtaDrive {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn sector_count(&self) -> u64 {
+        self.identity.sectors
+    }
+    
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        check_range(self, lba, buf.len())?;
+        let _guard = self.lock.lock();
+        
+        for (index, chunk) in buf.chunks_mut(MAX_TRANSFER * SECTOR_SIZE).enumerate() {
+            self.issue(lba + (index * MAX_TRANSFER) as u64, chunk.len() / SECTOR_SIZE, command::READ, command::READ_EXT);
+            for sector in chunk.chunks_exact_mut(SECTOR_SIZE) {
+                wait(&*self.channel, true)?;
+                for pair in sector.chunks_exact_mut(2) {
+                    pair.copy_from_slice(&self.channel.read_data().to_le_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError> {
+        check_range(self, lba, data.len())?;
+        let _guard = self.lock.lock();
+        
+        for (index, chunk) in data.chunks(MAX_TRANSFER * SECTOR_SIZE).enumerate() {
+            self.issue(lba + (index * MAX_TRANSFER) as u64, chunk.len() / SECTOR_SIZE, command::WRITE, command::WRITE_EXT);
+            for sector in chunk.chunks_exact(SECTOR_SIZE) {
+                wait(&*self.channel, true)?;
+                for pair in sector.chunks_exact(2) {
+                    self.channel.write_data(u16::from_le_bytes([pair[0], pair[1]]));
+                }
+            }
+            wait(&*self.channel, false)?;
+        }
+        Ok(())
+    }
+    
+    fn flush(&self) -> Result<(), BlockError> {
+        let _guard = self.lock.lock();
+        self.channel.write(register::DRIVE, 0xA0 | (self.slave as u8) << 4);
+        settle(&*self.channel);
+        self.channel.write(register::STATUS, if self.identity.lba48 { command::FLUSH_EXT } else { command::FLUSH });
+        wait(&*self.channel, false)
+    }
+}
+
+/// الأقراص التي سجلها التعريف (لنزعها في `remove`)
+static DRIVES: Mutex<Vec<(PciAddress, String)>> = Mutex::new(Vec::new());
+
+/// اكتشاف قرصي القناة `index` وتسجيلهما
+fn probe_channel(device: &PciDevice, index: usize, channel: Arc<dyn Channel>) -> usize {
+    let lock = Arc::new(Mutex::new(()));
+    let mut found = 0;
+    
+    for slave in [false, true] {
+        let Some(identity) = identify(&*channel, slave) else {
+            continue;
+        };
+        let name = alloc::format!("hd{}", (b'a' + (index * 2) as u8 + slave as u8) as char);
+        info!("  💿 {}: {} ({} ميجابايت{})", name, identity.model, identity.sectors * SECTOR_SIZE as u64 / 1024 / 1024,
+            if identity.lba48 { "، LBA48" } else { "" });
+        
+        let drive = AtaDrive { name: name.clone(), channel: channel.clone(), slave, identity, lock: lock.clone() };
+        super::register_disk(Arc::new(drive));
+        DRIVES.lock().push((device.address, name));
+        found += 1;
+    }
+    found
+}
+
+/// منافذ القناة: من BAR إن كانت في الوضع الأصلي وإلا التقليدية
+fn channel_ports(device: &PciDevice, index: usize) -> (u16, u16) {
+    const LEGACY: [(u16, u16); 2] = [(0x1F0, 0x3F6), (0x170, 0x376)];
+    
+    let native = device.prog_if & (1 << (index * 2)) != 0;
+    let bar = |n: usize| match device.bars.get(n) {
+        Some(Some(pci::Bar::Io { port, .. })) => Some(*port),
+        _ => None,
+    };
+    match (native, bar(index * 2), bar(index * 2 + 1)) {
+        // منفذ التحكم في BAR الثاني بإزاحة 2
+        (true, Some(base), Some(control)) => (base, control + 2),
+        _ => LEGACY[index],
+    }
+}
+
+fn probe(device: &PciDevice) -> Result<(), String> {
+    let mut found = 0;
+    for index in 0..2 {
+        let (base, control) = channel_ports(device, index);
+        found += probe_channel(device, index, channel(base, control));
+    }
+    
+    if found == 0 {
+        warn!("  ⚠️ متحكم IDE {} بلا أقراص", device.address);
+    }
+    Ok(())
+}
+
+fn remove(device: &PciDevice) {
+    DRIVES.lock().retain(|(address, name)| {
+        if *address == device.address {
+            super::unregister_disk(name);
+        }
+        *address != device.address
+    });
+}
+
+/// تعريف متحكمات IDE
+pub static DRIVER: PciDriver = PciDriver {
+    name: "ata-pio",
+    ids: &[DeviceId::class(0x01, 0x01)],
+    probe,
+    remove,
+};
+
+#[cfg(not(feature = "hosted"))]
+fn channel(base: u16, control: u16) -> Arc<dyn Channel> {
+    Arc::new(PortChannel::new(base, control))
+}
+
+#[cfg(feature = "hosted")]
+fn channel(base: u16, _control: u16) -> Arc<dyn Channel> {
+    hosted_channel(if base == 0x170 { 1 } else { 0 })
+}
+
+#[cfg(feature = "hosted")]
+pub use self::simulated::{hosted_channel, SimulatedChannel};
+
+/// قناة محاكاة تنفذ أوامر ATA على صور أقراص في الذاكرة
+#[cfg(feature = "hosted")]
+mod simulated {
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use lazy_static::lazy_static;
+    use spin::Mutex;
+    
+    use super::{command, register, status, Channel, SECTOR_SIZE};
+    
+    #[derive(Default)]
+    struct State {
+        drives: [Option<Vec<u8>>; 2],
+        selected: usize,
+        /// قيم المسجلات 1..5، والسابقة منها لأوامر LBA48
+        registers: [u8; 8],
+        previous: [u8; 8],
+        status: u8,
+        /// الكلمات المنتظر قراءتها أو المكتوبة حتى الآن
+        buffer: Vec<u16>,
+        position: usize,
+        /// النقل الجاري: (كتابة؟، القطاع التالي، القطاعات الباقية)
+        transfer: Option<(bool, u64, u64)>,
+    }
+    
+    impl State {
+        fn drive(&mut self) -> Option<&mut Vec<u8>> {
+            self.drives[self.selected].as_mut()
+        }
+        
+        fn lba(&self, extended: bool) -> (u64, u64) {
+            let [_, _, count, low, mid, high, drive, _] = self.registers;
+            if extended {
+                let [_, _, count_high, low_high, mid_high, high_high, _, _] = self.previous;
+                let lba = [low, mid, high, low_high, mid_high, high_high].iter().enumerate().fold(0u64, |lba, (i, &b)| lba | (b as u64) << (8 * i));
+                let count = (count_high as u64) << 8 | count as u64;
+                (lba, if count == 0 { 65536 } else { count })
+            } else {
+                let lba = low as u64 | (mid as u64) << 8 | (high as u64) << 16 | ((drive & 0x0F) as u64) << 24;
+                (lba, if count == 0 { 256 } else { count as u64 })
+            }
+        }
+        
+        fn load_sector(&mut self, lba: u64) {
+            let start = lba as usize * SECTOR_SIZE;
+            let words = self.drive().map(|image| image[start..start + SECTOR_SIZE].chunks_exact(2).map(|p| u16::from_le_bytes([p[0], p[1]])).collect());
+            self.buffer = words.unwrap_or_default();
+            self.position = 0;
+        }
+        
+        fn identify(&mut self) -> Vec<u16> {
+            let sectors = (self.drive().map_or(0, |image| image.len()) / SECTOR_SIZE) as u64;
+            let mut words = alloc::vec![0u16; 256];
+            for (i, pair) in b"QEMU HARDDISK                           ".chunks_exact(2).enumerate() {
+                words[27 + i] = u16::from_be_bytes([pair[0], pair[1]]);
+            }
+            words[60] = sectors.min(0x0FFF_FFFF) as u16;
+            words[61] = (sectors.min(0x0FFF_FFFF) >> 16) as u16;
+            words[83] = 1 << 10;
+            for i in 0..4 {
+                words[100 + i] = (sectors >> (16 * i)) as u16;
+            }
+            words
+        }
+        
+        fn execute(&mut self, code: u8) {
+            if self.drive().is_none() {
+                self.status = 0;
+                return;
+            }
+            let sectors = (self.drive().map_or(0, |image| image.len()) / SECTOR_SIZE) as u64;
+            self.transfer = None;
+            
+            match code {
+                command::IDENTIFY => {
+                    self.buffer = self.identify();
+                    self.position = 0;
+                    self.status = status::DRDY | status::DRQ;
+                }
+                command::READ | command::READ_EXT | command::WRITE | command::WRITE_EXT => {
+                    let (lba, count) = self.lba(matches!(code, command::READ_EXT | command::WRITE_EXT));
+                    if lba + count > sectors {
+                        self.status = status::DRDY | status::ERR;
+                        return;
+                    }
+                    let write = matches!(code, command::WRITE | command::WRITE_EXT);
+                    self.transfer = Some((write, lba, count));
+                    if write {
+                        self.buffer.clear();
+                    } else {
+                        self.load_sector(lba);
+                    }
+                    self.status = status::DRDY | status::DRQ;
+                }
+                command::FLUSH | command::FLUSH_EXT => self.status = status::DRDY,
+                _ => self.status = status::DRDY | status::ERR,
+            }
+        }
+        
+        /// انتهى قطاع: التالي أو نهاية النقل
+        fn advance(&mut self) {
+            let Some((write, lba, remaining)) = self.transfer else {
+                self.status = status::DRDY;
+                return;
+            };
+            if remaining > 1 {
+                self.transfer = Some((write, lba + 1, remaining - 1));
+                if !write {
+                    self.load_sector(lba + 1);
+                }
+                self.status = status::DRDY | status::DRQ;
+            } else {
+                self.transfer = None;
+                self.status = status::DRDY;
+            }
+        }
+    }
+    
+    /// قناة IDE محاكاة بقرصين اختياريين
+    pub struct SimulatedChannel {
+        state: Mutex<State>,
+    }
+    
+    impl SimulatedChannel {
+        fn new() -> Self {
+            Self { state: Mutex::new(State::default()) }
+        }
+        
+        /// تركيب صورة قرص في الموقع الرئيسي أو التابع
+        pub fn attach(&self, slave: bool, mut image: Vec<u8>) {
+            image.resize(image.len().div_ceil(SECTOR_SIZE) * SECTOR_SIZE, 0);
+            self.state.lock().drives[slave as usize] = Some(image);
+        }
+        
+        /// نزع القرص وإعادة صورته
+        pub fn detach(&self, slave: bool) -> Option<Vec<u8>> {
+            self.state.lock().drives[slave as usize].take()
+        }
+        
+        /// نسخة من صورة القرص الحالية
+        pub fn image(&self, slave: bool) -> Option<Vec<u8>> {
+            self.state.lock().drives[slave as usize].clone()
+        }
+    }
+    
+    impl Channel for SimulatedChannel {
+        fn read(&self, reg: u16) -> u8 {
+            let state = self.state.lock();
+            match reg {
+                register::STATUS => state.status,
+                _ => state.registers[reg as usize],
+            }
+        }
+        
+        fn write(&self, reg: u16, value: u8) {
+            let mut state = self.state.lock();
+            match reg {
+                register::STATUS => state.execute(value),
+                register::DRIVE => {
+                    state.selected = (value >> 4 & 1) as usize;
+                    state.registers[reg as usize] = value;
+                    // القرص الغائب لا يرد
+                    state.status = if state.drive().is_some() { status::DRDY } else { 0 };
+                }
+                _ => {
+                    let index = reg as usize;
+                    state.previous[index] = state.registers[index];
+                    state.registers[index] = value;
+                }
+            }
+        }
+        
+        fn read_data(&self) -> u16 {
+            let mut state = self.state.lock();
+            let Some(&word) = state.buffer.get(state.position) else {
+                return 0;
+            };
+            state.position += 1;
+            if state.position == state.buffer.len() {
+                state.buffer.clear();
+                state.advance();
+            }
+            word
+        }
+        
+        fn write_data(&self, value: u16) {
+            let mut state = self.state.lock();
+            let Some((true, lba, _)) = state.transfer else {
+                return;
+            };
+            state.buffer.push(value);
+            if state.buffer.len() == SECTOR_SIZE / 2 {
+                let bytes: Vec<u8> = state.buffer.drain(..).flat_map(u16::to_le_bytes).collect();
+                let start = lba as usize * SECTOR_SIZE;
+                if let Some(image) = state.drive() {
+                    image[start..start + SECTOR_SIZE].copy_from_slice(&bytes);
+                }
+                state.advance();
+            }
+        }
+        
+        fn alternate_status(&self) -> u8 {
+            self.state.lock().status
+        }
+        
+        fn set_control(&self, _value: u8) {}
+    }
+    
+    lazy_static! {
+        static ref CHANNELS: [Arc<SimulatedChannel>; 2] = [Arc::new(SimulatedChannel::new()), Arc::new(SimulatedChannel::new())];
+    }
+    
+    /// القناة المحاكاة الأولى (0) أو الثانية (1)
+    pub fn hosted_channel(index: usize) -> Arc<SimulatedChannel> {
+        CHANNELS[index].clone()
+    }
+}
@@ -0,0 +1,214 @@
+//! 🗃️ ذاكرة مؤقتة للقطاعات بالكتابة المؤجلة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! جهاز كتلي يغلف جهازاً آخر: القراءة تمر بالذاكرة المؤقتة، والكتابة تبقى
+//! فيها متسخة حتى `flush` أو حتى تُطرد لإفساح المجال. `flush` يكتب المتسخ
+//! بترتيب القطاعات ثم يفرغ ذاكرة الجهاز نفسه، فهو ما تستدعيه مزامنة أنظمة
+//! الملفات قبل الإغلاق وبعد الذعر.
+//!
+//! الطرد للأقدم استخداماً (LRU) بعداد وصول لكل قطاع.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+use super::{check_range, BlockDevice, BlockError, SECTOR_SIZE};
+
+/// السعة الافتراضية بالقطاعات (512 كيلوبايت)
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+struct Entry {
+    data: Box<[u8; SECTOR_SIZE]>,
+    dirty: bool,
+    /// قيمة عداد الوصول عند آخر استخدام
+    used: u64,
+}
+
+struct State {
+    entries: BTreeMap<u64, Entry>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+/// إحصائيات الذاكرة المؤقتة
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// القطاعات المكتوبة إلى الجهاز (بالطرد أو الإفراغ)
+    pub writebacks: u64,
+}
+
+pub struct BufferCache {
+    name: String,
+    device: Arc<dyn BlockDevice>,
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+impl BufferCache {
+    pub fn new(device: Arc<dyn BlockDevice>, capacity: usize) -> Self {
+        Self {
+            name: alloc::format!("{}+cache", device.name()),
+            device,
+            capacity: capacity.max(1),
+            state: Mutex::new(State { entries: BTreeMap::new(), clock: 0, stats: CacheStats::default() }),
+        }
+    }
+    
+    /// الجهاز المغلف
+    pub fn inner(&self) -> &Arc<dyn BlockDevice> {
+        &self.device
+    }
+    
+    pub fn stats(&self) -> CacheStats {
+        self.state.lock().stats
+    }
+    
+    /// عدد القطاعات المتسخة التي لم تُكتب بعد
+    pub fn dirty_count(&self) -> usize {
+        self.state.lock().entries.values().filter(|entry| entry.dirty).count()
+    }
+    
+    /// إفساح مكان لقطاع جديد بطرد الأقدم استخداماً
+    fn make_room(&self, state: &mut State) -> Result<(), BlockError> {
+        while state.entries.len() >= self.capacity {
+            let Some((&lba, _)) = state.entries.iter().min_by_key(|(_, entry)| entry.used) else {
+                break;
+            };
+            let entry = state.entries.remove(&lba).unwrap();
+            if entry.dirty {
+                if let Err(error) = self.device.write_sectors(lba, &entry.data[..]) {
+                    // يبقى متسخاً ليُعاد في الإفراغ التالي
+                    state.entries.insert(lba, entry);
+                    return Err(error);
+                }
+                state.stats.writebacks += 1;
+            }
+        }
+        Ok(())
+    }
+    
+    /// القطاع `lba` في الذاكرة المؤقتة (يُقرأ من الجهاز عند الحاجة)
+    fn entry<'a>(&self, state: &'a mut State, lba: u64, load: bool) -> Result<&'a mut Entry, BlockError> {
+        state.clock += 1;
+        let clock = state.clock;
+        
+        if state.entries.contains_key(&lba) {
+            state.stats.hits += 1;
+        } else {
+            state.stats.misses += 1;
+            self.make_room(state)?;
+            let mut data = Box::new([0u8; SECTOR_SIZE]);
+            if load {
+                self.device.read_sectors(lba, &mut data[..])?;
+            }
+            state.entries.insert(lba, Entry { data, dirty: false, used: clock });
+        }
+        
+        let entry = state.entries.get_mut(&lba).unwrap();
+        entry.used = clock;
+        Ok(entry)
+    }
+    
+    /// نسيان كل شيء دون كتابة (بعد تغيير الجهاز من تحتنا)
+    pub fn invalidate(&self) {
+        self.state.lock().entries.clear();
+    }
+}
+
+impl BlockDevice for BufferCache {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn sector_count(&self) -> u64 {
+        self.device.sector_count()
+    }
+    
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        check_range(self, lba, buf.len())?;
+        let mut state = self.state.lock();
+        for (index, chunk) in buf.chunks_exact_mut(SECTOR_SIZE).enumerate() {
+            chunk.copy_from_slice(&self.entry(&mut state, lba + index as u64, true)?.data[..]);
+        }
+        Ok(())
+    }
+    
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError> {
+        check_range(self, lba, data.len())?;
+        let mut state = self.state.lock();
+        for (index, chunk) in data.chunks_exact(SECTOR_SIZE).enumerate() {
+            // القطاع الكامل يُستبدل فلا حاجة لقراءته أولاً
+            let entry = self.entry(&mut state, lba + index as u64, false)?;
+            entry.data.copy_from_slice(chunk);
+            entry.dirty = true;
+        }
+        Ok(())
+    }
+    
+    fn flush(&self) -> Result<(), BlockError> {
+        let mut state = self.state.lock();
+        let dirty: Vec<u64> = state.entries.iter().filter(|(_, entry)| entry.dirty).map(|(&lba, _)| lba).collect();
+        
+        // القطاعات المتتالية تُكتب بطلب واحد
+        let mut start = 0;
+        while start < dirty.len() {
+            let mut end = start + 1;
+            while end < dirty.len() && dirty[end] == dirty[end - 1] + 1 {
+                end += 1;
+            }
+            
+            let mut run = Vec::with_capacity((end - start) * SECTOR_SIZE);
+            for lba in &dirty[start..end] {
+                run.extend_from_slice(&state.entries[lba].data[..]);
+            }
+            self.device.write_sectors(dirty[start], &run)?;
+            
+            for lba in &dirty[start..end] {
+                state.entries.get_mut(lba).unwrap().dirty = false;
+            }
+            state.stats.writebacks += (end - start) as u64;
+            start = end;
+        }
+        drop(state);
+        
+        self.device.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::block::ramdisk::RamDisk;
+    
+    #[test_case]
+    fn test_write_back_and_eviction() {
+        // الكتابة تبقى في الذاكرة المؤقتة حتى الطرد أو الإفراغ
+        let disk = Arc::new(RamDisk::zeroed("cache-test", 16));
+        let cache = BufferCache::new(disk.clone(), 4);
+        cache.write_sectors(0, &[1; 2 * SECTOR_SIZE]).unwrap();
+        assert!(disk.snapshot().iter().all(|&b| b == 0));
+        let mut back = [0u8; 2 * SECTOR_SIZE];
+        cache.read_sectors(0, &mut back).unwrap();
+        assert!(back.iter().all(|&b| b == 1));
+        assert_eq!((cache.stats().hits, cache.stats().misses, cache.dirty_count()), (2, 2, 2));
+        
+        // القطاع 0 هو الأقدم استخداماً فيُطرد ويُكتب
+        cache.write_sectors(5, &[2; 3 * SECTOR_SIZE]).unwrap();
+        let snapshot = disk.snapshot();
+        assert!(snapshot[..SECTOR_SIZE].iter().all(|&b| b == 1));
+        assert!(snapshot[SECTOR_SIZE..].iter().all(|&b| b == 0));
+        assert_eq!((cache.stats().writebacks, cache.dirty_count()), (1, 4));
+        
+        cache.flush().unwrap();
+        let snapshot = disk.snapshot();
+        assert!(snapshot[SECTOR_SIZE..2 * SECTOR_SIZE].iter().all(|&b| b == 1));
+        assert!(snapshot[5 * SECTOR_SIZE..8 * SECTOR_SIZE].iter().all(|&b| b == 2));
+        assert_eq!((cache.stats().writebacks, cache.dirty_count()), (5, 0));
+        assert_eq!(cache.read_sectors(16, &mut back[..SECTOR_SIZE]), Err(BlockError::OutOfRange));
+    }
+}
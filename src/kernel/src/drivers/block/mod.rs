@@ -0,0 +1,238 @@
+//! 💽 طبقة أجهزة التخزين الكتلية
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الأجهزة تتعامل بقطاعات من 512 بايت؛ `read_at` و `write_at` تبنيان عليها
+//! وصولاً بالبايت تحتاجه أنظمة الملفات (قراءة ثم تعديل ثم كتابة للأطراف).
+//!
+//! تعريفات العتاد (ATA و virtio-blk) تسجل أقراصها هنا عند ربطها على ناقل
+//! PCI، فيُقرأ جدول أقسامها ويصير كل قسم جهازاً مستقلاً:
+//!
+//! ```text
+//! ata/virtio ──register_disk──▶ hda ──MBR/GPT──▶ hda1، hda2...
+//!                                        └──قسم "crash"──▶ crash::attach
+//! fs::init ──أول قسم بنظام ملفات معروف──▶ BufferCache ──▶ /data
+//! ```
+//!
+//! القائمة في /proc/partitions.
+
+pub mod ata;
+pub mod cache;
+pub mod partition;
+pub mod ramdisk;
+pub mod virtio;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use log::{info, warn};
+use spin::Mutex;
+
+use partition::{Partition, PartitionTable};
+
+/// حجم القطاع
+pub const SECTOR_SIZE: usize = 512;
+
+/// أخطاء الأجهزة الكتلية
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockError {
+    /// القطاع خارج حدود الجهاز
+    OutOfRange,
+    /// الطول ليس من مضاعفات حجم القطاع
+    Misaligned,
+    /// الجهاز للقراءة فقط
+    ReadOnly,
+    /// خطأ من العتاد
+    Io,
+}
+
+/// جهاز تخزين كتلي
+pub trait BlockDevice: Send + Sync {
+    /// اسم الجهاز
+    fn name(&self) -> &str;
+    
+    /// عدد القطاعات
+    fn sector_count(&self) -> u64;
+    
+    /// قراءة قطاعات متتالية؛ طول `buf` من مضاعفات `SECTOR_SIZE`
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError>;
+    
+    /// كتابة قطاعات متتالية؛ طول `data` من مضاعفات `SECTOR_SIZE`
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError>;
+    
+    /// إفراغ ذاكرة الكتابة المؤقتة للجهاز
+    fn flush(&self) -> Result<(), BlockError> {
+        Ok(())
+    }
+    
+    /// السعة بالبايت
+    fn size(&self) -> u64 {
+        self.sector_count() * SECTOR_SIZE as u64
+    }
+}
+
+/// التحقق من أن طلباً يقع داخل الجهاز
+pub fn check_range(device: &dyn BlockDevice, lba: u64, len: usize) -> Result<(), BlockError> {
+    if !len.is_multiple_of(SECTOR_SIZE) {
+        return Err(BlockError::Misaligned);
+    }
+    
+    let count = (len / SECTOR_SIZE) as u64;
+    match lba.checked_add(count) {
+        Some(end) if end <= device.sector_count() => Ok(()),
+        _ => Err(BlockError::OutOfRange),
+    }
+}
+
+/// قراءة بايتات من أي إزاحة
+pub fn read_at(device: &dyn BlockDevice, offset: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+    let mut sector = [0u8; SECTOR_SIZE];
+    let mut done = 0;
+    
+    while done < buf.len() {
+        let position = offset + done as u64;
+        let lba = position / SECTOR_SIZE as u64;
+        let within = (position % SECTOR_SIZE as u64) as usize;
+        let remaining = buf.len() - done;
+        
+        if within == 0 && remaining >= SECTOR_SIZE {
+            // الجزء المحاذي يُقرأ دفعة واحدة
+            let len = remaining - remaining % SECTOR_SIZE;
+            device.read_sectors(lba, &mut buf[done..done + len])?;
+            done += len;
+        } else {
+            let len = remaining.min(SECTOR_SIZE - within);
+            device.read_sectors(lba, &mut sector)?;
+            buf[done..done + len].copy_from_slice(&sector[within..within + len]);
+            done += len;
+        }
+    }
+    
+    Ok(())
+}
+
+/// كتابة بايتات في أي إزاحة
+pub fn write_at(device: &dyn BlockDevice, offset: u64, data: &[u8]) -> Result<(), BlockError> {
+    let mut sector = [0u8; SECTOR_SIZE];
+    let mut done = 0;
+    
+    while done < data.len() {
+        let position = offset + done as u64;
+        let lba = position / SECTOR_SIZE as u64;
+        let within = (position % SECTOR_SIZE as u64) as usize;
+        let remaining = data.len() - done;
+        
+        if within == 0 && remaining >= SECTOR_SIZE {
+            let len = remaining - remaining % SECTOR_SIZE;
+            device.write_sectors(lba, &data[done..done + len])?;
+            done += len;
+        } else {
+            let len = remaining.min(SECTOR_SIZE - within);
+            device.read_sectors(lba, &mut sector)?;
+            sector[within..within + len].copy_from_slice(&data[done..done + len]);
+            device.write_sectors(lba, &sector)?;
+            done += len;
+        }
+    }
+    
+    Ok(())
+}
+
+/// قرص مسجل وأقسامه
+struct Disk {
+    device: Arc<dyn BlockDevice>,
+    table: Option<PartitionTable>,
+    partitions: Vec<Arc<Partition>>,
+}
+
+static DISKS: Mutex<Vec<Disk>> = Mutex::new(Vec::new());
+
+/// تسجيل قرص وقراءة جدول أقسامه؛ قسم سجلات الأعطال يُربط فوراً إن لم تكن
+/// هناك منطقة مربوطة
+pub fn register_disk(device: Arc<dyn BlockDevice>) {
+    let table = match partition::parse(device.as_ref()) {
+        Ok(table) => table,
+        Err(e) => {
+            warn!("⚠️ تعذرت قراءة جدول أقسام {}: {:?}", device.name(), e);
+            None
+        }
+    };
+    
+    let partitions: Vec<Arc<Partition>> = table.iter()
+        .flat_map(|table| table.partitions.iter())
+        .map(|info| Arc::new(Partition::new(alloc::format!("{}{}", device.name(), info.number), device.clone(), info.clone())))
+        .collect();
+    
+    match &table {
+        Some(table) => info!("  🧱 {}: {:?} ({} قسم)", device.name(), table.scheme, partitions.len()),
+        None => info!("  🧱 {}: بلا جدول أقسام", device.name()),
+    }
+    
+    let crash_region = partitions.iter().find(|partition| partition.info().is_crash_region()).cloned();
+    DISKS.lock().push(Disk { device, table, partitions });
+    
+    if let Some(region) = crash_region {
+        if !crate::crash::attached() {
+            let sectors = region.sector_count().min(crate::crash::REGION_SECTORS);
+            if let Err(e) = crate::crash::attach(region, 0, sectors) {
+                warn!("⚠️ تعذر ربط منطقة سجلات الأعطال: {:?}", e);
+            }
+        }
+    }
+}
+
+/// إزالة قرص وأقسامه (الأجهزة المفتوحة تبقى صالحة حتى تُغلق)
+pub fn unregister_disk(name: &str) {
+    let mut disks = DISKS.lock();
+    if let Some(index) = disks.iter().position(|disk| disk.device.name() == name) {
+        disks.remove(index);
+        info!("  🧱 {} أُزيل", name);
+    }
+}
+
+/// كل الأجهزة: كل قرص تليه أقسامه
+pub fn devices() -> Vec<Arc<dyn BlockDevice>> {
+    DISKS.lock().iter()
+        .flat_map(|disk| core::iter::once(disk.device.clone()).chain(disk.partitions.iter().map(|p| p.clone() as Arc<dyn BlockDevice>)))
+        .collect()
+}
+
+/// جهاز بالاسم (hda، hda1، vdb...)
+pub fn find(name: &str) -> Option<Arc<dyn BlockDevice>> {
+    devices().into_iter().find(|device| device.name() == name)
+}
+
+/// الأجهزة المرشحة لأنظمة الملفات: الأقسام (عدا منطقة الأعطال)، والأقراص
+/// التي بلا جدول أقسام
+pub fn volumes() -> Vec<Arc<dyn BlockDevice>> {
+    DISKS.lock().iter()
+        .flat_map(|disk| {
+            let whole = disk.table.is_none().then(|| disk.device.clone());
+            let partitions = disk.partitions.iter()
+                .filter(|p| !p.info().is_crash_region())
+                .map(|p| p.clone() as Arc<dyn BlockDevice>);
+            whole.into_iter().chain(partitions).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// تسجيل تعريفات العتاد على ناقل PCI وملف /proc/partitions
+pub fn init() {
+    crate::fs::procfs::register("partitions", proc_partitions);
+    crate::drivers::pci::register_driver(&ata::DRIVER);
+    crate::drivers::pci::register_driver(&virtio::DRIVER);
+}
+
+/// /proc/partitions
+fn proc_partitions() -> String {
+    use core::fmt::Write;
+    
+    let mut table = String::from("name\tstart\tsectors\ttype\tlabel\n");
+    for disk in DISKS.lock().iter() {
+        let _ = writeln!(table, "{}\t0\t{}\tdisk\t", disk.device.name(), disk.device.sector_count());
+        for partition in &disk.partitions {
+            let info = partition.info();
+            let _ = writeln!(table, "{}\t{}\t{}\t{}\t{}", partition.name(), info.start, info.sectors, info.type_name(), info.name);
+        }
+    }
+    table
+}
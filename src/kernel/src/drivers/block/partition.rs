@@ -0,0 +1,392 @@
+//! 🧱 جداول الأقسام (MBR و GPT)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! MBR: أربعة مداخل في القطاع 0، والقسم الممتد (0x05/0x0F/0x85) سلسلة من
+//! سجلات EBR تعطي الأقسام المنطقية بدءاً من الرقم 5 كما في لينكس. النوع 0xEE
+//! يعني أن الجدول الحقيقي GPT:
+//!
+//! ```text
+//! LBA 1: "EFI PART" + CRC32 الترويسة ──▶ مداخل الأقسام (+ CRC32 المداخل)
+//! المدخل: نوع GUID، GUID فريد، أول LBA، آخر LBA (شاملاً)، اسم UTF-16
+//! ```
+//!
+//! منطقة سجلات الأعطال قسم من النوع 0xDA في MBR أو قسم GPT اسمه "crash".
+
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{check_range, BlockDevice, BlockError, SECTOR_SIZE};
+use crate::utils::inflate::crc32;
+
+/// نوع قسم MBR لمنطقة سجلات الأعطال ("بيانات ليست نظام ملفات")
+pub const MBR_CRASH_TYPE: u8 = 0xDA;
+
+/// اسم قسم GPT لمنطقة سجلات الأعطال
+pub const GPT_CRASH_NAME: &str = "crash";
+
+/// أقصى عدد للأقسام المنطقية (يحمي من سلاسل EBR الدائرية)
+const MAX_LOGICAL: usize = 64;
+
+/// معرف GUID بترتيب بايتاته على القرص
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Guid(pub [u8; 16]);
+
+impl Guid {
+    /// "بيانات نظام ملفات لينكس"
+    pub const LINUX_DATA: Guid = Guid::parse("0FC63DAF-8483-4772-8E79-3D69D8477DE4");
+    /// قسم نظام EFI
+    pub const EFI_SYSTEM: Guid = Guid::parse("C12A7328-F81F-11D2-BA4B-00A0C93EC93B");
+    /// "بيانات أساسية" من مايكروسوفت (FAT و NTFS)
+    pub const BASIC_DATA: Guid = Guid::parse("EBD0A0A2-B9E5-4433-87C0-68B6B72699C7");
+    
+    /// من الصيغة النصية؛ الحقول الثلاثة الأولى صغيرة الطرف على القرص
+    pub const fn parse(text: &str) -> Guid {
+        const ORDER: [usize; 16] = [3, 2, 1, 0, 5, 4, 7, 6, 8, 9, 10, 11, 12, 13, 14, 15];
+        let text = text.as_bytes();
+        let mut bytes = [0u8; 16];
+        let (mut index, mut digit) = (0, 0);
+        while index < text.len() {
+            let value = match text[index] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            };
+            bytes[ORDER[digit / 2]] |= value << if digit % 2 == 0 { 4 } else { 0 };
+            digit += 1;
+            index += 1;
+        }
+        Guid(bytes)
+    }
+    
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 16]
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let b = &self.0;
+        write!(f, "{:02X}{:02X}{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-{:02X}{:02X}-", b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9])?;
+        b[10..].iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
+
+/// نوع القسم بحسب الجدول
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionType {
+    Mbr(u8),
+    Gpt(Guid),
+}
+
+/// صيغة الجدول
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Mbr,
+    Gpt,
+}
+
+/// مدخل في جدول الأقسام
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionInfo {
+    /// الرقم كما يظهر في الاسم (hda1...)
+    pub number: u32,
+    pub start: u64,
+    pub sectors: u64,
+    pub kind: PartitionType,
+    /// اسم GPT (فارغ في MBR)
+    pub name: String,
+}
+
+impl PartitionInfo {
+    /// هل هو منطقة سجلات الأعطال؟
+    pub fn is_crash_region(&self) -> bool {
+        match self.kind {
+            PartitionType::Mbr(kind) => kind == MBR_CRASH_TYPE,
+            PartitionType::Gpt(_) => self.name == GPT_CRASH_NAME,
+        }
+    }
+    
+    /// وصف النوع لـ /proc/partitions
+    pub fn type_name(&self) -> String {
+        match self.kind {
+            PartitionType::Mbr(kind) => format!("mbr:{:02x}", kind),
+            PartitionType::Gpt(guid) => format!("gpt:{}", guid),
+        }
+    }
+}
+
+/// جدول أقسام مقروء
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionTable {
+    pub scheme: Scheme,
+    pub partitions: Vec<PartitionInfo>,
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> u64 {
+    u32_at(bytes, at) as u64 | (u32_at(bytes, at + 4) as u64) << 32
+}
+
+fn is_extended(kind: u8) -> bool {
+    matches!(kind, 0x05 | 0x0F | 0x85)
+}
+
+/// قراءة جدول الأقسام؛ `None` إن لم يكن على الجهاز جدول
+pub fn parse(device: &dyn BlockDevice) -> Result<Option<PartitionTable>, BlockError> {
+    let mut mbr = [0u8; SECTOR_SIZE];
+    device.read_sectors(0, &mut mbr)?;
+    if mbr[510..512] != [0x55, 0xAA] {
+        return Ok(None);
+    }
+    
+    let entries: Vec<(u8, u64, u64)> = (0..4)
+        .map(|i| {
+            let entry = &mbr[446 + i * 16..462 + i * 16];
+            (entry[4], u32_at(entry, 8) as u64, u32_at(entry, 12) as u64)
+        })
+        .collect();
+    
+    if entries.iter().any(|&(kind, _, _)| kind == 0xEE) {
+        return Ok(parse_gpt(device)?.map(|partitions| PartitionTable { scheme: Scheme::Gpt, partitions }));
+    }
+    
+    // قطاع إقلاع بلا جدول (نظام ملفات على القرص كله) له توقيع أيضاً؛
+    // المداخل التي تخرج عن الجهاز تكشفه
+    let total = device.sector_count();
+    let valid = |start: u64, sectors: u64| sectors > 0 && start > 0 && start.checked_add(sectors).is_some_and(|end| end <= total);
+    if entries.iter().any(|&(kind, start, sectors)| kind != 0 && !valid(start, sectors)) {
+        return Ok(None);
+    }
+    
+    let mut partitions = Vec::new();
+    for (index, &(kind, start, sectors)) in entries.iter().enumerate() {
+        if kind == 0 {
+            continue;
+        }
+        partitions.push(PartitionInfo { number: index as u32 + 1, start, sectors, kind: PartitionType::Mbr(kind), name: String::new() });
+        
+        if is_extended(kind) {
+            parse_logical(device, start, sectors, &mut partitions)?;
+        }
+    }
+    
+    Ok(Some(PartitionTable { scheme: Scheme::Mbr, partitions }))
+}
+
+/// سلسلة EBR: كل سجل يصف قسماً منطقياً (نسبةً إليه) والسجل التالي (نسبةً
+/// إلى بداية القسم الممتد)
+fn parse_logical(device: &dyn BlockDevice, base: u64, length: u64, partitions: &mut Vec<PartitionInfo>) -> Result<(), BlockError> {
+    let mut ebr = [0u8; SECTOR_SIZE];
+    let mut offset = 0;
+    
+    for number in 5..5 + MAX_LOGICAL as u32 {
+        device.read_sectors(base + offset, &mut ebr)?;
+        if ebr[510..512] != [0x55, 0xAA] {
+            break;
+        }
+        
+        let (kind, start, sectors) = (ebr[446 + 4], u32_at(&ebr, 446 + 8) as u64, u32_at(&ebr, 446 + 12) as u64);
+        if kind != 0 && sectors > 0 && offset + start + sectors <= length {
+            partitions.push(PartitionInfo { number, start: base + offset + start, sectors, kind: PartitionType::Mbr(kind), name: String::new() });
+        }
+        
+        let next = u32_at(&ebr, 462 + 8) as u64;
+        if ebr[462 + 4] == 0 || next == 0 || next <= offset || next >= length {
+            break;
+        }
+        offset = next;
+    }
+    Ok(())
+}
+
+/// ترويسة GPT ومداخلها بعد التحقق من CRC32
+fn parse_gpt(device: &dyn BlockDevice) -> Result<Option<Vec<PartitionInfo>>, BlockError> {
+    let mut header = [0u8; SECTOR_SIZE];
+    device.read_sectors(1, &mut header)?;
+    if &header[0..8] != b"EFI PART" {
+        return Ok(None);
+    }
+    
+    let header_size = u32_at(&header, 12) as usize;
+    if !(92..=SECTOR_SIZE).contains(&header_size) {
+        return Ok(None);
+    }
+    let mut checked = header;
+    checked[16..20].fill(0);
+    if crc32(&checked[..header_size]) != u32_at(&header, 16) {
+        return Ok(None);
+    }
+    
+    let (entries_lba, count, entry_size) = (u64_at(&header, 72), u32_at(&header, 80) as usize, u32_at(&header, 84) as usize);
+    if entry_size < 128 || count > 1024 {
+        return Ok(None);
+    }
+    
+    let bytes = (count * entry_size).div_ceil(SECTOR_SIZE) * SECTOR_SIZE;
+    check_range(device, entries_lba, bytes)?;
+    let mut entries = vec![0u8; bytes];
+    device.read_sectors(entries_lba, &mut entries)?;
+    if crc32(&entries[..count * entry_size]) != u32_at(&header, 88) {
+        return Ok(None);
+    }
+    
+    let total = device.sector_count();
+    let mut partitions = Vec::new();
+    for (index, entry) in entries[..count * entry_size].chunks_exact(entry_size).enumerate() {
+        let kind = Guid(entry[0..16].try_into().unwrap());
+        let (first, last) = (u64_at(entry, 32), u64_at(entry, 40));
+        if kind.is_zero() || last < first || last >= total {
+            continue;
+        }
+        
+        let units: Vec<u16> = entry[56..128].chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).take_while(|&unit| unit != 0).collect();
+        partitions.push(PartitionInfo {
+            number: index as u32 + 1,
+            start: first,
+            sectors: last - first + 1,
+            kind: PartitionType::Gpt(kind),
+            name: String::from_utf16_lossy(&units),
+        });
+    }
+    
+    Ok(Some(partitions))
+}
+
+/// قسم كجهاز مستقل: الطلبات تُزاح ببداية القسم وتُحصر في طوله
+pub struct Partition {
+    name: String,
+    device: Arc<dyn BlockDevice>,
+    info: PartitionInfo,
+}
+
+impl Partition {
+    pub fn new(name: String, device: Arc<dyn BlockDevice>, info: PartitionInfo) -> Self {
+        Self { name, device, info }
+    }
+    
+    pub fn info(&self) -> &PartitionInfo {
+        &self.info
+    }
+}
+
+impl BlockDevice for Partition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn sector_count(&self) -> u64 {
+        self.info.sectors
+    }
+    
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        check_range(self, lba, buf.len())?;
+        self.device.read_sectors(self.info.start + lba, buf)
+    }
+    
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError> {
+        check_range(self, lba, data.len())?;
+        self.device.write_sectors(self.info.start + lba, data)
+    }
+    
+    fn flush(&self) -> Result<(), BlockError> {
+        self.device.flush()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::drivers::block::ramdisk::RamDisk;
+    
+    /// جدول MBR (أو EBR) في القطاع `lba`
+    pub(crate) fn mbr(image: &mut [u8], lba: usize, entries: &[(u8, u32, u32)]) {
+        let sector = &mut image[lba * SECTOR_SIZE..(lba + 1) * SECTOR_SIZE];
+        for (index, &(kind, start, sectors)) in entries.iter().enumerate() {
+            let entry = &mut sector[446 + index * 16..462 + index * 16];
+            entry[4] = kind;
+            entry[8..12].copy_from_slice(&start.to_le_bytes());
+            entry[12..16].copy_from_slice(&sectors.to_le_bytes());
+        }
+        sector[510..512].copy_from_slice(&[0x55, 0xAA]);
+    }
+    
+    #[test_case]
+    fn test_mbr_extended_partitions() {
+        // قسم أولي، وقسم ممتد فيه قسمان منطقيان (الأول للأعطال)
+        let mut image = vec![0u8; 256 * SECTOR_SIZE];
+        mbr(&mut image, 0, &[(0x83, 8, 64), (0x05, 100, 100)]);
+        mbr(&mut image, 100, &[(0xDA, 1, 32), (0x05, 50, 21)]);
+        mbr(&mut image, 150, &[(0x83, 1, 20)]);
+        let disk = Arc::new(RamDisk::new("mbr-test", image.clone()));
+        let table = parse(disk.as_ref()).unwrap().unwrap();
+        let layout: Vec<_> = table.partitions.iter().map(|p| (p.number, p.start, p.sectors, p.kind, p.is_crash_region())).collect();
+        assert_eq!(table.scheme, Scheme::Mbr);
+        assert_eq!(layout, [
+            (1, 8, 64, PartitionType::Mbr(0x83), false),
+            (2, 100, 100, PartitionType::Mbr(0x05), false),
+            (5, 101, 32, PartitionType::Mbr(0xDA), true),
+            (6, 151, 20, PartitionType::Mbr(0x83), false),
+        ]);
+        
+        // القسم جهاز مستقل بإزاحته وحدوده
+        let logical = Partition::new(String::from("mbr-test5"), disk.clone(), table.partitions[2].clone());
+        logical.write_sectors(31, &[0xC3; SECTOR_SIZE]).unwrap();
+        assert!(disk.snapshot()[132 * SECTOR_SIZE..133 * SECTOR_SIZE].iter().all(|&b| b == 0xC3));
+        assert_eq!(logical.write_sectors(32, &[0; SECTOR_SIZE]), Err(BlockError::OutOfRange));
+        
+        // مدخل يتجاوز القرص: قطاع إقلاع لنظام ملفات بلا جدول
+        mbr(&mut image, 0, &[(0x83, 8, 1000)]);
+        assert_eq!(parse(&RamDisk::new("bare-test", image)).unwrap(), None);
+    }
+    
+    #[test_case]
+    fn test_gpt_partitions() {
+        // MBR حامٍ، ترويسة في LBA 1، أربعة مداخل في LBA 2
+        let mut image = vec![0u8; 128 * SECTOR_SIZE];
+        mbr(&mut image, 0, &[(0xEE, 1, 127)]);
+        let crash_type = Guid::parse("4A1B2C3D-0000-4000-8000-00000000DA7A");
+        for (index, kind, first, last, name) in [(0, Guid::LINUX_DATA, 34u64, 99u64, "data"), (2, crash_type, 100, 115, "crash")] {
+            let entry = &mut image[2 * SECTOR_SIZE + index * 128..][..128];
+            entry[0..16].copy_from_slice(&kind.0);
+            entry[32..40].copy_from_slice(&first.to_le_bytes());
+            entry[40..48].copy_from_slice(&last.to_le_bytes());
+            for (unit, slot) in name.encode_utf16().zip(entry[56..].chunks_exact_mut(2)) {
+                slot.copy_from_slice(&unit.to_le_bytes());
+            }
+        }
+        let entries_crc = crc32(&image[2 * SECTOR_SIZE..3 * SECTOR_SIZE]);
+        let header = &mut image[SECTOR_SIZE..2 * SECTOR_SIZE];
+        header[0..8].copy_from_slice(b"EFI PART");
+        header[8..12].copy_from_slice(&0x0001_0000u32.to_le_bytes());
+        header[12..16].copy_from_slice(&92u32.to_le_bytes());
+        header[24..32].copy_from_slice(&1u64.to_le_bytes());
+        header[72..80].copy_from_slice(&2u64.to_le_bytes());
+        header[80..84].copy_from_slice(&4u32.to_le_bytes());
+        header[84..88].copy_from_slice(&128u32.to_le_bytes());
+        header[88..92].copy_from_slice(&entries_crc.to_le_bytes());
+        let header_crc = crc32(&header[..92]);
+        header[16..20].copy_from_slice(&header_crc.to_le_bytes());
+        
+        let table = parse(&RamDisk::new("gpt-test", image.clone())).unwrap().unwrap();
+        let layout: Vec<_> = table.partitions.iter().map(|p| (p.number, p.start, p.sectors, p.name.as_str(), p.is_crash_region())).collect();
+        assert_eq!(table.scheme, Scheme::Gpt);
+        assert_eq!(layout, [(1, 34, 66, "data", false), (3, 100, 16, "crash", true)]);
+        assert_eq!(table.partitions[0].type_name(), "gpt:0FC63DAF-8483-4772-8E79-3D69D8477DE4");
+        assert_eq!(format!("{}", crash_type), "4A1B2C3D-0000-4000-8000-00000000DA7A");
+        
+        // مدخل تالف يُسقط الجدول بفحص CRC
+        image[2 * SECTOR_SIZE + 40] ^= 1;
+        assert_eq!(parse(&RamDisk::new("gpt-bad", image)).unwrap(), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! 🧊 قرص في الذاكرة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! جهاز كتلي فوق مخزن في الكومة؛ تستخدمه الاختبارات لتركيب صور أقراص
+//! أنشئت على لينكس، ويصلح كقرص مؤقت.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+use super::{check_range, BlockDevice, BlockError, SECTOR_SIZE};
+
+pub struct RamDisk {
+    name: String,
+    data: Mutex<Vec<u8>>,
+}
+
+impl RamDisk {
+    /// إنشاء قرص من صورة؛ يُقرّب الطول إلى مضاعف حجم القطاع
+    pub fn new(name: &str, mut image: Vec<u8>) -> Self {
+        let padded = image.len().div_ceil(SECTOR_SIZE) * SECTOR_SIZE;
+        image.resize(padded, 0);
+        
+        Self {
+            name: String::from(name),
+            data: Mutex::new(image),
+        }
+    }
+    
+    /// قرص فارغ بعدد قطاعات محدد
+    pub fn zeroed(name: &str, sectors: usize) -> Self {
+        Self::new(name, alloc::vec![0; sectors * SECTOR_SIZE])
+    }
+    
+    /// نسخة من محتوى القرص الحالي
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.data.lock().clone()
+    }
+}
+
+impl BlockDevice for RamDisk {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn sector_count(&self) -> u64 {
+        (self.data.lock().len() / SECTOR_SIZE) as u64
+    }
+    
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        check_range(self, lba, buf.len())?;
+        let start = lba as usize * SECTOR_SIZE;
+        buf.copy_from_slice(&self.data.lock()[start..start + buf.len()]);
+        Ok(())
+    }
+    
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError> {
+        check_range(self, lba, data.len())?;
+        let start = lba as usize * SECTOR_SIZE;
+        self.data.lock()[start..start + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+}
@@ -0,0 +1,352 @@
+//! 🌀 أقراص virtio-blk (الواجهة القديمة على PCI)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الجهاز 1AF4:1001 بطابور واحد (انظر `drivers::virtio`). الطلب سلسلة ثلاثة
+//! واصفات:
+//!
+//! ```text
+//! الترويسة (النوع، القطاع) ← البيانات ← بايت الحالة
+//! ```
+//!
+//! الطلبات متتالية (طلب واحد في الطابور)، والبيانات تمر بمخزن وسيط في ذاكرة
+//! DMA لأن ذاكرة المستدعي ليست متجاورة فيزيائياً بالضرورة. الانتظار
+//! بالاستطلاع على الطابور المستعمل. الأقراص تُسمى vda و vdb...
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ptr::{read_volatile, write_volatile};
+use log::info;
+use spin::Mutex;
+
+use super::{check_range, BlockDevice, BlockError, SECTOR_SIZE};
+use crate::drivers::pci::{self, DeviceId, PciAddress, PciDevice, PciDriver};
+use crate::drivers::virtio::{self, register, Transport, Virtqueue};
+use crate::memory::{self, DmaRegion, FRAME_SIZE};
+
+/// إعدادات virtio-blk: السعة بالقطاعات (64 بت)
+const CAPACITY: u16 = register::CONFIG;
+
+/// ميزات virtio-blk التي نفهمها
+pub const FEATURE_READ_ONLY: u32 = 1 << 5;
+pub const FEATURE_FLUSH: u32 = 1 << 9;
+
+/// أنواع الطلبات
+pub const REQUEST_IN: u32 = 0;
+pub const REQUEST_OUT: u32 = 1;
+pub const REQUEST_FLUSH: u32 = 4;
+
+/// حجم المخزن الوسيط (64 كيلوبايت = 128 قطاعاً في الطلب)
+const BOUNCE_SIZE: usize = 16 * FRAME_SIZE as usize;
+
+/// محاولات الاستطلاع قبل اعتبار الجهاز معطلاً
+const SPINS: usize = 10_000_000;
+
+/// الطابور ومخزن الطلب
+struct Queue {
+    virtqueue: Virtqueue,
+    /// الترويسة في أول 16 بايت، والحالة بعدها، والبيانات من الصفحة الثانية
+    request: DmaRegion,
+}
+
+impl Queue {
+    /// بيانات الطلب في المخزن الوسيط
+    fn data(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.request.virt.add(FRAME_SIZE as usize), BOUNCE_SIZE) }
+    }
+    
+    /// إرسال طلب وانتظار اكتماله
+    fn submit(&mut self, transport: &dyn Transport, kind: u32, sector: u64, len: usize) -> Result<(), BlockError> {
+        let request = self.request.virt;
+        unsafe {
+            write_volatile(request as *mut u32, kind);
+            write_volatile(request.add(4) as *mut u32, 0);
+            write_volatile(request.add(8) as *mut u64, sector);
+            write_volatile(request.add(16), 0xFF);
+        }
+        
+        let (header, status, data) = (self.request.phys, self.request.phys + 16, self.request.phys + FRAME_SIZE);
+        let chain = if len > 0 {
+            // FLUSH بلا بيانات: الترويسة ثم الحالة مباشرة
+            alloc::vec![(header, 16, false), (data, len as u32, kind == REQUEST_IN), (status, 1, true)]
+        } else {
+            alloc::vec![(header, 16, false), (status, 1, true)]
+        };
+        self.virtqueue.add(&chain).ok_or(BlockError::Io)?;
+        self.virtqueue.notify(transport);
+        
+        if !(0..SPINS).any(|_| self.virtqueue.pop_used().is_some()) {
+            return Err(BlockError::Io);
+        }
+        transport.read8(register::ISR);
+        
+        match unsafe { read_volatile(request.add(16)) } {
+            0 => Ok(()),
+            _ => Err(BlockError::Io),
+        }
+    }
+}
+
+/// قرص virtio
+pub struct VirtioBlk {
+    name: String,
+    transport: Arc<dyn Transport>,
+    queue: Mutex<Queue>,
+    sectors: u64,
+    features: u32,
+}
+
+impl VirtioBlk {
+    /// تهيئة الجهاز: إعادة الضبط، الاتفاق على الميزات، الطابور 0، ثم التشغيل
+    pub fn new(name: String, transport: Arc<dyn Transport>) -> Result<Self, &'static str> {
+        let t = &*transport;
+        let features = virtio::negotiate(t, FEATURE_READ_ONLY | FEATURE_FLUSH);
+        
+        let queue = Virtqueue::new(t, 0).and_then(|virtqueue| {
+            let request = memory::alloc_dma(FRAME_SIZE as usize + BOUNCE_SIZE).ok_or("لا ذاكرة DMA")?;
+            Ok(Queue { virtqueue, request })
+        });
+        let queue = match queue {
+            Ok(queue) => queue,
+            Err(e) => {
+                virtio::fail(t);
+                return Err(e);
+            }
+        };
+        virtio::driver_ok(t);
+        
+        let sectors = t.read32(CAPACITY) as u64 | (t.read32(CAPACITY + 4) as u64) << 32;
+        Ok(Self { name, transport, queue: Mutex::new(queue), sectors, features })
+    }
+    
+    pub fn read_only(&self) -> bool {
+        self.features & FEATURE_READ_ONLY != 0
+    }
+}
+
+impl BlockDevice for VirtioBlk {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn sector_count(&self) -> u64 {
+        self.sectors
+    }
+    
+    fn read_sectors(&self, lba: u64, buf: &mut [u8]) -> Result<(), BlockError> {
+        check_range(self, lba, buf.len())?;
+        let mut queue = self.queue.lock();
+        
+        for (index, chunk) in buf.chunks_mut(BOUNCE_SIZE).enumerate() {
+            let sector = lba + (index * BOUNCE_SIZE / SECTOR_SIZE) as u64;
+            queue.submit(&*self.transport, REQUEST_IN, sector, chunk.len())?;
+            chunk.copy_from_slice(&queue.data()[..chunk.len()]);
+        }
+        Ok(())
+    }
+    
+    fn write_sectors(&self, lba: u64, data: &[u8]) -> Result<(), BlockError> {
+        check_range(self, lba, data.len())?;
+        if self.read_only() {
+            return Err(BlockError::ReadOnly);
+        }
+        let mut queue = self.queue.lock();
+        
+        for (index, chunk) in data.chunks(BOUNCE_SIZE).enumerate() {
+            let sector = lba + (index * BOUNCE_SIZE / SECTOR_SIZE) as u64;
+            queue.data()[..chunk.len()].copy_from_slice(chunk);
+            queue.submit(&*self.transport, REQUEST_OUT, sector, chunk.len())?;
+        }
+        Ok(())
+    }
+    
+    fn flush(&self) -> Result<(), BlockError> {
+        // بلا ميزة FLUSH فالجهاز يكتب مباشرة
+        if self.features & FEATURE_FLUSH == 0 {
+            return Ok(());
+        }
+        self.queue.lock().submit(&*self.transport, REQUEST_FLUSH, 0, 0)
+    }
+}
+
+/// الأقراص التي سجلها التعريف وأجهزة PCI التي تعود إليها
+static DISKS: Mutex<Vec<(PciAddress, String)>> = Mutex::new(Vec::new());
+
+fn probe(device: &PciDevice) -> Result<(), String> {
+    let port = device.io_bar().ok_or_else(|| String::from("لا BAR منافذ (الواجهة الحديثة فقط غير مدعومة)"))?;
+    let transport = virtio::transport(port).ok_or_else(|| String::from("لا جهاز على المنفذ"))?;
+    pci::enable_bus_master(device.address);
+    
+    // أول حرف غير مستخدم حتى تبقى الأسماء ثابتة بعد إعادة الفحص
+    let mut disks = DISKS.lock();
+    let letter = (b'a'..=b'z').find(|&letter| disks.iter().all(|(_, name)| name.as_bytes()[2] != letter)).ok_or_else(|| String::from("أقراص كثيرة"))?;
+    let name = alloc::format!("vd{}", letter as char);
+    let disk = VirtioBlk::new(name.clone(), transport).map_err(String::from)?;
+    info!("  🌀 {}: virtio-blk ({} ميجابايت{})", name, disk.sectors * SECTOR_SIZE as u64 / 1024 / 1024,
+        if disk.read_only() { "، للقراءة فقط" } else { "" });
+    
+    disks.push((device.address, name));
+    drop(disks);
+    super::register_disk(Arc::new(disk));
+    Ok(())
+}
+
+fn remove(device: &PciDevice) {
+    DISKS.lock().retain(|(address, name)| {
+        if *address == device.address {
+            super::unregister_disk(name);
+        }
+        *address != device.address
+    });
+}
+
+/// تعريف virtio-blk
+pub static DRIVER: PciDriver = PciDriver {
+    name: "virtio-blk",
+    ids: &[DeviceId::new(0x1AF4, 0x1001)],
+    probe,
+    remove,
+};
+
+#[cfg(feature = "hosted")]
+pub use self::simulated::{hosted_device, SimulatedBlk};
+
+/// قرص virtio-blk محاكى ينفذ الطلبات عند الإشعار
+#[cfg(feature = "hosted")]
+mod simulated {
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use spin::Mutex;
+    
+    use super::*;
+    use crate::drivers::virtio::simulated::{self, Common};
+    
+    /// حجم الطابور الذي يعلنه الجهاز
+    const QUEUE_SIZE: u16 = 64;
+    
+    struct State {
+        common: Common,
+        image: Vec<u8>,
+        flushes: usize,
+    }
+    
+    /// قرص virtio-blk في الذاكرة
+    pub struct SimulatedBlk {
+        state: Mutex<State>,
+    }
+    
+    /// جهاز جديد على المنفذ `port` (يُعلن بعدها في فضاء إعداد PCI المحاكى)
+    pub fn hosted_device(port: u16, mut image: Vec<u8>, features: u32) -> Arc<SimulatedBlk> {
+        image.resize(image.len().div_ceil(SECTOR_SIZE) * SECTOR_SIZE, 0);
+        let device = Arc::new(SimulatedBlk { state: Mutex::new(State { common: Common::new(features, 1, QUEUE_SIZE), image, flushes: 0 }) });
+        simulated::attach(port, device.clone());
+        device
+    }
+    
+    impl SimulatedBlk {
+        pub fn image(&self) -> Vec<u8> {
+            self.state.lock().image.clone()
+        }
+        
+        /// عدد طلبات FLUSH المنفذة
+        pub fn flushes(&self) -> usize {
+            self.state.lock().flushes
+        }
+        
+        /// الحالة التي كتبها التعريف (DRIVER_OK...)
+        pub fn status(&self) -> u8 {
+            self.state.lock().common.status
+        }
+        
+        /// تنفيذ كل ما في الطابور المتاح
+        fn process(state: &mut State) {
+            if !state.common.ready() {
+                return;
+            }
+            
+            while let Some(chain) = state.common.queues[0].pop() {
+                let header = chain.buffer(0);
+                let kind = u32::from_le_bytes(header[0..4].try_into().unwrap());
+                let mut offset = u64::from_le_bytes(header[8..16].try_into().unwrap()) as usize * SECTOR_SIZE;
+                let last = chain.buffers.len() - 1;
+                let mut written = 1;
+                
+                let result = match kind {
+                    REQUEST_IN | REQUEST_OUT => {
+                        let mut ok = true;
+                        for index in 1..last {
+                            let buffer = chain.buffer(index);
+                            if offset + buffer.len() > state.image.len() {
+                                ok = false;
+                                break;
+                            }
+                            if kind == REQUEST_IN {
+                                buffer.copy_from_slice(&state.image[offset..offset + buffer.len()]);
+                                written += buffer.len();
+                            } else if state.common.features & FEATURE_READ_ONLY == 0 {
+                                state.image[offset..offset + buffer.len()].copy_from_slice(buffer);
+                            } else {
+                                ok = false;
+                            }
+                            offset += buffer.len();
+                        }
+                        if ok { 0 } else { 1 }
+                    }
+                    REQUEST_FLUSH => {
+                        state.flushes += 1;
+                        0
+                    }
+                    _ => 2,
+                };
+                chain.buffer(last)[0] = result;
+                
+                state.common.queues[0].push_used(chain.head, written);
+                state.common.isr = 1;
+            }
+        }
+        
+        fn read(&self, offset: u16) -> u32 {
+            let mut state = self.state.lock();
+            let sectors = (state.image.len() / SECTOR_SIZE) as u64;
+            match state.common.read(offset) {
+                Some(value) => value,
+                None if offset == CAPACITY => sectors as u32,
+                None if offset == CAPACITY + 4 => (sectors >> 32) as u32,
+                None => 0,
+            }
+        }
+        
+        fn write(&self, offset: u16, value: u32) {
+            let mut state = self.state.lock();
+            if state.common.write(offset, value).is_some() {
+                Self::process(&mut state);
+            }
+        }
+    }
+    
+    impl Transport for SimulatedBlk {
+        fn read8(&self, offset: u16) -> u8 {
+            self.read(offset) as u8
+        }
+        
+        fn read16(&self, offset: u16) -> u16 {
+            self.read(offset) as u16
+        }
+        
+        fn read32(&self, offset: u16) -> u32 {
+            self.read(offset)
+        }
+        
+        fn write8(&self, offset: u16, value: u8) {
+            self.write(offset, value as u32)
+        }
+        
+        fn write16(&self, offset: u16, value: u16) {
+            self.write(offset, value as u32)
+        }
+        
+        fn write32(&self, offset: u16, value: u32) {
+            self.write(offset, value)
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! ⌨️ تعريف لوحة مفاتيح PS/2
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! معالج IRQ1 يقرأ رمز المسح من المنفذ 0x60 ويضعه في مخزن صغير فقط؛ فك
+//! الرموز بـ `pc_keyboard` يجري في الحلقة الرئيسية (`process_pending`) ثم
+//! تذهب الأحداث إلى طابور الإدخال.
+//!
+//! التخطيطان: الأمريكي والعربي (101)، و Alt+Shift يبدّل بينهما. التخطيط
+//! الأول من سطر الأوامر:
+//!
+//! ```text
+//! keyboard=ar      العربي عند الإقلاع (us افتراضياً)
+//! ```
+
+use alloc::collections::VecDeque;
+use core::sync::atomic::{AtomicBool, Ordering};
+use lazy_static::lazy_static;
+use log::info;
+use pc_keyboard::{layouts, DecodedKey, HandleControl, KeyCode, KeyState, Keyboard, KeyboardLayout, Modifiers, ScancodeSet1};
+use spin::Mutex;
+
+use crate::arch;
+use crate::input::{self, InputEvent, Key, Layout, SpecialKey};
+
+/// خط مقاطعة لوحة المفاتيح
+const IRQ: u8 = 1;
+
+/// رموز المسح التي لم تُفك بعد (الزائد يُهمل)
+const PENDING_CAPACITY: usize = 128;
+
+#[cfg(not(feature = "hosted"))]
+const DATA_PORT: u16 = 0x60;
+
+/// رموز المسح من المقاطعة؛ يُقفل خارجها والمقاطعات معطلة
+static PENDING: Mutex<VecDeque<u8>> = Mutex::new(VecDeque::new());
+
+/// التخطيط الحالي: العربي إن كانت القيمة صحيحة
+static ARABIC: AtomicBool = AtomicBool::new(false);
+
+/// تخطيط `pc_keyboard` يقرأ التخطيط الحالي عند كل مفتاح
+pub struct Switchable;
+
+impl KeyboardLayout for Switchable {
+    fn map_keycode(keycode: KeyCode, modifiers: &Modifiers, handle_ctrl: HandleControl) -> DecodedKey {
+        if ARABIC.load(Ordering::Relaxed) && !modifiers.is_ctrl() {
+            if let Some(c) = arabic_char(keycode, modifiers.is_shifted()) {
+                return DecodedKey::Unicode(c);
+            }
+        }
+        layouts::Us104Key::map_keycode(keycode, modifiers, handle_ctrl)
+    }
+}
+
+/// حرف المفتاح في التخطيط العربي (101)؛ `None` لما يبقى كالأمريكي
+/// (الأرقام والمسافة ومفاتيح التحكم). لام ألف بأشكالها المركبة.
+pub fn arabic_char(keycode: KeyCode, shifted: bool) -> Option<char> {
+    let (normal, shift) = match keycode {
+        KeyCode::BackTick => ('ذ', 'ّ'),
+        KeyCode::Q => ('ض', 'َ'),
+        KeyCode::W => ('ص', 'ً'),
+        KeyCode::E => ('ث', 'ُ'),
+        KeyCode::R => ('ق', 'ٌ'),
+        KeyCode::T => ('ف', 'ﻹ'),
+        KeyCode::Y => ('غ', 'إ'),
+        KeyCode::U => ('ع', '‘'),
+        KeyCode::I => ('ه', '÷'),
+        KeyCode::O => ('خ', '×'),
+        KeyCode::P => ('ح', '؛'),
+        KeyCode::BracketSquareLeft => ('ج', '<'),
+        KeyCode::BracketSquareRight => ('د', '>'),
+        KeyCode::A => ('ش', 'ِ'),
+        KeyCode::S => ('س', 'ٍ'),
+        KeyCode::D => ('ي', ']'),
+        KeyCode::F => ('ب', '['),
+        KeyCode::G => ('ل', 'ﻷ'),
+        KeyCode::H => ('ا', 'أ'),
+        KeyCode::J => ('ت', 'ـ'),
+        KeyCode::K => ('ن', '،'),
+        KeyCode::L => ('م', '/'),
+        KeyCode::SemiColon => ('ك', ':'),
+        KeyCode::Quote => ('ط', '"'),
+        KeyCode::Z => ('ئ', '~'),
+        KeyCode::X => ('ء', 'ْ'),
+        KeyCode::C => ('ؤ', '}'),
+        KeyCode::V => ('ر', '{'),
+        KeyCode::B => ('ﻻ', 'ﻵ'),
+        KeyCode::N => ('ى', 'آ'),
+        KeyCode::M => ('ة', '’'),
+        KeyCode::Comma => ('و', ','),
+        KeyCode::Fullstop => ('ز', '.'),
+        KeyCode::Slash => ('ظ', '؟'),
+        _ => return None,
+    };
+    Some(if shifted { shift } else { normal })
+}
+
+/// تتبع Alt و Shift لاختصار تبديل التخطيط
+#[derive(Debug, Default)]
+struct Hotkey {
+    alt: bool,
+    shift: bool,
+}
+
+impl Hotkey {
+    /// يعيد صحيحاً عند ضغط أحد المفتاحين والآخر مضغوط
+    fn update(&mut self, code: KeyCode, state: KeyState) -> bool {
+        let down = state == KeyState::Down;
+        let (this, other) = match code {
+            KeyCode::AltLeft | KeyCode::AltRight => (&mut self.alt, self.shift),
+            KeyCode::ShiftLeft | KeyCode::ShiftRight => (&mut self.shift, self.alt),
+            _ => return false,
+        };
+        let pressed = down && !*this;
+        *this = down;
+        pressed && other
+    }
+}
+
+lazy_static! {
+    static ref DECODER: Mutex<(Keyboard<Switchable, ScancodeSet1>, Hotkey)> =
+        Mutex::new((Keyboard::new(Switchable, ScancodeSet1, HandleControl::Ignore), Hotkey::default()));
+}
+
+/// تهيئة التعريف وإلغاء حجب IRQ1
+pub fn init() {
+    if let Some(layout) = crate::boot::params().get("keyboard").and_then(Layout::from_name) {
+        ARABIC.store(layout == Layout::Arabic, Ordering::SeqCst);
+    }
+    arch::idt::unmask_irq(IRQ);
+    info!("  ✅ لوحة مفاتيح PS/2 (التخطيط {}، Alt+Shift للتبديل)", layout().name());
+}
+
+/// التخطيط الحالي
+pub fn layout() -> Layout {
+    if ARABIC.load(Ordering::SeqCst) { Layout::Arabic } else { Layout::Us }
+}
+
+/// تغيير التخطيط وإعلان ذلك في طابور الإدخال
+pub fn set_layout(layout: Layout) {
+    ARABIC.store(layout == Layout::Arabic, Ordering::SeqCst);
+    input::push(InputEvent::LayoutChanged(layout));
+}
+
+/// معالج IRQ1: قراءة رمز المسح فقط
+#[cfg(not(feature = "hosted"))]
+pub fn on_interrupt() {
+    let scancode: u8 = unsafe { x86_64::instructions::port::Port::new(DATA_PORT).read() };
+    let mut pending = PENDING.lock();
+    if pending.len() < PENDING_CAPACITY {
+        pending.push_back(scancode);
+    }
+}
+
+/// حقن رموز مسح كأنها من IRQ1
+#[cfg(feature = "hosted")]
+pub fn inject_scancodes(scancodes: &[u8]) {
+    let mut pending = PENDING.lock();
+    for &scancode in scancodes {
+        if pending.len() < PENDING_CAPACITY {
+            pending.push_back(scancode);
+        }
+    }
+}
+
+/// فك الرموز المعلقة إلى أحداث؛ يعيد عدد الأحداث المضافة
+pub fn process_pending() -> usize {
+    let mut added = 0;
+    
+    while let Some(scancode) = arch::interrupts::without_interrupts(|| PENDING.lock().pop_front()) {
+        let mut decoder = DECODER.lock();
+        let (keyboard, hotkey) = &mut *decoder;
+        let Ok(Some(event)) = keyboard.add_byte(scancode) else {
+            continue;
+        };
+        
+        // مفتاحا الاختصار يمران على فك الرموز أيضاً ليبقى تتبع Shift صحيحاً
+        let toggle = hotkey.update(event.code, event.state);
+        let decoded = keyboard.process_keyevent(event);
+        drop(decoder);
+        
+        if toggle {
+            let next = match layout() {
+                Layout::Us => Layout::Arabic,
+                Layout::Arabic => Layout::Us,
+            };
+            set_layout(next);
+            added += 1;
+            continue;
+        }
+        
+        let key = match decoded {
+            Some(DecodedKey::Unicode(c)) => Key::Char(c),
+            Some(DecodedKey::RawKey(code)) => match special_key(code) {
+                Some(special) => Key::Special(special),
+                None => continue,
+            },
+            None => continue,
+        };
+        input::push(InputEvent::Key { key, layout: layout() });
+        added += 1;
+    }
+    added
+}
+
+/// المفاتيح الخاصة التي تهم القراء؛ مفاتيح التعديل لا تُعلن
+fn special_key(code: KeyCode) -> Option<SpecialKey> {
+    Some(match code {
+        KeyCode::ArrowUp => SpecialKey::Up,
+        KeyCode::ArrowDown => SpecialKey::Down,
+        KeyCode::ArrowLeft => SpecialKey::Left,
+        KeyCode::ArrowRight => SpecialKey::Right,
+        KeyCode::Home => SpecialKey::Home,
+        KeyCode::End => SpecialKey::End,
+        KeyCode::PageUp => SpecialKey::PageUp,
+        KeyCode::PageDown => SpecialKey::PageDown,
+        KeyCode::Insert => SpecialKey::Insert,
+        KeyCode::Delete => SpecialKey::Delete,
+        KeyCode::F1 => SpecialKey::Function(1),
+        KeyCode::F2 => SpecialKey::Function(2),
+        KeyCode::F3 => SpecialKey::Function(3),
+        KeyCode::F4 => SpecialKey::Function(4),
+        KeyCode::F5 => SpecialKey::Function(5),
+        KeyCode::F6 => SpecialKey::Function(6),
+        KeyCode::F7 => SpecialKey::Function(7),
+        KeyCode::F8 => SpecialKey::Function(8),
+        KeyCode::F9 => SpecialKey::Function(9),
+        KeyCode::F10 => SpecialKey::Function(10),
+        KeyCode::F11 => SpecialKey::Function(11),
+        KeyCode::F12 => SpecialKey::Function(12),
+        KeyCode::AltLeft | KeyCode::AltRight | KeyCode::ShiftLeft | KeyCode::ShiftRight
+        | KeyCode::ControlLeft | KeyCode::ControlRight | KeyCode::CapsLock | KeyCode::NumpadLock => return None,
+        _ => SpecialKey::Other,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_arabic_layout() {
+        assert_eq!(arabic_char(KeyCode::H, false), Some('ا'));
+        assert_eq!(arabic_char(KeyCode::Slash, true), Some('؟'));
+        assert_eq!(arabic_char(KeyCode::B, false), Some('ﻻ'));
+        assert_eq!(arabic_char(KeyCode::Key1, false), None);
+    }
+}
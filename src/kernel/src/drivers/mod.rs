@@ -0,0 +1,57 @@
+//! 🔌 تعريفات الأجهزة لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! في وضع المحاكاة المستضافة (`hosted`) تستبدل التعريفات منافذ العتاد
+//! بمخازن في الذاكرة ومخرجات لينكس القياسية.
+
+pub mod block;
+pub mod keyboard;
+pub mod mouse;
+pub mod net;
+pub mod pci;
+pub mod serial;
+pub mod vga;
+pub mod virtio;
+
+use alloc::string::String;
+use log::{info, warn};
+
+use crate::power::{self, HookContext, PowerAction};
+
+/// تهيئة جميع التعريفات
+pub fn init_all() {
+    serial::init();
+    info!("  ✅ المنفذ التسلسلي COM1");
+    
+    vga::WRITER.lock().clear_screen();
+    info!("  ✅ شاشة VGA النصية");
+    
+    keyboard::init();
+    mouse::init();
+    
+    block::init();
+    net::init();
+    pci::init();
+    
+    power::register("drivers", power::priority::DRIVERS, 50, announce_shutdown);
+}
+
+/// خطاف الإغلاق الأخير: إعلام المستخدم على الشاشة
+fn announce_shutdown(context: &HookContext) -> Result<(), String> {
+    let message = match context.action {
+        PowerAction::PowerOff => "Islam OS: powering off...",
+        PowerAction::Reboot => "Islam OS: rebooting...",
+    };
+    
+    let mut writer = vga::WRITER.lock();
+    writer.clear_screen();
+    writer.print_at(message, 0, 0);
+    Ok(())
+}
+
+/// إعادة تهيئة التعريفات الحرجة بعد الذعر
+pub fn reset_critical() {
+    warn!("🔌 إعادة تهيئة التعريفات الحرجة...");
+    serial::init();
+    pci::reprobe();
+}
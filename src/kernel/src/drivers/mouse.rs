@@ -0,0 +1,316 @@
+//! 🖱️ تعريف فأرة PS/2
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الفأرة على المنفذ الإضافي لمتحكم 8042. معالج IRQ12 يقرأ البايت من المنفذ
+//! 0x60 فقط؛ تجميع الحزم وتحويلها إلى أحداث يجري في الحلقة الرئيسية
+//! (`process_pending`) مثل لوحة المفاتيح.
+//!
+//! الحزمة ثلاثة بايتات، أو أربعة إن قبلت الفأرة تسلسل IntelliMouse
+//! (معدلات العينات 200 ثم 100 ثم 80) فيصير الرابع للعجلة:
+//!
+//! ```text
+//! البايت 0: Yovf Xovf Ysign Xsign 1 M R L
+//! البايت 1: X      البايت 2: Y (الموجب للأعلى)      البايت 3: العجلة (4 بتات)
+//! ```
+//!
+//! الموضع يُتتبع بنقاط شاشة افتراضية 640×400 (خلية نصية = 8×16 نقطة) حتى
+//! تكون الحركة البطيئة ممكنة، وتحمل الأحداث الخلية التي تحته.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use log::{info, warn};
+use spin::Mutex;
+
+use crate::arch;
+use crate::drivers::vga::{BUFFER_HEIGHT, BUFFER_WIDTH};
+use crate::input::{self, InputEvent, MouseButton, MouseEvent};
+
+/// خط مقاطعة الفأرة
+const IRQ: u8 = 12;
+
+/// بايتات لم تُجمع بعد (الزائد يُهمل)
+const PENDING_CAPACITY: usize = 256;
+
+/// أبعاد النقطة داخل الخلية النصية
+const CELL_WIDTH: i32 = 8;
+const CELL_HEIGHT: i32 = 16;
+
+/// بايتات المقاطعة؛ يُقفل خارجها والمقاطعات معطلة
+static PENDING: Mutex<VecDeque<u8>> = Mutex::new(VecDeque::new());
+
+/// حزمة مكتملة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    /// البت 0 الأيسر، 1 الأيمن، 2 الأوسط
+    pub buttons: u8,
+    pub dx: i16,
+    /// الموجب للأعلى كما ترسله الفأرة
+    pub dy: i16,
+    pub wheel: i8,
+}
+
+/// تجميع البايتات في حزم
+#[derive(Debug)]
+pub struct PacketDecoder {
+    bytes: [u8; 4],
+    len: usize,
+    wheel: bool,
+}
+
+impl PacketDecoder {
+    pub const fn new(wheel: bool) -> Self {
+        Self { bytes: [0; 4], len: 0, wheel }
+    }
+    
+    /// إضافة بايت؛ تعاد الحزمة عند اكتمالها
+    pub fn feed(&mut self, byte: u8) -> Option<Packet> {
+        // البت 3 من أول بايت دائماً واحد؛ بدونه فقدنا التزامن فننتظر بداية حزمة
+        if self.len == 0 && byte & 0x08 == 0 {
+            return None;
+        }
+        
+        self.bytes[self.len] = byte;
+        self.len += 1;
+        if self.len < if self.wheel { 4 } else { 3 } {
+            return None;
+        }
+        self.len = 0;
+        
+        let [flags, x, y, z] = self.bytes;
+        // الفيض يجعل الإزاحة بلا معنى، فتُهمل وتبقى الأزرار
+        let overflow = flags & 0xC0 != 0;
+        let delta = |value: u8, negative: bool| {
+            if overflow { 0 } else { value as i16 - if negative { 0x100 } else { 0 } }
+        };
+        Some(Packet {
+            buttons: flags & 0x07,
+            dx: delta(x, flags & 0x10 != 0),
+            dy: delta(y, flags & 0x20 != 0),
+            wheel: if self.wheel { ((z << 4) as i8) >> 4 } else { 0 },
+        })
+    }
+}
+
+/// موضع المؤشر والأزرار المضغوطة
+#[derive(Debug)]
+pub struct Tracker {
+    x: i32,
+    y: i32,
+    buttons: u8,
+}
+
+impl Tracker {
+    /// المؤشر في منتصف الشاشة
+    pub const fn new() -> Self {
+        Self {
+            x: BUFFER_WIDTH as i32 * CELL_WIDTH / 2,
+            y: BUFFER_HEIGHT as i32 * CELL_HEIGHT / 2,
+            buttons: 0,
+        }
+    }
+    
+    /// الخلية تحت المؤشر (العمود، السطر)
+    pub fn cell(&self) -> (u16, u16) {
+        ((self.x / CELL_WIDTH) as u16, (self.y / CELL_HEIGHT) as u16)
+    }
+    
+    /// أحداث الحزمة بالترتيب: الحركة ثم الأزرار ثم العجلة
+    pub fn apply(&mut self, packet: Packet) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        
+        if packet.dx != 0 || packet.dy != 0 {
+            self.x = (self.x + packet.dx as i32).clamp(0, BUFFER_WIDTH as i32 * CELL_WIDTH - 1);
+            self.y = (self.y - packet.dy as i32).clamp(0, BUFFER_HEIGHT as i32 * CELL_HEIGHT - 1);
+            events.push(MouseEvent::Move { dx: packet.dx, dy: -packet.dy });
+        }
+        
+        let changed = packet.buttons ^ self.buttons;
+        for (bit, button) in [(0x01, MouseButton::Left), (0x02, MouseButton::Right), (0x04, MouseButton::Middle)] {
+            if changed & bit != 0 {
+                events.push(MouseEvent::Button { button, pressed: packet.buttons & bit != 0 });
+            }
+        }
+        self.buttons = packet.buttons;
+        
+        if packet.wheel != 0 {
+            events.push(MouseEvent::Scroll(packet.wheel));
+        }
+        
+        let (column, row) = self.cell();
+        events.into_iter().map(|event| InputEvent::Mouse { event, column, row }).collect()
+    }
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static DECODER: Mutex<(PacketDecoder, Tracker)> = Mutex::new((PacketDecoder::new(false), Tracker::new()));
+
+/// تهيئة الفأرة وإلغاء حجب IRQ12؛ بلا فأرة يبقى الخط محجوباً
+pub fn init() {
+    match arch::interrupts::without_interrupts(controller::enable) {
+        Ok(wheel) => {
+            *DECODER.lock() = (PacketDecoder::new(wheel), Tracker::new());
+            arch::idt::unmask_irq(IRQ);
+            info!("  ✅ فأرة PS/2{}", if wheel { " بعجلة" } else { "" });
+        }
+        Err(error) => warn!("  ⚠️ لا فأرة PS/2: {}", error),
+    }
+}
+
+/// معالج IRQ12: قراءة البايت فقط
+#[cfg(not(feature = "hosted"))]
+pub fn on_interrupt() {
+    let byte: u8 = unsafe { x86_64::instructions::port::Port::new(controller::DATA_PORT).read() };
+    let mut pending = PENDING.lock();
+    if pending.len() < PENDING_CAPACITY {
+        pending.push_back(byte);
+    }
+}
+
+/// حقن بايتات كأنها من IRQ12
+#[cfg(feature = "hosted")]
+pub fn inject_bytes(bytes: &[u8]) {
+    let mut pending = PENDING.lock();
+    for &byte in bytes {
+        if pending.len() < PENDING_CAPACITY {
+            pending.push_back(byte);
+        }
+    }
+}
+
+/// تجميع البايتات المعلقة إلى أحداث؛ يعيد عدد الأحداث المضافة
+pub fn process_pending() -> usize {
+    let mut added = 0;
+    
+    while let Some(byte) = arch::interrupts::without_interrupts(|| PENDING.lock().pop_front()) {
+        let mut decoder = DECODER.lock();
+        let (packets, tracker) = &mut *decoder;
+        let Some(packet) = packets.feed(byte) else {
+            continue;
+        };
+        let events = tracker.apply(packet);
+        drop(decoder);
+        
+        added += events.len();
+        for event in events {
+            input::push(event);
+        }
+    }
+    added
+}
+
+/// الحوار مع متحكم 8042
+#[cfg(not(feature = "hosted"))]
+mod controller {
+    use x86_64::instructions::port::Port;
+    
+    pub const DATA_PORT: u16 = 0x60;
+    const COMMAND_PORT: u16 = 0x64;
+    
+    /// محاولات الانتظار قبل اعتبار المتحكم صامتاً
+    const SPINS: usize = 100_000;
+    
+    const ACK: u8 = 0xFA;
+    
+    fn status() -> u8 {
+        unsafe { Port::new(COMMAND_PORT).read() }
+    }
+    
+    fn write(port: u16, value: u8) -> Result<(), &'static str> {
+        (0..SPINS).find(|_| status() & 0x02 == 0).ok_or("المتحكم لا يقبل الكتابة")?;
+        unsafe { Port::new(port).write(value) };
+        Ok(())
+    }
+    
+    fn read() -> Result<u8, &'static str> {
+        (0..SPINS).find(|_| status() & 0x01 != 0).ok_or("لا رد")?;
+        Ok(unsafe { Port::new(DATA_PORT).read() })
+    }
+    
+    /// أمر إلى الفأرة وانتظار الإقرار
+    fn send(value: u8) -> Result<(), &'static str> {
+        write(COMMAND_PORT, 0xD4)?;
+        write(DATA_PORT, value)?;
+        match read()? {
+            ACK => Ok(()),
+            _ => Err("الفأرة رفضت الأمر"),
+        }
+    }
+    
+    fn set_sample_rate(rate: u8) -> Result<(), &'static str> {
+        send(0xF3)?;
+        send(rate)
+    }
+    
+    /// تفعيل المنفذ الإضافي ومقاطعته ثم الفأرة؛ يعيد صحيحاً إن كانت بعجلة
+    pub fn enable() -> Result<bool, &'static str> {
+        write(COMMAND_PORT, 0xA8)?;
+        
+        // بايت الإعداد: البت 1 مقاطعة المنفذ الإضافي، والبت 5 يوقف ساعته
+        write(COMMAND_PORT, 0x20)?;
+        let config = (read()? | 0x02) & !0x20;
+        write(COMMAND_PORT, 0x60)?;
+        write(DATA_PORT, config)?;
+        
+        send(0xF6)?;
+        
+        set_sample_rate(200)?;
+        set_sample_rate(100)?;
+        set_sample_rate(80)?;
+        send(0xF2)?;
+        let wheel = read()? == 3;
+        
+        send(0xF4)?;
+        Ok(wheel)
+    }
+}
+
+/// فأرة المحاكاة: IntelliMouse بعجلة
+#[cfg(feature = "hosted")]
+mod controller {
+    pub fn enable() -> Result<bool, &'static str> {
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_packet_decoding() {
+        // بايت بلا البت 3 يُهمل حتى تبدأ حزمة، والإشارة والفيض من البايت الأول
+        let mut decoder = PacketDecoder::new(false);
+        assert_eq!(decoder.feed(0x00), None);
+        assert_eq!(decoder.feed(0x39), None);
+        assert_eq!(decoder.feed(0xF0), None);
+        assert_eq!(decoder.feed(0x05), Some(Packet { buttons: 0x01, dx: -16, dy: -251, wheel: 0 }));
+        let packet = [0x4A, 0x7F, 0x7F].iter().find_map(|&byte| decoder.feed(byte));
+        assert_eq!(packet, Some(Packet { buttons: 0x02, dx: 0, dy: 0, wheel: 0 }));
+        let mut wheel = PacketDecoder::new(true);
+        let packet = [0x08, 0x00, 0x00, 0x0F].iter().find_map(|&byte| wheel.feed(byte));
+        assert_eq!(packet, Some(Packet { buttons: 0, dx: 0, dy: 0, wheel: -1 }));
+    }
+    
+    #[test_case]
+    fn test_pointer_tracking() {
+        // الحركة للأعلى تنقص السطر، والموضع لا يخرج من الشاشة
+        let mut tracker = Tracker::new();
+        assert_eq!(tracker.cell(), (40, 12));
+        let events = tracker.apply(Packet { buttons: 0x01, dx: 16, dy: 32, wheel: 2 });
+        let at = |event| InputEvent::Mouse { event, column: 42, row: 10 };
+        assert_eq!(events, [
+            at(MouseEvent::Move { dx: 16, dy: -32 }),
+            at(MouseEvent::Button { button: MouseButton::Left, pressed: true }),
+            at(MouseEvent::Scroll(2)),
+        ]);
+        tracker.apply(Packet { buttons: 0x01, dx: -255, dy: -255, wheel: 0 });
+        tracker.apply(Packet { buttons: 0x01, dx: -255, dy: -255, wheel: 0 });
+        tracker.apply(Packet { buttons: 0x01, dx: -255, dy: -255, wheel: 0 });
+        assert_eq!(tracker.cell(), (0, 24));
+    }
+}
@@ -0,0 +1,62 @@
+//! 🌐 طبقة بطاقات الشبكة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! التعريفات تسجل بطاقاتها هنا عند ربطها على ناقل PCI، والمكدس (`crate::net`)
+//! يأخذ أول بطاقة مسجلة. البطاقة ترسل وتستقبل إطارات إيثرنت كاملة بلا FCS.
+
+pub mod virtio;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use log::info;
+use spin::Mutex;
+
+use crate::net::ethernet::MacAddress;
+use crate::net::NetError;
+
+/// بطاقة شبكة
+pub trait NetDevice: Send + Sync {
+    /// اسم البطاقة (eth0...)
+    fn name(&self) -> &str;
+    
+    fn mac(&self) -> MacAddress;
+    
+    /// إرسال إطار
+    fn transmit(&self, frame: &[u8]) -> Result<(), NetError>;
+    
+    /// الإطار الوارد التالي إن وجد
+    fn receive(&self) -> Option<Vec<u8>>;
+    
+    /// هل الوصلة قائمة؟
+    fn link_up(&self) -> bool {
+        true
+    }
+    
+    /// إيقاف البطاقة عند الإغلاق: لا ترسل ولا تستقبل بعده
+    fn stop(&self) {}
+}
+
+static DEVICES: Mutex<Vec<Arc<dyn NetDevice>>> = Mutex::new(Vec::new());
+
+pub fn register_device(device: Arc<dyn NetDevice>) {
+    info!("  🌐 {}: {}", device.name(), device.mac());
+    DEVICES.lock().push(device);
+}
+
+pub fn unregister_device(name: &str) {
+    DEVICES.lock().retain(|device| device.name() != name);
+}
+
+/// البطاقات بترتيب تسجيلها
+pub fn devices() -> Vec<Arc<dyn NetDevice>> {
+    DEVICES.lock().clone()
+}
+
+pub fn find(name: &str) -> Option<Arc<dyn NetDevice>> {
+    DEVICES.lock().iter().find(|device| device.name() == name).cloned()
+}
+
+/// تسجيل تعريفات العتاد على ناقل PCI
+pub fn init() {
+    crate::drivers::pci::register_driver(&virtio::DRIVER);
+}
@@ -0,0 +1,353 @@
+//! 🌀 بطاقات virtio-net (الواجهة القديمة على PCI)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الجهاز 1AF4:1000 بطابورين: الاستقبال (0) والإرسال (1). كل إطار يسبقه
+//! ترويسة virtio-net من 10 بايت (بلا تفريغ مجموع التحقق ولا TSO فتبقى
+//! أصفاراً)، وكل خانة في ذاكرة DMA تُرسل واصفين:
+//!
+//! ```text
+//! خانة 2048 بايت: [الترويسة 10][حشو][الإطار حتى 1514 من الإزاحة 16]
+//! ```
+//!
+//! خانات الاستقبال كلها في الطابور من البداية، وتعود إليه بعد نسخ إطارها.
+//! لا مقاطعات: المكدس يستطلع الطابورين دورياً. البطاقات تُسمى eth0 و eth1...
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ptr::write_bytes;
+use log::info;
+use spin::Mutex;
+
+use super::NetDevice;
+use crate::drivers::pci::{self, DeviceId, PciAddress, PciDevice, PciDriver};
+use crate::drivers::virtio::{self, register, Transport, Virtqueue};
+use crate::memory::{self, DmaRegion};
+use crate::net::ethernet::{MacAddress, HEADER_LEN as ETHERNET_HEADER_LEN, MTU};
+use crate::net::NetError;
+
+/// إعدادات virtio-net: عنوان MAC ثم حالة الوصلة (16 بت)
+const MAC: u16 = register::CONFIG;
+const STATUS: u16 = register::CONFIG + 6;
+
+/// بت الوصلة القائمة في `STATUS`
+const STATUS_LINK_UP: u16 = 1;
+
+/// ميزات virtio-net التي نفهمها
+pub const FEATURE_MAC: u32 = 1 << 5;
+pub const FEATURE_STATUS: u32 = 1 << 16;
+
+/// طول ترويسة virtio-net بلا MRG_RXBUF
+pub const NET_HEADER_LEN: usize = 10;
+
+/// أكبر إطار بلا FCS
+pub const MAX_FRAME: usize = ETHERNET_HEADER_LEN + MTU;
+
+/// الخانة وإزاحة الإطار فيها
+const SLOT_SIZE: usize = 2048;
+const DATA_OFFSET: usize = 16;
+
+/// أقصى عدد خانات لكل طابور (كل خانة واصفان)
+const MAX_SLOTS: usize = 64;
+
+/// طابور وخاناته
+struct Ring {
+    virtqueue: Virtqueue,
+    buffers: DmaRegion,
+    /// الخانة بحسب رأس سلسلتها
+    heads: Vec<Option<usize>>,
+    /// خانات الإرسال الحرة
+    free: Vec<usize>,
+}
+
+impl Ring {
+    fn new(transport: &dyn Transport, index: u16) -> Result<Self, &'static str> {
+        let virtqueue = Virtqueue::new(transport, index)?;
+        let slots = (virtqueue.size() as usize / 2).min(MAX_SLOTS);
+        let buffers = memory::alloc_dma(slots * SLOT_SIZE).ok_or("لا ذاكرة DMA")?;
+        let heads = alloc::vec![None; virtqueue.size() as usize];
+        Ok(Self { virtqueue, buffers, heads, free: (0..slots).rev().collect() })
+    }
+    
+    fn slot(&mut self, slot: usize) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.buffers.virt.add(slot * SLOT_SIZE), SLOT_SIZE) }
+    }
+    
+    /// وضع الخانة في الطابور بترويستها و`len` بايت من إطارها
+    fn post(&mut self, slot: usize, len: usize, writable: bool) -> bool {
+        let phys = self.buffers.phys + (slot * SLOT_SIZE) as u64;
+        let chain = [(phys, NET_HEADER_LEN as u32, writable), (phys + DATA_OFFSET as u64, len as u32, writable)];
+        match self.virtqueue.add(&chain) {
+            Some(head) => {
+                self.heads[head as usize] = Some(slot);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// خانة أعادها الجهاز وعدد البايتات التي كتبها
+    fn completed(&mut self) -> Option<(usize, usize)> {
+        let (head, written) = self.virtqueue.pop_used()?;
+        let slot = self.heads[head as usize].take()?;
+        Some((slot, written as usize))
+    }
+}
+
+struct Queues {
+    rx: Ring,
+    tx: Ring,
+}
+
+/// بطاقة virtio-net
+pub struct VirtioNet {
+    name: String,
+    transport: Arc<dyn Transport>,
+    queues: Mutex<Queues>,
+    mac: MacAddress,
+    features: u32,
+}
+
+impl VirtioNet {
+    /// تهيئة الجهاز: الميزات، الطابوران، خانات الاستقبال، ثم التشغيل
+    pub fn new(name: String, transport: Arc<dyn Transport>) -> Result<Self, &'static str> {
+        let t = &*transport;
+        let features = virtio::negotiate(t, FEATURE_MAC | FEATURE_STATUS);
+        
+        let queues = Ring::new(t, 0).and_then(|rx| Ok(Queues { rx, tx: Ring::new(t, 1)? }));
+        let mut queues = match queues {
+            Ok(queues) => queues,
+            Err(e) => {
+                virtio::fail(t);
+                return Err(e);
+            }
+        };
+        
+        // بلا ميزة MAC نختار عنواناً محلياً عشوائياً
+        let mac = if features & FEATURE_MAC != 0 {
+            MacAddress(core::array::from_fn(|i| t.read8(MAC + i as u16)))
+        } else {
+            let random = crate::arch::cpu::random_u64().to_le_bytes();
+            MacAddress([0x02, random[0], random[1], random[2], random[3], random[4]])
+        };
+        
+        let rx = &mut queues.rx;
+        while let Some(slot) = rx.free.pop() {
+            rx.post(slot, MAX_FRAME, true);
+        }
+        virtio::driver_ok(t);
+        queues.rx.virtqueue.notify(t);
+        
+        Ok(Self { name, transport, queues: Mutex::new(queues), mac, features })
+    }
+}
+
+impl NetDevice for VirtioNet {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    
+    fn mac(&self) -> MacAddress {
+        self.mac
+    }
+    
+    fn transmit(&self, frame: &[u8]) -> Result<(), NetError> {
+        if frame.len() > MAX_FRAME {
+            return Err(NetError::InvalidInput);
+        }
+        let mut queues = self.queues.lock();
+        let tx = &mut queues.tx;
+        while let Some((slot, _)) = tx.completed() {
+            tx.free.push(slot);
+        }
+        
+        let slot = tx.free.pop().ok_or(NetError::BufferFull)?;
+        let buffer = tx.slot(slot);
+        unsafe { write_bytes(buffer.as_mut_ptr(), 0, NET_HEADER_LEN) };
+        buffer[DATA_OFFSET..DATA_OFFSET + frame.len()].copy_from_slice(frame);
+        if !tx.post(slot, frame.len(), false) {
+            tx.free.push(slot);
+            return Err(NetError::Io);
+        }
+        tx.virtqueue.notify(&*self.transport);
+        Ok(())
+    }
+    
+    fn receive(&self) -> Option<Vec<u8>> {
+        let mut queues = self.queues.lock();
+        let rx = &mut queues.rx;
+        let (slot, written) = rx.completed()?;
+        
+        let len = written.saturating_sub(NET_HEADER_LEN).min(MAX_FRAME);
+        let frame = rx.slot(slot)[DATA_OFFSET..DATA_OFFSET + len].to_vec();
+        rx.post(slot, MAX_FRAME, true);
+        rx.virtqueue.notify(&*self.transport);
+        Some(frame)
+    }
+    
+    fn link_up(&self) -> bool {
+        self.features & FEATURE_STATUS == 0 || self.transport.read16(STATUS) & STATUS_LINK_UP != 0
+    }
+    
+    fn stop(&self) {
+        // القفل يمنع إرسالاً أو استقبالاً أثناء إعادة الضبط
+        let _queues = self.queues.lock();
+        virtio::reset(&*self.transport);
+    }
+}
+
+/// البطاقات التي سجلها التعريف وأجهزة PCI التي تعود إليها
+static CARDS: Mutex<Vec<(PciAddress, String)>> = Mutex::new(Vec::new());
+
+fn probe(device: &PciDevice) -> Result<(), String> {
+    let port = device.io_bar().ok_or_else(|| String::from("لا BAR منافذ (الواجهة الحديثة فقط غير مدعومة)"))?;
+    let transport = virtio::transport(port).ok_or_else(|| String::from("لا جهاز على المنفذ"))?;
+    pci::enable_bus_master(device.address);
+    
+    // أول رقم غير مستخدم حتى تبقى الأسماء ثابتة بعد إعادة الفحص
+    let mut cards = CARDS.lock();
+    let index = (0..).find(|index| cards.iter().all(|(_, name)| *name != alloc::format!("eth{}", index))).unwrap_or(0);
+    let name = alloc::format!("eth{}", index);
+    let card = VirtioNet::new(name.clone(), transport).map_err(String::from)?;
+    info!("  🌀 {}: virtio-net{}", name, if card.link_up() { "" } else { " (الوصلة مقطوعة)" });
+    
+    cards.push((device.address, name));
+    drop(cards);
+    super::register_device(Arc::new(card));
+    Ok(())
+}
+
+fn remove(device: &PciDevice) {
+    CARDS.lock().retain(|(address, name)| {
+        if *address == device.address {
+            super::unregister_device(name);
+        }
+        *address != device.address
+    });
+}
+
+/// تعريف virtio-net
+pub static DRIVER: PciDriver = PciDriver {
+    name: "virtio-net",
+    ids: &[DeviceId::new(0x1AF4, 0x1000)],
+    probe,
+    remove,
+};
+
+#[cfg(feature = "hosted")]
+pub use self::simulated::{hosted_device, SimulatedNet};
+
+/// بطاقة virtio-net محاكاة: الصادر يُجمع، والوارد يُحقن في خانات الاستقبال
+#[cfg(feature = "hosted")]
+mod simulated {
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use spin::Mutex;
+    
+    use super::*;
+    use crate::drivers::virtio::simulated::{self, Common};
+    
+    /// حجم الطابورين
+    const QUEUE_SIZE: u16 = 64;
+    
+    struct State {
+        common: Common,
+        mac: MacAddress,
+        link: bool,
+        sent: Vec<Vec<u8>>,
+    }
+    
+    pub struct SimulatedNet {
+        state: Mutex<State>,
+    }
+    
+    /// بطاقة جديدة على المنفذ `port` (تُعلن بعدها في فضاء إعداد PCI المحاكى)
+    pub fn hosted_device(port: u16, mac: MacAddress) -> Arc<SimulatedNet> {
+        let common = Common::new(FEATURE_MAC | FEATURE_STATUS, 2, QUEUE_SIZE);
+        let device = Arc::new(SimulatedNet { state: Mutex::new(State { common, mac, link: true, sent: Vec::new() }) });
+        simulated::attach(port, device.clone());
+        device
+    }
+    
+    impl SimulatedNet {
+        /// تسليم إطار للتعريف؛ `false` إن لم تكن خانة استقبال متاحة
+        pub fn inject(&self, frame: &[u8]) -> bool {
+            let mut state = self.state.lock();
+            if !state.common.ready() {
+                return false;
+            }
+            let chain = match state.common.queues[0].pop() {
+                Some(chain) => chain,
+                None => return false,
+            };
+            let mut data = alloc::vec![0u8; NET_HEADER_LEN];
+            data.extend_from_slice(frame);
+            let written = chain.write(&data);
+            state.common.queues[0].push_used(chain.head, written);
+            state.common.isr = 1;
+            true
+        }
+        
+        /// الإطارات التي أرسلها التعريف منذ آخر استدعاء
+        pub fn take_sent(&self) -> Vec<Vec<u8>> {
+            core::mem::take(&mut self.state.lock().sent)
+        }
+        
+        pub fn set_link(&self, up: bool) {
+            self.state.lock().link = up;
+        }
+        
+        /// الحالة التي كتبها التعريف (DRIVER_OK...)
+        pub fn status(&self) -> u8 {
+            self.state.lock().common.status
+        }
+        
+        fn read(&self, offset: u16) -> u32 {
+            let mut state = self.state.lock();
+            match state.common.read(offset) {
+                Some(value) => value,
+                None if (MAC..MAC + 6).contains(&offset) => state.mac.0[(offset - MAC) as usize] as u32,
+                None if offset == STATUS => state.link as u32,
+                None => 0,
+            }
+        }
+        
+        fn write(&self, offset: u16, value: u32) {
+            let mut state = self.state.lock();
+            if state.common.write(offset, value) == Some(1) && state.common.ready() {
+                while let Some(chain) = state.common.queues[1].pop() {
+                    let bytes = chain.readable();
+                    state.sent.push(bytes[NET_HEADER_LEN.min(bytes.len())..].to_vec());
+                    state.common.queues[1].push_used(chain.head, 0);
+                }
+                state.common.isr = 1;
+            }
+        }
+    }
+    
+    impl Transport for SimulatedNet {
+        fn read8(&self, offset: u16) -> u8 {
+            self.read(offset) as u8
+        }
+        
+        fn read16(&self, offset: u16) -> u16 {
+            self.read(offset) as u16
+        }
+        
+        fn read32(&self, offset: u16) -> u32 {
+            self.read(offset)
+        }
+        
+        fn write8(&self, offset: u16, value: u8) {
+            self.write(offset, value as u32)
+        }
+        
+        fn write16(&self, offset: u16, value: u16) {
+            self.write(offset, value as u32)
+        }
+        
+        fn write32(&self, offset: u16, value: u32) {
+            self.write(offset, value)
+        }
+    }
+}
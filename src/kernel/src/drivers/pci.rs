@@ -0,0 +1,719 @@
+//! 🧩 ناقل PCI وربط التعريفات بالأجهزة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! فضاء الإعداد يُقرأ بالآلية الأولى (المنفذان 0xCF8 و 0xCFC)، وفي المحاكاة
+//! من خريطة في الذاكرة فيها أجهزة QEMU المعتادة. المسح يمر على كل ناقل
+//! وفتحة، والدوال الأخرى لا تُفحص إلا إن كان الجهاز متعدد الدوال.
+//!
+//! كل تعريف يعلن جداول مطابقة (مصنّع/جهاز أو فئة/فئة فرعية) مع دالتي
+//! `probe` و `remove`. أول تعريف يقبل الجهاز يرتبط به:
+//!
+//! ```text
+//! scan() ──▶ 00:01.1 8086:7010 (01:01) ──مطابقة──▶ ata.probe() ──Ok──▶ مرتبط
+//! register_driver() ──▶ الأجهزة غير المرتبطة التي سبق اكتشافها
+//! ```
+//!
+//! الشجرة في /proc/bus/pci/devices والتعريفات في /proc/bus/pci/drivers.
+//!
+//! لا نعتمد على حزمة `pci`: المنشور منها 0.0.1 وحده ولا يُبنى على المترجم
+//! الحالي، والآلية الأولى بضعة أسطر فوق `x86_64::instructions::port`.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use lazy_static::lazy_static;
+use log::{info, warn};
+use spin::Mutex;
+
+/// موقع دالة على الناقل
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PciAddress {
+    pub bus: u8,
+    pub device: u8,
+    pub function: u8,
+}
+
+impl PciAddress {
+    pub const fn new(bus: u8, device: u8, function: u8) -> Self {
+        Self { bus, device, function }
+    }
+}
+
+impl fmt::Display for PciAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}:{:02x}.{}", self.bus, self.device, self.function)
+    }
+}
+
+/// إزاحات رأس فضاء الإعداد
+pub mod offset {
+    pub const VENDOR_ID: u8 = 0x00;
+    pub const COMMAND: u8 = 0x04;
+    pub const REVISION: u8 = 0x08;
+    pub const HEADER_TYPE: u8 = 0x0C;
+    pub const BAR0: u8 = 0x10;
+    pub const INTERRUPT_LINE: u8 = 0x3C;
+}
+
+/// بتات مسجل الأوامر
+const COMMAND_IO_SPACE: u16 = 1 << 0;
+const COMMAND_MEMORY_SPACE: u16 = 1 << 1;
+const COMMAND_BUS_MASTER: u16 = 1 << 2;
+
+/// المصنّع 0xFFFF يعني لا جهاز
+const NO_DEVICE: u16 = 0xFFFF;
+
+/// قراءة وكتابة فضاء الإعداد بكلمات 32 بت محاذاة
+pub trait ConfigSpace: Send + Sync {
+    fn read(&self, address: PciAddress, offset: u8) -> u32;
+    fn write(&self, address: PciAddress, offset: u8, value: u32);
+}
+
+/// الآلية الأولى: عنوان في 0xCF8 ثم البيانات من 0xCFC
+#[cfg(not(feature = "hosted"))]
+pub struct PortConfigSpace {
+    lock: Mutex<()>,
+}
+
+#[cfg(not(feature = "hosted"))]
+impl PortConfigSpace {
+    const ADDRESS_PORT: u16 = 0xCF8;
+    const DATA_PORT: u16 = 0xCFC;
+    
+    const fn new() -> Self {
+        Self { lock: Mutex::new(()) }
+    }
+    
+    fn select(address: PciAddress, offset: u8) {
+        use x86_64::instructions::port::Port;
+        
+        let value = 1 << 31
+            | (address.bus as u32) << 16
+            | (address.device as u32) << 11
+            | (address.function as u32) << 8
+            | (offset & 0xFC) as u32;
+        unsafe { Port::<u32>::new(Self::ADDRESS_PORT).write(value) }
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+impl ConfigSpace for PortConfigSpace {
+    fn read(&self, address: PciAddress, offset: u8) -> u32 {
+        let _guard = self.lock.lock();
+        Self::select(address, offset);
+        unsafe { x86_64::instructions::port::Port::<u32>::new(Self::DATA_PORT).read() }
+    }
+    
+    fn write(&self, address: PciAddress, offset: u8, value: u32) {
+        let _guard = self.lock.lock();
+        Self::select(address, offset);
+        unsafe { x86_64::instructions::port::Port::<u32>::new(Self::DATA_PORT).write(value) }
+    }
+}
+
+/// فضاء إعداد في الذاكرة: 256 بايت لكل دالة، والغائب يُقرأ 0xFFFFFFFF.
+/// مسجلات BAR تحاكي التحجيم: كتابة كل الواحدات تعيد قناع الحجم، والنصف
+/// الأعلى من BAR ذي 64 بت يقبل أي قيمة (الأحجام أقل من 4 جيجابايت)
+pub struct MemoryConfigSpace {
+    functions: Mutex<BTreeMap<PciAddress, Function>>,
+}
+
+struct Function {
+    config: [u32; 64],
+    bar_sizes: [u32; 6],
+}
+
+impl MemoryConfigSpace {
+    pub const fn new() -> Self {
+        Self { functions: Mutex::new(BTreeMap::new()) }
+    }
+    
+    /// إضافة دالة برأس من النوع 0؛ `bars` عناوين BAR وأحجامها
+    pub fn attach(&self, address: PciAddress, header: &Header) {
+        let mut config = [0u32; 64];
+        config[0] = header.vendor_id as u32 | (header.device_id as u32) << 16;
+        config[1] = 0x0010_0000;
+        config[2] = header.revision as u32
+            | (header.prog_if as u32) << 8
+            | (header.subclass as u32) << 16
+            | (header.class as u32) << 24;
+        config[3] = ((header.header_type | if header.multifunction { 0x80 } else { 0 }) as u32) << 16;
+        let mut bar_sizes = [0u32; 6];
+        for (index, &(bar, size)) in header.bars.iter().enumerate().take(6) {
+            config[4 + index] = bar;
+            bar_sizes[index] = size;
+        }
+        config[15] = header.interrupt_line as u32 | (header.interrupt_pin as u32) << 8;
+        
+        self.functions.lock().insert(address, Function { config, bar_sizes });
+    }
+    
+    /// إزالة دالة (محاكاة نزع جهاز)
+    pub fn detach(&self, address: PciAddress) {
+        self.functions.lock().remove(&address);
+    }
+}
+
+impl Default for MemoryConfigSpace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigSpace for MemoryConfigSpace {
+    fn read(&self, address: PciAddress, offset: u8) -> u32 {
+        self.functions.lock().get(&address).map_or(u32::MAX, |function| function.config[offset as usize / 4])
+    }
+    
+    fn write(&self, address: PciAddress, offset: u8, value: u32) {
+        let mut functions = self.functions.lock();
+        let Some(function) = functions.get_mut(&address) else {
+            return;
+        };
+        let index = offset as usize / 4;
+        
+        match index {
+            4..=9 => {
+                let size = function.bar_sizes[index - 4];
+                let old = function.config[index];
+                let upper_half = index > 4 && function.config[index - 1] & 0x7 == 0x4 && function.bar_sizes[index - 5] != 0;
+                if upper_half {
+                    function.config[index] = value;
+                } else if size != 0 {
+                    let flags = if old & 1 != 0 { old & 0x3 } else { old & 0xF };
+                    function.config[index] = (value & !(size - 1)) | flags;
+                }
+            }
+            // المصنّع والفئة للقراءة فقط
+            0 | 2 => {}
+            _ => function.config[index] = value,
+        }
+    }
+}
+
+/// وصف رأس لـ `MemoryConfigSpace::attach`
+#[derive(Debug, Clone, Default)]
+pub struct Header {
+    pub vendor_id: u16,
+    pub device_id: u16,
+    pub class: u8,
+    pub subclass: u8,
+    pub prog_if: u8,
+    pub revision: u8,
+    pub header_type: u8,
+    pub multifunction: bool,
+    /// (القيمة الأولية، الحجم بالبايت أو صفر لغير المستخدم)
+    pub bars: Vec<(u32, u32)>,
+    pub interrupt_line: u8,
+    pub interrupt_pin: u8,
+}
+
+/// منطقة يعلنها مسجل BAR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bar {
+    Memory { address: u64, size: u64, prefetchable: bool },
+    Io { port: u16, size: u32 },
+}
+
+/// دالة مكتشفة على الناقل
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PciDevice {
+    pub address: PciAddress,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    pub class: u8,
+    pub subclass: u8,
+    pub prog_if: u8,
+    pub revision: u8,
+    /// نوع الرأس دون بت تعدد الدوال (0 جهاز، 1 جسر)
+    pub header_type: u8,
+    pub interrupt_line: u8,
+    pub interrupt_pin: u8,
+    pub bars: Vec<Option<Bar>>,
+}
+
+impl PciDevice {
+    /// قراءة رأس الدالة؛ `None` إن لم يوجد جهاز
+    pub fn read(config: &dyn ConfigSpace, address: PciAddress) -> Option<Self> {
+        let id = config.read(address, offset::VENDOR_ID);
+        if id as u16 == NO_DEVICE {
+            return None;
+        }
+        let class = config.read(address, offset::REVISION);
+        let header_type = (config.read(address, offset::HEADER_TYPE) >> 16) as u8 & 0x7F;
+        let interrupt = config.read(address, offset::INTERRUPT_LINE);
+        
+        let bar_count = match header_type {
+            0 => 6,
+            1 => 2,
+            _ => 0,
+        };
+        
+        Some(Self {
+            address,
+            vendor_id: id as u16,
+            device_id: (id >> 16) as u16,
+            class: (class >> 24) as u8,
+            subclass: (class >> 16) as u8,
+            prog_if: (class >> 8) as u8,
+            revision: class as u8,
+            header_type,
+            interrupt_line: interrupt as u8,
+            interrupt_pin: (interrupt >> 8) as u8,
+            bars: read_bars(config, address, bar_count),
+        })
+    }
+    
+    /// اسم الفئة للعرض
+    pub fn class_name(&self) -> &'static str {
+        class_name(self.class, self.subclass)
+    }
+    
+    /// أول BAR من نوع الذاكرة
+    pub fn memory_bar(&self) -> Option<(u64, u64)> {
+        self.bars.iter().flatten().find_map(|bar| match *bar {
+            Bar::Memory { address, size, .. } => Some((address, size)),
+            Bar::Io { .. } => None,
+        })
+    }
+    
+    /// أول BAR من نوع المنافذ
+    pub fn io_bar(&self) -> Option<u16> {
+        self.bars.iter().flatten().find_map(|bar| match *bar {
+            Bar::Io { port, .. } => Some(port),
+            Bar::Memory { .. } => None,
+        })
+    }
+}
+
+/// قراءة مسجلات BAR وأحجامها (كتابة كل الواحدات ثم الاستعادة، وفك
+/// التشفير معطل أثناء ذلك حتى لا يظهر الجهاز على عنوان عشوائي)
+fn read_bars(config: &dyn ConfigSpace, address: PciAddress, count: usize) -> Vec<Option<Bar>> {
+    let mut bars = alloc::vec![None; count];
+    let command = config.read(address, offset::COMMAND);
+    config.write(address, offset::COMMAND, command & !((COMMAND_IO_SPACE | COMMAND_MEMORY_SPACE) as u32));
+    
+    let mut index = 0;
+    while index < count {
+        let register = offset::BAR0 + index as u8 * 4;
+        let original = config.read(address, register);
+        config.write(address, register, u32::MAX);
+        let mask = config.read(address, register);
+        config.write(address, register, original);
+        
+        if original & 1 != 0 {
+            let size = !(mask & !0x3) as u16 as u32 + 1;
+            if mask & !0x3 != 0 {
+                bars[index] = Some(Bar::Io { port: (original & !0x3) as u16, size });
+            }
+            index += 1;
+            continue;
+        }
+        
+        let is_64bit = (original >> 1) & 0x3 == 0x2 && index + 1 < count;
+        let (address_value, size_mask) = if is_64bit {
+            let high_register = register + 4;
+            let high = config.read(address, high_register);
+            config.write(address, high_register, u32::MAX);
+            let high_mask = config.read(address, high_register);
+            config.write(address, high_register, high);
+            (
+                (high as u64) << 32 | (original & !0xF) as u64,
+                (high_mask as u64) << 32 | (mask & !0xF) as u64,
+            )
+        } else {
+            ((original & !0xF) as u64, (mask & !0xF) as u64 | 0xFFFF_FFFF_0000_0000)
+        };
+        
+        if mask & !0xF != 0 {
+            bars[index] = Some(Bar::Memory {
+                address: address_value,
+                size: !size_mask + 1,
+                prefetchable: original & 0x8 != 0,
+            });
+        }
+        index += if is_64bit { 2 } else { 1 };
+    }
+    
+    config.write(address, offset::COMMAND, command);
+    bars
+}
+
+/// أسماء الفئات الشائعة
+pub fn class_name(class: u8, subclass: u8) -> &'static str {
+    match (class, subclass) {
+        (0x01, 0x01) => "IDE controller",
+        (0x01, 0x06) => "SATA controller",
+        (0x01, 0x08) => "NVMe controller",
+        (0x01, 0x00) => "SCSI controller",
+        (0x01, _) => "storage controller",
+        (0x02, 0x00) => "Ethernet controller",
+        (0x02, _) => "network controller",
+        (0x03, 0x00) => "VGA controller",
+        (0x03, _) => "display controller",
+        (0x04, _) => "multimedia controller",
+        (0x05, _) => "memory controller",
+        (0x06, 0x00) => "host bridge",
+        (0x06, 0x01) => "ISA bridge",
+        (0x06, 0x04) => "PCI bridge",
+        (0x06, _) => "bridge",
+        (0x07, _) => "communication controller",
+        (0x08, _) => "system peripheral",
+        (0x0C, 0x03) => "USB controller",
+        (0x0C, 0x05) => "SMBus controller",
+        (0x0C, _) => "serial bus controller",
+        _ => "unclassified device",
+    }
+}
+
+/// جدول مطابقة: الحقول الفارغة تطابق أي قيمة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceId {
+    pub vendor: Option<u16>,
+    pub device: Option<u16>,
+    pub class: Option<u8>,
+    pub subclass: Option<u8>,
+}
+
+impl DeviceId {
+    /// مطابقة مصنّع وجهاز بعينهما
+    pub const fn new(vendor: u16, device: u16) -> Self {
+        Self { vendor: Some(vendor), device: Some(device), class: None, subclass: None }
+    }
+    
+    /// مطابقة كل أجهزة فئة فرعية
+    pub const fn class(class: u8, subclass: u8) -> Self {
+        Self { vendor: None, device: None, class: Some(class), subclass: Some(subclass) }
+    }
+    
+    pub fn matches(&self, device: &PciDevice) -> bool {
+        self.vendor.is_none_or(|vendor| vendor == device.vendor_id)
+            && self.device.is_none_or(|id| id == device.device_id)
+            && self.class.is_none_or(|class| class == device.class)
+            && self.subclass.is_none_or(|subclass| subclass == device.subclass)
+    }
+}
+
+/// تعريف جهاز PCI
+pub struct PciDriver {
+    pub name: &'static str,
+    pub ids: &'static [DeviceId],
+    /// تهيئة الجهاز؛ الخطأ يترك الجهاز حراً لتعريف آخر
+    pub probe: fn(&PciDevice) -> Result<(), String>,
+    /// فك الارتباط قبل إعادة الفحص أو الإغلاق
+    pub remove: fn(&PciDevice),
+}
+
+impl PciDriver {
+    pub fn matches(&self, device: &PciDevice) -> bool {
+        self.ids.iter().any(|id| id.matches(device))
+    }
+}
+
+struct Slot {
+    device: PciDevice,
+    driver: Option<&'static PciDriver>,
+}
+
+/// الأجهزة المكتشفة والتعريفات المسجلة
+pub struct PciBus {
+    slots: Vec<Slot>,
+    drivers: Vec<&'static PciDriver>,
+}
+
+impl PciBus {
+    pub const fn new() -> Self {
+        Self { slots: Vec::new(), drivers: Vec::new() }
+    }
+    
+    /// الدوال الموجودة على كل النواقل بترتيب العنوان
+    pub fn enumerate(config: &dyn ConfigSpace) -> Vec<PciDevice> {
+        let mut devices = Vec::new();
+        for bus in 0..=255u8 {
+            for slot in 0..32u8 {
+                let Some(first) = PciDevice::read(config, PciAddress::new(bus, slot, 0)) else {
+                    continue;
+                };
+                let multifunction = config.read(first.address, offset::HEADER_TYPE) & (0x80 << 16) != 0;
+                devices.push(first);
+                
+                if multifunction {
+                    devices.extend((1..8).filter_map(|function| PciDevice::read(config, PciAddress::new(bus, slot, function))));
+                }
+            }
+        }
+        devices
+    }
+    
+    /// استبدال قائمة الأجهزة بنتيجة مسح جديد؛ الأجهزة الباقية تحتفظ بتعريفاتها.
+    /// يعيد الأجهزة التي اختفت مع تعريفاتها ليُستدعى `remove` عليها
+    pub fn update(&mut self, devices: Vec<PciDevice>) -> Vec<(PciDevice, &'static PciDriver)> {
+        let mut previous: BTreeMap<PciAddress, Slot> = self.slots.drain(..).map(|slot| (slot.device.address, slot)).collect();
+        
+        for device in devices {
+            let driver = previous.remove(&device.address)
+                .filter(|slot| slot.device.vendor_id == device.vendor_id && slot.device.device_id == device.device_id)
+                .and_then(|slot| slot.driver);
+            self.slots.push(Slot { device, driver });
+        }
+        
+        previous.into_values()
+            .filter_map(|slot| slot.driver.map(|driver| (slot.device, driver)))
+            .collect()
+    }
+    
+    /// تسجيل تعريف؛ التسجيل بالاسم نفسه يُتجاهل
+    pub fn add_driver(&mut self, driver: &'static PciDriver) -> bool {
+        if self.drivers.iter().any(|existing| existing.name == driver.name) {
+            return false;
+        }
+        self.drivers.push(driver);
+        true
+    }
+    
+    /// أزواج (جهاز حر، تعريف مرشح) بترتيب الأجهزة ثم التسجيل
+    pub fn candidates(&self) -> Vec<(PciDevice, Vec<&'static PciDriver>)> {
+        self.slots.iter()
+            .filter(|slot| slot.driver.is_none())
+            .map(|slot| (slot.device.clone(), self.drivers.iter().copied().filter(|driver| driver.matches(&slot.device)).collect::<Vec<_>>()))
+            .filter(|(_, drivers)| !drivers.is_empty())
+            .collect()
+    }
+    
+    /// تسجيل ارتباط ناجح
+    pub fn bind(&mut self, address: PciAddress, driver: &'static PciDriver) {
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.device.address == address) {
+            slot.driver = Some(driver);
+        }
+    }
+    
+    /// فك كل الارتباطات وإعادة الأجهزة مع تعريفاتها
+    pub fn unbind_all(&mut self) -> Vec<(PciDevice, &'static PciDriver)> {
+        self.slots.iter_mut()
+            .filter_map(|slot| slot.driver.take().map(|driver| (slot.device.clone(), driver)))
+            .collect()
+    }
+    
+    pub fn devices(&self) -> Vec<(PciDevice, Option<&'static str>)> {
+        self.slots.iter().map(|slot| (slot.device.clone(), slot.driver.map(|driver| driver.name))).collect()
+    }
+    
+    pub fn drivers(&self) -> Vec<&'static str> {
+        self.drivers.iter().map(|driver| driver.name).collect()
+    }
+}
+
+impl Default for PciBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+static CONFIG: PortConfigSpace = PortConfigSpace::new();
+
+#[cfg(feature = "hosted")]
+lazy_static! {
+    /// في المحاكاة: آلة QEMU الافتراضية (i440FX و PIIX3)، ويضيف المزيد `hosted_config`
+    static ref CONFIG: MemoryConfigSpace = {
+        let config = MemoryConfigSpace::new();
+        config.attach(PciAddress::new(0, 0, 0), &Header { vendor_id: 0x8086, device_id: 0x1237, class: 0x06, subclass: 0x00, ..Header::default() });
+        config.attach(PciAddress::new(0, 1, 0), &Header { vendor_id: 0x8086, device_id: 0x7000, class: 0x06, subclass: 0x01, multifunction: true, ..Header::default() });
+        config.attach(PciAddress::new(0, 1, 1), &Header {
+            vendor_id: 0x8086, device_id: 0x7010, class: 0x01, subclass: 0x01, prog_if: 0x80,
+            bars: alloc::vec![(0, 0), (0, 0), (0, 0), (0, 0), (0xC041, 16)],
+            ..Header::default()
+        });
+        config.attach(PciAddress::new(0, 2, 0), &Header {
+            vendor_id: 0x1234, device_id: 0x1111, class: 0x03, subclass: 0x00,
+            bars: alloc::vec![(0xFD00_0008, 0x100_0000)],
+            ..Header::default()
+        });
+        config
+    };
+}
+
+/// فضاء الإعداد المحاكى لإضافة أجهزة أو نزعها
+#[cfg(feature = "hosted")]
+pub fn hosted_config() -> &'static MemoryConfigSpace {
+    &CONFIG
+}
+
+/// فضاء الإعداد الفعلي
+pub fn config() -> &'static dyn ConfigSpace {
+    #[cfg(not(feature = "hosted"))]
+    {
+        &CONFIG
+    }
+    
+    #[cfg(feature = "hosted")]
+    {
+        &*CONFIG
+    }
+}
+
+lazy_static! {
+    static ref BUS: Mutex<PciBus> = Mutex::new(PciBus::new());
+}
+
+/// تسجيل تعريف وربطه بما يطابقه من الأجهزة المكتشفة
+pub fn register_driver(driver: &'static PciDriver) {
+    if BUS.lock().add_driver(driver) {
+        bind_pending();
+    }
+}
+
+/// مسح النواقل، ثم `remove` لما اختفى و `probe` لما لم يرتبط بعد
+pub fn scan() -> usize {
+    let devices = PciBus::enumerate(config());
+    let count = devices.len();
+    let removed = BUS.lock().update(devices);
+    
+    for (device, driver) in removed {
+        info!("  🧩 {} نُزع ({})", device.address, driver.name);
+        (driver.remove)(&device);
+    }
+    bind_pending();
+    count
+}
+
+/// محاولة ربط الأجهزة الحرة؛ الاستدعاءات خارج القفل لأن التعريف قد يستخدم الناقل
+fn bind_pending() {
+    let candidates = BUS.lock().candidates();
+    
+    for (device, drivers) in candidates {
+        for driver in drivers {
+            match (driver.probe)(&device) {
+                Ok(()) => {
+                    info!("  🧩 {} {:04x}:{:04x} ← {}", device.address, device.vendor_id, device.device_id, driver.name);
+                    BUS.lock().bind(device.address, driver);
+                    break;
+                }
+                Err(e) => warn!("⚠️ {} رفض {}: {}", driver.name, device.address, e),
+            }
+        }
+    }
+}
+
+/// فك كل التعريفات ثم إعادة فحصها (بعد الذعر)
+pub fn reprobe() {
+    // الذعر قد يكون وقع والقفل محجوز
+    let Some(mut bus) = BUS.try_lock() else {
+        warn!("⚠️ ناقل PCI مقفل، تخطي إعادة الفحص");
+        return;
+    };
+    let bound = bus.unbind_all();
+    drop(bus);
+    
+    for (device, driver) in &bound {
+        (driver.remove)(device);
+    }
+    bind_pending();
+}
+
+/// الأجهزة المكتشفة مع اسم التعريف المرتبط
+pub fn devices() -> Vec<(PciDevice, Option<&'static str>)> {
+    BUS.lock().devices()
+}
+
+/// تمكين فك عناوين الذاكرة والمنافذ والوصول المباشر للذاكرة (DMA)
+pub fn enable_bus_master(address: PciAddress) {
+    let command = config().read(address, offset::COMMAND);
+    let enable = (COMMAND_IO_SPACE | COMMAND_MEMORY_SPACE | COMMAND_BUS_MASTER) as u32;
+    config().write(address, offset::COMMAND, command | enable);
+}
+
+/// المسح الأول وتسجيل ملفات /proc
+pub fn init() -> usize {
+    crate::fs::procfs::register("bus/pci/devices", proc_devices);
+    crate::fs::procfs::register("bus/pci/drivers", proc_drivers);
+    
+    let count = scan();
+    info!("  ✅ ناقل PCI: {} دالة", count);
+    count
+}
+
+/// /proc/bus/pci/devices
+fn proc_devices() -> String {
+    use core::fmt::Write;
+    
+    let mut table = String::from("address\tvendor\tdevice\tclass\tirq\tdriver\tname\n");
+    for (device, driver) in devices() {
+        let _ = writeln!(table, "{}\t{:04x}\t{:04x}\t{:02x}{:02x}{:02x}\t{}\t{}\t{}",
+            device.address, device.vendor_id, device.device_id, device.class, device.subclass, device.prog_if,
+            device.interrupt_line, driver.unwrap_or("-"), device.class_name());
+    }
+    table
+}
+
+/// /proc/bus/pci/drivers
+fn proc_drivers() -> String {
+    let mut text = String::new();
+    for name in BUS.lock().drivers() {
+        text.push_str(name);
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    
+    fn accept(_: &PciDevice) -> Result<(), String> {
+        Ok(())
+    }
+    fn reject(_: &PciDevice) -> Result<(), String> {
+        Err(String::from("غير مدعوم"))
+    }
+    fn ignore(_: &PciDevice) {}
+    
+    static NET: PciDriver = PciDriver { name: "test-net", ids: &[DeviceId::new(0x1af4, 0x1000)], probe: accept, remove: ignore };
+    static ETHERNET: PciDriver = PciDriver { name: "test-ethernet", ids: &[DeviceId::class(0x02, 0x00)], probe: reject, remove: ignore };
+    
+    #[test_case]
+    fn test_pci_enumeration_and_binding() {
+        // الدالة 2 في جهاز غير متعدد الدوال لا تُفحص
+        let config = MemoryConfigSpace::new();
+        config.attach(PciAddress::new(0, 0, 0), &Header { vendor_id: 0x8086, device_id: 0x29c0, class: 0x06, ..Header::default() });
+        config.attach(PciAddress::new(0, 3, 0), &Header {
+            vendor_id: 0x1af4, device_id: 0x1000, class: 0x02, interrupt_line: 11, interrupt_pin: 1,
+            bars: alloc::vec![(0xC001, 32), (0xFEBF_0000, 0x1000), (0, 0), (0, 0), (0xC, 0x4000), (0x1, 0)],
+            ..Header::default()
+        });
+        config.attach(PciAddress::new(0, 3, 2), &Header { vendor_id: 0x1af4, device_id: 0x1000, ..Header::default() });
+        config.attach(PciAddress::new(1, 0, 0), &Header { vendor_id: 0x1b36, device_id: 0x000d, class: 0x0C, subclass: 0x03, ..Header::default() });
+        
+        let devices = PciBus::enumerate(&config);
+        let addresses: Vec<String> = devices.iter().map(|device| device.address.to_string()).collect();
+        assert_eq!(addresses, ["00:00.0", "00:03.0", "01:00.0"]);
+        
+        // BAR منافذ، وذاكرة 32 بت، وذاكرة 64 بت تأخذ مسجلين
+        let nic = devices[1].clone();
+        assert_eq!(nic.bars[0], Some(Bar::Io { port: 0xC000, size: 32 }));
+        assert_eq!(nic.bars[1], Some(Bar::Memory { address: 0xFEBF_0000, size: 0x1000, prefetchable: false }));
+        assert_eq!(nic.bars[4], Some(Bar::Memory { address: 0x1_0000_0000, size: 0x4000, prefetchable: true }));
+        assert_eq!(nic.bars[5], None);
+        assert_eq!((nic.io_bar(), nic.memory_bar()), (Some(0xC000), Some((0xFEBF_0000, 0x1000))));
+        assert_eq!((nic.interrupt_line, nic.class_name()), (11, "Ethernet controller"));
+        
+        // المرشحون بترتيب التسجيل، والجهاز المرتبط يبقى مع تعريفه بعد إعادة المسح
+        let mut bus = PciBus::new();
+        assert!(bus.update(devices.clone()).is_empty());
+        assert!(bus.add_driver(&ETHERNET) && bus.add_driver(&NET));
+        assert!(!bus.add_driver(&NET));
+        let candidates = bus.candidates();
+        assert_eq!(candidates.len(), 1);
+        let names: Vec<&str> = candidates[0].1.iter().map(|driver| driver.name).collect();
+        assert_eq!((candidates[0].0.address, names), (nic.address, alloc::vec!["test-ethernet", "test-net"]));
+        
+        bus.bind(nic.address, &NET);
+        assert!(bus.candidates().is_empty());
+        assert!(bus.update(devices.clone()).is_empty());
+        assert_eq!(bus.devices()[1].1, Some("test-net"));
+        let removed = bus.update(alloc::vec![devices[0].clone()]);
+        assert_eq!(removed.iter().map(|(device, driver)| (device.address, driver.name)).collect::<Vec<_>>(), [(nic.address, "test-net")]);
+    }
+}
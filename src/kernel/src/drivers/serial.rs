@@ -0,0 +1,107 @@
+//! 📟 تعريف المنفذ التسلسلي COM1
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use core::fmt;
+use lazy_static::lazy_static;
+use spin::Mutex;
+
+#[cfg(not(feature = "hosted"))]
+use uart_16550::SerialPort;
+
+/// عنوان المنفذ COM1
+#[cfg(not(feature = "hosted"))]
+const COM1: u16 = 0x3F8;
+
+#[cfg(not(feature = "hosted"))]
+lazy_static! {
+    pub static ref SERIAL1: Mutex<SerialPort> = Mutex::new(unsafe { SerialPort::new(COM1) });
+}
+
+// في المحاكاة: المخرجات إلى stderr والمدخلات من طابور يُغذى بـ `inject_input`
+#[cfg(feature = "hosted")]
+lazy_static! {
+    static ref HOSTED_INPUT: Mutex<alloc::collections::VecDeque<u8>> =
+        Mutex::new(alloc::collections::VecDeque::new());
+}
+
+/// تهيئة المنفذ التسلسلي
+pub fn init() {
+    #[cfg(not(feature = "hosted"))]
+    SERIAL1.lock().init();
+}
+
+/// كتابة نص منسق إلى المنفذ التسلسلي
+pub fn write_fmt(args: fmt::Arguments) {
+    #[cfg(not(feature = "hosted"))]
+    {
+        use core::fmt::Write;
+        x86_64::instructions::interrupts::without_interrupts(|| {
+            let _ = SERIAL1.lock().write_fmt(args);
+        });
+    }
+    
+    #[cfg(feature = "hosted")]
+    {
+        use std::io::Write;
+        let _ = std::io::stderr().write_fmt(args);
+    }
+}
+
+/// الكتابة إن كان المنفذ متاحاً؛ false إن كان محتجزاً (للمسجل داخل المقاطعات والذعر)
+pub fn try_write_fmt(args: fmt::Arguments) -> bool {
+    #[cfg(not(feature = "hosted"))]
+    {
+        use core::fmt::Write;
+        x86_64::instructions::interrupts::without_interrupts(|| match SERIAL1.try_lock() {
+            Some(mut serial) => serial.write_fmt(args).is_ok(),
+            None => false,
+        })
+    }
+    
+    #[cfg(feature = "hosted")]
+    {
+        write_fmt(args);
+        true
+    }
+}
+
+/// الكتابة من معالج الذعر: يُكسر القفل إن كان محتجزاً لأن صاحبه لن يعود
+pub fn emergency_write_fmt(args: fmt::Arguments) {
+    #[cfg(not(feature = "hosted"))]
+    {
+        use core::fmt::Write;
+        if SERIAL1.is_locked() {
+            unsafe { SERIAL1.force_unlock() };
+        }
+        let _ = SERIAL1.lock().write_fmt(args);
+    }
+    
+    #[cfg(feature = "hosted")]
+    write_fmt(args);
+}
+
+/// قراءة بايت إن وُجد دون انتظار
+pub fn try_read_byte() -> Option<u8> {
+    #[cfg(not(feature = "hosted"))]
+    {
+        use x86_64::instructions::port::Port;
+        
+        // مسجل حالة الخط: البت 0 يعني وجود بيانات
+        let mut line_status: Port<u8> = Port::new(COM1 + 5);
+        if unsafe { line_status.read() } & 1 == 0 {
+            return None;
+        }
+        Some(SERIAL1.lock().receive())
+    }
+    
+    #[cfg(feature = "hosted")]
+    {
+        HOSTED_INPUT.lock().pop_front()
+    }
+}
+
+/// حقن مدخلات في المنفذ المحاكى
+#[cfg(feature = "hosted")]
+pub fn inject_input(bytes: &[u8]) {
+    HOSTED_INPUT.lock().extend(bytes.iter().copied());
+}
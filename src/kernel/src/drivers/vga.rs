@@ -0,0 +1,284 @@
+//! 🖥️ تعريف شاشة VGA النصية (80×25)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use core::fmt;
+use lazy_static::lazy_static;
+use spin::Mutex;
+
+pub const BUFFER_HEIGHT: usize = 25;
+pub const BUFFER_WIDTH: usize = 80;
+
+lazy_static! {
+    pub static ref WRITER: Mutex<Writer> = Mutex::new(Writer::new());
+}
+
+/// ألوان VGA القياسية
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum Color {
+    #[default]
+    Black = 0,
+    Blue = 1,
+    Green = 2,
+    Cyan = 3,
+    Red = 4,
+    Magenta = 5,
+    Brown = 6,
+    LightGray = 7,
+    DarkGray = 8,
+    LightBlue = 9,
+    LightGreen = 10,
+    LightCyan = 11,
+    LightRed = 12,
+    LightMagenta = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+impl Color {
+    /// تقريب لون RGB إلى أقرب لون من لوحة VGA
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Color {
+        const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+            (Color::Black, (0, 0, 0)),
+            (Color::Blue, (0, 0, 170)),
+            (Color::Green, (0, 170, 0)),
+            (Color::Cyan, (0, 170, 170)),
+            (Color::Red, (170, 0, 0)),
+            (Color::Magenta, (170, 0, 170)),
+            (Color::Brown, (170, 85, 0)),
+            (Color::LightGray, (170, 170, 170)),
+            (Color::DarkGray, (85, 85, 85)),
+            (Color::LightBlue, (85, 85, 255)),
+            (Color::LightGreen, (85, 255, 85)),
+            (Color::LightCyan, (85, 255, 255)),
+            (Color::LightRed, (255, 85, 85)),
+            (Color::LightMagenta, (255, 85, 255)),
+            (Color::Yellow, (255, 255, 85)),
+            (Color::White, (255, 255, 255)),
+        ];
+        
+        let distance = |(pr, pg, pb): (u8, u8, u8)| {
+            let dr = pr as i32 - r as i32;
+            let dg = pg as i32 - g as i32;
+            let db = pb as i32 - b as i32;
+            dr * dr + dg * dg + db * db
+        };
+        
+        PALETTE.iter()
+            .min_by_key(|(_, rgb)| distance(*rgb))
+            .map(|(color, _)| *color)
+            .unwrap_or(Color::Black)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct ColorCode(u8);
+
+impl ColorCode {
+    const fn new(foreground: Color, background: Color) -> ColorCode {
+        ColorCode((background as u8) << 4 | (foreground as u8))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+struct ScreenChar {
+    ascii_character: u8,
+    color_code: ColorCode,
+}
+
+#[repr(transparent)]
+pub struct Buffer {
+    chars: [[ScreenChar; BUFFER_WIDTH]; BUFFER_HEIGHT],
+}
+
+/// ذاكرة الشاشة: العنوان 0xb8000 على العتاد، ومخزن عادي في المحاكاة
+#[cfg(not(feature = "hosted"))]
+fn screen_buffer() -> &'static mut Buffer {
+    unsafe { &mut *(0xb8000 as *mut Buffer) }
+}
+
+#[cfg(feature = "hosted")]
+static mut HOSTED_BUFFER: Buffer = Buffer {
+    chars: [[ScreenChar { ascii_character: b' ', color_code: ColorCode(0x07) }; BUFFER_WIDTH]; BUFFER_HEIGHT],
+};
+
+#[cfg(feature = "hosted")]
+fn screen_buffer() -> &'static mut Buffer {
+    unsafe { &mut *core::ptr::addr_of_mut!(HOSTED_BUFFER) }
+}
+
+pub struct Writer {
+    column_position: usize,
+    row_position: usize,
+    foreground: Color,
+    background: Color,
+    buffer: &'static mut Buffer,
+}
+
+impl Writer {
+    fn new() -> Self {
+        Self {
+            column_position: 0,
+            row_position: BUFFER_HEIGHT - 1,
+            foreground: Color::LightGray,
+            background: Color::Black,
+            buffer: screen_buffer(),
+        }
+    }
+    
+    fn color_code(&self) -> ColorCode {
+        ColorCode::new(self.foreground, self.background)
+    }
+    
+    pub fn set_foreground_color(&mut self, color: Color) {
+        self.foreground = color;
+    }
+    
+    pub fn set_background_color(&mut self, color: Color) {
+        self.background = color;
+    }
+    
+    pub fn set_color(&mut self, foreground: Color, background: Color) {
+        self.foreground = foreground;
+        self.background = background;
+    }
+    
+    /// لونا الكتابة الحاليان (المقدمة، الخلفية)
+    pub fn colors(&self) -> (Color, Color) {
+        (self.foreground, self.background)
+    }
+    
+    pub fn clear_screen(&mut self) {
+        for row in 0..BUFFER_HEIGHT {
+            self.clear_row(row);
+        }
+        self.column_position = 0;
+        self.row_position = BUFFER_HEIGHT - 1;
+    }
+    
+    /// كتابة نص في موضع محدد دون تحريك المؤشر
+    pub fn print_at(&mut self, text: &str, row: usize, col: usize) {
+        if row >= BUFFER_HEIGHT {
+            return;
+        }
+        
+        let color_code = self.color_code();
+        for (i, c) in text.chars().enumerate() {
+            let x = col + i;
+            if x >= BUFFER_WIDTH {
+                break;
+            }
+            self.write_cell(row, x, ScreenChar {
+                ascii_character: to_code_page_437(c),
+                color_code,
+            });
+        }
+    }
+    
+    /// كتابة نص في منتصف السطر
+    pub fn print_centered(&mut self, text: &str, row: usize) {
+        let width = text.chars().count().min(BUFFER_WIDTH);
+        self.print_at(text, row, (BUFFER_WIDTH - width) / 2);
+    }
+    
+    pub fn write_byte(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.new_line(),
+            byte => {
+                if self.column_position >= BUFFER_WIDTH {
+                    self.new_line();
+                }
+                
+                let color_code = self.color_code();
+                self.write_cell(self.row_position, self.column_position, ScreenChar {
+                    ascii_character: byte,
+                    color_code,
+                });
+                self.column_position += 1;
+            }
+        }
+    }
+    
+    fn write_cell(&mut self, row: usize, col: usize, character: ScreenChar) {
+        unsafe {
+            core::ptr::write_volatile(&mut self.buffer.chars[row][col], character);
+        }
+    }
+    
+    fn read_cell(&self, row: usize, col: usize) -> ScreenChar {
+        unsafe { core::ptr::read_volatile(&self.buffer.chars[row][col]) }
+    }
+    
+    fn new_line(&mut self) {
+        for row in 1..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                let character = self.read_cell(row, col);
+                self.write_cell(row - 1, col, character);
+            }
+        }
+        self.clear_row(BUFFER_HEIGHT - 1);
+        self.column_position = 0;
+    }
+    
+    fn clear_row(&mut self, row: usize) {
+        let blank = ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code(),
+        };
+        for col in 0..BUFFER_WIDTH {
+            self.write_cell(row, col, blank);
+        }
+    }
+    
+    /// بايت ألوان الخلية (الخلفية في النصف الأعلى)
+    pub fn cell_attribute(&self, row: usize, col: usize) -> u8 {
+        self.read_cell(row, col).color_code.0
+    }
+    
+    /// تغيير ألوان الخلية دون حرفها
+    pub fn set_cell_attribute(&mut self, row: usize, col: usize, attribute: u8) {
+        let character = self.read_cell(row, col);
+        self.write_cell(row, col, ScreenChar { color_code: ColorCode(attribute), ..character });
+    }
+    
+    /// قراءة سطر من الشاشة كنص (للاختبارات وأدوات التشخيص)
+    pub fn row_text(&self, row: usize) -> alloc::string::String {
+        (0..BUFFER_WIDTH)
+            .map(|col| self.read_cell(row, col).ascii_character as char)
+            .collect()
+    }
+}
+
+impl fmt::Write for Writer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.write_byte(if c == '\n' { b'\n' } else { to_code_page_437(c) });
+        }
+        Ok(())
+    }
+}
+
+/// تحويل الحرف إلى صفحة الرموز 437 التي تعرضها شاشة VGA النصية
+fn to_code_page_437(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '═' => 0xCD,
+        '║' => 0xBA,
+        '╔' => 0xC9,
+        '╗' => 0xBB,
+        '╚' => 0xC8,
+        '╝' => 0xBC,
+        '─' => 0xC4,
+        '│' => 0xB3,
+        '┌' => 0xDA,
+        '┐' => 0xBF,
+        '└' => 0xC0,
+        '┘' => 0xD9,
+        '█' => 0xDB,
+        '░' => 0xB0,
+        _ => 0xFE,
+    }
+}
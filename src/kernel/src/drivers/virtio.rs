@@ -0,0 +1,442 @@
+//! 🌀 أساسيات virtio المشتركة (الواجهة القديمة على PCI)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! أجهزة virtio القديمة (1AF4:1000-103F) مسجلاتها في BAR0 (منافذ إدخال
+//! وإخراج)، وكل طابور (virtqueue) في ذاكرة DMA متجاورة بمحاذاة صفحة:
+//!
+//! ```text
+//! [واصفات 16×N][الطابور المتاح avail]··محاذاة 4096··[الطابور المستعمل used]
+//! ```
+//!
+//! التعريف يضع سلاسل واصفات في المتاح ويُشعر الجهاز، والجهاز يعيد رؤوسها في
+//! المستعمل مع عدد البايتات التي كتبها. يستخدمها virtio-blk و virtio-net.
+
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{fence, Ordering};
+
+use crate::memory::{self, DmaRegion, FRAME_SIZE};
+
+/// المسجلات المشتركة (إزاحات من BAR0)
+pub mod register {
+    pub const DEVICE_FEATURES: u16 = 0x00;
+    pub const GUEST_FEATURES: u16 = 0x04;
+    pub const QUEUE_PFN: u16 = 0x08;
+    pub const QUEUE_SIZE: u16 = 0x0C;
+    pub const QUEUE_SELECT: u16 = 0x0E;
+    pub const QUEUE_NOTIFY: u16 = 0x10;
+    pub const DEVICE_STATUS: u16 = 0x12;
+    pub const ISR: u16 = 0x13;
+    /// بداية إعدادات الجهاز الخاصة (بلا MSI-X)
+    pub const CONFIG: u16 = 0x14;
+}
+
+/// بتات حالة الجهاز
+pub const STATUS_ACKNOWLEDGE: u8 = 1;
+pub const STATUS_DRIVER: u8 = 2;
+pub const STATUS_DRIVER_OK: u8 = 4;
+pub const STATUS_FAILED: u8 = 128;
+
+/// بتات الواصف
+pub const DESC_NEXT: u16 = 1;
+pub const DESC_WRITE: u16 = 2;
+
+/// الوصول إلى مسجلات الجهاز
+pub trait Transport: Send + Sync {
+    fn read8(&self, offset: u16) -> u8;
+    fn read16(&self, offset: u16) -> u16;
+    fn read32(&self, offset: u16) -> u32;
+    fn write8(&self, offset: u16, value: u8);
+    fn write16(&self, offset: u16, value: u16);
+    fn write32(&self, offset: u16, value: u32);
+}
+
+/// مسجلات على منافذ الإدخال والإخراج
+#[cfg(not(feature = "hosted"))]
+pub struct PortTransport {
+    base: u16,
+}
+
+#[cfg(not(feature = "hosted"))]
+impl PortTransport {
+    pub const fn new(base: u16) -> Self {
+        Self { base }
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+impl Transport for PortTransport {
+    fn read8(&self, offset: u16) -> u8 {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).read() }
+    }
+    
+    fn read16(&self, offset: u16) -> u16 {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).read() }
+    }
+    
+    fn read32(&self, offset: u16) -> u32 {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).read() }
+    }
+    
+    fn write8(&self, offset: u16, value: u8) {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).write(value) }
+    }
+    
+    fn write16(&self, offset: u16, value: u16) {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).write(value) }
+    }
+    
+    fn write32(&self, offset: u16, value: u32) {
+        unsafe { x86_64::instructions::port::Port::new(self.base + offset).write(value) }
+    }
+}
+
+/// مسجلات الجهاز الذي يعلن BAR منافذه عن `base`
+#[cfg(not(feature = "hosted"))]
+pub fn transport(base: u16) -> Option<Arc<dyn Transport>> {
+    Some(Arc::new(PortTransport::new(base)))
+}
+
+/// الجهاز المحاكى المسجل على المنفذ `base`
+#[cfg(feature = "hosted")]
+pub fn transport(base: u16) -> Option<Arc<dyn Transport>> {
+    simulated::DEVICES.lock().get(&base).cloned()
+}
+
+/// إعادة ضبط الجهاز والاتفاق على الميزات؛ يعيد ما قبله الجهاز من `wanted`
+pub fn negotiate(transport: &dyn Transport, wanted: u32) -> u32 {
+    transport.write8(register::DEVICE_STATUS, 0);
+    transport.write8(register::DEVICE_STATUS, STATUS_ACKNOWLEDGE);
+    transport.write8(register::DEVICE_STATUS, STATUS_ACKNOWLEDGE | STATUS_DRIVER);
+    
+    let features = transport.read32(register::DEVICE_FEATURES) & wanted;
+    transport.write32(register::GUEST_FEATURES, features);
+    features
+}
+
+/// تشغيل الجهاز بعد إعداد طوابيره
+pub fn driver_ok(transport: &dyn Transport) {
+    transport.write8(register::DEVICE_STATUS, STATUS_ACKNOWLEDGE | STATUS_DRIVER | STATUS_DRIVER_OK);
+}
+
+/// إعادة ضبط الجهاز: يترك طوابيره ويتوقف حتى يُهيأ من جديد
+pub fn reset(transport: &dyn Transport) {
+    transport.write8(register::DEVICE_STATUS, 0);
+}
+
+/// إعلام الجهاز بفشل التهيئة
+pub fn fail(transport: &dyn Transport) {
+    transport.write8(register::DEVICE_STATUS, STATUS_FAILED);
+}
+
+/// مواضع أجزاء الطابور داخل ذاكرته
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueLayout {
+    pub size: u16,
+    pub avail: usize,
+    pub used: usize,
+    pub total: usize,
+}
+
+impl QueueLayout {
+    pub fn new(size: u16) -> Self {
+        let page = FRAME_SIZE as usize;
+        let n = size as usize;
+        let avail = 16 * n;
+        let used = (avail + 6 + 2 * n).div_ceil(page) * page;
+        Self { size, avail, used, total: used + (6 + 8 * n).div_ceil(page) * page }
+    }
+}
+
+fn write<T>(region: &DmaRegion, offset: usize, value: T) {
+    unsafe { write_volatile(region.virt.add(offset) as *mut T, value) }
+}
+
+fn read<T>(region: &DmaRegion, offset: usize) -> T {
+    unsafe { read_volatile(region.virt.add(offset) as *const T) }
+}
+
+/// طابور من جهة التعريف
+pub struct Virtqueue {
+    index: u16,
+    layout: QueueLayout,
+    ring: DmaRegion,
+    /// الواصفات الحرة
+    free: Vec<u16>,
+    /// طول السلسلة بحسب رأسها (لإعادة واصفاتها)
+    chains: Vec<u16>,
+    /// فهرس الطابور المتاح التالي
+    next_avail: u16,
+    /// آخر `used.idx` رأيناه
+    last_used: u16,
+}
+
+impl Virtqueue {
+    /// إعداد الطابور `index` بالحجم الذي يعلنه الجهاز
+    pub fn new(transport: &dyn Transport, index: u16) -> Result<Self, &'static str> {
+        transport.write16(register::QUEUE_SELECT, index);
+        let size = transport.read16(register::QUEUE_SIZE);
+        if size == 0 || transport.read32(register::QUEUE_PFN) != 0 {
+            return Err("الطابور غير متاح");
+        }
+        
+        let layout = QueueLayout::new(size);
+        let ring = memory::alloc_dma(layout.total).ok_or("لا ذاكرة DMA")?;
+        transport.write32(register::QUEUE_PFN, (ring.phys / FRAME_SIZE) as u32);
+        Ok(Self {
+            index,
+            layout,
+            ring,
+            free: (0..size).rev().collect(),
+            chains: vec![0; size as usize],
+            next_avail: 0,
+            last_used: 0,
+        })
+    }
+    
+    pub fn size(&self) -> u16 {
+        self.layout.size
+    }
+    
+    /// عدد الواصفات الحرة
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+    
+    /// إضافة سلسلة من (العنوان الفيزيائي، الطول، هل يكتبها الجهاز)؛ يعيد رأسها
+    /// أو `None` إن لم تكفِ الواصفات الحرة
+    pub fn add(&mut self, buffers: &[(u64, u32, bool)]) -> Option<u16> {
+        if buffers.is_empty() || buffers.len() > self.free.len() {
+            return None;
+        }
+        
+        let indices: Vec<u16> = (0..buffers.len()).filter_map(|_| self.free.pop()).collect();
+        for (position, &(address, len, writable)) in buffers.iter().enumerate() {
+            let next = indices.get(position + 1).copied();
+            let flags = if writable { DESC_WRITE } else { 0 } | if next.is_some() { DESC_NEXT } else { 0 };
+            let at = indices[position] as usize * 16;
+            write(&self.ring, at, address);
+            write(&self.ring, at + 8, len);
+            write(&self.ring, at + 12, flags);
+            write(&self.ring, at + 14, next.unwrap_or(0));
+        }
+        
+        let head = indices[0];
+        self.chains[head as usize] = buffers.len() as u16;
+        let slot = self.layout.avail + 4 + 2 * (self.next_avail % self.layout.size) as usize;
+        write(&self.ring, slot, head);
+        self.next_avail = self.next_avail.wrapping_add(1);
+        // الواصفات قبل الفهرس
+        fence(Ordering::SeqCst);
+        write(&self.ring, self.layout.avail + 2, self.next_avail);
+        fence(Ordering::SeqCst);
+        Some(head)
+    }
+    
+    /// إشعار الجهاز بوجود سلاسل جديدة
+    pub fn notify(&self, transport: &dyn Transport) {
+        transport.write16(register::QUEUE_NOTIFY, self.index);
+    }
+    
+    /// سلسلة أعادها الجهاز: (رأسها، البايتات التي كتبها)؛ واصفاتها تعود حرة
+    pub fn pop_used(&mut self) -> Option<(u16, u32)> {
+        fence(Ordering::SeqCst);
+        if read::<u16>(&self.ring, self.layout.used + 2) == self.last_used {
+            return None;
+        }
+        
+        let slot = self.layout.used + 4 + 8 * (self.last_used % self.layout.size) as usize;
+        let (head, len) = (read::<u32>(&self.ring, slot) as u16, read::<u32>(&self.ring, slot + 4));
+        self.last_used = self.last_used.wrapping_add(1);
+        
+        let mut index = head;
+        for _ in 0..self.chains[head as usize] {
+            self.free.push(index);
+            index = read::<u16>(&self.ring, index as usize * 16 + 14);
+        }
+        Some((head, len))
+    }
+}
+
+/// جهة الجهاز لأجهزة virtio المحاكاة؛ عناوين DMA تُترجم بـ `memory::dma_pointer`
+#[cfg(feature = "hosted")]
+pub mod simulated {
+    use alloc::collections::BTreeMap;
+    use alloc::vec::Vec;
+    use core::ptr::{read_volatile, write_volatile};
+    use spin::Mutex;
+    
+    use super::*;
+    
+    /// الأجهزة المحاكاة بحسب منفذ BAR0
+    pub(super) static DEVICES: Mutex<BTreeMap<u16, Arc<dyn Transport>>> = Mutex::new(BTreeMap::new());
+    
+    /// إعلان جهاز على المنفذ `port` (يُضاف بعدها إلى فضاء إعداد PCI المحاكى)
+    pub fn attach(port: u16, device: Arc<dyn Transport>) {
+        DEVICES.lock().insert(port, device);
+    }
+    
+    fn at<T>(address: u64) -> *mut T {
+        memory::dma_pointer(address).expect("عنوان DMA صالح") as *mut T
+    }
+    
+    /// ذاكرة DMA كشريحة
+    fn slice(address: u64, len: usize) -> &'static mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(at::<u8>(address), len) }
+    }
+    
+    /// سلسلة أخذها الجهاز من الطابور المتاح
+    pub struct Chain {
+        pub head: u16,
+        /// (العنوان، الطول، هل يكتبها الجهاز)
+        pub buffers: Vec<(u64, usize, bool)>,
+    }
+    
+    impl Chain {
+        /// المخازن التي يقرؤها الجهاز متتالية
+        pub fn readable(&self) -> Vec<u8> {
+            self.buffers.iter()
+                .filter(|&&(_, _, writable)| !writable)
+                .flat_map(|&(address, len, _)| slice(address, len).iter().copied())
+                .collect()
+        }
+        
+        /// كتابة `data` في مخازن الكتابة بالترتيب؛ يعيد عدد ما كُتب
+        pub fn write(&self, data: &[u8]) -> usize {
+            let mut done = 0;
+            for &(address, len, _) in self.buffers.iter().filter(|&&(_, _, writable)| writable) {
+                let count = len.min(data.len() - done);
+                slice(address, count).copy_from_slice(&data[done..done + count]);
+                done += count;
+            }
+            done
+        }
+        
+        /// مخزن بعينه من السلسلة
+        pub fn buffer(&self, index: usize) -> &'static mut [u8] {
+            let (address, len, _) = self.buffers[index];
+            slice(address, len)
+        }
+    }
+    
+    /// طابور من جهة الجهاز
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct DeviceQueue {
+        pub pfn: u32,
+        size: u16,
+        last_avail: u16,
+    }
+    
+    impl DeviceQueue {
+        fn layout(&self) -> (u64, QueueLayout) {
+            (self.pfn as u64 * FRAME_SIZE, QueueLayout::new(self.size))
+        }
+        
+        /// السلسلة التالية في الطابور المتاح
+        pub fn pop(&mut self) -> Option<Chain> {
+            if self.pfn == 0 {
+                return None;
+            }
+            let (base, layout) = self.layout();
+            let n = self.size as u64;
+            
+            unsafe {
+                if read_volatile(at::<u16>(base + layout.avail as u64 + 2)) == self.last_avail {
+                    return None;
+                }
+                let head = read_volatile(at::<u16>(base + layout.avail as u64 + 4 + 2 * (self.last_avail as u64 % n)));
+                self.last_avail = self.last_avail.wrapping_add(1);
+                
+                let mut buffers = Vec::new();
+                let mut index = head as u64;
+                loop {
+                    let desc = base + 16 * index;
+                    let flags = read_volatile(at::<u16>(desc + 12));
+                    buffers.push((read_volatile(at::<u64>(desc)), read_volatile(at::<u32>(desc + 8)) as usize, flags & DESC_WRITE != 0));
+                    if flags & DESC_NEXT == 0 || buffers.len() >= self.size as usize {
+                        break;
+                    }
+                    index = read_volatile(at::<u16>(desc + 14)) as u64;
+                }
+                Some(Chain { head, buffers })
+            }
+        }
+        
+        /// إعادة السلسلة `head` في الطابور المستعمل
+        pub fn push_used(&mut self, head: u16, written: usize) {
+            let (base, layout) = self.layout();
+            let used = base + layout.used as u64;
+            unsafe {
+                let index = read_volatile(at::<u16>(used + 2));
+                let slot = used + 4 + 8 * (index as u64 % self.size as u64);
+                write_volatile(at::<u32>(slot), head as u32);
+                write_volatile(at::<u32>(slot + 4), written as u32);
+                write_volatile(at::<u16>(used + 2), index.wrapping_add(1));
+            }
+        }
+    }
+    
+    /// المسجلات المشتركة لجهاز محاكى
+    #[derive(Debug, Default)]
+    pub struct Common {
+        pub features: u32,
+        pub guest_features: u32,
+        pub status: u8,
+        pub isr: u8,
+        select: usize,
+        pub queues: Vec<DeviceQueue>,
+    }
+    
+    impl Common {
+        pub fn new(features: u32, queues: usize, size: u16) -> Self {
+            Self { features, queues: alloc::vec![DeviceQueue { size, ..DeviceQueue::default() }; queues], ..Self::default() }
+        }
+        
+        /// هل شغّله التعريف؟
+        pub fn ready(&self) -> bool {
+            self.status & STATUS_DRIVER_OK != 0
+        }
+        
+        /// قراءة مسجل مشترك؛ `None` لإعدادات الجهاز الخاصة
+        pub fn read(&mut self, offset: u16) -> Option<u32> {
+            let queue = self.queues.get(self.select);
+            Some(match offset {
+                register::DEVICE_FEATURES => self.features,
+                register::GUEST_FEATURES => self.guest_features,
+                register::QUEUE_PFN => queue.map_or(0, |queue| queue.pfn),
+                register::QUEUE_SIZE => queue.map_or(0, |queue| queue.size as u32),
+                register::QUEUE_SELECT => self.select as u32,
+                register::DEVICE_STATUS => self.status as u32,
+                register::ISR => core::mem::take(&mut self.isr) as u32,
+                offset if offset < register::CONFIG => 0,
+                _ => return None,
+            })
+        }
+        
+        /// كتابة مسجل مشترك؛ يعيد رقم الطابور عند الإشعار
+        pub fn write(&mut self, offset: u16, value: u32) -> Option<u16> {
+            match offset {
+                register::GUEST_FEATURES => self.guest_features = value,
+                register::QUEUE_PFN => {
+                    if let Some(queue) = self.queues.get_mut(self.select) {
+                        queue.pfn = value;
+                        queue.last_avail = 0;
+                    }
+                }
+                register::QUEUE_SELECT => self.select = value as usize,
+                register::QUEUE_NOTIFY => return Some(value as u16),
+                register::DEVICE_STATUS => {
+                    // الصفر إعادة ضبط: تُنسى الطوابير
+                    if value == 0 {
+                        self.queues.iter_mut().for_each(|queue| *queue = DeviceQueue { size: queue.size, ..DeviceQueue::default() });
+                    }
+                    self.status = value as u8;
+                }
+                _ => {}
+            }
+            None
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! 📦 محلل أرشيف cpio بصيغة newc
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الصيغة التي ينتجها `cpio -o -H newc`: ترويسة نصية من 110 بايت
+//! (ستة أحرف توقيع و13 حقلاً سداسياً عشرياً)، ثم الاسم، ثم البيانات،
+//! وكل منها مبطن إلى حد 4 بايت. ينتهي الأرشيف بالاسم `TRAILER!!!`.
+
+use super::FsError;
+
+const HEADER_SIZE: usize = 110;
+const MAGIC_NEWC: &[u8] = b"070701";
+const MAGIC_NEWC_CRC: &[u8] = b"070702";
+const TRAILER: &str = "TRAILER!!!";
+
+/// بتات نوع الملف في حقل mode
+pub const S_IFMT: u32 = 0o170000;
+pub const S_IFDIR: u32 = 0o040000;
+pub const S_IFREG: u32 = 0o100000;
+pub const S_IFLNK: u32 = 0o120000;
+
+/// مدخل واحد في الأرشيف
+#[derive(Debug, Clone, Copy)]
+pub struct CpioEntry<'a> {
+    pub name: &'a str,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub mtime: u32,
+    pub data: &'a [u8],
+}
+
+impl<'a> CpioEntry<'a> {
+    pub fn file_type(&self) -> u32 {
+        self.mode & S_IFMT
+    }
+}
+
+fn align4(value: usize) -> usize {
+    (value + 3) & !3
+}
+
+fn parse_hex(field: &[u8]) -> Result<u32, FsError> {
+    let text = core::str::from_utf8(field).map_err(|_| FsError::Corrupted)?;
+    u32::from_str_radix(text, 16).map_err(|_| FsError::Corrupted)
+}
+
+/// مكرر على مدخلات الأرشيف
+pub struct CpioReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    finished: bool,
+}
+
+impl<'a> CpioReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0, finished: false }
+    }
+    
+    fn next_entry(&mut self) -> Result<Option<CpioEntry<'a>>, FsError> {
+        let header = self.data.get(self.offset..self.offset + HEADER_SIZE).ok_or(FsError::Corrupted)?;
+        if &header[..6] != MAGIC_NEWC && &header[..6] != MAGIC_NEWC_CRC {
+            return Err(FsError::Corrupted);
+        }
+        
+        let field = |index: usize| parse_hex(&header[6 + index * 8..14 + index * 8]);
+        let mode = field(1)?;
+        let uid = field(2)?;
+        let gid = field(3)?;
+        let mtime = field(5)?;
+        let file_size = field(6)? as usize;
+        let name_size = field(11)? as usize;
+        
+        let name_start = self.offset + HEADER_SIZE;
+        let name_bytes = self.data.get(name_start..name_start + name_size).ok_or(FsError::Corrupted)?;
+        // الاسم يشمل الصفر الختامي
+        let name = core::str::from_utf8(&name_bytes[..name_size.saturating_sub(1)])
+            .map_err(|_| FsError::Corrupted)?;
+        
+        let data_start = align4(name_start + name_size);
+        let data = self.data.get(data_start..data_start + file_size).ok_or(FsError::Corrupted)?;
+        self.offset = align4(data_start + file_size);
+        
+        if name == TRAILER {
+            return Ok(None);
+        }
+        
+        Ok(Some(CpioEntry { name, mode, uid, gid, mtime, data }))
+    }
+}
+
+impl<'a> Iterator for CpioReader<'a> {
+    type Item = Result<CpioEntry<'a>, FsError>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        
+        match self.next_entry() {
+            Ok(Some(entry)) => Some(Ok(entry)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
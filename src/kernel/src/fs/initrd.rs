@@ -0,0 +1,224 @@
+//! 💿 قرص الإقلاع الأولي (initrd)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يفك ضغط `initrd.img` الذي يحمّله GRUB كوحدة Multiboot2 (cpio newc مضغوط
+//! بـ gzip من `scripts/build.sh`) ويقدمه كنظام ملفات جذر للقراءة فقط.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::collections::BTreeMap;
+
+use super::cpio::{self, CpioReader};
+use super::vfs::{DirEntry, FileKind, FileSystem, InodeId, Metadata};
+use super::FsError;
+use crate::utils::inflate;
+
+/// اسم الوحدة التي يمررها GRUB (`module2 /boot/initrd.img initrd.img`)
+pub const MODULE_NAME: &str = "initrd.img";
+
+/// عقدة في الشجرة المفكوكة
+struct Node {
+    kind: FileKind,
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    mtime: u64,
+    /// مدى البيانات داخل الأرشيف المفكوك
+    data: (usize, usize),
+    children: BTreeMap<String, InodeId>,
+}
+
+impl Node {
+    fn directory() -> Self {
+        Self {
+            kind: FileKind::Directory,
+            mode: 0o755,
+            uid: 0,
+            gid: 0,
+            mtime: 0,
+            data: (0, 0),
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+/// نظام ملفات initrd؛ يملك الأرشيف المفكوك ويشير إلى بياناته مباشرة
+pub struct InitrdFs {
+    archive: Vec<u8>,
+    nodes: Vec<Node>,
+}
+
+impl InitrdFs {
+    const ROOT: InodeId = 0;
+    
+    /// بناء نظام الملفات من صورة cpio، مضغوطة بـ gzip أو خام
+    pub fn from_image(image: &[u8]) -> Result<Self, FsError> {
+        let archive = if inflate::is_gzip(image) {
+            inflate::gunzip(image).map_err(|e| {
+                log::error!("❌ فشل فك ضغط initrd: {:?}", e);
+                FsError::Corrupted
+            })?
+        } else {
+            image.to_vec()
+        };
+        
+        Self::from_archive(archive)
+    }
+    
+    /// بناء نظام الملفات من أرشيف cpio غير مضغوط
+    pub fn from_archive(archive: Vec<u8>) -> Result<Self, FsError> {
+        let mut fs = Self { archive: Vec::new(), nodes: alloc::vec![Node::directory()] };
+        let base = archive.as_ptr() as usize;
+        
+        for entry in CpioReader::new(&archive) {
+            let entry = entry?;
+            let components: Vec<&str> = entry.name
+                .split('/')
+                .filter(|part| !part.is_empty() && *part != ".")
+                .collect();
+            
+            let kind = match entry.file_type() {
+                cpio::S_IFDIR => FileKind::Directory,
+                cpio::S_IFREG => FileKind::File,
+                cpio::S_IFLNK => FileKind::Symlink,
+                _ => FileKind::Other,
+            };
+            
+            let inode = match components.split_last() {
+                Some((name, parents)) => {
+                    let parent = fs.ensure_directories(parents)?;
+                    fs.ensure_child(parent, name, kind)?
+                }
+                // المدخل "." هو الجذر نفسه
+                None => Self::ROOT,
+            };
+            
+            let start = entry.data.as_ptr() as usize - base;
+            let node = &mut fs.nodes[inode as usize];
+            node.mode = entry.mode & !cpio::S_IFMT;
+            node.uid = entry.uid;
+            node.gid = entry.gid;
+            node.mtime = entry.mtime as u64;
+            node.data = (start, start + entry.data.len());
+        }
+        
+        fs.archive = archive;
+        Ok(fs)
+    }
+    
+    /// إنشاء الأدلة الوسيطة التي لا يذكرها الأرشيف صراحة
+    fn ensure_directories(&mut self, path: &[&str]) -> Result<InodeId, FsError> {
+        let mut dir = Self::ROOT;
+        for name in path {
+            dir = self.ensure_child(dir, name, FileKind::Directory)?;
+        }
+        Ok(dir)
+    }
+    
+    fn ensure_child(&mut self, dir: InodeId, name: &str, kind: FileKind) -> Result<InodeId, FsError> {
+        if self.nodes[dir as usize].kind != FileKind::Directory {
+            return Err(FsError::NotADirectory);
+        }
+        
+        if let Some(&inode) = self.nodes[dir as usize].children.get(name) {
+            return Ok(inode);
+        }
+        
+        let inode = self.nodes.len() as InodeId;
+        let mut node = Node::directory();
+        node.kind = kind;
+        self.nodes.push(node);
+        self.nodes[dir as usize].children.insert(name.to_string(), inode);
+        Ok(inode)
+    }
+    
+    fn node(&self, inode: InodeId) -> Result<&Node, FsError> {
+        self.nodes.get(inode as usize).ok_or(FsError::NotFound)
+    }
+    
+    /// عدد الملفات العادية
+    pub fn file_count(&self) -> usize {
+        self.nodes.iter().filter(|node| node.kind == FileKind::File).count()
+    }
+}
+
+impl FileSystem for InitrdFs {
+    fn name(&self) -> &str {
+        "initrd"
+    }
+    
+    fn root(&self) -> InodeId {
+        Self::ROOT
+    }
+    
+    fn lookup(&self, dir: InodeId, name: &str) -> Result<InodeId, FsError> {
+        let node = self.node(dir)?;
+        if node.kind != FileKind::Directory {
+            return Err(FsError::NotADirectory);
+        }
+        node.children.get(name).copied().ok_or(FsError::NotFound)
+    }
+    
+    fn metadata(&self, inode: InodeId) -> Result<Metadata, FsError> {
+        let node = self.node(inode)?;
+        Ok(Metadata {
+            inode,
+            kind: node.kind,
+            size: (node.data.1 - node.data.0) as u64,
+            mode: node.mode,
+            uid: node.uid,
+            gid: node.gid,
+            mtime: node.mtime,
+        })
+    }
+    
+    fn read(&self, inode: InodeId, offset: u64, buf: &mut [u8]) -> Result<usize, FsError> {
+        let node = self.node(inode)?;
+        if node.kind == FileKind::Directory {
+            return Err(FsError::IsADirectory);
+        }
+        
+        let data = &self.archive[node.data.0..node.data.1];
+        let offset = (offset as usize).min(data.len());
+        let count = buf.len().min(data.len() - offset);
+        buf[..count].copy_from_slice(&data[offset..offset + count]);
+        Ok(count)
+    }
+    
+    fn read_dir(&self, dir: InodeId) -> Result<Vec<DirEntry>, FsError> {
+        let node = self.node(dir)?;
+        if node.kind != FileKind::Directory {
+            return Err(FsError::NotADirectory);
+        }
+        
+        Ok(node.children.iter()
+            .map(|(name, &inode)| DirEntry {
+                name: name.clone(),
+                inode,
+                kind: self.nodes[inode as usize].kind,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_initrd_cpio_gzip() {
+        let initrd = InitrdFs::from_image(include_bytes!("testdata/initrd.cpio.gz")).expect("initrd صالح");
+        assert_eq!(initrd.file_count(), 3);
+        
+        let etc = initrd.lookup(initrd.root(), "etc").unwrap();
+        let release = initrd.lookup(etc, "os-release").unwrap();
+        let mut buf = [0u8; 64];
+        let read = initrd.read(release, 0, &mut buf).unwrap();
+        assert!(buf[..read].starts_with(b"NAME=\"Islam OS\""));
+        
+        // الدليل config غير مذكور في الأرشيف ويُنشأ ضمنياً
+        let config = initrd.lookup(initrd.root(), "config").unwrap();
+        assert_eq!(initrd.metadata(config).unwrap().kind, FileKind::Directory);
+        assert_eq!(initrd.write(release, 0, b"x"), Err(FsError::ReadOnly));
+    }
+}
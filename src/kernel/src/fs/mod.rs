@@ -0,0 +1,262 @@
+//! 📁 نظام الملفات لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+pub mod cpio;
+pub mod ext2;
+pub mod fat32;
+pub mod initrd;
+pub mod procfs;
+pub mod vfs;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use log::{error, info, warn};
+
+use crate::{accounts, boot, power};
+use crate::accounts::{Privilege, SessionId};
+use crate::drivers::block::{self, cache::BufferCache, BlockDevice, BlockError};
+use ext2::Ext2Fs;
+use fat32::Fat32Fs;
+use initrd::InitrdFs;
+
+/// وضع التركيب
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountMode {
+    ReadWrite,
+    /// للقراءة فقط (وضع الاسترداد)
+    ReadOnly,
+}
+
+/// أخطاء نظام الملفات
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsError {
+    /// نظام الملفات مركب للقراءة فقط
+    ReadOnly,
+    /// لا يوجد نظام ملفات مركب لحفظ البيانات
+    NotMounted,
+    /// المسار غير موجود
+    NotFound,
+    /// مكون في المسار ليس دليلاً
+    NotADirectory,
+    /// العملية تتطلب ملفاً لا دليلاً
+    IsADirectory,
+    /// الاسم موجود مسبقاً
+    AlreadyExists,
+    /// مسار غير مطلق أو غير صالح
+    InvalidPath,
+    /// بيانات نظام الملفات تالفة
+    Corrupted,
+    /// الدليل ليس فارغاً
+    NotEmpty,
+    /// لا توجد مساحة أو عقد حرة
+    NoSpace,
+    /// الاسم أطول من حد نظام الملفات
+    NameTooLong,
+    /// واصف ملف غير صالح أو لا يسمح بالعملية
+    BadDescriptor,
+    /// وسيط غير صالح (إزاحة سالبة مثلاً)
+    InvalidArgument,
+    /// نقطة التركيب مستخدمة بملفات مفتوحة
+    Busy,
+    /// ميزة غير مدعومة في هذا النظام
+    Unsupported,
+    /// الجلسة لا تملك صلاحية التركيب
+    PermissionDenied,
+    /// خطأ في الجهاز الكتلي
+    Io(BlockError),
+}
+
+impl From<BlockError> for FsError {
+    fn from(error: BlockError) -> Self {
+        FsError::Io(error)
+    }
+}
+
+/// نقطة تركيب قرص البيانات القابل للكتابة
+pub const DATA_MOUNT: &str = "/data";
+
+/// ملف لقطة حالة النظام على قرص البيانات
+const STATE_FILE: &str = "/data/system-state";
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// هل قرص البيانات مركب للكتابة؟
+pub fn data_available() -> bool {
+    !is_read_only() && vfs::mounts().iter().any(|(path, _, mode)| path == DATA_MOUNT && *mode == MountMode::ReadWrite)
+}
+
+/// تهيئة نظام الملفات
+pub fn init(mode: MountMode) {
+    READ_ONLY.store(mode == MountMode::ReadOnly, Ordering::SeqCst);
+    
+    match mode {
+        MountMode::ReadWrite => info!("📁 أنظمة الملفات: قراءة وكتابة"),
+        MountMode::ReadOnly => warn!("📁 أنظمة الملفات: للقراءة فقط"),
+    }
+    
+    mount_initrd();
+    match procfs::mount() {
+        Ok(()) => procfs::register("mounts", proc_mounts),
+        Err(e) => error!("❌ فشل تركيب {}: {:?}", procfs::MOUNT_POINT, e),
+    }
+    if let Err(e) = mount_data(mode) {
+        warn!("⚠️ لا قرص بيانات: {:?}", e);
+    }
+    power::register("filesystems", power::priority::FILESYSTEMS, 1000, sync_on_shutdown);
+}
+
+/// /proc/mounts: المسار ونوع نظام الملفات والوضع لكل تركيب
+fn proc_mounts() -> String {
+    vfs::mounts().iter()
+        .map(|(path, fs, mode)| alloc::format!("{} {} {}\n", path, fs, if *mode == MountMode::ReadOnly { "ro" } else { "rw" }))
+        .collect()
+}
+
+/// تركيب initrd كجذر للقراءة فقط
+fn mount_initrd() {
+    let Some(info) = boot::info() else {
+        warn!("⚠️ لا معلومات إقلاع، لا يوجد initrd");
+        return;
+    };
+    
+    let module = info.modules()
+        .find(|module| module.command_line.ends_with(initrd::MODULE_NAME))
+        .or_else(|| info.modules().next());
+    let Some(module) = module else {
+        warn!("⚠️ لم يحمّل المحمل أي initrd");
+        return;
+    };
+    
+    let image = match module_bytes(module.start, module.size()) {
+        Some(image) => image,
+        None => {
+            error!("❌ تعذر ربط ذاكرة initrd");
+            return;
+        }
+    };
+    
+    let fs = match InitrdFs::from_image(image) {
+        Ok(fs) => fs,
+        Err(e) => {
+            error!("❌ initrd غير صالح: {:?}", e);
+            return;
+        }
+    };
+    
+    info!("💿 initrd: {} ملف ({} بايت مضغوط)", fs.file_count(), image.len());
+    if let Err(e) = vfs::mount("/", Arc::new(fs), MountMode::ReadOnly) {
+        error!("❌ فشل تركيب الجذر: {:?}", e);
+        return;
+    }
+    
+    if let Ok(release) = vfs::read_to_string("/etc/os-release") {
+        if let Some(name) = release.lines().find_map(|line| line.strip_prefix("PRETTY_NAME=")) {
+            info!("🕌 {}", name.trim_matches('"'));
+        }
+    }
+}
+
+/// التعرف على نظام الملفات في جهاز وتركيبه (يتطلب صلاحية `Mount`)
+pub fn mount_device(session: SessionId, device: Arc<dyn BlockDevice>, path: &str, mode: MountMode) -> Result<(), FsError> {
+    accounts::authorize(session, Privilege::Mount).map_err(|_| FsError::PermissionDenied)?;
+    mount_detected(device, path, mode)
+}
+
+/// التركيب دون سؤال جلسة، لما تركبه النواة نفسها
+fn mount_detected(device: Arc<dyn BlockDevice>, path: &str, mode: MountMode) -> Result<(), FsError> {
+    if Ext2Fs::probe(device.as_ref()) {
+        return vfs::mount(path, Arc::new(Ext2Fs::mount(device)?), mode);
+    }
+    
+    if Fat32Fs::probe(device.as_ref()) {
+        return vfs::mount(path, Arc::new(Fat32Fs::mount(device)?), mode);
+    }
+    
+    warn!("⚠️ لا نظام ملفات معروف على {}", device.name());
+    Err(FsError::Unsupported)
+}
+
+/// تركيب قرص البيانات على /data عبر الذاكرة المؤقتة للقطاعات: الجهاز من
+/// `data=<اسم>` في سطر الأوامر، وإلا أول قسم بنظام ملفات معروف
+pub fn mount_data(mode: MountMode) -> Result<(), FsError> {
+    let device = match boot::params().get("data") {
+        Some(name) => block::find(name).ok_or(FsError::NotFound)?,
+        None => block::volumes().into_iter()
+            .find(|volume| Ext2Fs::probe(volume.as_ref()) || Fat32Fs::probe(volume.as_ref()))
+            .ok_or(FsError::NotMounted)?,
+    };
+    
+    let name = String::from(device.name());
+    let cached: Arc<dyn BlockDevice> = Arc::new(BufferCache::new(device, block::cache::DEFAULT_CAPACITY));
+    mount_detected(cached, DATA_MOUNT, mode)?;
+    info!("💽 قرص البيانات {} على {}", name, DATA_MOUNT);
+    Ok(())
+}
+
+/// ذاكرة وحدة الإقلاع (مربوطة على عنوانها الفيزيائي المطابق)
+fn module_bytes(start: u64, size: u64) -> Option<&'static [u8]> {
+    #[cfg(not(feature = "hosted"))]
+    {
+        use x86_64::structures::paging::PageTableFlags;
+        crate::memory::paging::map_physical(start, size, PageTableFlags::NO_EXECUTE).ok()?;
+    }
+    
+    // الوحدة محجوزة في مخصص الإطارات ولا تُحرر أبداً
+    Some(unsafe { core::slice::from_raw_parts(start as *const u8, size as usize) })
+}
+
+/// هل أنظمة الملفات للقراءة فقط؟
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+/// فرض القراءة فقط على كل أنظمة الملفات أو رفعه (الوضع المتدهور)
+///
+/// التركيبات تحتفظ بوضعها الأصلي، فرفع القيد يعيد الكتابة لما رُكّب للكتابة.
+pub fn set_read_only(read_only: bool) {
+    if READ_ONLY.swap(read_only, Ordering::SeqCst) == read_only {
+        return;
+    }
+    
+    if read_only {
+        // ما كُتب قبل القيد يجب أن يصل إلى الأقراص
+        if let Err(e) = vfs::sync_all() {
+            error!("❌ فشل المزامنة قبل القراءة فقط: {:?}", e);
+        }
+        warn!("📁 أنظمة الملفات: للقراءة فقط");
+    } else {
+        info!("📁 أنظمة الملفات: قراءة وكتابة");
+    }
+}
+
+/// خطاف الإغلاق: حفظ البيانات المعلقة على الأقراص
+fn sync_on_shutdown(_: &power::HookContext) -> Result<(), String> {
+    if is_read_only() {
+        warn!("📁 تخطي الحفظ قبل الإغلاق: أنظمة الملفات للقراءة فقط");
+        return Ok(());
+    }
+    
+    info!("💾 حفظ البيانات المعلقة...");
+    vfs::sync_all().map_err(|e| alloc::format!("{:?}", e))
+}
+
+/// حفظ حالة النظام بعد الذعر
+pub fn save_system_state() -> Result<(), FsError> {
+    if is_read_only() {
+        return Err(FsError::ReadOnly);
+    }
+    
+    if !data_available() {
+        return Err(FsError::NotMounted);
+    }
+    
+    // قد تكون الحالة مقفلة لحظة الذعر
+    let snapshot = match crate::SYSTEM_STATE.try_lock() {
+        Some(state) => alloc::format!("{:#?}\n", *state),
+        None => alloc::string::String::from("SystemState: locked\n"),
+    };
+    
+    vfs::write_file(STATE_FILE, snapshot.as_bytes())?;
+    vfs::sync_all()
+}
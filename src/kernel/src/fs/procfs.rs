@@ -0,0 +1,218 @@
+//! 📊 نظام ملفات /proc
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! ملفات افتراضية تُولَّد نصوصها عند القراءة من حالة النواة الحية، فتقرأ
+//! الصدفة والأدوات معلومات النظام كملفات عادية دون الارتباط بداخل النواة.
+//! تسجل كل وحدة ملفاتها عند تهيئتها:
+//!
+//! ```text
+//! procfs::register("tasks", task_table);          →  /proc/tasks
+//! procfs::register("security/threat", threat);    →  /proc/security/threat
+//! ```
+//!
+//! الملفات نصية بسطر `مفتاح=قيمة` أو جدول بسطر عناوين، وللقراءة فقط.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use lazy_static::lazy_static;
+use spin::RwLock;
+
+use super::vfs::{self, DirEntry, FileKind, FileSystem, InodeId, Metadata};
+use super::{FsError, MountMode};
+
+/// نقطة التركيب
+pub const MOUNT_POINT: &str = "/proc";
+
+/// دالة تولد محتوى الملف
+pub type Generator = fn() -> String;
+
+enum Node {
+    Directory(BTreeMap<String, InodeId>),
+    File(Generator),
+}
+
+/// شجرة الملفات المسجلة
+pub struct ProcFs {
+    nodes: RwLock<Vec<Node>>,
+}
+
+impl ProcFs {
+    const ROOT: InodeId = 0;
+    
+    pub fn new() -> Self {
+        Self { nodes: RwLock::new(alloc::vec![Node::Directory(BTreeMap::new())]) }
+    }
+    
+    /// تسجيل ملف بمسار نسبي (`security/firewall`) مع إنشاء أدلته؛ التسجيل
+    /// بالمسار نفسه يستبدل المولد السابق
+    pub fn register(&self, path: &str, generator: Generator) -> Result<InodeId, FsError> {
+        let components: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+        let (name, dirs) = components.split_last().ok_or(FsError::InvalidPath)?;
+        
+        let mut nodes = self.nodes.write();
+        let mut dir = Self::ROOT;
+        for component in dirs {
+            dir = match Self::child(&nodes, dir, component) {
+                Some(child) if matches!(nodes[child as usize], Node::Directory(_)) => child,
+                Some(_) => return Err(FsError::NotADirectory),
+                None => Self::insert(&mut nodes, dir, component, Node::Directory(BTreeMap::new())),
+            };
+        }
+        
+        match Self::child(&nodes, dir, name) {
+            Some(existing) => match &mut nodes[existing as usize] {
+                Node::File(current) => {
+                    *current = generator;
+                    Ok(existing)
+                }
+                Node::Directory(_) => Err(FsError::IsADirectory),
+            },
+            None => Ok(Self::insert(&mut nodes, dir, name, Node::File(generator))),
+        }
+    }
+    
+    fn child(nodes: &[Node], dir: InodeId, name: &str) -> Option<InodeId> {
+        match &nodes[dir as usize] {
+            Node::Directory(children) => children.get(name).copied(),
+            Node::File(_) => None,
+        }
+    }
+    
+    fn insert(nodes: &mut Vec<Node>, dir: InodeId, name: &str, node: Node) -> InodeId {
+        let id = nodes.len() as InodeId;
+        nodes.push(node);
+        if let Node::Directory(children) = &mut nodes[dir as usize] {
+            children.insert(name.to_string(), id);
+        }
+        id
+    }
+    
+    /// محتوى الملف الآن (المولد يُستدعى خارج القفل فقد يقرأ /proc نفسه)
+    fn render(&self, inode: InodeId) -> Result<String, FsError> {
+        let generator = match self.nodes.read().get(inode as usize) {
+            Some(Node::File(generator)) => *generator,
+            Some(Node::Directory(_)) => return Err(FsError::IsADirectory),
+            None => return Err(FsError::NotFound),
+        };
+        Ok(generator())
+    }
+}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for ProcFs {
+    fn name(&self) -> &str {
+        "procfs"
+    }
+    
+    fn root(&self) -> InodeId {
+        Self::ROOT
+    }
+    
+    fn lookup(&self, dir: InodeId, name: &str) -> Result<InodeId, FsError> {
+        let nodes = self.nodes.read();
+        match nodes.get(dir as usize) {
+            Some(Node::Directory(children)) => children.get(name).copied().ok_or(FsError::NotFound),
+            Some(Node::File(_)) => Err(FsError::NotADirectory),
+            None => Err(FsError::NotFound),
+        }
+    }
+    
+    fn metadata(&self, inode: InodeId) -> Result<Metadata, FsError> {
+        let is_dir = matches!(self.nodes.read().get(inode as usize).ok_or(FsError::NotFound)?, Node::Directory(_));
+        
+        // الحجم الحقيقي للمحتوى الحالي حتى يقرأ `read_file` الملف كاملاً
+        let (kind, size, mode) = if is_dir {
+            (FileKind::Directory, 0, 0o555)
+        } else {
+            (FileKind::File, self.render(inode)?.len() as u64, 0o444)
+        };
+        Ok(Metadata { inode, kind, size, mode, uid: 0, gid: 0, mtime: 0 })
+    }
+    
+    fn read(&self, inode: InodeId, offset: u64, buf: &mut [u8]) -> Result<usize, FsError> {
+        let content = self.render(inode)?;
+        let bytes = content.as_bytes();
+        let start = (offset as usize).min(bytes.len());
+        let count = buf.len().min(bytes.len() - start);
+        buf[..count].copy_from_slice(&bytes[start..start + count]);
+        Ok(count)
+    }
+    
+    fn read_dir(&self, dir: InodeId) -> Result<Vec<DirEntry>, FsError> {
+        let nodes = self.nodes.read();
+        let Some(Node::Directory(children)) = nodes.get(dir as usize) else {
+            return Err(FsError::NotADirectory);
+        };
+        
+        Ok(children.iter().map(|(name, &inode)| DirEntry {
+            name: name.clone(),
+            inode,
+            kind: match nodes[inode as usize] {
+                Node::Directory(_) => FileKind::Directory,
+                Node::File(_) => FileKind::File,
+            },
+        }).collect())
+    }
+}
+
+lazy_static! {
+    static ref PROCFS: Arc<ProcFs> = Arc::new(ProcFs::new());
+}
+
+/// تسجيل ملف تحت /proc
+pub fn register(path: &str, generator: Generator) {
+    if let Err(e) = PROCFS.register(path, generator) {
+        log::error!("❌ تعذر تسجيل {}/{}: {:?}", MOUNT_POINT, path, e);
+    }
+}
+
+/// تركيب /proc
+pub fn mount() -> Result<(), FsError> {
+    vfs::mount(MOUNT_POINT, PROCFS.clone(), MountMode::ReadOnly)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_procfs() {
+        let procfs = ProcFs::new();
+        let root = procfs.root();
+        let threat = procfs.register("security/threat", || String::from("Low\n")).unwrap();
+        procfs.register("uptime", || String::from("42\n")).unwrap();
+        
+        let security = procfs.lookup(root, "security").unwrap();
+        assert_eq!(procfs.lookup(security, "threat"), Ok(threat));
+        let names: Vec<_> = procfs.read_dir(root).unwrap().into_iter().map(|entry| (entry.name, entry.kind)).collect();
+        assert_eq!(names, [(String::from("security"), FileKind::Directory), (String::from("uptime"), FileKind::File)]);
+        
+        // الحجم يطابق المحتوى المولد، والقراءة تحترم الإزاحة
+        assert_eq!(procfs.metadata(threat).unwrap().size, 4);
+        let mut buf = [0u8; 8];
+        assert_eq!(procfs.read(threat, 1, &mut buf), Ok(3));
+        assert_eq!(&buf[..3], b"ow\n");
+        
+        // إعادة التسجيل تستبدل المولد، ولا ملف تحت ملف
+        assert_eq!(procfs.register("security/threat", || String::from("High\n")), Ok(threat));
+        assert_eq!(procfs.metadata(threat).unwrap().size, 5);
+        assert_eq!(procfs.register("uptime/seconds", String::new), Err(FsError::NotADirectory));
+        assert_eq!(procfs.register("security", String::new), Err(FsError::IsADirectory));
+        
+        #[cfg(feature = "hosted")]
+        {
+            crate::hosted::boot();
+            let tasks = vfs::read_to_string("/proc/tasks").unwrap();
+            assert!(tasks.starts_with("id\tname\tpriority\tstate"));
+            assert!(vfs::read_to_string("/proc/meminfo").unwrap().contains("total="));
+            assert!(vfs::read_to_string("/proc/tokens").unwrap().contains("locked="));
+        }
+    }
+}
@@ -0,0 +1,540 @@
+//! 🗂️ نظام الملفات الافتراضي (VFS)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! جدول تركيب يربط المسارات بأنظمة ملفات تنفذ `FileSystem`، وتحليل مسارات
+//! يختار أطول نقطة تركيب مطابقة ثم يمشي المكونات عبر `lookup` مع ذاكرة
+//! مداخل (dentries)، وجدول واصفات ملفات مفتوحة فوق العقد المحلولة.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::BitOr;
+use core::sync::atomic::{AtomicU64, Ordering};
+use lazy_static::lazy_static;
+use spin::{Mutex, RwLock};
+
+use super::{FsError, MountMode};
+use crate::accounts::{self, Privilege, SessionId};
+
+/// رقم العقدة داخل نظام ملفات واحد
+pub type InodeId = u64;
+
+/// نوع الملف
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    File,
+    Directory,
+    Symlink,
+    Other,
+}
+
+/// بيانات وصفية لعقدة
+#[derive(Debug, Clone, Copy)]
+pub struct Metadata {
+    pub inode: InodeId,
+    pub kind: FileKind,
+    pub size: u64,
+    /// صلاحيات يونكس (الـ 12 بتاً الدنيا)
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub mtime: u64,
+}
+
+/// مدخل دليل
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    pub name: String,
+    pub inode: InodeId,
+    pub kind: FileKind,
+}
+
+/// واجهة نظام ملفات قابل للتركيب؛ عمليات الكتابة اختيارية
+pub trait FileSystem: Send + Sync {
+    /// اسم نوع نظام الملفات
+    fn name(&self) -> &str;
+    
+    /// عقدة الجذر
+    fn root(&self) -> InodeId;
+    
+    /// البحث عن اسم داخل دليل
+    fn lookup(&self, dir: InodeId, name: &str) -> Result<InodeId, FsError>;
+    
+    fn metadata(&self, inode: InodeId) -> Result<Metadata, FsError>;
+    
+    /// قراءة من إزاحة؛ يعيد عدد البايتات المقروءة (0 عند النهاية)
+    fn read(&self, inode: InodeId, offset: u64, buf: &mut [u8]) -> Result<usize, FsError>;
+    
+    fn read_dir(&self, dir: InodeId) -> Result<Vec<DirEntry>, FsError>;
+    
+    /// كتابة في إزاحة مع توسيع الملف عند الحاجة
+    fn write(&self, _inode: InodeId, _offset: u64, _data: &[u8]) -> Result<usize, FsError> {
+        Err(FsError::ReadOnly)
+    }
+    
+    /// إنشاء ملف أو دليل فارغ
+    fn create(&self, _dir: InodeId, _name: &str, _kind: FileKind) -> Result<InodeId, FsError> {
+        Err(FsError::ReadOnly)
+    }
+    
+    /// حذف ملف أو دليل فارغ
+    fn remove(&self, _dir: InodeId, _name: &str) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+    
+    fn truncate(&self, _inode: InodeId, _size: u64) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+    
+    /// كتابة كل البيانات المعلقة إلى الجهاز
+    fn sync(&self) -> Result<(), FsError> {
+        Ok(())
+    }
+}
+
+/// عقدة محلولة: نظام الملفات المركب ورقم العقدة فيه
+#[derive(Clone)]
+pub struct Inode {
+    pub fs: Arc<dyn FileSystem>,
+    pub id: InodeId,
+    pub mode: MountMode,
+    mount: u64,
+}
+
+impl Inode {
+    pub fn metadata(&self) -> Result<Metadata, FsError> {
+        self.fs.metadata(self.id)
+    }
+    
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<usize, FsError> {
+        self.fs.read(self.id, offset, buf)
+    }
+    
+    pub fn write(&self, offset: u64, data: &[u8]) -> Result<usize, FsError> {
+        self.writable()?;
+        self.fs.write(self.id, offset, data)
+    }
+    
+    pub fn truncate(&self, size: u64) -> Result<(), FsError> {
+        self.writable()?;
+        self.fs.truncate(self.id, size)
+    }
+    
+    pub fn read_dir(&self) -> Result<Vec<DirEntry>, FsError> {
+        self.fs.read_dir(self.id)
+    }
+    
+    fn writable(&self) -> Result<(), FsError> {
+        match self.mode {
+            MountMode::ReadWrite if !super::is_read_only() => Ok(()),
+            _ => Err(FsError::ReadOnly),
+        }
+    }
+}
+
+/// نقطة تركيب
+struct Mount {
+    id: u64,
+    /// مكونات المسار (فارغة للجذر)
+    components: Vec<String>,
+    fs: Arc<dyn FileSystem>,
+    mode: MountMode,
+}
+
+/// مدخل محلول في ذاكرة المداخل
+#[derive(Clone, Copy)]
+struct Dentry {
+    mount: u64,
+    inode: InodeId,
+}
+
+/// أقصى عدد للمداخل المحفوظة قبل تفريغها
+const MAX_DENTRIES: usize = 512;
+
+static NEXT_MOUNT_ID: AtomicU64 = AtomicU64::new(1);
+
+lazy_static! {
+    static ref MOUNTS: RwLock<Vec<Mount>> = RwLock::new(Vec::new());
+    static ref DENTRIES: Mutex<BTreeMap<String, Dentry>> = Mutex::new(BTreeMap::new());
+}
+
+/// تقسيم مسار مطلق إلى مكونات بعد معالجة `.` و `..`
+pub fn normalize(path: &str) -> Result<Vec<String>, FsError> {
+    if !path.starts_with('/') {
+        return Err(FsError::InvalidPath);
+    }
+    
+    let mut components: Vec<String> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            name => components.push(name.to_string()),
+        }
+    }
+    
+    Ok(components)
+}
+
+/// المسار المطبع لقائمة مكونات
+fn join(components: &[String]) -> String {
+    if components.is_empty() {
+        return "/".to_string();
+    }
+    
+    let mut path = String::new();
+    for component in components {
+        path.push('/');
+        path.push_str(component);
+    }
+    path
+}
+
+/// تركيب نظام ملفات على مسار؛ يُفرض وضع القراءة فقط في وضع الاسترداد
+pub fn mount(path: &str, fs: Arc<dyn FileSystem>, mode: MountMode) -> Result<(), FsError> {
+    let components = normalize(path)?;
+    let mode = if super::is_read_only() { MountMode::ReadOnly } else { mode };
+    let mut mounts = MOUNTS.write();
+    
+    if mounts.iter().any(|m| m.components == components) {
+        return Err(FsError::AlreadyExists);
+    }
+    
+    log::info!("📌 تركيب {} على {} ({:?})", fs.name(), path, mode);
+    let id = NEXT_MOUNT_ID.fetch_add(1, Ordering::SeqCst);
+    mounts.push(Mount { id, components, fs, mode });
+    
+    // الأطول أولاً حتى تفوز نقطة التركيب الأعمق
+    mounts.sort_by_key(|mount| core::cmp::Reverse(mount.components.len()));
+    
+    // مداخل المسارات تحت نقطة التركيب الجديدة صارت تشير للنظام القديم
+    DENTRIES.lock().clear();
+    Ok(())
+}
+
+/// فك تركيب مسار بعد مزامنته (يتطلب صلاحية `Mount`)
+pub fn unmount(session: SessionId, path: &str) -> Result<(), FsError> {
+    accounts::authorize(session, Privilege::Mount).map_err(|_| FsError::PermissionDenied)?;
+    let components = normalize(path)?;
+    let mut mounts = MOUNTS.write();
+    let index = mounts.iter()
+        .position(|m| m.components == components)
+        .ok_or(FsError::NotMounted)?;
+    
+    let id = mounts[index].id;
+    if FILES.lock().values().any(|file| file.inode.mount == id) {
+        return Err(FsError::Busy);
+    }
+    
+    mounts[index].fs.sync()?;
+    mounts.remove(index);
+    DENTRIES.lock().retain(|_, dentry| dentry.mount != id);
+    Ok(())
+}
+
+/// قائمة نقاط التركيب: (المسار، نوع نظام الملفات، الوضع)
+pub fn mounts() -> Vec<(String, String, MountMode)> {
+    MOUNTS.read().iter()
+        .map(|m| (join(&m.components), m.fs.name().to_string(), m.mode))
+        .collect()
+}
+
+/// مزامنة جميع أنظمة الملفات المركبة للكتابة
+pub fn sync_all() -> Result<(), FsError> {
+    let mut result = Ok(());
+    for mount in MOUNTS.read().iter().filter(|m| m.mode == MountMode::ReadWrite) {
+        if let Err(e) = mount.fs.sync() {
+            log::error!("❌ فشل مزامنة {}: {:?}", mount.fs.name(), e);
+            result = Err(e);
+        }
+    }
+    result
+}
+
+/// حفظ مدخل محلول
+fn cache_dentry(path: String, dentry: Dentry) {
+    let mut dentries = DENTRIES.lock();
+    if dentries.len() >= MAX_DENTRIES {
+        dentries.clear();
+    }
+    dentries.insert(path, dentry);
+}
+
+/// إسقاط مدخل مسار وكل ما تحته (بعد الحذف)
+fn invalidate(path: &str) {
+    let prefix = alloc::format!("{}/", path);
+    DENTRIES.lock().retain(|key, _| key != path && !key.starts_with(&prefix));
+}
+
+/// تحليل مكونات مطبعة إلى عقدة
+fn resolve_components(components: &[String]) -> Result<Inode, FsError> {
+    let (id, fs, mode, skip) = {
+        let mounts = MOUNTS.read();
+        let mount = mounts.iter()
+            .find(|m| components.starts_with(&m.components))
+            .ok_or(FsError::NotMounted)?;
+        (mount.id, mount.fs.clone(), mount.mode, mount.components.len())
+    };
+    
+    let mut inode = fs.root();
+    for depth in skip..components.len() {
+        let key = join(&components[..=depth]);
+        let cached = DENTRIES.lock().get(&key).copied();
+        
+        inode = match cached {
+            Some(dentry) if dentry.mount == id => dentry.inode,
+            _ => {
+                if fs.metadata(inode)?.kind != FileKind::Directory {
+                    return Err(FsError::NotADirectory);
+                }
+                let child = fs.lookup(inode, &components[depth])?;
+                cache_dentry(key, Dentry { mount: id, inode: child });
+                child
+            }
+        };
+    }
+    
+    Ok(Inode { fs, id: inode, mode, mount: id })
+}
+
+/// تحليل مسار مطلق إلى عقدة
+pub fn resolve(path: &str) -> Result<Inode, FsError> {
+    resolve_components(&normalize(path)?)
+}
+
+/// الدليل الأب واسم المكون الأخير
+fn resolve_parent(path: &str) -> Result<(Inode, String), FsError> {
+    let mut components = normalize(path)?;
+    let name = components.pop().ok_or(FsError::InvalidPath)?;
+    let parent = resolve_components(&components)?;
+    
+    if parent.metadata()?.kind != FileKind::Directory {
+        return Err(FsError::NotADirectory);
+    }
+    
+    Ok((parent, name))
+}
+
+/// البيانات الوصفية لمسار
+pub fn stat(path: &str) -> Result<Metadata, FsError> {
+    resolve(path)?.metadata()
+}
+
+/// هل المسار موجود؟
+pub fn exists(path: &str) -> bool {
+    resolve(path).is_ok()
+}
+
+/// قراءة ملف كامل
+pub fn read_file(path: &str) -> Result<Vec<u8>, FsError> {
+    let inode = resolve(path)?;
+    let metadata = inode.metadata()?;
+    if metadata.kind == FileKind::Directory {
+        return Err(FsError::IsADirectory);
+    }
+    
+    let mut data = alloc::vec![0u8; metadata.size as usize];
+    let mut offset = 0;
+    while offset < data.len() {
+        let read = inode.read(offset as u64, &mut data[offset..])?;
+        if read == 0 {
+            break;
+        }
+        offset += read;
+    }
+    data.truncate(offset);
+    
+    Ok(data)
+}
+
+/// قراءة ملف نصي كامل
+pub fn read_to_string(path: &str) -> Result<String, FsError> {
+    String::from_utf8(read_file(path)?).map_err(|_| FsError::Corrupted)
+}
+
+/// محتويات دليل
+pub fn read_dir(path: &str) -> Result<Vec<DirEntry>, FsError> {
+    resolve(path)?.read_dir()
+}
+
+/// إنشاء عقدة جديدة تحت دليلها الأب
+fn create(path: &str, kind: FileKind) -> Result<Inode, FsError> {
+    let (parent, name) = resolve_parent(path)?;
+    parent.writable()?;
+    
+    let id = parent.fs.create(parent.id, &name, kind)?;
+    Ok(Inode { id, ..parent })
+}
+
+/// إنشاء دليل
+pub fn create_dir(path: &str) -> Result<(), FsError> {
+    create(path, FileKind::Directory).map(|_| ())
+}
+
+/// حذف ملف أو دليل فارغ
+pub fn remove(path: &str) -> Result<(), FsError> {
+    let (parent, name) = resolve_parent(path)?;
+    parent.writable()?;
+    
+    parent.fs.remove(parent.id, &name)?;
+    invalidate(&join(&normalize(path)?));
+    Ok(())
+}
+
+/// كتابة ملف كامل (إنشاؤه أو استبدال محتواه)
+pub fn write_file(path: &str, data: &[u8]) -> Result<(), FsError> {
+    let fd = open(path, OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE)?;
+    let result = write(fd, data).and_then(|written| {
+        if written == data.len() { Ok(()) } else { Err(FsError::NoSpace) }
+    });
+    close(fd)?;
+    result
+}
+
+/// رقم واصف ملف مفتوح
+pub type Fd = usize;
+
+/// أول واصف متاح (0-2 محجوزة للطرفية)
+const FIRST_FD: Fd = 3;
+
+/// أعلام فتح الملف
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenFlags(u32);
+
+impl OpenFlags {
+    pub const READ: Self = Self(1 << 0);
+    pub const WRITE: Self = Self(1 << 1);
+    /// إنشاء الملف إن لم يكن موجوداً
+    pub const CREATE: Self = Self(1 << 2);
+    /// تفريغ الملف عند الفتح
+    pub const TRUNCATE: Self = Self(1 << 3);
+    /// كل كتابة تذهب إلى نهاية الملف
+    pub const APPEND: Self = Self(1 << 4);
+    
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for OpenFlags {
+    type Output = Self;
+    
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// موضع الإزاحة الجديد لـ `seek`
+#[derive(Debug, Clone, Copy)]
+pub enum SeekFrom {
+    Start(u64),
+    Current(i64),
+    End(i64),
+}
+
+/// ملف مفتوح
+struct OpenFile {
+    inode: Inode,
+    offset: u64,
+    flags: OpenFlags,
+}
+
+lazy_static! {
+    static ref FILES: Mutex<BTreeMap<Fd, OpenFile>> = Mutex::new(BTreeMap::new());
+}
+
+/// فتح ملف وإرجاع واصفه
+pub fn open(path: &str, flags: OpenFlags) -> Result<Fd, FsError> {
+    let inode = match resolve(path) {
+        Ok(inode) => inode,
+        Err(FsError::NotFound) if flags.contains(OpenFlags::CREATE) => create(path, FileKind::File)?,
+        Err(e) => return Err(e),
+    };
+    
+    let metadata = inode.metadata()?;
+    if metadata.kind == FileKind::Directory && flags.contains(OpenFlags::WRITE) {
+        return Err(FsError::IsADirectory);
+    }
+    
+    if flags.contains(OpenFlags::WRITE) {
+        inode.writable()?;
+        if flags.contains(OpenFlags::TRUNCATE) && metadata.size > 0 {
+            inode.truncate(0)?;
+        }
+    }
+    
+    let mut files = FILES.lock();
+    let fd = (FIRST_FD..).find(|fd| !files.contains_key(fd)).unwrap_or(FIRST_FD);
+    files.insert(fd, OpenFile { inode, offset: 0, flags });
+    Ok(fd)
+}
+
+/// نسخة من العقدة والإزاحة (تُنفذ العمليات دون إمساك الجدول)
+fn file_state(fd: Fd) -> Result<(Inode, u64, OpenFlags), FsError> {
+    let files = FILES.lock();
+    let file = files.get(&fd).ok_or(FsError::BadDescriptor)?;
+    Ok((file.inode.clone(), file.offset, file.flags))
+}
+
+fn set_offset(fd: Fd, offset: u64) {
+    if let Some(file) = FILES.lock().get_mut(&fd) {
+        file.offset = offset;
+    }
+}
+
+/// القراءة من الإزاحة الحالية وتقديمها
+pub fn read(fd: Fd, buf: &mut [u8]) -> Result<usize, FsError> {
+    let (inode, offset, flags) = file_state(fd)?;
+    if flags.contains(OpenFlags::WRITE) && !flags.contains(OpenFlags::READ) {
+        return Err(FsError::BadDescriptor);
+    }
+    
+    let read = inode.read(offset, buf)?;
+    set_offset(fd, offset + read as u64);
+    Ok(read)
+}
+
+/// الكتابة في الإزاحة الحالية (أو النهاية مع `APPEND`) وتقديمها
+pub fn write(fd: Fd, data: &[u8]) -> Result<usize, FsError> {
+    let (inode, offset, flags) = file_state(fd)?;
+    if !flags.contains(OpenFlags::WRITE) {
+        return Err(FsError::BadDescriptor);
+    }
+    
+    let offset = if flags.contains(OpenFlags::APPEND) { inode.metadata()?.size } else { offset };
+    let written = inode.write(offset, data)?;
+    set_offset(fd, offset + written as u64);
+    Ok(written)
+}
+
+/// تغيير الإزاحة الحالية
+pub fn seek(fd: Fd, position: SeekFrom) -> Result<u64, FsError> {
+    let (inode, offset, _) = file_state(fd)?;
+    let target = match position {
+        SeekFrom::Start(offset) => Some(offset),
+        SeekFrom::Current(delta) => offset.checked_add_signed(delta),
+        SeekFrom::End(delta) => inode.metadata()?.size.checked_add_signed(delta),
+    };
+    
+    let target = target.ok_or(FsError::InvalidArgument)?;
+    set_offset(fd, target);
+    Ok(target)
+}
+
+/// البيانات الوصفية لملف مفتوح
+pub fn fstat(fd: Fd) -> Result<Metadata, FsError> {
+    file_state(fd)?.0.metadata()
+}
+
+/// إغلاق واصف
+pub fn close(fd: Fd) -> Result<(), FsError> {
+    FILES.lock().remove(&fd).map(|_| ()).ok_or(FsError::BadDescriptor)
+}
+
+/// عدد الملفات المفتوحة
+pub fn open_count() -> usize {
+    FILES.lock().len()
+}
@@ -0,0 +1,44 @@
+//! 🎨 واجهة المستخدم لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+pub mod notifications;
+pub mod pointer;
+
+use alloc::format;
+use log::info;
+use spin::Mutex;
+
+use crate::input::{self, InputEvent, Layout, MouseEvent};
+
+/// موضع الواجهة في طابور الإدخال
+static INPUT: Mutex<Option<input::Reader>> = Mutex::new(None);
+
+/// تهيئة واجهة المستخدم
+pub fn init() {
+    crate::health::subscribe("gui", notifications::health_alert);
+    info!("  ✅ منطقة الإشعارات");
+    
+    *INPUT.lock() = Some(input::reader());
+}
+
+/// أحداث الإدخال الجديدة منذ الدورة السابقة
+pub fn handle_input() {
+    let events = match INPUT.lock().as_mut() {
+        Some(reader) => reader.poll(),
+        None => return,
+    };
+    
+    for event in events {
+        match event {
+            InputEvent::LayoutChanged(layout) => {
+                let name = match layout {
+                    Layout::Us => "English (US)",
+                    Layout::Arabic => "Arabic (101)",
+                };
+                notifications::notify("keyboard", notifications::Urgency::Info, format!("Keyboard layout: {}", name));
+            }
+            InputEvent::Mouse { event: MouseEvent::Move { .. }, column, row } => pointer::show(column, row),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! 🔔 منطقة الإشعارات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! سطر فوق شريط المهام يعرض أحدث إشعار ملوناً بحسب أهميته، وتحفظ آخر
+//! الإشعارات في الذاكرة. النص على الشاشة بالإنجليزية لأن شاشة VGA النصية
+//! لا تعرض الحروف العربية.
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+use crate::drivers::vga::{self, Color, BUFFER_HEIGHT, BUFFER_WIDTH};
+use crate::health::{Alert, Severity};
+
+/// عدد الإشعارات المحفوظة
+pub const CAPACITY: usize = 16;
+
+/// سطر منطقة الإشعارات (فوق شريط المهام)
+pub const ROW: usize = BUFFER_HEIGHT - 2;
+
+/// أهمية الإشعار
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Urgency {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// إشعار معروض
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub tick: u64,
+    pub source: &'static str,
+    pub urgency: Urgency,
+    pub text: String,
+}
+
+static NOTIFICATIONS: Mutex<VecDeque<Notification>> = Mutex::new(VecDeque::new());
+
+/// إضافة إشعار وعرضه
+pub fn notify(source: &'static str, urgency: Urgency, text: String) {
+    let notification = Notification {
+        tick: crate::process::scheduler::try_ticks().unwrap_or(0),
+        source,
+        urgency,
+        text,
+    };
+    render(&notification);
+    
+    let mut notifications = NOTIFICATIONS.lock();
+    if notifications.len() == CAPACITY {
+        notifications.pop_front();
+    }
+    notifications.push_back(notification);
+}
+
+/// آخر `count` إشعاراً من الأقدم للأحدث
+pub fn recent(count: usize) -> Vec<Notification> {
+    let notifications = NOTIFICATIONS.lock();
+    notifications.iter().skip(notifications.len().saturating_sub(count)).cloned().collect()
+}
+
+fn render(notification: &Notification) {
+    // الإشعار قد يصدر والشاشة محتجزة: يبقى في القائمة ويظهر الإشعار التالي
+    let Some(mut writer) = vga::WRITER.try_lock() else {
+        return;
+    };
+    
+    let (foreground, background) = writer.colors();
+    match notification.urgency {
+        Urgency::Info => writer.set_color(Color::Black, Color::LightGreen),
+        Urgency::Warning => writer.set_color(Color::Black, Color::Yellow),
+        Urgency::Critical => writer.set_color(Color::White, Color::Red),
+    }
+    let text = format!(" [{}] {}", notification.source, notification.text);
+    writer.print_at(&format!("{:1$}", text, BUFFER_WIDTH), ROW, 0);
+    writer.set_color(foreground, background);
+}
+
+/// مشترك تنبيهات الصحة
+pub fn health_alert(alert: &Alert) {
+    let urgency = match alert.severity {
+        Severity::Ok => Urgency::Info,
+        Severity::Warning => Urgency::Warning,
+        Severity::Critical => Urgency::Critical,
+    };
+    notify("health", urgency, format!("{}: {} -> {}", alert.check.name(), alert.previous.name(), alert.severity.name()));
+}
@@ -0,0 +1,41 @@
+//! 🖱️ مؤشر الفأرة على الشاشة النصية
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! المؤشر خلية بألوان معكوسة (المقدمة مكان الخلفية). لون الخلية الأصلي
+//! يُحفظ ويُعاد عند التحرك، إلا إن رسم أحد فوقها منذ ذلك فيبقى ما رسمه.
+
+use spin::Mutex;
+
+use crate::drivers::vga::{self, BUFFER_HEIGHT, BUFFER_WIDTH};
+
+/// الخلية المرسومة: (السطر، العمود، اللون الأصلي، اللون المعكوس)
+static DRAWN: Mutex<Option<(usize, usize, u8, u8)>> = Mutex::new(None);
+
+/// تبديل نصفي بايت الألوان، مع إبقاء المؤشر ظاهراً على خلية بلونين متساويين
+pub fn inverted(attribute: u8) -> u8 {
+    let swapped = attribute.rotate_left(4);
+    if swapped == attribute { swapped ^ 0x70 } else { swapped }
+}
+
+/// رسم المؤشر على الخلية (العمود، السطر)
+pub fn show(column: u16, row: u16) {
+    let (row, col) = ((row as usize).min(BUFFER_HEIGHT - 1), (column as usize).min(BUFFER_WIDTH - 1));
+    let mut drawn = DRAWN.lock();
+    let mut writer = vga::WRITER.lock();
+    
+    if let Some((old_row, old_col, original, shown)) = drawn.take() {
+        if writer.cell_attribute(old_row, old_col) == shown {
+            writer.set_cell_attribute(old_row, old_col, original);
+        }
+    }
+    
+    let original = writer.cell_attribute(row, col);
+    let shown = inverted(original);
+    writer.set_cell_attribute(row, col, shown);
+    *drawn = Some((row, col, original, shown));
+}
+
+/// موضع المؤشر المرسوم (العمود، السطر)
+pub fn position() -> Option<(u16, u16)> {
+    DRAWN.lock().map(|(row, col, _, _)| (col as u16, row as u16))
+}
@@ -0,0 +1,352 @@
+//! 🩺 مراقبة صحة النظام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يقارن فحص الصحة الدوري عينة من حالة النظام بعتبات قابلة للضبط، ويُصدر
+//! تنبيهاً عند تغير مرحلة أي فحص فقط (لا في كل عينة):
+//!
+//! ```text
+//! health.memory=80,95       نسبة الذاكرة المستخدمة: تحذير، حرج
+//! health.cpu=90,99          نسبة انشغال المعالج
+//! health.processes=256,1024 عدد المهام النشطة
+//! health.loop=0.1           أدنى نسبة لدورات الحلقة الرئيسية إلى تكات المؤقت
+//! health.watchdog=2000      مهلة المراقب بالميلي ثانية (off للتعطيل)
+//! ```
+//!
+//! المراقب (watchdog) يُغذى من كل دورة للحلقة الرئيسية ويُفحص من مقاطعة المؤقت،
+//! فيكشف توقف الحلقة حتى وهي لا تعمل. التنبيهات تُسجل في سجل التدقيق ثم تُرسل
+//! إلى المشتركين (حارس إسلام ومنطقة الإشعارات).
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use log::{error, info, warn};
+use spin::Mutex;
+
+use crate::boot::KernelParams;
+use crate::process::timer;
+use crate::{arch, audit};
+
+/// مهلة المراقب الافتراضية بالميلي ثانية
+pub const DEFAULT_WATCHDOG_MS: u64 = 2000;
+
+/// شدة نتيجة الفحص
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Ok => "ok",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// الفحوص المعروفة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Memory,
+    Cpu,
+    Processes,
+    Uptime,
+    Watchdog,
+}
+
+impl Check {
+    pub const ALL: [Check; 5] = [Check::Memory, Check::Cpu, Check::Processes, Check::Uptime, Check::Watchdog];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Check::Memory => "memory",
+            Check::Cpu => "cpu",
+            Check::Processes => "processes",
+            Check::Uptime => "uptime",
+            Check::Watchdog => "watchdog",
+        }
+    }
+}
+
+/// عتبتا فحص قيمته الأعلى هي الأسوأ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub warning: f32,
+    pub critical: f32,
+}
+
+impl Limits {
+    pub const fn new(warning: f32, critical: f32) -> Self {
+        Self { warning, critical }
+    }
+    
+    pub fn classify(&self, value: f32) -> Severity {
+        if value >= self.critical {
+            Severity::Critical
+        } else if value >= self.warning {
+            Severity::Warning
+        } else {
+            Severity::Ok
+        }
+    }
+    
+    /// من `تحذير,حرج`
+    fn parse(value: &str) -> Option<Self> {
+        let (warning, critical) = value.split_once(',')?;
+        Some(Self::new(warning.trim().parse().ok()?, critical.trim().parse().ok()?))
+    }
+}
+
+/// عتبات الفحوص
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thresholds {
+    pub memory: Limits,
+    pub cpu: Limits,
+    pub processes: Limits,
+    /// أدنى نسبة لدورات الحلقة الرئيسية إلى تكات المؤقت بين فحصين
+    pub min_loop_ratio: f32,
+    /// مهلة المراقب، أو None للتعطيل
+    pub watchdog_ms: Option<u64>,
+}
+
+impl Thresholds {
+    /// من `health.*`؛ القيم غير المفهومة تُبقي الافتراضي
+    pub fn from_params(params: &KernelParams) -> Self {
+        let mut thresholds = Self::default();
+        let limits = |key: &str, default: Limits| params.get(key).and_then(Limits::parse).unwrap_or(default);
+        
+        thresholds.memory = limits("health.memory", thresholds.memory);
+        thresholds.cpu = limits("health.cpu", thresholds.cpu);
+        thresholds.processes = limits("health.processes", thresholds.processes);
+        if let Some(ratio) = params.get("health.loop").and_then(|value| value.parse().ok()) {
+            thresholds.min_loop_ratio = ratio;
+        }
+        thresholds.watchdog_ms = match params.get("health.watchdog") {
+            Some("off") | Some("0") => None,
+            Some(value) => Some(value.parse().unwrap_or(DEFAULT_WATCHDOG_MS)),
+            None => Some(DEFAULT_WATCHDOG_MS),
+        };
+        thresholds
+    }
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            memory: Limits::new(80.0, 95.0),
+            cpu: Limits::new(90.0, 99.0),
+            processes: Limits::new(256.0, 1024.0),
+            min_loop_ratio: 0.1,
+            watchdog_ms: Some(DEFAULT_WATCHDOG_MS),
+        }
+    }
+}
+
+/// عينة من حالة النظام
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sample {
+    pub memory_percent: f32,
+    pub cpu_percent: f32,
+    pub processes: u32,
+    /// دورات الحلقة الرئيسية منذ الإقلاع
+    pub loop_iterations: u64,
+    /// تكات المؤقت منذ الإقلاع
+    pub timer_ticks: u64,
+}
+
+/// تغير في مرحلة فحص
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub check: Check,
+    pub severity: Severity,
+    pub previous: Severity,
+    pub message: String,
+}
+
+/// حالة الفحوص بين العينات
+#[derive(Debug, Clone)]
+pub struct HealthMonitor {
+    pub thresholds: Thresholds,
+    status: [Severity; 5],
+    last: Option<Sample>,
+}
+
+impl HealthMonitor {
+    pub fn new(thresholds: Thresholds) -> Self {
+        Self { thresholds, status: [Severity::Ok; 5], last: None }
+    }
+    
+    pub fn status(&self, check: Check) -> Severity {
+        self.status[check as usize]
+    }
+    
+    /// تقييم عينة وإعادة تنبيه لكل فحص تغيرت مرحلته
+    pub fn evaluate(&mut self, sample: Sample) -> Vec<Alert> {
+        let thresholds = &self.thresholds;
+        let mut results = Vec::with_capacity(4);
+        
+        results.push((Check::Memory, thresholds.memory.classify(sample.memory_percent),
+            format!("الذاكرة {:.1}% (تحذير {}%، حرج {}%)", sample.memory_percent, thresholds.memory.warning, thresholds.memory.critical)));
+        results.push((Check::Cpu, thresholds.cpu.classify(sample.cpu_percent),
+            format!("المعالج {:.1}% (تحذير {}%، حرج {}%)", sample.cpu_percent, thresholds.cpu.warning, thresholds.cpu.critical)));
+        results.push((Check::Processes, thresholds.processes.classify(sample.processes as f32),
+            format!("{} مهمة نشطة (تحذير {}، حرج {})", sample.processes, thresholds.processes.warning, thresholds.processes.critical)));
+        if let Some(last) = self.last {
+            results.push(uptime_check(&last, &sample, thresholds.min_loop_ratio));
+        }
+        self.last = Some(sample);
+        
+        results.into_iter().filter_map(|(check, severity, message)| self.transition(check, severity, message)).collect()
+    }
+    
+    fn transition(&mut self, check: Check, severity: Severity, message: String) -> Option<Alert> {
+        let previous = core::mem::replace(&mut self.status[check as usize], severity);
+        (previous != severity).then_some(Alert { check, severity, previous, message })
+    }
+}
+
+/// وقت التشغيل لا يرجع للخلف، والحلقة الرئيسية تدور بقدر معقول من تكات المؤقت
+fn uptime_check(last: &Sample, sample: &Sample, min_ratio: f32) -> (Check, Severity, String) {
+    if sample.loop_iterations < last.loop_iterations || sample.timer_ticks < last.timer_ticks {
+        return (Check::Uptime, Severity::Critical, format!(
+            "رجع وقت التشغيل للخلف (الحلقة {} ← {}، المؤقت {} ← {})",
+            last.loop_iterations, sample.loop_iterations, last.timer_ticks, sample.timer_ticks,
+        ));
+    }
+    
+    let iterations = sample.loop_iterations - last.loop_iterations;
+    let ticks = sample.timer_ticks - last.timer_ticks;
+    let ratio = if ticks == 0 { 1.0 } else { iterations as f32 / ticks as f32 };
+    let severity = if ratio < min_ratio { Severity::Warning } else { Severity::Ok };
+    (Check::Uptime, severity, format!("{} دورة للحلقة الرئيسية في {} تكة (الحد الأدنى {:.2})", iterations, ticks, min_ratio))
+}
+
+/// مراقب توقف الحلقة الرئيسية؛ الأزمنة بالميلي ثانية
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Watchdog {
+    pub timeout_ms: Option<u64>,
+    last_pet: Option<u64>,
+    stalled_since: Option<u64>,
+}
+
+impl Watchdog {
+    pub const fn new(timeout_ms: Option<u64>) -> Self {
+        Self { timeout_ms, last_pet: None, stalled_since: None }
+    }
+    
+    /// تغذية من الحلقة الرئيسية (أول تغذية تُسلّح المراقب)؛ تعيد مدة التوقف إن كان
+    pub fn pet(&mut self, now: u64) -> Option<u64> {
+        let last = self.last_pet.replace(now);
+        self.stalled_since.take().map(|_| now.saturating_sub(last.unwrap_or(now)))
+    }
+    
+    /// فحص من مقاطعة المؤقت؛ true عند اكتشاف توقف جديد
+    pub fn check(&mut self, now: u64) -> bool {
+        let (Some(timeout), Some(last)) = (self.timeout_ms, self.last_pet) else {
+            return false;
+        };
+        if self.stalled_since.is_some() || now.saturating_sub(last) <= timeout {
+            return false;
+        }
+        self.stalled_since = Some(last);
+        true
+    }
+    
+    pub fn is_stalled(&self) -> bool {
+        self.stalled_since.is_some()
+    }
+}
+
+/// مشترك في التنبيهات
+pub type AlertFn = fn(&Alert);
+
+static MONITOR: Mutex<Option<HealthMonitor>> = Mutex::new(None);
+static WATCHDOG: Mutex<Watchdog> = Mutex::new(Watchdog::new(None));
+static SUBSCRIBERS: Mutex<Vec<(&'static str, AlertFn)>> = Mutex::new(Vec::new());
+
+/// ضبط العتبات والمراقب من سطر الأوامر
+pub fn init(params: &KernelParams) {
+    let thresholds = Thresholds::from_params(params);
+    info!("🩺 مراقبة الصحة: الذاكرة {:?}، المعالج {:?}، المراقب {:?} م.ث",
+        thresholds.memory, thresholds.cpu, thresholds.watchdog_ms);
+    
+    arch::interrupts::without_interrupts(|| WATCHDOG.lock().timeout_ms = thresholds.watchdog_ms);
+    *MONITOR.lock() = Some(HealthMonitor::new(thresholds));
+}
+
+/// الاشتراك في التنبيهات؛ الاشتراك بالاسم نفسه يستبدل السابق
+pub fn subscribe(name: &'static str, alert: AlertFn) {
+    let mut subscribers = SUBSCRIBERS.lock();
+    subscribers.retain(|&(existing, _)| existing != name);
+    subscribers.push((name, alert));
+}
+
+/// فحص عينة وإرسال تنبيهات الفحوص التي تغيرت
+pub fn check(sample: Sample) -> Vec<Alert> {
+    let alerts = match MONITOR.lock().as_mut() {
+        Some(monitor) => monitor.evaluate(sample),
+        None => return Vec::new(),
+    };
+    for alert in &alerts {
+        dispatch(alert);
+    }
+    alerts
+}
+
+/// مرحلة كل فحص
+pub fn status() -> Vec<(Check, Severity)> {
+    let monitor = MONITOR.lock();
+    let watchdog = arch::interrupts::without_interrupts(|| WATCHDOG.lock().is_stalled());
+    Check::ALL.iter().map(|&check| {
+        let severity = match (check, monitor.as_ref()) {
+            (Check::Watchdog, _) if watchdog => Severity::Critical,
+            (_, Some(monitor)) => monitor.status(check),
+            (_, None) => Severity::Ok,
+        };
+        (check, severity)
+    }).collect()
+}
+
+/// تغذية المراقب من الحلقة الرئيسية؛ يُبلغ عن التوقف السابق إن وُجد
+pub fn pet() {
+    let now = timer::uptime_ms();
+    let stalled = arch::interrupts::without_interrupts(|| WATCHDOG.lock().pet(now));
+    if let Some(duration) = stalled {
+        dispatch(&Alert {
+            check: Check::Watchdog,
+            severity: Severity::Critical,
+            previous: Severity::Ok,
+            message: format!("توقفت الحلقة الرئيسية {} م.ث ثم عادت", duration),
+        });
+    }
+}
+
+/// يُستدعى من معالج مقاطعة المؤقت (المقاطعات معطلة)
+pub fn watchdog_tick() {
+    let now = timer::uptime_ms();
+    let stalled = WATCHDOG.lock().check(now);
+    if stalled {
+        // لا أقفال أخرى هنا: الحلقة المتوقفة قد تحتجزها، والتنبيه الكامل عند عودتها
+        error!("🐕 المراقب: الحلقة الرئيسية متوقفة منذ أكثر من {:?} م.ث", WATCHDOG.lock().timeout_ms);
+    }
+}
+
+fn dispatch(alert: &Alert) {
+    match alert.severity {
+        Severity::Ok => info!("🩺 {}: عاد طبيعياً ({})", alert.check.name(), alert.message),
+        Severity::Warning => warn!("🩺 {}: {}", alert.check.name(), alert.message),
+        Severity::Critical => error!("🩺 {}: {}", alert.check.name(), alert.message),
+    }
+    audit::record("health", format_args!(
+        "{} {} -> {}: {}", alert.check.name(), alert.previous.name(), alert.severity.name(), alert.message,
+    ));
+    
+    // نسخة من القائمة: المشترك قد يشترك أو يطلق فحصاً
+    let subscribers = SUBSCRIBERS.lock().clone();
+    for (_, subscriber) in subscribers {
+        subscriber(alert);
+    }
+}
@@ -0,0 +1,117 @@
+//! 🖥️ وضع المحاكاة المستضافة لنواة نظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يشغّل تسلسل الإقلاع والحلقة الرئيسية كعملية لينكس عادية فوق واجهات
+//! خلفية وهمية لـ `arch` و `drivers` و `memory`، ويُفعّل بالميزة `hosted`:
+//!
+//! ```text
+//! cargo run -p kernel --features hosted --bin islam_hosted -- 5000
+//! cargo test -p kernel --features hosted
+//! ```
+
+use alloc::boxed::Box;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use log::info;
+
+use crate::boot::multiboot2::{builder::InfoBuilder, BOOTLOADER_MAGIC};
+
+/// خريطة الذاكرة المحاكاة (128 ميجابايت كذاكرة QEMU الافتراضية)
+const HOSTED_MEMORY_MAP: [(u64, u64, u32); 3] = [
+    (0, 0x9_F000, 1),
+    (0xF_0000, 0x1_0000, 2),
+    (0x10_0000, 0x7F0_0000, 1),
+];
+
+/// هل اكتمل الإقلاع المحاكى؟
+static BOOTED: AtomicBool = AtomicBool::new(false);
+
+/// عداد تكات الحلقة الرئيسية المحاكاة
+static TICK_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// تشغيل تسلسل الإقلاع نفسه الذي ينفذه `_start` مرة واحدة فقط
+pub fn boot() {
+    boot_with_command_line("/boot/kernel.bin");
+}
+
+/// الإقلاع ببنية Multiboot2 محاكاة تحمل سطر الأوامر المعطى
+pub fn boot_with_command_line(command_line: &str) {
+    if BOOTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    
+    // تبقى البنية حية طوال عمر العملية كما تبقى ذاكرة GRUB محجوزة
+    let info: &'static [u8] = Box::leak(
+        InfoBuilder::new()
+            .command_line(command_line)
+            .memory_map(&HOSTED_MEMORY_MAP)
+            .rsdp(&crate::arch::acpi::builder::rsdp((5, 5)))
+            .build()
+            .into_boxed_slice(),
+    );
+    
+    // البرنامج الثابت المحاكى سلّم المسجلات للنظام (SCI_EN)
+    crate::arch::acpi::io::write_u16(crate::arch::acpi::builder::PM1A_CONTROL, 1);
+    
+    crate::early_init(BOOTLOADER_MAGIC, info.as_ptr() as usize);
+    crate::display_startup_banner();
+    crate::full_system_init();
+    
+    info!("🖥️ اكتمل الإقلاع في وضع المحاكاة المستضافة");
+}
+
+/// التشغيل من جديد بعد إغلاق محاكى: طلبات الإطفاء وتقريرها، ووضع القراءة
+/// فقط، وسجل التوكنات تعود كما يجدها الإقلاع التالي
+pub fn power_on() {
+    crate::arch::cpu::clear_requests();
+    crate::arch::acpi::io::write_u16(crate::arch::acpi::builder::PM1A_CONTROL, 1);
+    crate::power::reset();
+    crate::net::reset();
+    crate::fs::set_read_only(crate::boot::mode() == crate::boot::BootMode::Recovery);
+    crate::open_token_ledger();
+    crate::TOKEN_MANAGER.lock().check_tokens();
+    
+    info!("🖥️ أعيد تشغيل المحاكاة");
+}
+
+/// تشغيل عدد محدد من تكات الحلقة الرئيسية
+pub fn run_ticks(count: u64) {
+    for _ in 0..count {
+        let tick = TICK_COUNTER.fetch_add(1, Ordering::SeqCst);
+        crate::system_tick();
+        crate::arch::interrupts::wait_for_interrupt();
+        
+        if crate::arch::cpu::shutdown_requested() {
+            info!("🛑 توقفت المحاكاة عند التكة {} بسبب طلب الإغلاق", tick);
+            break;
+        }
+    }
+}
+
+/// الإقلاع ثم تشغيل الحلقة الرئيسية لعدد محدد من التكات
+pub fn run(ticks: u64) {
+    boot();
+    run_ticks(ticks);
+}
+
+/// عدد التكات التي نُفذت منذ بدء المحاكاة
+pub fn ticks() -> u64 {
+    TICK_COUNTER.load(Ordering::SeqCst)
+}
+
+/// ملخص حالة النظام بعد المحاكاة
+pub fn report() -> alloc::string::String {
+    let state = crate::SYSTEM_STATE.lock();
+    let tokens = crate::TOKEN_MANAGER.lock();
+    
+    alloc::format!(
+        "🕒 التكات: {}\n💾 الذاكرة: {}/{} ({:.1}%)\n🔄 العمليات: {}\n💰 {}: {}{}\n",
+        state.uptime_ticks,
+        state.memory_usage.used,
+        state.memory_usage.total,
+        state.memory_usage.percent(),
+        state.active_processes,
+        crate::TOKEN_NAME,
+        tokens.current_tokens,
+        if tokens.is_locked { " (مغلق)" } else { "" },
+    )
+}
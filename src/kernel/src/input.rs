@@ -0,0 +1,184 @@
+//! ⌨️ طابور أحداث الإدخال
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! التعريفات تضع أحداثها هنا من الحلقة الرئيسية (لا من المقاطعات)، ولكل قارئ
+//! موضعه الخاص فيرى مدير النوافذ والصدفة الأحداث نفسها دون أن يسرقها أحدهما:
+//!
+//! ```text
+//! IRQ1  ──رموز المسح──▶ keyboard::process_pending ──push──▶ [طابور دائري 256]
+//! IRQ12 ──بايتات الحزم──▶ mouse::process_pending ────push──▶   ├──▶ Reader (الواجهة)
+//!                                                             └──▶ Reader (الصدفة)
+//! ```
+//!
+//! القارئ المتأخر بأكثر من سعة الطابور يفقد الأقدم ويكمل من أول المتاح.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+/// سعة الطابور
+pub const CAPACITY: usize = 256;
+
+/// تخطيط لوحة المفاتيح
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// الإنجليزي الأمريكي (104)
+    Us,
+    /// العربي (101)
+    Arabic,
+}
+
+impl Layout {
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Us => "us",
+            Layout::Arabic => "ar",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "us" | "en" => Some(Layout::Us),
+            "ar" | "arabic" => Some(Layout::Arabic),
+            _ => None,
+        }
+    }
+}
+
+/// مفاتيح لا تنتج حرفاً
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecialKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Function(u8),
+    Other,
+}
+
+/// مفتاح مضغوط
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// حرف بحسب التخطيط الحالي (Enter هو '\n' و Backspace هو '\x08')
+    Char(char),
+    Special(SpecialKey),
+}
+
+/// زر الفأرة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// ما فعلته الفأرة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEvent {
+    /// حركة بالنقاط (الموجب يميناً وإلى الأسفل)
+    Move { dx: i16, dy: i16 },
+    Button { button: MouseButton, pressed: bool },
+    /// دوران العجلة (الموجب نحو المستخدم)
+    Scroll(i8),
+}
+
+/// حدث إدخال
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    Key { key: Key, layout: Layout },
+    LayoutChanged(Layout),
+    /// حدث فأرة وموضع المؤشر بعده بخلايا الشاشة النصية
+    Mouse { event: MouseEvent, column: u16, row: u16 },
+}
+
+/// طابور دائري بأرقام تسلسلية
+pub struct EventQueue {
+    events: VecDeque<InputEvent>,
+    /// رقم الحدث التالي
+    next: u64,
+}
+
+impl EventQueue {
+    pub const fn new() -> Self {
+        Self { events: VecDeque::new(), next: 0 }
+    }
+    
+    pub fn push(&mut self, event: InputEvent) {
+        if self.events.len() == CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+        self.next += 1;
+    }
+    
+    /// الأحداث من `position` حتى النهاية، ونقل الموضع إلى النهاية
+    pub fn read(&self, position: &mut u64) -> Vec<InputEvent> {
+        let first = self.next - self.events.len() as u64;
+        let start = (*position).max(first);
+        *position = self.next;
+        self.events.iter().skip((start - first) as usize).copied().collect()
+    }
+    
+    /// موضع نهاية الطابور
+    pub fn end(&self) -> u64 {
+        self.next
+    }
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static QUEUE: Mutex<EventQueue> = Mutex::new(EventQueue::new());
+
+/// إضافة حدث
+pub fn push(event: InputEvent) {
+    QUEUE.lock().push(event);
+}
+
+/// قارئ بموضع مستقل
+pub struct Reader {
+    position: u64,
+}
+
+impl Reader {
+    /// الأحداث الجديدة منذ القراءة السابقة
+    pub fn poll(&mut self) -> Vec<InputEvent> {
+        QUEUE.lock().read(&mut self.position)
+    }
+}
+
+/// قارئ جديد يبدأ من الأحداث التالية فقط
+pub fn reader() -> Reader {
+    Reader { position: QUEUE.lock().end() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_event_queue_readers() {
+        // لكل قارئ موضعه، والمتأخر بأكثر من السعة يكمل من أقدم المتاح
+        let event = InputEvent::Key { key: Key::Char('a'), layout: Layout::Us };
+        let mut queue = EventQueue::new();
+        let (mut first, mut second) = (queue.end(), queue.end());
+        queue.push(event);
+        queue.push(InputEvent::LayoutChanged(Layout::Arabic));
+        assert_eq!(queue.read(&mut first), [event, InputEvent::LayoutChanged(Layout::Arabic)]);
+        assert!(queue.read(&mut first).is_empty());
+        for _ in 0..CAPACITY {
+            queue.push(event);
+        }
+        assert_eq!(queue.read(&mut second).len(), CAPACITY);
+        assert_eq!(second, first + CAPACITY as u64);
+    }
+}
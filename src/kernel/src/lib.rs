@@ -5,53 +5,82 @@
 //! البريد: islamrenewaltech@gmail.com
 //! الهواتف: +201556328989, +201508599689
 
-#![no_std]
-#![no_main]
-#![feature(alloc_error_handler)]
-#![feature(panic_info_message)]
-#![feature(asm_const)]
-#![feature(const_mut_refs)]
-#![feature(naked_functions)]
+#![cfg_attr(not(feature = "hosted"), no_std)]
+#![cfg_attr(not(feature = "hosted"), no_main)]
+#![cfg_attr(not(feature = "hosted"), feature(alloc_error_handler))]
+#![cfg_attr(not(feature = "hosted"), feature(abi_x86_interrupt))]
 #![feature(custom_test_frameworks)]
-#![test_runner(crate::test_runner)]
-#![reexport_test_harness_main = "test_main"]
+#![test_runner(crate::tests::test_runner)]
+#![cfg_attr(not(feature = "hosted"), reexport_test_harness_main = "test_main")]
 
 // وحدات النظام
 pub mod arch;
+pub mod boot;
 pub mod memory;
 pub mod drivers;
 pub mod process;
 pub mod fs;
 pub mod net;
+pub mod recovery;
+pub mod accounts;
+pub mod audit;
+pub mod crash;
+pub mod backtrace;
+pub mod dmesg;
+pub mod logger;
 pub mod gui;
-pub mod syscall;
+pub mod health;
+pub mod input;
+pub mod power;
+pub mod tokens;
 pub mod utils;
 
+// وضع المحاكاة المستضافة (عملية لينكس عادية)
+#[cfg(feature = "hosted")]
+pub mod hosted;
+
 extern crate alloc;
 
+#[cfg(not(feature = "hosted"))]
 use core::panic::PanicInfo;
+#[cfg(not(feature = "hosted"))]
 use core::alloc::Layout;
 use log::{error, info, warn, debug};
 use spin::Mutex;
 use lazy_static::lazy_static;
-use alloc::boxed::Box;
-
-// معلومات النظام الثابتة
-const SYSTEM_NAME: &str = "نظام تشغيل إسلام";
-const SYSTEM_VERSION: &str = "0.1.0";
-const DEVELOPER: &str = "إسلام بن الحسن - Islam Bin El-Hassan (I-H)";
-const COMPANY: &str = "شركة إسلام لتجديد العلوم والتقنيات المستقبلية إسلام-إنسان";
-const COMPANY_EN: &str = "Islam for Scientific Renewal and Future Technologies - Islam-Insan";
-const ADDRESS: &str = "الحي الإفرنجي، مدينة الإسماعيلية، محافظة الإسماعيلية، مصر";
-const ADDRESS_EN: &str = "El-Afrangi District, Ismailia City, Ismailia Governorate, Arab Republic of Egypt";
-const EMAIL: &str = "islamrenewaltech@gmail.com";
-const PHONES: &str = "+201556328989, +201508599689";
-const GITHUB: &str = "https://github.com/ai1islam1411";
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::vec;
+
+use accounts::User;
+
+// معلومات النظام الثابتة، عامة لمن يعرضها من التطبيقات
+pub const SYSTEM_NAME: &str = "نظام تشغيل إسلام";
+pub const SYSTEM_VERSION: &str = "0.1.0";
+pub const DEVELOPER: &str = "إسلام بن الحسن - Islam Bin El-Hassan (I-H)";
+pub const COMPANY: &str = "شركة إسلام لتجديد العلوم والتقنيات المستقبلية إسلام-إنسان";
+pub const COMPANY_EN: &str = "Islam for Scientific Renewal and Future Technologies - Islam-Insan";
+pub const ADDRESS: &str = "الحي الإفرنجي، مدينة الإسماعيلية، محافظة الإسماعيلية، مصر";
+pub const ADDRESS_EN: &str = "El-Afrangi District, Ismailia City, Ismailia Governorate, Arab Republic of Egypt";
+pub const EMAIL: &str = "islamrenewaltech@gmail.com";
+pub const PHONES: &str = "+201556328989, +201508599689";
+pub const GITHUB: &str = "https://github.com/ai1islam1411";
 const CONTRACT_ADDRESS: &str = "0xa23D57f128Df2517517CA0c195C5159d81324711";
 const TOKEN_NAME: &str = "INSAN";
 const MIN_TOKENS: u32 = 100;
-const FOUNDATION_YEAR: u32 = 2024;
-const HIJRI_YEAR: u32 = 1448;
+/// رصيد سجل الذاكرة الافتراضي: بدء بأكثر من الحد الأدنى
+const DEFAULT_TOKENS: u64 = MIN_TOKENS as u64 + 50;
+pub const FOUNDATION_YEAR: u32 = 2024;
+pub const HIJRI_YEAR: u32 = 1448;
+
+// فترات المهام الدورية بالميلي ثانية
+const SECURITY_INTERVAL_MS: u64 = 100;
+const AI_INTERVAL_MS: u64 = 50;
+const HEALTH_INTERVAL_MS: u64 = 500;
+const TOKEN_CHECK_INTERVAL_MS: u64 = 1000;
+const NET_POLL_INTERVAL_MS: u64 = 10;
 
 // حالة النظام العالمية
 lazy_static! {
@@ -96,25 +125,80 @@ struct TokenManager {
     current_tokens: u32,
     is_locked: bool,
     last_check: u64,
+    /// مصدر الرصيد الحقيقي
+    ledger: Arc<dyn tokens::TokenLedger>,
+    /// قُرئ رصيد من السجل الحالي مرة على الأقل
+    balance_known: bool,
+    /// أول قراءة من السجل ما زالت في الطريق (سجل الشبكة)
+    balance_pending: bool,
+    wallet: String,
+    /// خصم أو إضافة لم يقبلها سجل للقراءة فقط
+    local_adjustment: i64,
+    /// مراحل الرصيد المنخفض
+    policy: tokens::TokenPolicy,
 }
 
 impl TokenManager {
     fn new() -> Self {
+        // سجل في الذاكرة حتى تُركَّب أنظمة الملفات ويُفتح السجل المطلوب
         TokenManager {
             contract_address: CONTRACT_ADDRESS,
             min_tokens: MIN_TOKENS,
-            current_tokens: MIN_TOKENS + 50, // بدء بأكثر من الحد الأدنى
+            current_tokens: DEFAULT_TOKENS as u32,
             is_locked: false,
             last_check: 0,
+            ledger: Arc::new(tokens::MemoryLedger::with_balance(tokens::DEFAULT_WALLET, DEFAULT_TOKENS)),
+            balance_known: true,
+            balance_pending: false,
+            wallet: String::from(tokens::DEFAULT_WALLET),
+            local_adjustment: 0,
+            policy: tokens::TokenPolicy::default(),
         }
     }
     
+    /// استبدال السجل والمحفظة؛ الرصيد صفر حتى يُقرأ من السجل الجديد
+    fn attach(&mut self, ledger: Arc<dyn tokens::TokenLedger>, wallet: String) {
+        self.ledger = ledger;
+        self.wallet = wallet;
+        self.local_adjustment = 0;
+        self.current_tokens = 0;
+        self.balance_known = false;
+        self.balance_pending = false;
+    }
+    
+    fn effective(&self, balance: u64) -> u32 {
+        let balance = (balance as i128 + self.local_adjustment as i128).max(0);
+        balance.min(u32::MAX as i128) as u32
+    }
+    
+    /// قراءة الرصيد من السجل؛ عند تعذرها يبقى آخر رصيد معروف
+    fn refresh(&mut self) -> Result<u32, tokens::LedgerError> {
+        let balance = self.ledger.balance(&self.wallet)?;
+        self.current_tokens = self.effective(balance);
+        self.balance_known = true;
+        self.last_check = process::scheduler::try_ticks().unwrap_or(self.last_check);
+        Ok(self.current_tokens)
+    }
+    
     fn check_tokens(&mut self) -> bool {
-        // في النسخة الحقيقية، هنا يتم التحقق من العقد الذكي
+        let refreshed = self.refresh();
+        self.balance_pending = !self.balance_known && refreshed == Err(tokens::LedgerError::Pending);
+        match refreshed {
+            Ok(_) | Err(tokens::LedgerError::Pending) => {}
+            Err(e) => warn!("⚠️ تعذرت قراءة رصيد {} من السجل {}: {:?}", TOKEN_NAME, self.ledger.name(), e),
+        }
+        
+        // سجل لم يُقرأ منه رصيد بعد لا يُفترض فيه رصيد
+        if !self.balance_known {
+            self.is_locked = true;
+            warn!("⚠️ لم يُقرأ رصيد {} من السجل {} بعد", TOKEN_NAME, self.ledger.name());
+            return false;
+        }
+        
         if self.current_tokens < self.min_tokens {
             self.is_locked = true;
-            error!("🚨 النظام مغلق! الرصيد أقل من {} {}", self.min_tokens, TOKEN_NAME);
-            error!("💳 يرجى شراء المزيد من {} من العقد: {}", TOKEN_NAME, self.contract_address);
+            warn!("⚠️ رصيد {} ({}) أقل من الحد الأدنى {}", TOKEN_NAME, self.current_tokens, self.min_tokens);
+            warn!("💳 يرجى شراء المزيد من {} من العقد: {}", TOKEN_NAME, self.contract_address);
             false
         } else {
             self.is_locked = false;
@@ -123,52 +207,81 @@ impl TokenManager {
     }
     
     fn use_token(&mut self, amount: u32) -> bool {
-        if self.current_tokens >= amount && !self.is_locked {
-            self.current_tokens -= amount;
-            true
-        } else {
-            false
+        if self.current_tokens < amount || self.is_locked {
+            return false;
+        }
+        
+        match self.ledger.debit(&self.wallet, amount as u64) {
+            Ok(balance) => self.current_tokens = self.effective(balance),
+            Err(tokens::LedgerError::ReadOnly) => {
+                self.local_adjustment -= amount as i64;
+                self.current_tokens -= amount;
+            }
+            Err(e) => {
+                warn!("⚠️ رفض السجل {} خصم {} {}: {:?}", self.ledger.name(), amount, TOKEN_NAME, e);
+                return false;
+            }
         }
+        true
     }
     
+    #[allow(dead_code)]
     fn add_tokens(&mut self, amount: u32) {
-        self.current_tokens += amount;
+        match self.ledger.credit(&self.wallet, amount as u64) {
+            Ok(balance) => self.current_tokens = self.effective(balance),
+            Err(e) => {
+                warn!("⚠️ السجل {} لا يقبل الإضافة ({:?})، تُحسب محلياً", self.ledger.name(), e);
+                self.local_adjustment += amount as i64;
+                self.current_tokens = self.current_tokens.saturating_add(amount);
+            }
+        }
         info!("💰 تم إضافة {} {}، الرصيد الحالي: {}", amount, TOKEN_NAME, self.current_tokens);
     }
 }
 
 /// نقطة دخول النواة الرئيسية
+/// (`boot_entry` في `arch/x86_64/boot.rs` ينقل EAX/EBX من GRUB إلى EDI/ESI قبل الاستدعاء)
+#[cfg(not(feature = "hosted"))]
 #[no_mangle]
-pub extern "C" fn _start() -> ! {
+pub extern "C" fn _start(multiboot_magic: u32, multiboot_info: usize) -> ! {
     // تهيئة النظام الأساسية
-    early_init();
+    early_init(multiboot_magic, multiboot_info);
     
     // عرض بانر النظام
     display_startup_banner();
     
-    // التحقق من التوكنات
-    check_token_requirement();
-    
-    // تهيئة كاملة للنظام
+    // تهيئة كاملة للنظام (تشمل التحقق من التوكنات بعد تركيب أنظمة الملفات)
     full_system_init();
     
-    // تشغيل الحلقة الرئيسية
-    main_loop();
+    // تشغيل الحلقة الرئيسية أو وحدة تحكم الاسترداد
+    match boot::mode() {
+        boot::BootMode::Normal => main_loop(),
+        boot::BootMode::Recovery => recovery::run(),
+    }
 }
 
 /// التهيئة المبكرة
-fn early_init() {
+fn early_init(multiboot_magic: u32, multiboot_info: usize) {
     // تعطيل المقاطعات
     unsafe {
         arch::interrupts::disable();
     }
     
+    // المسجل أولاً حتى لا يضيع شيء من رسائل الإقلاع
+    drivers::serial::init();
+    logger::init();
+    
     // تهيئة GDT و IDT
     arch::gdt::init();
     arch::idt::init();
+    arch::pit::init();
     
-    // تهيئة الذاكرة
-    memory::init();
+    // قراءة معلومات الإقلاع من GRUB
+    boot::init(multiboot_magic, multiboot_info);
+    logger::configure(boot::params());
+    
+    // تهيئة الذاكرة من خريطة البرنامج الثابت
+    memory::init(boot::memory_map(), boot::reserved_ranges());
     
     // تمكين المقاطعات
     unsafe {
@@ -209,24 +322,31 @@ fn display_startup_banner() {
     writer.print_centered("║    📞 +201556328989  📞 +201508599689                        ║", 19);
     writer.print_centered("║    📧 islamrenewaltech@gmail.com                             ║", 20);
     writer.print_centered("║    🐙 https://github.com/ai1islam1411                        ║", 21);
-    writer.print_centered("║    💎 {}: {}                        ║", 22, TOKEN_NAME, CONTRACT_ADDRESS);
+    writer.print_centered(&format!("║    💎 {}: {}                        ║", TOKEN_NAME, CONTRACT_ADDRESS), 22);
     writer.print_centered("║                                                              ║", 23);
     writer.print_centered("╚══════════════════════════════════════════════════════════════╝", 24);
     
     writer.set_foreground_color(Color::LightGray);
 }
 
-/// التحقق من متطلبات التوكن
-fn check_token_requirement() {
+/// فتح السجل والمحفظة والسياسة من سطر الأوامر
+fn open_token_ledger() {
+    let ledger = tokens::open(boot::params(), DEFAULT_TOKENS);
     let mut token_manager = TOKEN_MANAGER.lock();
-    
+    token_manager.attach(ledger, tokens::wallet(boot::params()));
+    token_manager.policy = tokens::TokenPolicy::from_params(boot::params());
+}
+
+/// التحقق من متطلبات التوكن عند الإقلاع
+fn check_token_requirement() {
     info!("🔐 التحقق من رصيد {}...", TOKEN_NAME);
     
-    if !token_manager.check_tokens() {
-        // النظام مغلق
-        panic!("🚨 النظام مغلق بسبب عدم كفاية رصيد {}!", TOKEN_NAME);
+    // الرصيد المنخفض يبدأ مهلة بدلاً من إيقاف الإقلاع
+    if !enforce_token_policy() {
+        return;
     }
     
+    let mut token_manager = TOKEN_MANAGER.lock();
     info!("✅ الرصيد الحالي: {} {}", token_manager.current_tokens, TOKEN_NAME);
     info!("📋 الحد الأدنى المطلوب: {} {}", MIN_TOKENS, TOKEN_NAME);
     
@@ -236,9 +356,83 @@ fn check_token_requirement() {
     }
 }
 
+/// فحص الرصيد وتطبيق سياسة الرصيد المنخفض؛ يعيد true إن كان الرصيد كافياً
+fn enforce_token_policy() -> bool {
+    use tokens::BalanceState;
+    
+    let now = process::timer::uptime_ms();
+    let (sufficient, transition, state, remaining, balance, min) = {
+        let mut token_manager = TOKEN_MANAGER.lock();
+        let sufficient = token_manager.check_tokens();
+        // لا حكم قبل أول رد من سجل الشبكة، فيُترك للفحص الدوري التالي
+        if token_manager.balance_pending {
+            return false;
+        }
+        let transition = token_manager.policy.evaluate(sufficient, now);
+        let policy = &token_manager.policy;
+        (sufficient, transition, policy.state(), policy.remaining(now), token_manager.current_tokens, token_manager.min_tokens)
+    };
+    
+    // الانتقالات تُنفذ خارج القفل: الإغلاق يمر بأنظمة كثيرة
+    if let Some(transition) = transition {
+        audit::record("tokens", format_args!(
+            "{} -> {} (الرصيد {}، الحد الأدنى {})",
+            transition.from.name(), transition.to.name(), balance, min,
+        ));
+        
+        match transition.to {
+            BalanceState::Normal => {
+                info!("✅ عاد رصيد {} كافياً", TOKEN_NAME);
+                if matches!(transition.from, BalanceState::Degraded { .. }) {
+                    fs::set_read_only(boot::mode() == boot::BootMode::Recovery);
+                }
+            }
+            BalanceState::Grace { .. } => warn!("⏳ بدأت مهلة الرصيد المنخفض"),
+            BalanceState::Degraded { .. } => {
+                error!("🔒 انتهت المهلة: وضع متدهور، أنظمة الملفات للقراءة فقط");
+                fs::set_read_only(true);
+            }
+            BalanceState::Shutdown => {
+                // الوضع المتدهور يمنع الكتابة، وحفظ ما قبل الإغلاق مستثنى منه
+                fs::set_read_only(boot::mode() == boot::BootMode::Recovery);
+                power::kernel_shutdown(power::PowerAction::PowerOff, "رصيد التوكنات غير كافٍ بعد انتهاء الوضع المتدهور");
+                return false;
+            }
+        }
+    }
+    
+    if state != BalanceState::Normal || transition.is_some() {
+        show_token_status(state, remaining, balance, min);
+    }
+    sufficient
+}
+
+/// شريط حالة الرصيد أعلى الشاشة، يُمسح عند عودة الرصيد
+fn show_token_status(state: tokens::BalanceState, remaining: Option<u64>, balance: u32, min: u32) {
+    use drivers::vga::{WRITER, Color, BUFFER_WIDTH};
+    
+    let text = match (state, remaining) {
+        (tokens::BalanceState::Normal, _) => String::new(),
+        (_, Some(ms)) => format!(" {} {}/{} - {} mode, {}s left", TOKEN_NAME, balance, min, state.name(), ms.div_ceil(1000)),
+        (_, None) => format!(" {} {}/{} - {} mode", TOKEN_NAME, balance, min, state.name()),
+    };
+    
+    let mut writer = WRITER.lock();
+    if text.is_empty() {
+        writer.set_color(Color::LightGray, Color::Black);
+    } else {
+        writer.set_color(Color::White, Color::Red);
+    }
+    writer.print_at(&format!("{:1$}", text, BUFFER_WIDTH), 0, 0);
+    writer.set_color(Color::LightGray, Color::Black);
+}
+
 /// التهيئة الكاملة للنظام
 fn full_system_init() {
     info!("🚀 بدء تهيئة نظام تشغيل إسلام...");
+    boot::log_summary();
+    
+    let recovery = boot::mode() == boot::BootMode::Recovery;
     
     // 1. تهيئة المعالج والعمارة
     info!("⚡ تهيئة المعالج...");
@@ -248,41 +442,79 @@ fn full_system_init() {
     info!("💾 تهيئة إدارة الذاكرة...");
     memory::advanced::init();
     
+    // جداول ACPI تحتاج ربط الذاكرة الفيزيائية
+    info!("⚡ قراءة جداول ACPI...");
+    arch::acpi::init();
+    
     // 3. تهيئة جميع التعريفات
     info!("🔌 تهيئة التعريفات...");
     drivers::init_all();
     
     // 4. تهيئة نظام الملفات
     info!("📁 تهيئة نظام الملفات...");
-    fs::init();
+    fs::init(if recovery { fs::MountMode::ReadOnly } else { fs::MountMode::ReadWrite });
+    register_proc_files();
+    accounts::init();
+    
+    // سجل التوكنات قد يكون ملفاً على القرص، فيُفتح بعد التركيب
+    info!("💎 فتح سجل التوكنات...");
+    open_token_ledger();
+    power::register("tokens", power::priority::PAYMENT, power::DEFAULT_BUDGET_TICKS, record_final_balance);
     
     // 5. تهيئة جدولة العمليات
     info!("⏱️ تهيئة جدولة العمليات...");
     process::scheduler::init();
     
+    // المعالجات التطبيقية تحتاج طابور الإقلاع وتكات PIT للانتظار
+    info!("🧵 تشغيل المعالجات الأخرى...");
+    arch::smp::init();
+    health::init(boot::params());
+    
     // 6. تهيئة نظام الشبكات
     info!("🌐 تهيئة الشبكات...");
     net::init();
     
+    // فحص الرصيد بعد الشبكة: سجل إيثيريوم يُقرأ عبرها
+    check_token_requirement();
+    
     // 7. تفعيل النظام الأمني
     info!("🛡️ تفعيل حارس إسلام...");
     security::haris_core::activate();
+    health::subscribe("haris", escalate_to_haris);
+    fs::procfs::register("security/firewall", proc_firewall);
+    fs::procfs::register("security/threat", proc_threat);
     
     // 8. تشغيل ذكاء إسلام
-    info!("🤖 تشغيل Zaka Islam...");
-    ai::zaka_core::start();
+    if recovery {
+        warn!("🧰 وضع الاسترداد: تخطي Zaka Islam");
+    } else {
+        info!("🤖 تشغيل Zaka Islam...");
+        ai::zaka_core::start();
+        power::register("ai", power::priority::AI, power::DEFAULT_BUDGET_TICKS, stop_zaka);
+    }
     
     // 9. تهيئة واجهة المستخدم
     info!("🎨 تهيئة واجهة المستخدم...");
     gui::init();
     
-    // 10. تحميل التطبيقات الأساسية
-    info!("📦 تحميل التطبيقات الأساسية...");
-    load_essential_apps();
+    // 10. تحميل التطبيقات الأساسية (ومنها نظام الدفع)
+    if recovery {
+        warn!("🧰 وضع الاسترداد: تخطي التطبيقات الأساسية ونظام الدفع");
+    } else {
+        info!("📦 تحميل التطبيقات الأساسية...");
+        load_essential_apps();
+    }
     
     // تحديث حالة النظام
     let mut state = SYSTEM_STATE.lock();
     state.is_initialized = true;
+    state.ai_enabled = !recovery;
+    drop(state);
+    
+    // وضع الاسترداد لا يدخل الحلقة الرئيسية ولا يحتاج المهام الدورية
+    if !recovery {
+        register_periodic_jobs();
+    }
     
     info!("✨ تم تهيئة النظام بنجاح!");
     info!("🕒 تاريخ الإصدار: 1448 هـ - 2024 م");
@@ -290,11 +522,77 @@ fn full_system_init() {
     info!("🏢 الشركة: {}", COMPANY);
 }
 
+/// ملفات /proc لحالة النواة
+fn register_proc_files() {
+    fs::procfs::register("system", proc_system);
+    fs::procfs::register("meminfo", proc_meminfo);
+    fs::procfs::register("cpu", proc_cpu);
+    fs::procfs::register("tokens", proc_tokens);
+}
+
+/// /proc/system
+fn proc_system() -> String {
+    let state = SYSTEM_STATE.lock();
+    format!(
+        "name={}\nversion={}\ninitialized={}\nuptime_ticks={}\nsecurity_level={:?}\nai_enabled={}\nnetwork_connected={}\nusers={}\nactive_processes={}\n",
+        SYSTEM_NAME, SYSTEM_VERSION, state.is_initialized, state.uptime_ticks, state.security_level,
+        state.ai_enabled, state.network_connected, state.users.len(), state.active_processes,
+    )
+}
+
+/// /proc/meminfo (بالبايت)
+fn proc_meminfo() -> String {
+    let memory = SYSTEM_STATE.lock().memory_usage;
+    format!(
+        "total={}\nused={}\nfree={}\ncached={}\npercent={:.1}\n",
+        memory.total, memory.used, memory.free, memory.cached, memory.percent(),
+    )
+}
+
+/// /proc/cpu
+fn proc_cpu() -> String {
+    let cpu = SYSTEM_STATE.lock().cpu_usage.clone();
+    let mut text = format!(
+        "usage={:.1}\nfrequency={}\ntemperature={:.1}\ncores={}\n",
+        cpu.usage, cpu.frequency, cpu.temperature, cpu.cores.len(),
+    );
+    if let Some(info) = arch::cpuinfo::info() {
+        text.push_str(&format!(
+            "vendor={}\nbrand={}\nfamily={}\nmodel={}\nstepping={}\nfeatures={}\n",
+            info.vendor, info.brand, info.family, info.model, info.stepping, info.features.join(" "),
+        ));
+    }
+    for (core, usage) in cpu.cores.iter().enumerate() {
+        text.push_str(&format!("cpu{}.usage={:.1}\n", core, usage));
+    }
+    text
+}
+
+/// /proc/tokens
+fn proc_tokens() -> String {
+    let tokens = TOKEN_MANAGER.lock();
+    format!(
+        "token={}\ncontract={}\nbalance={}\nmin={}\nlocked={}\nstate={}\nledger={}\nwallet={}\nlast_check={}\n",
+        TOKEN_NAME, tokens.contract_address, tokens.current_tokens, tokens.min_tokens, tokens.is_locked,
+        tokens.policy.state().name(), tokens.ledger.name(), tokens.wallet, tokens.last_check,
+    )
+}
+
+/// /proc/security/firewall: قواعد جدار الحماية بترتيب التطبيق
+fn proc_firewall() -> String {
+    security::haris_core::HARIS_SYSTEM.lock().firewall.ruleset()
+}
+
+/// /proc/security/threat
+fn proc_threat() -> String {
+    format!("{:?}\n", security::haris_core::HARIS_SYSTEM.lock().threat_level)
+}
+
 /// تحميل التطبيقات الأساسية
 fn load_essential_apps() {
     let apps = vec![
         "Islam Shell",
-        "Islam Browser", 
+        "Islam Browser",
         "Islam Video",
         "Islam Sound",
         "Islam Payment",
@@ -309,48 +607,60 @@ fn load_essential_apps() {
 }
 
 /// الحلقة الرئيسية للنظام
+#[cfg(not(feature = "hosted"))]
 fn main_loop() -> ! {
     info!("🔄 بدء الحلقة الرئيسية للنظام...");
     
-    let mut tick_counter: u64 = 0;
-    
     loop {
-        // تحديث حالة النظام
-        update_system_state();
-        
-        // جدولة العمليات
-        process::scheduler::run();
-        
-        // معالجة أحداث المدخلات
-        handle_input_events();
-        
-        // تحديث النظام الأمني
-        if tick_counter % 100 == 0 {
-            update_security_system();
-        }
-        
-        // تحديث الذكاء الاصطناعي
-        if tick_counter % 50 == 0 {
-            update_ai_system();
-        }
-        
-        // فحص صحة النظام
-        if tick_counter % 500 == 0 {
-            perform_health_check();
-        }
-        
-        // التحقق من التوكنات
-        if tick_counter % 1000 == 0 {
-            check_token_balance();
-        }
-        
-        tick_counter += 1;
+        system_tick();
         
         // السماح بالمقاطعات والانتظار
         arch::interrupts::wait_for_interrupt();
     }
 }
 
+/// تكة واحدة من الحلقة الرئيسية
+/// (مفصولة عن الحلقة ليتمكن وضع المحاكاة المستضافة من تشغيل عدد محدد من التكات)
+fn system_tick() {
+    // الحلقة الرئيسية حية
+    health::pet();
+    
+    // تحديث حالة النظام
+    update_system_state();
+    
+    // جدولة العمليات
+    process::scheduler::run();
+    
+    // معالجة أحداث المدخلات
+    handle_input_events();
+    
+    // زر الطاقة يبدأ إغلاقاً منظماً كأمر halt
+    if arch::acpi::take_power_button() {
+        power::kernel_shutdown(power::PowerAction::PowerOff, "ضغط زر الطاقة");
+    }
+    
+    // المهام الدورية التي انتهت مؤقتاتها منذ التكة السابقة
+    process::timer::run_pending();
+}
+
+/// فك رموز لوحة المفاتيح إلى طابور الإدخال ثم تمرير الجديد إلى الواجهة
+fn handle_input_events() {
+    drivers::keyboard::process_pending();
+    drivers::mouse::process_pending();
+    gui::handle_input();
+}
+
+/// تسجيل المهام الدورية للأنظمة الفرعية
+fn register_periodic_jobs() {
+    use process::timer;
+    
+    timer::every("security", SECURITY_INTERVAL_MS, update_security_system);
+    timer::every("ai", AI_INTERVAL_MS, update_ai_system);
+    timer::every("health", HEALTH_INTERVAL_MS, perform_health_check);
+    timer::every("tokens", TOKEN_CHECK_INTERVAL_MS, check_token_balance);
+    timer::every("net", NET_POLL_INTERVAL_MS, net::poll);
+}
+
 /// تحديث حالة النظام
 fn update_system_state() {
     let mut state = SYSTEM_STATE.lock();
@@ -368,6 +678,9 @@ fn update_system_state() {
 
 /// تحديث النظام الأمني
 fn update_security_system() {
+    // ساعة حارس تتبع زمن تشغيل النواة
+    security::time::set_current_timestamp(process::timer::uptime_ms() / 1000);
+    
     // تحديث جدار الحماية
     security::firewall::update();
     
@@ -390,63 +703,65 @@ fn update_ai_system() {
     ai::zaka_core::optimize();
 }
 
-/// فحص صحة النظام
+/// فحص صحة النظام: مقارنة الحالة بالعتبات وإرسال تنبيهات ما تغير
 fn perform_health_check() {
-    info!("🔍 فحص صحة النظام...");
-    
-    let state = SYSTEM_STATE.lock();
-    
-    info!("📊 حالة النظام:");
-    info!("  🕒 وقت التشغيل: {} تكت", state.uptime_ticks);
-    info!("  💾 الذاكرة: {}/{} ({:.1}%)", 
-        state.memory_usage.used, 
-        state.memory_usage.total,
-        state.memory_usage.percent());
-    info!("  ⚡ المعالج: {:.1}%", state.cpu_usage.usage);
-    info!("  🔄 العمليات النشطة: {}", state.active_processes);
-    info!("  🛡️ مستوى الأمن: {:?}", state.security_level);
-    info!("  🤖 الذكاء الاصطناعي: {}", if state.ai_enabled { "مفعل" } else { "معطل" });
-    info!("  🌐 الشبكة: {}", if state.network_connected { "متصل" } else { "غير متصل" });
-    
-    // فحص التوكنات
-    let token_manager = TOKEN_MANAGER.lock();
-    info!("  💰 {}: {} (الحد الأدنى: {})", 
-        TOKEN_NAME, 
-        token_manager.current_tokens,
-        token_manager.min_tokens);
-}
-
-/// التحقق من رصيد التوكنات
-fn check_token_balance() {
-    let mut token_manager = TOKEN_MANAGER.lock();
-    
-    if !token_manager.check_tokens() {
-        error!("🚨 رصيد {} غير كافٍ!", TOKEN_NAME);
-        error!("💳 الرصيد الحالي: {}، المطلوب: {}", 
-            token_manager.current_tokens, 
-            token_manager.min_tokens);
-        error!("🔗 يرجى إضافة {} إلى: {}", TOKEN_NAME, token_manager.contract_address);
+    let sample = {
+        let state = SYSTEM_STATE.lock();
+        debug!("🔍 فحص الصحة: الذاكرة {}/{} ({:.1}%)، المعالج {:.1}%، {} عملية، {} تكت",
+            state.memory_usage.used,
+            state.memory_usage.total,
+            state.memory_usage.percent(),
+            state.cpu_usage.usage,
+            state.active_processes,
+            state.uptime_ticks);
         
-        // إغلاق النظام تدريجياً
-        emergency_shutdown();
+        health::Sample {
+            memory_percent: state.memory_usage.percent(),
+            cpu_percent: state.cpu_usage.usage,
+            processes: state.active_processes,
+            loop_iterations: state.uptime_ticks,
+            timer_ticks: process::timer::ticks(),
+        }
+    };
+    
+    health::check(sample);
+}
+
+/// تصعيد تنبيهات الصحة إلى حارس إسلام (العودة للطبيعي لا تُصعّد)
+fn escalate_to_haris(alert: &health::Alert) {
+    if alert.severity == health::Severity::Ok {
+        return;
     }
+    security::haris_core::HARIS_SYSTEM.lock().report_health_alert(
+        alert.check.name(),
+        alert.severity == health::Severity::Critical,
+        &alert.message,
+    );
 }
 
-/// إغلاق الطوارئ
-fn emergency_shutdown() {
-    error!("🛑 بدء إغلاق الطوارئ...");
-    
-    // حفظ جميع البيانات
-    fs::emergency_save();
-    
-    // إغلاق جميع العمليات
-    process::scheduler::emergency_stop();
-    
-    // إيقاف النظام
-    arch::cpu::shutdown();
+/// التحقق الدوري من رصيد التوكنات
+fn check_token_balance() {
+    enforce_token_policy();
+}
+
+/// خطاف الإغلاق: تسجيل الرصيد الأخير والخصم المحلي الذي لم يقبله السجل
+fn record_final_balance(_: &power::HookContext) -> Result<(), String> {
+    let token_manager = TOKEN_MANAGER.try_lock().ok_or_else(|| String::from("مدير التوكنات مقفل"))?;
+    audit::record("tokens", format_args!(
+        "الرصيد عند الإغلاق {} في {} (تعديل محلي {})",
+        token_manager.current_tokens, token_manager.ledger.name(), token_manager.local_adjustment,
+    ));
+    Ok(())
+}
+
+/// خطاف الإغلاق: إيقاف Zaka قبل أن تُغلق الأنظمة التي يستعملها
+fn stop_zaka(_: &power::HookContext) -> Result<(), String> {
+    ai::zaka_core::ZAKA_CORE.try_lock().ok_or_else(|| String::from("Zaka مشغول"))?.stop();
+    Ok(())
 }
 
 /// معالج الذعر للنظام
+#[cfg(not(feature = "hosted"))]
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
     error!("🛑 === ذعر في نواة نظام إسلام ===");
@@ -456,9 +771,9 @@ fn panic(info: &PanicInfo) -> ! {
         error!("📍 الموقع: {}:{}", location.file(), location.line());
     }
     
-    if let Some(message) = info.message() {
-        error!("💬 الرسالة: {}", message);
-    }
+    error!("💬 الرسالة: {}", info.message());
+    
+    backtrace::print();
     
     // معلومات المطور
     error!("👨💻 المطور: {}", DEVELOPER);
@@ -467,17 +782,23 @@ fn panic(info: &PanicInfo) -> ! {
     error!("📧 البريد: {}", EMAIL);
     error!("🔗 GitHub: {}", GITHUB);
     
+    // حفظ سجل العطل ليعرضه الإقلاع التالي
+    crash::record_panic(info);
+    
     // محاولة استعادة النظام
     attempt_recovery();
     
     // إذا فشلت الاستعادة، توقف
     loop {
-        arch::interrupts::disable();
+        unsafe {
+            arch::interrupts::disable();
+        }
         arch::cpu::halt();
     }
 }
 
 /// محاولة استعادة النظام بعد الذعر
+#[cfg(not(feature = "hosted"))]
 fn attempt_recovery() {
     info!("🔄 محاولة استعادة النظام...");
     
@@ -492,17 +813,20 @@ fn attempt_recovery() {
 }
 
 /// معالج أخطاء التخصيص
+#[cfg(not(feature = "hosted"))]
 #[alloc_error_handler]
 fn alloc_error_handler(layout: Layout) -> ! {
     error!("💾 خطأ في تخصيص الذاكرة!");
     error!("📏 الحجم المطلوب: {} بايت", layout.size());
     error!("📍 المحاذاة: {}", layout.align());
+    backtrace::print();
     
-    panic!("فشل تخصيص الذاكرة");
+    // الرسالة تحمل التفاصيل حتى تصل إلى سجل العطل
+    panic!("فشل تخصيص الذاكرة: {} بايت بمحاذاة {}", layout.size(), layout.align());
 }
 
 /// هياكل البيانات المساعدة
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 struct MemoryStats {
     total: usize,
     used: usize,
@@ -510,17 +834,6 @@ struct MemoryStats {
     cached: usize,
 }
 
-impl Default for MemoryStats {
-    fn default() -> Self {
-        Self {
-            total: 0,
-            used: 0,
-            free: 0,
-            cached: 0,
-        }
-    }
-}
-
 impl MemoryStats {
     fn percent(&self) -> f32 {
         if self.total > 0 {
@@ -531,11 +844,16 @@ impl MemoryStats {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct CpuStats {
+    /// متوسط استخدام المعالجات العاملة
     usage: f32,
+    /// تردد TSC بالهرتز
     frequency: u64,
+    /// بالدرجات المئوية، صفر إن لم يوجد حساس
     temperature: f32,
+    /// استخدام كل معالج بترتيب رقمه
+    cores: Vec<f32>,
 }
 
 impl Default for CpuStats {
@@ -544,25 +862,12 @@ impl Default for CpuStats {
             usage: 0.0,
             frequency: 0,
             temperature: 0.0,
+            cores: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Clone)]
-struct User {
-    id: u32,
-    name: String,
-    role: UserRole,
-    token_balance: u32,
-}
-
-#[derive(Debug, Clone)]
-enum UserRole {
-    Admin,
-    User,
-    Guest,
-}
-
+#[expect(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum SecurityLevel {
     Low,
@@ -593,10 +898,736 @@ mod tests {
         assert!(tm.use_token(1));
         assert_eq!(tm.current_tokens, MIN_TOKENS + 49);
     }
+    
+    #[test_case]
+    fn test_token_ledgers() {
+        use tokens::{ethereum, EthereumLedger, FileLedger, LedgerError, RpcTransport, TokenLedger, VoucherLedger};
+        
+        const KEY: &str = "03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8";
+        const WALLET: &str = "0x00000000000000000000000000000000000000AA";
+        const VOUCHERS: [&str; 2] = [
+            "0x00000000000000000000000000000000000000aa:120:n1:705f229d7ebc497dbb223f242060492ba70474f8d10a05b88c79038059bfb0eff53276179fb69adc96ff096248905ee4a0b2d5be75caff99eb9ffec156167f0f",
+            "0x00000000000000000000000000000000000000aa:30:n2:b2056ab3f86b0d674090b7bcf80e4cb5e090577998a10fa61d1fe71b6c1b0456eb7e015998f903c97f88c9940ed3a97a94b7cbe340216b3975e447855ea7ea01",
+        ];
+        
+        // رد ثابت من عقدة وهمية: 0x7e8 = 2024 ثم ست منازل عشرية
+        struct FixedNode;
+        impl RpcTransport for FixedNode {
+            fn post(&self, _endpoint: &str, body: &str) -> Result<String, LedgerError> {
+                assert!(body.contains(r#""data":"0x70a0823100000000000000000000000000000000000000000000000000000000000000aa""#));
+                Ok(String::from(r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000078a3ca00"}"#))
+            }
+        }
+        
+        // عقدة لا ترد: لا رصيد مفترض قبل أول قراءة
+        struct DownNode;
+        impl RpcTransport for DownNode {
+            fn post(&self, _endpoint: &str, _body: &str) -> Result<String, LedgerError> {
+                Err(LedgerError::Unavailable)
+            }
+        }
+        
+        // طلب في الطريق: لا حكم على الرصيد حتى يصل الرد
+        struct SlowNode;
+        impl RpcTransport for SlowNode {
+            fn post(&self, _endpoint: &str, _body: &str) -> Result<String, LedgerError> {
+                Err(LedgerError::Pending)
+            }
+        }
+        
+        let mut tm = TokenManager::new();
+        tm.attach(Arc::new(EthereumLedger::new("http://node", CONTRACT_ADDRESS, 6, Box::new(DownNode)).unwrap()), String::from(WALLET));
+        assert!(!tm.check_tokens() && tm.is_locked && !tm.balance_pending);
+        assert_eq!(tm.current_tokens, 0);
+        assert!(!tm.use_token(1));
+        tm.attach(Arc::new(EthereumLedger::new("http://node", CONTRACT_ADDRESS, 6, Box::new(SlowNode)).unwrap()), String::from(WALLET));
+        assert!(!tm.check_tokens() && tm.balance_pending);
+        
+        let node = net::SocketAddr::new(net::Ipv4Addr::new(10, 0, 2, 2), 8545);
+        assert_eq!(ethereum::parse_endpoint("http://10.0.2.2:8545"), Some((node, "10.0.2.2:8545", "/")));
+        assert_eq!(ethereum::parse_endpoint("http://10.0.2.2/rpc").map(|(address, _, path)| (address.port, path)), Some((80, "/rpc")));
+        assert_eq!(ethereum::parse_endpoint("http://node:8545"), None);
+        assert_eq!(ethereum::parse_endpoint("https://10.0.2.2"), None);
+        
+        // إيثيريوم للقراءة فقط: الخصم يُحسب محلياً في المدير
+        let ethereum = EthereumLedger::new("http://node", CONTRACT_ADDRESS, 6, Box::new(FixedNode)).unwrap();
+        assert_eq!(ethereum.balance(WALLET), Ok(2024));
+        assert_eq!(ethereum::parse_response(r#"{"error":{"code":-32000,"message":"x"}}"#), Err(LedgerError::Rpc(-32000)));
+        let mut tm = TokenManager::new();
+        tm.attach(Arc::new(ethereum), String::from(WALLET));
+        assert!(tm.check_tokens() && tm.use_token(24));
+        assert!(tm.check_tokens());
+        assert_eq!(tm.current_tokens, 2000);
+        
+        // القسائم: التوقيع والتكرار والإنفاق، ثم الحفظ على قرص البيانات
+        let admin = admin_session();
+        let disk = disk_image("tokens-data", include_bytes!("fs/testdata/ext2.img.gz"));
+        fs::mount_device(admin, disk, fs::DATA_MOUNT, fs::MountMode::ReadWrite).unwrap();
+        
+        let vouchers = VoucherLedger::open(Some(KEY)).unwrap();
+        assert_eq!(vouchers.redeem(VOUCHERS[0]), Ok(120));
+        assert_eq!(vouchers.redeem(VOUCHERS[0]), Err(LedgerError::AlreadyRedeemed));
+        assert_eq!(vouchers.redeem(&VOUCHERS[1].replace(":30:", ":300:")), Err(LedgerError::InvalidSignature));
+        assert_eq!(vouchers.redeem(VOUCHERS[1]), Ok(30));
+        assert_eq!(vouchers.debit(WALLET, 200), Err(LedgerError::InsufficientFunds));
+        assert_eq!(vouchers.debit(WALLET, 50), Ok(100));
+        assert_eq!(vouchers.credit(WALLET, 1), Err(LedgerError::ReadOnly));
+        assert_eq!(VoucherLedger::open(Some(KEY)).unwrap().balance(WALLET), Ok(100));
+        
+        // الملف: أول خصم يرفضه الرصيد الصفري، والإضافة تنشئ الملف
+        let file = FileLedger::new("/data/tokens/ledger", None);
+        assert_eq!(file.debit(WALLET, 1), Err(LedgerError::InsufficientFunds));
+        assert_eq!(file.credit(WALLET, 150), Ok(150));
+        assert_eq!(file.debit(WALLET, 40), Ok(110));
+        assert_eq!(fs::vfs::read_to_string("/data/tokens/ledger").unwrap(), "0x00000000000000000000000000000000000000aa=110\n");
+        
+        fs::vfs::unmount(admin, fs::DATA_MOUNT).unwrap();
+        accounts::logout(admin).unwrap();
+    }
+    
+    #[test_case]
+    fn test_health_monitor_and_watchdog() {
+        use health::{Check, HealthMonitor, Sample, Severity, Thresholds, Watchdog};
+        
+        let params = boot::KernelParams::parse("health.memory=70,90 health.cpu=كثير health.watchdog=off");
+        let thresholds = Thresholds::from_params(&params);
+        assert_eq!(thresholds.memory.classify(75.0), Severity::Warning);
+        assert_eq!(thresholds.cpu, Thresholds::default().cpu);
+        assert_eq!(thresholds.watchdog_ms, None);
+        
+        let changes = |alerts: Vec<health::Alert>| -> Vec<(Check, Severity)> {
+            alerts.iter().map(|alert| (alert.check, alert.severity)).collect()
+        };
+        let mut monitor = HealthMonitor::new(thresholds);
+        let sample = Sample { memory_percent: 50.0, cpu_percent: 10.0, processes: 3, loop_iterations: 100, timer_ticks: 100 };
+        assert!(monitor.evaluate(sample).is_empty());
+        
+        // تنبيه عند تغير المرحلة فقط لا في كل عينة
+        let critical = Sample { memory_percent: 92.0, loop_iterations: 200, timer_ticks: 200, ..sample };
+        assert_eq!(changes(monitor.evaluate(critical)), [(Check::Memory, Severity::Critical)]);
+        assert!(monitor.evaluate(Sample { loop_iterations: 300, timer_ticks: 300, ..critical }).is_empty());
+        
+        // دورة واحدة للحلقة في 1000 تكة، ثم عداد يرجع للخلف
+        let sluggish = Sample { loop_iterations: 301, timer_ticks: 1300, ..sample };
+        assert_eq!(changes(monitor.evaluate(sluggish)), [(Check::Memory, Severity::Ok), (Check::Uptime, Severity::Warning)]);
+        let alerts = monitor.evaluate(Sample { loop_iterations: 5, timer_ticks: 1400, ..sample });
+        assert_eq!(changes(alerts.clone()), [(Check::Uptime, Severity::Critical)]);
+        assert_eq!(monitor.status(Check::Uptime), Severity::Critical);
+        
+        // المراقب يتسلح بأول تغذية ويبلغ عن التوقف مرة واحدة
+        let mut watchdog = Watchdog::new(Some(100));
+        assert!(!watchdog.check(1000));
+        assert_eq!(watchdog.pet(1000), None);
+        assert!(!watchdog.check(1100));
+        assert!(watchdog.check(1101));
+        assert!(!watchdog.check(1500));
+        assert_eq!(watchdog.pet(1600), Some(600));
+        assert!(!watchdog.is_stalled());
+        
+        // التنبيه يظهر في منطقة الإشعارات
+        gui::notifications::health_alert(&alerts[0]);
+        assert_eq!(gui::notifications::recent(1)[0].urgency, gui::notifications::Urgency::Critical);
+        assert!(drivers::vga::WRITER.lock().row_text(gui::notifications::ROW).contains("uptime: warning -> critical"));
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_pci_hotplug() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use drivers::pci::{self, DeviceId, Header, PciAddress, PciDevice, PciDriver};
+        
+        static PROBES: AtomicUsize = AtomicUsize::new(0);
+        static REMOVES: AtomicUsize = AtomicUsize::new(0);
+        fn probe(device: &PciDevice) -> Result<(), String> {
+            pci::enable_bus_master(device.address);
+            PROBES.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+        fn remove(_: &PciDevice) {
+            REMOVES.fetch_add(1, Ordering::SeqCst);
+        }
+        static BLOCK: PciDriver = PciDriver { name: "test-blk", ids: &[DeviceId::new(0x1af4, 0x1001)], probe, remove };
+        
+        hosted::boot();
+        let address = PciAddress::new(0, 5, 0);
+        pci::hosted_config().attach(address, &Header { vendor_id: 0x1af4, device_id: 0x1001, class: 0x01, subclass: 0x00, ..Header::default() });
+        
+        // الجهاز الجديد يظهر بالمسح التالي ويرتبط بتعريفه
+        pci::register_driver(&BLOCK);
+        assert_eq!(PROBES.load(Ordering::SeqCst), 0);
+        pci::scan();
+        assert_eq!(PROBES.load(Ordering::SeqCst), 1);
+        assert_eq!(pci::config().read(address, pci::offset::COMMAND) & 0x7, 0x7);
+        
+        let table = fs::vfs::read_to_string("/proc/bus/pci/devices").unwrap();
+        assert!(table.contains("00:01.1\t8086\t7010\t010180"));
+        assert!(table.contains("00:05.0\t1af4\t1001\t010000\t0\ttest-blk"));
+        assert!(fs::vfs::read_to_string("/proc/bus/pci/drivers").unwrap().contains("test-blk\n"));
+        
+        pci::reprobe();
+        assert_eq!((PROBES.load(Ordering::SeqCst), REMOVES.load(Ordering::SeqCst)), (2, 1));
+        
+        pci::hosted_config().detach(address);
+        pci::scan();
+        assert_eq!(REMOVES.load(Ordering::SeqCst), 2);
+        assert!(pci::devices().iter().all(|(device, _)| device.address != address));
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_keyboard_input() {
+        use drivers::keyboard;
+        use input::{InputEvent, Key, Layout, SpecialKey};
+        
+        hosted::boot();
+        keyboard::set_layout(Layout::Us);
+        let mut reader = input::reader();
+        let key = |c, layout| InputEvent::Key { key: Key::Char(c), layout };
+        
+        // h ثم i، ثم Alt+Shift، ثم h و Shift+/ بالعربي، ثم السهم للأعلى
+        keyboard::inject_scancodes(&[0x23, 0xA3, 0x17, 0x97]);
+        keyboard::inject_scancodes(&[0x38, 0x2A, 0xAA, 0xB8]);
+        keyboard::inject_scancodes(&[0x23, 0xA3, 0x2A, 0x35, 0xB5, 0xAA, 0xE0, 0x48, 0xE0, 0xC8]);
+        handle_input_events();
+        
+        assert_eq!(reader.poll(), [
+            key('h', Layout::Us),
+            key('i', Layout::Us),
+            InputEvent::LayoutChanged(Layout::Arabic),
+            key('ا', Layout::Arabic),
+            key('؟', Layout::Arabic),
+            InputEvent::Key { key: Key::Special(SpecialKey::Up), layout: Layout::Arabic },
+        ]);
+        assert_eq!(keyboard::layout(), Layout::Arabic);
+        assert_eq!(gui::notifications::recent(1)[0].text, "Keyboard layout: Arabic (101)");
+        
+        keyboard::set_layout(Layout::Us);
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_mouse_pointer() {
+        use drivers::{mouse, vga};
+        use input::{InputEvent, MouseButton, MouseEvent};
+        
+        hosted::boot();
+        let mut reader = input::reader();
+        
+        // حزمتا IntelliMouse: يمين 8 نقاط، ثم ضغط الأيسر
+        mouse::inject_bytes(&[0x08, 0x08, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00]);
+        handle_input_events();
+        let events = reader.poll();
+        let (column, row) = match events.as_slice() {
+            [InputEvent::Mouse { event: MouseEvent::Move { dx: 8, dy: 0 }, column, row },
+             InputEvent::Mouse { event: MouseEvent::Button { button: MouseButton::Left, pressed: true }, .. }] => (*column, *row),
+            other => panic!("أحداث غير متوقعة: {:?}", other),
+        };
+        
+        // المؤشر خلية بألوان معكوسة تعود كما كانت بعد التحرك
+        assert_eq!(gui::pointer::position(), Some((column, row)));
+        let attribute = |column: u16, row: u16| vga::WRITER.lock().cell_attribute(row as usize, column as usize);
+        assert_eq!(gui::pointer::inverted(0x1F), 0xF1);
+        let under = attribute(column, row);
+        mouse::inject_bytes(&[0x08, 0x00, 0x10, 0x00]);
+        handle_input_events();
+        assert_eq!(gui::pointer::position(), Some((column, row - 1)));
+        assert_eq!(gui::pointer::inverted(attribute(column, row)), under);
+        
+        // رفع الزر يعيد الفأرة كما وجدها الاختبار التالي
+        mouse::inject_bytes(&[0x08, 0x00, 0x00, 0x00]);
+        handle_input_events();
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_timer_jobs_run_from_work_queue() {
+        use core::sync::atomic::{AtomicU32, Ordering};
+        use process::timer;
+        
+        static PERIODIC: AtomicU32 = AtomicU32::new(0);
+        static ONCE: AtomicU32 = AtomicU32::new(0);
+        fn periodic() {
+            PERIODIC.fetch_add(1, Ordering::SeqCst);
+        }
+        fn once() {
+            ONCE.fetch_add(1, Ordering::SeqCst);
+        }
+        
+        timer::run_pending();
+        let periodic_id = timer::every("test-periodic", 2, periodic);
+        timer::after("test-once", 5, once);
+        
+        // المقاطعة تنقل المهام إلى الطابور فقط، والتنفيذ في الحلقة الرئيسية
+        for _ in 0..10 {
+            arch::interrupts::wait_for_interrupt();
+        }
+        assert_eq!((PERIODIC.load(Ordering::SeqCst), ONCE.load(Ordering::SeqCst)), (0, 0));
+        assert!(timer::queued() >= 2);
+        
+        // المهمة الدورية المتأخرة تُنفذ مرة واحدة لا خمس مرات
+        timer::run_pending();
+        assert_eq!((PERIODIC.load(Ordering::SeqCst), ONCE.load(Ordering::SeqCst)), (1, 1));
+        
+        timer::defer("test-deferred", periodic);
+        assert_eq!(timer::run_pending(), 1);
+        assert_eq!(PERIODIC.load(Ordering::SeqCst), 2);
+        
+        assert!(timer::cancel(periodic_id));
+        for _ in 0..4 {
+            arch::interrupts::wait_for_interrupt();
+        }
+        timer::run_pending();
+        assert_eq!((PERIODIC.load(Ordering::SeqCst), ONCE.load(Ordering::SeqCst)), (2, 1));
+    }
+    
+    /// جلسة مدير للاختبارات التي تركب الأقراص أو تطلب الإغلاق؛ يغلقها الاختبار
+    /// حتى لا يجدها خطاف حفظ الجلسات في اختبار لاحق
+    pub(crate) fn admin_session() -> accounts::SessionId {
+        use accounts::{Account, Database, PasswordHash, UserRole};
+        
+        let mut database = Database::default();
+        database.insert(Account::new("root", 0, UserRole::Admin, PasswordHash::new("test"))).unwrap();
+        accounts::replace(database);
+        accounts::login("root", "test").unwrap()
+    }
+    
+    /// جولات نقل بين البطاقة المحاكاة والطرف الآخر
+    #[cfg(feature = "hosted")]
+    fn wire(card: &drivers::net::virtio::SimulatedNet, peer: &mut net::Interface) {
+        let now = process::timer::uptime_ms();
+        for _ in 0..8 {
+            net::poll();
+            for frame in card.take_sent() {
+                peer.receive(&frame, now);
+            }
+            peer.poll(now);
+            while let Some(frame) = peer.transmit() {
+                assert!(card.inject(&frame));
+            }
+        }
+    }
+    
+    /// قرص في الذاكرة من صورة مضغوطة أنشئت على لينكس (`scripts/make-test-images.sh`)
+    fn disk_image(name: &str, image: &[u8]) -> alloc::sync::Arc<drivers::block::ramdisk::RamDisk> {
+        let bytes = utils::inflate::gunzip(image).expect("صورة قرص صالحة");
+        alloc::sync::Arc::new(drivers::block::ramdisk::RamDisk::new(name, bytes))
+    }
+    
+    #[test_case]
+    fn test_accounts_and_roles() {
+        use accounts::{Account, AuthError, Database, PasswordHash, Privilege, UserRole};
+        
+        let mut database = Database::default();
+        database.insert(Account::new("islam", 0, UserRole::Admin, PasswordHash::new("كلمة-سر"))).unwrap();
+        database.insert(Account::new("guest", 65534, UserRole::Guest, PasswordHash::Empty)).unwrap();
+        accounts::replace(database);
+        
+        assert_eq!(accounts::login("islam", "خطأ"), Err(AuthError::BadPassword));
+        assert_eq!(accounts::login("nobody", ""), Err(AuthError::UnknownUser));
+        let admin = accounts::login("islam", "كلمة-سر").unwrap();
+        let guest = accounts::login("guest", "").unwrap();
+        
+        // المجلدات الرئيسية تُنشأ على قرص البيانات، والتركيب للمدير وحده
+        let disk = disk_image("accounts-data", include_bytes!("fs/testdata/ext2.img.gz"));
+        assert_eq!(fs::mount_device(guest, disk.clone(), fs::DATA_MOUNT, fs::MountMode::ReadWrite), Err(fs::FsError::PermissionDenied));
+        fs::mount_device(admin, disk, fs::DATA_MOUNT, fs::MountMode::ReadWrite).unwrap();
+        
+        let uid = accounts::add_user(admin, "fatima", "secret", UserRole::User).unwrap();
+        assert_eq!(uid, 1000);
+        assert_eq!(accounts::add_user(guest, "intruder", "x", UserRole::Admin), Err(AuthError::PermissionDenied));
+        assert_eq!(accounts::add_user(admin, "bad:name", "x", UserRole::User), Err(AuthError::InvalidName));
+        assert!(fs::vfs::stat("/data/home/fatima").is_ok());
+        
+        let user = accounts::login("fatima", "secret").unwrap();
+        assert_eq!(accounts::authorize(user, Privilege::Power).err(), Some(AuthError::PermissionDenied));
+        assert_eq!(power::shutdown(user, power::PowerAction::Reboot, "اختبار"), Err(AuthError::PermissionDenied));
+        assert_eq!(fs::vfs::unmount(user, fs::DATA_MOUNT), Err(fs::FsError::PermissionDenied));
+        assert_eq!(accounts::remove_user(user, "guest"), Err(AuthError::PermissionDenied));
+        assert_eq!(accounts::set_password(guest, "guest", "x"), Err(AuthError::PermissionDenied));
+        accounts::set_password(user, "fatima", "new-secret").unwrap();
+        assert_eq!(accounts::credit_tokens(admin, "fatima", 25), Ok(25));
+        assert!(accounts::authorize(admin, Privilege::Mount).is_ok());
+        
+        // القاعدة المحفوظة تُقرأ كما كُتبت
+        let saved = Database::parse(
+            &fs::vfs::read_to_string("/data/etc/passwd").unwrap(),
+            &fs::vfs::read_to_string("/data/etc/shadow").unwrap(),
+        );
+        let fatima = saved.find("fatima").unwrap();
+        assert_eq!((fatima.user.role, fatima.user.token_balance), (UserRole::User, 25));
+        assert!(fatima.password.verify("new-secret"));
+        assert_eq!(SYSTEM_STATE.lock().users.len(), 3);
+        
+        // فشل الحفظ لا يغير القاعدة الحية
+        fs::vfs::remove("/data/etc/shadow").unwrap();
+        fs::vfs::create_dir("/data/etc/shadow").unwrap();
+        assert!(accounts::add_user(admin, "omar", "x", UserRole::User).is_err());
+        assert!(accounts::users().iter().all(|u| u.name != "omar"));
+        assert!(!fs::vfs::read_to_string("/data/etc/passwd").unwrap().contains("omar"));
+        fs::vfs::remove("/data/etc/shadow").unwrap();
+        
+        // حساب بلا سطر في shadow يُحمَّل مقفلاً
+        let orphan = Database::parse("ali:1001:user:0:/data/home/ali\n", "");
+        assert_eq!(orphan.find("ali").unwrap().password, PasswordHash::Locked);
+        
+        accounts::logout(user).unwrap();
+        assert_eq!(accounts::authorize(user, Privilege::ChangeOwnPassword).err(), Some(AuthError::NoSession));
+        fs::vfs::unmount(admin, fs::DATA_MOUNT).unwrap();
+        
+        // دون قرص البيانات لا يُقبل تعديل لن يبقى بعد الإقلاع
+        assert_eq!(accounts::credit_tokens(admin, "fatima", 1), Err(AuthError::Fs(fs::FsError::NotMounted)));
+        assert!(accounts::users().iter().any(|u| u.name == "fatima" && u.token_balance == 25));
+        accounts::replace(Database::bootstrap());
+    }
+    
+    #[test_case]
+    fn test_crash_record_survives_reboot() {
+        use drivers::block::ramdisk::RamDisk;
+        
+        let disk = alloc::sync::Arc::new(RamDisk::zeroed("crash-test", 128));
+        crash::attach(disk.clone(), 16, crash::REGION_SECTORS).unwrap();
+        assert_eq!(crash::last(), None);
+        
+        dmesg::push(log::Level::Warn, format_args!("آخر سطر قبل العطل"));
+        let registers = arch::RegisterSnapshot { rip: 0xFFFF_8000_0010_0000, ..Default::default() };
+        crash::record(format_args!("اختبار\nمتعدد الأسطر"), Some(("src/lib.rs", 42, 7)), registers);
+        
+        // الإقلاع التالي: إعادة ربط القرص نفسه
+        crash::detach();
+        crash::attach(disk.clone(), 16, crash::REGION_SECTORS).unwrap();
+        let record = crash::last().unwrap();
+        assert_eq!(record.message(), "اختبار\nمتعدد الأسطر");
+        assert_eq!(record.location(), "src/lib.rs:42:7");
+        assert_eq!(record.get("reg.rip"), Some("0xffff800000100000"));
+        assert_eq!(record.get("tokens.min"), Some("100"));
+        assert!(record.log.last().unwrap().ends_with("آخر سطر قبل العطل"));
+        
+        crash::clear().unwrap();
+        crash::detach();
+        crash::attach(disk, 16, crash::REGION_SECTORS).unwrap();
+        assert_eq!(crash::last(), None);
+        crash::detach();
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_block_devices() {
+        use drivers::block::partition::tests::mbr;
+        use drivers::block::{self, ata, virtio, SECTOR_SIZE};
+        use drivers::pci::{self, Header, PciAddress};
+        use fs::vfs;
+        
+        hosted::boot();
+        
+        // hda على متحكم IDE: قسم ext2 يليه قسم سجلات الأعطال
+        let ext2 = utils::inflate::gunzip(include_bytes!("fs/testdata/ext2.img.gz")).unwrap();
+        let sectors = (ext2.len() / SECTOR_SIZE) as u32;
+        let mut image = alloc::vec![0u8; (2048 + sectors as usize + 64) * SECTOR_SIZE];
+        image[2048 * SECTOR_SIZE..][..ext2.len()].copy_from_slice(&ext2);
+        mbr(&mut image, 0, &[(0x83, 2048, sectors), (0xDA, 2048 + sectors, 64)]);
+        let channel = ata::hosted_channel(0);
+        channel.attach(false, image);
+        pci::reprobe();
+        
+        assert_eq!(block::find("hda1").unwrap().sector_count(), sectors as u64);
+        assert!(crash::attached());
+        crash::detach();
+        let partitions = vfs::read_to_string("/proc/partitions").unwrap();
+        assert!(partitions.contains("hda1\t2048\t"));
+        assert!(partitions.contains("\tmbr:da\t"));
+        
+        // /data على أول قسم بنظام ملفات معروف، والكتابة تصل القرص بالمزامنة
+        fs::mount_data(fs::MountMode::ReadWrite).unwrap();
+        let before = channel.image(false).unwrap();
+        vfs::write_file("/data/note.txt", b"written through the buffer cache").unwrap();
+        assert_eq!(channel.image(false).unwrap(), before);
+        vfs::sync_all().unwrap();
+        assert!(channel.image(false).unwrap().windows(32).any(|window| window == b"written through the buffer cache"));
+        let admin = admin_session();
+        vfs::unmount(admin, fs::DATA_MOUNT).unwrap();
+        accounts::logout(admin).unwrap();
+        
+        // vda على virtio-blk
+        let device = virtio::hosted_device(0xC080, alloc::vec![0u8; 64 * SECTOR_SIZE], virtio::FEATURE_FLUSH);
+        let address = PciAddress::new(0, 4, 0);
+        pci::hosted_config().attach(address, &Header { vendor_id: 0x1af4, device_id: 0x1001, class: 0x01, bars: alloc::vec![(0xC081, 64)], ..Header::default() });
+        pci::scan();
+        
+        let vda = block::find("vda").unwrap();
+        assert_eq!(vda.sector_count(), 64);
+        vda.write_sectors(3, &[0x5A; 2 * SECTOR_SIZE]).unwrap();
+        let mut back = [0u8; SECTOR_SIZE];
+        vda.read_sectors(4, &mut back).unwrap();
+        assert_eq!(back, [0x5A; SECTOR_SIZE]);
+        assert!(device.image()[3 * SECTOR_SIZE..5 * SECTOR_SIZE].iter().all(|&b| b == 0x5A));
+        vda.flush().unwrap();
+        assert_eq!(device.flushes(), 1);
+        
+        pci::hosted_config().detach(address);
+        pci::scan();
+        assert!(block::find("vda").is_none());
+        channel.detach(false);
+        pci::reprobe();
+        assert!(block::find("hda").is_none());
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_virtio_net() {
+        use drivers::net::virtio;
+        use drivers::pci::{self, Header, PciAddress};
+        use net::ethernet::MacAddress;
+        use net::{Config, Interface, Ipv4Addr, SocketAddr, TcpState};
+        
+        hosted::boot();
+        let mac = MacAddress([0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        let card = virtio::hosted_device(0xC0C0, mac);
+        let address = PciAddress::new(0, 6, 0);
+        pci::hosted_config().attach(address, &Header { vendor_id: 0x1af4, device_id: 0x1000, class: 0x02, bars: alloc::vec![(0xC0C1, 32)], ..Header::default() });
+        pci::scan();
+        assert_eq!(drivers::net::find("eth0").unwrap().mac(), mac);
+        net::poll();
+        assert!(SYSTEM_STATE.lock().network_connected);
+        
+        // البوابة الافتراضية طرف TCP يصله الاتصال عبر البطاقة
+        let mut gateway = Interface::new(MacAddress([0x52, 0x55, 10, 0, 2, 2]), Config::parse("10.0.2.2/24", None).unwrap());
+        let listener = gateway.tcp_listen(9000, 1).unwrap();
+        let stream = net::TcpStream::connect(SocketAddr::new(Ipv4Addr::new(10, 0, 2, 2), 9000)).unwrap();
+        wire(&card, &mut gateway);
+        assert!(stream.is_connected());
+        assert_eq!(stream.send(b"hello from the kernel"), Ok(21));
+        wire(&card, &mut gateway);
+        let (accepted, peer) = gateway.tcp_accept(listener).unwrap();
+        assert_eq!(peer.ip, Ipv4Addr::new(10, 0, 2, 15));
+        let mut buf = [0u8; 64];
+        assert_eq!(gateway.tcp_recv(accepted, &mut buf), Ok(21));
+        assert_eq!(&buf[..21], b"hello from the kernel");
+        
+        let dev = fs::vfs::read_to_string("/proc/net/dev").unwrap();
+        assert!(dev.contains("eth0\t52:54:00:12:34:56\t10.0.2.15/24\t"));
+        assert!(fs::vfs::read_to_string("/proc/net/tcp").unwrap().contains("10.0.2.2:9000\tESTABLISHED"));
+        
+        // تحرير المقبس يغلقه بـ FIN
+        drop(stream);
+        wire(&card, &mut gateway);
+        assert_eq!(gateway.tcp_state(accepted), Ok(TcpState::CloseWait));
+        
+        pci::hosted_config().detach(address);
+        pci::scan();
+        assert!(drivers::net::find("eth0").is_none());
+        net::poll();
+        assert!(!SYSTEM_STATE.lock().network_connected);
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_ethereum_ledger_over_tcp() {
+        use drivers::net::virtio;
+        use drivers::pci::{self, Header, PciAddress};
+        use net::ethernet::MacAddress;
+        use net::{Config, Interface};
+        use tokens::ethereum::HttpTransport;
+        use tokens::{EthereumLedger, LedgerError, TokenLedger};
+        
+        const WALLET: &str = "0x00000000000000000000000000000000000000aa";
+        const RESULT: &str = r#"{"jsonrpc":"2.0","id":1,"result":"0x0000000000000000000000000000000000000000000000000000000078a3ca00"}"#;
+        
+        hosted::boot();
+        let card = virtio::hosted_device(0xC100, MacAddress([0x52, 0x54, 0x00, 0x12, 0x34, 0x57]));
+        let address = PciAddress::new(0, 8, 0);
+        pci::hosted_config().attach(address, &Header { vendor_id: 0x1af4, device_id: 0x1000, class: 0x02, bars: alloc::vec![(0xC101, 32)], ..Header::default() });
+        pci::scan();
+        net::poll();
+        
+        // البوابة تؤدي دور عقدة إيثيريوم
+        let mut node = Interface::new(MacAddress([0x52, 0x55, 10, 0, 2, 2]), Config::parse("10.0.2.2/24", None).unwrap());
+        let listener = node.tcp_listen(8545, 1).unwrap();
+        let ledger = EthereumLedger::new("http://10.0.2.2:8545", CONTRACT_ADDRESS, 6, Box::new(HttpTransport::new())).unwrap();
+        
+        // الطلب لا يحجب: كل سؤال يتابعه حتى يصل الرد
+        assert_eq!(ledger.balance(WALLET), Err(LedgerError::Pending));
+        wire(&card, &mut node);
+        assert_eq!(ledger.balance(WALLET), Err(LedgerError::Pending));
+        wire(&card, &mut node);
+        let (accepted, _) = node.tcp_accept(listener).unwrap();
+        let mut buf = alloc::vec![0u8; 2048];
+        let count = node.tcp_recv(accepted, &mut buf).unwrap();
+        let request = core::str::from_utf8(&buf[..count]).unwrap();
+        assert!(request.starts_with("POST / HTTP/1.0\r\nHost: 10.0.2.2:8545\r\n"));
+        assert!(request.contains(r#""method":"eth_call""#) && request.contains("70a08231"));
+        
+        let response = format!("HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n{}", RESULT);
+        node.tcp_send(accepted, response.as_bytes()).unwrap();
+        node.tcp_close(accepted).unwrap();
+        wire(&card, &mut node);
+        assert_eq!(ledger.balance(WALLET), Ok(2024));
+        
+        // منفذ بلا مستمع: الرفض يجعل السجل غير متاح
+        let refused = EthereumLedger::new("http://10.0.2.2:8546", CONTRACT_ADDRESS, 6, Box::new(HttpTransport::new())).unwrap();
+        assert_eq!(refused.balance(WALLET), Err(LedgerError::Pending));
+        wire(&card, &mut node);
+        assert_eq!(refused.balance(WALLET), Err(LedgerError::Unavailable));
+        
+        pci::hosted_config().detach(address);
+        pci::scan();
+        net::poll();
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_boot_sequence() {
+        hosted::boot();
+        assert!(SYSTEM_STATE.lock().is_initialized);
+        
+        // 1001 تكة تغطي جميع المهام الدورية مرة واحدة على الأقل
+        let before = SYSTEM_STATE.lock().uptime_ticks;
+        hosted::run_ticks(1001);
+        assert_eq!(SYSTEM_STATE.lock().uptime_ticks, before + 1001);
+        assert!(arch::interrupts::wait_count() >= 1001);
+        assert!(!arch::cpu::shutdown_requested());
+        
+        // الرصيد المنخفض يؤدي إلى إغلاق الطوارئ في فحص التوكنات التالي
+        {
+            let mut tokens = TOKEN_MANAGER.lock();
+            let balance = tokens.current_tokens;
+            assert!(tokens.use_token(balance));
+            assert_eq!(tokens.ledger.balance(&tokens.wallet), Ok(0));
+        }
+        
+        // الرصيد المنخفض يبدأ مهلة، ثم وضعاً متدهوراً، ثم إغلاقاً منظماً
+        check_token_balance();
+        assert!(TOKEN_MANAGER.lock().is_locked);
+        assert!(matches!(TOKEN_MANAGER.lock().policy.state(), tokens::BalanceState::Grace { .. }));
+        assert!(!arch::cpu::shutdown_requested());
+        assert!(drivers::vga::WRITER.lock().row_text(0).contains("grace mode"));
+        
+        TOKEN_MANAGER.lock().policy.grace_ms = Some(0);
+        TOKEN_MANAGER.lock().policy.degraded_ms = Some(0);
+        check_token_balance();
+        assert!(fs::is_read_only());
+        check_token_balance();
+        assert!(arch::cpu::shutdown_requested());
+        assert!(!fs::is_read_only());
+        
+        // كل انتقال في سجل التدقيق، ثم حدث الإغلاق وخطاف الدفع
+        let entries = audit::recent(5);
+        let categories: Vec<_> = entries.iter().map(|entry| entry.category).collect();
+        assert_eq!(categories, ["tokens", "tokens", "tokens", "power", "tokens"]);
+        assert!(entries[2].message.starts_with("degraded -> shutdown"));
+        assert!(entries[3].message.starts_with("poweroff"));
+        
+        // الخطافات بترتيب الأولوية، وكلها نجحت
+        let report = power::last_report();
+        let names: Vec<_> = report.iter().map(|entry| entry.name).collect();
+        assert_eq!(names, ["ai", "tokens", "network", "sessions", "filesystems", "drivers"]);
+        assert!(report.iter().all(|entry| entry.outcome == power::HookOutcome::Completed));
+        assert!(drivers::vga::WRITER.lock().row_text(0).contains("powering off"));
+        
+        // طلب ثانٍ أثناء الإغلاق لا يعيد تنفيذ الخطافات
+        let admin = admin_session();
+        power::shutdown(admin, power::PowerAction::Reboot, "طلب مكرر").unwrap();
+        accounts::logout(admin).unwrap();
+        assert!(!arch::cpu::reboot_requested());
+        
+        // التشغيل من جديد يعيد الرصيد والسياسة للاختبارات التالية
+        hosted::power_on();
+        assert!(!arch::cpu::shutdown_requested() && !power::in_progress());
+        assert!(power::last_report().is_empty());
+        let manager = TOKEN_MANAGER.lock();
+        assert!(!manager.is_locked && manager.current_tokens >= MIN_TOKENS);
+        assert_eq!(manager.policy.state(), tokens::BalanceState::Normal);
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_acpi_tables_and_power_button() {
+        use arch::acpi::{self, builder, Register};
+        
+        hosted::boot();
+        let info = acpi::info().expect("جداول ACPI المحاكاة");
+        assert_eq!((info.power.pm1a_control, info.power.pm1a_event), (builder::PM1A_CONTROL, builder::PM1A_EVENT));
+        assert_eq!(info.power.s5_sleep_type, Some((5, 5)));
+        assert_eq!(info.power.reset, Some((Register::Io(builder::RESET_PORT), builder::RESET_VALUE)));
+        assert_eq!(info.processors.len(), builder::PROCESSORS as usize);
+        assert!(info.processors[0].is_bsp && !info.processors[1].is_bsp);
+        assert_eq!(info.interrupts.unwrap().overrides, [(0, 2)]);
+        
+        // S5: SLP_TYP=5 في البتات 10-12 مع SLP_EN، دون مسح SCI_EN
+        acpi::poweroff();
+        assert_eq!(acpi::io::read_u16(builder::PM1A_CONTROL), (5 << 10) | (1 << 13) | 1);
+        acpi::io::write_u16(builder::PM1A_CONTROL, 1);
+        
+        // الضغطة تُلتقط مرة واحدة وتُمسح من مسجل الحالة
+        acpi::io::write_u16(builder::PM1A_EVENT, 1 << 8);
+        acpi::handle_sci();
+        assert!(acpi::take_power_button());
+        assert!(!acpi::take_power_button());
+        
+        assert_eq!(acpi::find_s5(&[0x08, b'\\', b'_', b'S', b'5', b'_', 0x12, 0x06, 0x04, 0x00, 0x01, 0x00, 0x00]), Some((0, 1)));
+        assert_eq!(acpi::find_s5(b"_S5_"), None);
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_scheduler_preemption() {
+        use process::{scheduler, Priority};
+        
+        hosted::boot();
+        let before = scheduler::get_active_count();
+        let id = scheduler::spawn_on(0, "test", Priority::High, || {});
+        assert_eq!(scheduler::get_active_count(), before + 1);
+        
+        // المهمة الأعلى أولوية تستبق الحالية في تكة المؤقت التالية
+        scheduler::on_timer_tick();
+        assert_eq!(scheduler::current_id(), id);
+        
+        // النوم يعيد المعالج للنواة، والاستيقاظ يستبقها من جديد
+        scheduler::sleep(3);
+        assert_eq!(scheduler::current_id(), scheduler::KERNEL_TASK);
+        for _ in 0..3 {
+            scheduler::on_timer_tick();
+        }
+        assert_eq!(scheduler::current_id(), id);
+        
+        scheduler::block_current();
+        assert_ne!(scheduler::current_id(), id);
+    }
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_hosted_smp_run_queues() {
+        use arch::smp;
+        use process::{scheduler, Priority};
+        
+        hosted::boot();
+        assert_eq!(smp::online(), arch::acpi::builder::PROCESSORS as usize);
+        
+        // كل معالج تطبيقي يبدأ بمهمة خموله، والمهمة الجديدة تذهب لأقلها حملاً
+        let idle = smp::run_on(3, scheduler::current_id);
+        let id = scheduler::spawn("smp", Priority::Normal, || {});
+        let task = scheduler::task_list().into_iter().find(|task| task.id == id).unwrap();
+        assert_ne!(task.cpu, 0);
+        
+        // تكة معالج الإقلاع تصل بقية المعالجات، فتستبق المهمة خمول معالجها وحده
+        scheduler::on_timer_tick();
+        assert_eq!(smp::run_on(task.cpu, scheduler::current_id), id);
+        assert_ne!(scheduler::current_id(), id);
+        if task.cpu != 3 {
+            assert_eq!(smp::run_on(3, scheduler::current_id), idle);
+        }
+        
+        // الإيقاظ يجد المهمة على معالجها
+        smp::run_on(task.cpu, scheduler::block_current);
+        assert!(scheduler::wake(id));
+        
+        assert_eq!(arch::cpu::get_usage_stats().cores.len(), smp::online());
+        update_system_state();
+        assert!(fs::vfs::read_to_string("/proc/cpu").unwrap().contains("cpu3.usage="));
+    }
 }
 
 /// نقطة دخول الاختبارات
-#[cfg(test)]
+#[cfg(all(test, not(feature = "hosted")))]
 #[no_mangle]
 pub extern "C" fn _start_test() -> ! {
     test_main();
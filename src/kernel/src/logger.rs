@@ -0,0 +1,270 @@
+//! 🪵 مسجل النواة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الواجهة الخلفية لماكرو `log`: كل سطر يمر بمرشح الهدف (مسار الوحدة) ثم
+//! يُكتب إلى كل مخرج يسمح مستواه به:
+//!
+//! ```text
+//! info!(...) ──مرشح الهدف──┬──▶ المنفذ التسلسلي COM1
+//!                          ├──▶ شاشة VGA (الأخطاء فقط افتراضياً)
+//!                          └──▶ حلقة dmesg (يقرؤها أمر dmesg وسجل العطل)
+//! ```
+//!
+//! الإعداد من سطر الأوامر:
+//!
+//! ```text
+//! loglevel=debug                   المستوى الافتراضي لكل الأهداف
+//! log.fs=trace log.process=warn    مستوى هدف وما تحته (بدون اسم الحزمة)
+//! log.serial=info log.vga=off      حد مخرج بعينه (serial و vga و dmesg)
+//! ```
+//!
+//! لا ينتظر المسجل أقفال الشاشة أو المنفذ: إن كانت محتجزة (ذعر أثناء الكتابة)
+//! يُتخطى ذلك المخرج ويبقى السطر في الحلقة.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::str::FromStr;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use spin::RwLock;
+
+use crate::boot::KernelParams;
+use crate::drivers::{serial, vga};
+use crate::{dmesg, process};
+
+/// مستوى الأهداف الافتراضي
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// مخرج من مخرجات السجل
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sink {
+    Serial,
+    Vga,
+    Dmesg,
+}
+
+impl Sink {
+    pub const ALL: [Sink; 3] = [Sink::Serial, Sink::Vga, Sink::Dmesg];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            Sink::Serial => "serial",
+            Sink::Vga => "vga",
+            Sink::Dmesg => "dmesg",
+        }
+    }
+    
+    const fn default_level(self) -> LevelFilter {
+        match self {
+            Sink::Serial => LevelFilter::Trace,
+            Sink::Vga => LevelFilter::Error,
+            Sink::Dmesg => LevelFilter::Trace,
+        }
+    }
+}
+
+/// مرشحات المسجل
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    /// مستوى الأهداف التي لا مرشح لها
+    pub default: LevelFilter,
+    /// (بادئة الهدف، المستوى)؛ الأطول تطابقاً يفوز
+    pub targets: Vec<(String, LevelFilter)>,
+    sinks: [LevelFilter; 3],
+}
+
+impl LogConfig {
+    pub const fn new() -> Self {
+        Self {
+            default: DEFAULT_LEVEL,
+            targets: Vec::new(),
+            sinks: [Sink::Serial.default_level(), Sink::Vga.default_level(), Sink::Dmesg.default_level()],
+        }
+    }
+    
+    /// من `loglevel=` و `log.<هدف أو مخرج>=`؛ القيم غير المفهومة تُتجاهل
+    pub fn from_params(params: &KernelParams) -> Self {
+        let mut config = Self::new();
+        if let Some(level) = params.get("loglevel").and_then(|value| LevelFilter::from_str(value).ok()) {
+            config.default = level;
+        }
+        
+        for (key, value) in params.iter() {
+            let (Some(target), Ok(level)) = (key.strip_prefix("log."), LevelFilter::from_str(value)) else {
+                continue;
+            };
+            match Sink::ALL.iter().find(|sink| sink.name() == target) {
+                Some(&sink) => config.set_sink(sink, level),
+                None => config.set_target(target, level),
+            }
+        }
+        config
+    }
+    
+    pub fn sink(&self, sink: Sink) -> LevelFilter {
+        self.sinks[sink as usize]
+    }
+    
+    pub fn set_sink(&mut self, sink: Sink, level: LevelFilter) {
+        self.sinks[sink as usize] = level;
+    }
+    
+    /// مرشح لهدف وما تحته؛ يستبدل المرشح السابق للبادئة نفسها
+    pub fn set_target(&mut self, prefix: &str, level: LevelFilter) {
+        self.targets.retain(|(existing, _)| existing != prefix);
+        self.targets.push((String::from(prefix), level));
+    }
+    
+    /// مستوى الهدف (`kernel::fs::vfs` أو `fs::vfs`)
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        let target = strip_crate(target);
+        self.targets.iter()
+            .filter(|(prefix, _)| matches_prefix(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|&(_, level)| level)
+            .unwrap_or(self.default)
+    }
+    
+    /// أعلى مستوى قد يمر: يسمح لـ `log` بإسقاط ما دونه دون استدعاء المسجل
+    pub fn max_level(&self) -> LevelFilter {
+        let targets = self.targets.iter().map(|&(_, level)| level).chain([self.default]).max().unwrap_or(LevelFilter::Off);
+        let sinks = self.sinks.iter().copied().max().unwrap_or(LevelFilter::Off);
+        targets.min(sinks)
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// حذف اسم الحزمة من بداية الهدف
+fn strip_crate(target: &str) -> &str {
+    let krate = module_path!().split("::").next().unwrap_or("");
+    target.strip_prefix(krate).and_then(|rest| rest.strip_prefix("::")).unwrap_or(target)
+}
+
+/// البادئة تطابق عند حدود `::` فقط (`fs` تطابق `fs::vfs` لا `fsck`)
+fn matches_prefix(target: &str, prefix: &str) -> bool {
+    match target.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+static CONFIG: RwLock<LogConfig> = RwLock::new(LogConfig::new());
+static LOGGER: KernelLogger = KernelLogger;
+
+struct KernelLogger;
+
+impl Log for KernelLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match CONFIG.try_read() {
+            Some(config) => metadata.level() <= config.level_for(metadata.target()),
+            None => metadata.level() <= DEFAULT_LEVEL,
+        }
+    }
+    
+    fn log(&self, record: &Record) {
+        // المرشحات قد تكون قيد التعديل في هذه اللحظة: الافتراضية تكفي لسطر واحد
+        let fallback;
+        let guard = CONFIG.try_read();
+        let config = match &guard {
+            Some(config) => &**config,
+            None => {
+                fallback = LogConfig::new();
+                &fallback
+            }
+        };
+        
+        let level = record.level();
+        if level > config.level_for(record.target()) {
+            return;
+        }
+        
+        if level <= config.sink(Sink::Dmesg) {
+            dmesg::push(level, *record.args());
+        }
+        
+        if level <= config.sink(Sink::Serial) {
+            let tick = process::scheduler::try_ticks().unwrap_or(0);
+            serial::try_write_fmt(format_args!(
+                "[{:>8}] {:<5} {}: {}\n", tick, level, strip_crate(record.target()), record.args(),
+            ));
+        }
+        
+        if level <= config.sink(Sink::Vga) {
+            if let Some(mut writer) = vga::WRITER.try_lock() {
+                let (foreground, background) = writer.colors();
+                writer.set_foreground_color(level_color(level));
+                let _ = writeln!(writer, "{}: {}", level, record.args());
+                writer.set_color(foreground, background);
+            }
+        }
+    }
+    
+    fn flush(&self) {}
+}
+
+fn level_color(level: Level) -> vga::Color {
+    match level {
+        Level::Error => vga::Color::LightRed,
+        Level::Warn => vga::Color::Yellow,
+        Level::Info => vga::Color::White,
+        Level::Debug | Level::Trace => vga::Color::DarkGray,
+    }
+}
+
+/// تثبيت المسجل بالمرشحات الافتراضية (قبل قراءة سطر الأوامر)
+pub fn init() {
+    // التثبيت مرة واحدة في عمر النواة؛ المحاولة الثانية لا تغير شيئاً
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(CONFIG.read().max_level());
+}
+
+/// تطبيق مرشحات سطر الأوامر
+pub fn configure(params: &KernelParams) {
+    apply(LogConfig::from_params(params));
+}
+
+/// استبدال المرشحات أثناء التشغيل
+pub fn apply(config: LogConfig) {
+    log::set_max_level(config.max_level());
+    *CONFIG.write() = config;
+}
+
+/// نسخة من المرشحات الحالية
+pub fn config() -> LogConfig {
+    CONFIG.read().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_logger_filters() {
+        let params = KernelParams::parse("loglevel=warn log.fs=debug log.fs::vfs=error log.vga=off log.serial=كثير");
+        let config = LogConfig::from_params(&params);
+        assert_eq!(config.level_for(module_path!()), LevelFilter::Warn);
+        assert_eq!(config.level_for("fs::fat32"), LevelFilter::Debug);
+        assert_eq!(config.level_for("fs::vfs::mount"), LevelFilter::Error);
+        assert_eq!(config.level_for("fsck"), LevelFilter::Warn);
+        assert_eq!(config.sink(Sink::Vga), LevelFilter::Off);
+        assert_eq!(config.sink(Sink::Serial), LevelFilter::Trace);
+        assert_eq!(config.max_level(), LevelFilter::Debug);
+        
+        // المسجل المثبت يملأ حلقة dmesg بما يمر من المرشح فقط
+        #[cfg(feature = "hosted")]
+        {
+            let previous = super::config();
+            init();
+            apply(LogConfig::from_params(&KernelParams::parse("loglevel=info log.vga=off")));
+            log::info!("سطر اختبار المسجل");
+            log::debug!("سطر مرشح");
+            assert_eq!(dmesg::recent(1)[0].text, "سطر اختبار المسجل");
+            apply(previous);
+        }
+    }
+}
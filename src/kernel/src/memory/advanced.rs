@@ -0,0 +1,20 @@
+//! 💾 إدارة الذاكرة المتقدمة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use log::info;
+
+/// تهيئة إدارة الذاكرة المتقدمة
+pub fn init() {
+    // منع النواة من الكتابة على الصفحات المقروءة فقط
+    #[cfg(not(feature = "hosted"))]
+    unsafe {
+        use x86_64::registers::control::{Cr0, Cr0Flags};
+        Cr0::update(|flags| flags.insert(Cr0Flags::WRITE_PROTECT));
+    }
+    
+    let stats = super::get_usage_stats();
+    info!("💾 الذاكرة الفيزيائية: {} ميجابايت، المستخدم: {} كيلوبايت ({:.1}%)",
+        stats.total / 1024 / 1024,
+        stats.used / 1024,
+        stats.percent());
+}
@@ -0,0 +1,211 @@
+//! 🧮 مخصص الإطارات الفيزيائية (خريطة بتات)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use lazy_static::lazy_static;
+use spin::Mutex;
+
+use super::{MemoryRegion, MemoryRegionKind, FRAME_SIZE};
+
+#[cfg(not(feature = "hosted"))]
+use x86_64::PhysAddr;
+#[cfg(not(feature = "hosted"))]
+use x86_64::structures::paging::{FrameAllocator, FrameDeallocator, PhysFrame, Size4KiB};
+
+/// أقصى ذاكرة فيزيائية يتتبعها المخصص
+pub const MAX_PHYSICAL_MEMORY: u64 = 4 * 1024 * 1024 * 1024; // 4 جيجابايت
+
+/// عدد كلمات خريطة البتات اللازمة لـ `MAX_PHYSICAL_MEMORY`
+pub const BITMAP_WORDS: usize = (MAX_PHYSICAL_MEMORY / FRAME_SIZE / 64) as usize;
+
+lazy_static! {
+    /// مخصص الإطارات العام (يُهيأ في `memory::init`)
+    pub static ref FRAME_ALLOCATOR: Mutex<Option<BitmapFrameAllocator<'static>>> = Mutex::new(None);
+}
+
+/// مخصص إطارات بخريطة بتات: البت 1 يعني إطاراً مستخدماً أو غير متاح
+pub struct BitmapFrameAllocator<'a> {
+    bitmap: &'a mut [u64],
+    frame_count: usize,
+    usable_frames: usize,
+    used_frames: usize,
+    next_hint: usize,
+}
+
+impl<'a> BitmapFrameAllocator<'a> {
+    /// بناء المخصص من خريطة الذاكرة؛ كل ما ليس `Usable` يبقى محجوزاً
+    pub fn new(bitmap: &'a mut [u64], memory_map: &[MemoryRegion]) -> Self {
+        for word in bitmap.iter_mut() {
+            *word = u64::MAX;
+        }
+        
+        let frame_count = bitmap.len() * 64;
+        let mut allocator = Self {
+            bitmap,
+            frame_count,
+            usable_frames: 0,
+            used_frames: 0,
+            next_hint: 0,
+        };
+        
+        for region in memory_map.iter().filter(|r| r.kind == MemoryRegionKind::Usable) {
+            // تقريب الحدود إلى الداخل حتى لا نسلم إطاراً جزئياً
+            let first = region.start.div_ceil(FRAME_SIZE);
+            let last = region.end() / FRAME_SIZE;
+            
+            for frame in first..last {
+                let frame = frame as usize;
+                if frame < allocator.frame_count && allocator.is_used(frame) {
+                    allocator.set_used(frame, false);
+                    allocator.usable_frames += 1;
+                }
+            }
+        }
+        
+        allocator
+    }
+    
+    fn is_used(&self, frame: usize) -> bool {
+        self.bitmap[frame / 64] & (1 << (frame % 64)) != 0
+    }
+    
+    fn set_used(&mut self, frame: usize, used: bool) {
+        if used {
+            self.bitmap[frame / 64] |= 1 << (frame % 64);
+        } else {
+            self.bitmap[frame / 64] &= !(1 << (frame % 64));
+        }
+    }
+    
+    /// حجز مدى فيزيائي (صورة النواة، ذاكرة الأجهزة...) حتى لا يُخصص
+    pub fn reserve_range(&mut self, start: u64, end: u64) {
+        let first = (start / FRAME_SIZE) as usize;
+        let last = end.div_ceil(FRAME_SIZE) as usize;
+        
+        for frame in first..last.min(self.frame_count) {
+            if !self.is_used(frame) {
+                self.set_used(frame, true);
+                self.used_frames += 1;
+            }
+        }
+    }
+    
+    /// تخصيص إطار وإرجاع عنوانه الفيزيائي
+    pub fn allocate(&mut self) -> Option<u64> {
+        let words = self.bitmap.len();
+        
+        for offset in 0..words {
+            let index = (self.next_hint + offset) % words;
+            let word = self.bitmap[index];
+            if word == u64::MAX {
+                continue;
+            }
+            
+            let bit = (!word).trailing_zeros() as usize;
+            let frame = index * 64 + bit;
+            self.set_used(frame, true);
+            self.used_frames += 1;
+            self.next_hint = index;
+            
+            return Some(frame as u64 * FRAME_SIZE);
+        }
+        
+        None
+    }
+    
+    /// تخصيص `count` إطاراً متجاورة (لذاكرة الأجهزة) وإرجاع عنوان أولها
+    pub fn allocate_contiguous(&mut self, count: usize) -> Option<u64> {
+        if count == 0 {
+            return None;
+        }
+        
+        let mut run = 0;
+        for frame in 0..self.frame_count {
+            run = if self.is_used(frame) { 0 } else { run + 1 };
+            if run == count {
+                let first = frame + 1 - count;
+                for used in first..=frame {
+                    self.set_used(used, true);
+                }
+                self.used_frames += count;
+                return Some(first as u64 * FRAME_SIZE);
+            }
+        }
+        
+        None
+    }
+    
+    /// تحرير إطار مخصص سابقاً
+    pub fn deallocate(&mut self, address: u64) {
+        let frame = (address / FRAME_SIZE) as usize;
+        if frame < self.frame_count && self.is_used(frame) {
+            self.set_used(frame, false);
+            self.used_frames -= 1;
+            self.next_hint = frame / 64;
+        }
+    }
+    
+    /// إجمالي الذاكرة القابلة للاستخدام بالبايت
+    pub fn total_bytes(&self) -> u64 {
+        self.usable_frames as u64 * FRAME_SIZE
+    }
+    
+    /// الذاكرة المخصصة من المنطقة القابلة للاستخدام بالبايت
+    pub fn used_bytes(&self) -> u64 {
+        self.used_frames as u64 * FRAME_SIZE
+    }
+    
+    pub fn free_bytes(&self) -> u64 {
+        self.total_bytes() - self.used_bytes()
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+unsafe impl<'a> FrameAllocator<Size4KiB> for BitmapFrameAllocator<'a> {
+    fn allocate_frame(&mut self) -> Option<PhysFrame> {
+        self.allocate()
+            .map(|address| PhysFrame::containing_address(PhysAddr::new(address)))
+    }
+}
+
+#[cfg(not(feature = "hosted"))]
+impl<'a> FrameDeallocator<Size4KiB> for BitmapFrameAllocator<'a> {
+    unsafe fn deallocate_frame(&mut self, frame: PhysFrame) {
+        self.deallocate(frame.start_address().as_u64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    
+    #[test_case]
+    fn test_frames_come_only_from_usable_regions() {
+        let mut bitmap = vec![0u64; 4];
+        let map = [
+            MemoryRegion::new(0, 0x1000, MemoryRegionKind::Reserved),
+            MemoryRegion::new(0x1000, 0x3000, MemoryRegionKind::Usable),
+            MemoryRegion::new(0x4000, 0x1000, MemoryRegionKind::AcpiNvs),
+        ];
+        let mut allocator = BitmapFrameAllocator::new(&mut bitmap, &map);
+        assert_eq!(allocator.total_bytes(), 3 * FRAME_SIZE);
+        
+        allocator.reserve_range(0x1000, 0x1800);
+        assert_eq!(allocator.allocate(), Some(0x2000));
+        assert_eq!(allocator.allocate(), Some(0x3000));
+        assert_eq!(allocator.allocate(), None);
+        assert_eq!(allocator.free_bytes(), 0);
+        
+        allocator.deallocate(0x2000);
+        assert_eq!(allocator.used_bytes(), 2 * FRAME_SIZE);
+        assert_eq!(allocator.allocate(), Some(0x2000));
+        
+        // المتجاورة لا تعبر إطاراً مستخدماً
+        allocator.deallocate(0x1000);
+        allocator.deallocate(0x3000);
+        assert_eq!(allocator.allocate_contiguous(2), None);
+        allocator.deallocate(0x2000);
+        assert_eq!(allocator.allocate_contiguous(2), Some(0x1000));
+        assert_eq!(allocator.allocate(), Some(0x3000));
+    }
+}
@@ -0,0 +1,94 @@
+//! 🧱 كومة النواة وربطها بالإطارات الفيزيائية
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use linked_list_allocator::LockedHeap;
+use log::info;
+use x86_64::VirtAddr;
+use x86_64::structures::paging::PageTableFlags;
+
+use super::frame::{BitmapFrameAllocator, BITMAP_WORDS, FRAME_ALLOCATOR};
+use super::{paging, DmaRegion, MemoryRegion, FRAME_SIZE};
+use crate::MemoryStats;
+
+/// العنوان الافتراضي لبداية الكومة
+pub const HEAP_START: u64 = 0x_4444_4444_0000;
+
+/// حجم الكومة
+pub const HEAP_SIZE: usize = 4 * 1024 * 1024; // 4 ميجابايت
+
+/// خريطة بتات الإطارات (ثابتة لأن الكومة لا تعمل قبلها)
+static mut FRAME_BITMAP: [u64; BITMAP_WORDS] = [0; BITMAP_WORDS];
+
+// حدود صورة النواة من سكربت الربط `src/boot/linker.ld`
+extern "C" {
+    static __kernel_start: u8;
+    static __kernel_end: u8;
+}
+
+#[global_allocator]
+static ALLOCATOR: LockedHeap = LockedHeap::empty();
+
+pub fn init(memory_map: &[MemoryRegion], reserved: &[(u64, u64)]) {
+    let bitmap = unsafe { &mut *core::ptr::addr_of_mut!(FRAME_BITMAP) };
+    let mut frames = BitmapFrameAllocator::new(bitmap, memory_map);
+    
+    // أول ميجابايت (BIOS و VGA) وصورة النواة
+    frames.reserve_range(0, 0x10_0000);
+    let (kernel_start, kernel_end) = unsafe {
+        (&__kernel_start as *const u8 as u64, &__kernel_end as *const u8 as u64)
+    };
+    frames.reserve_range(kernel_start, kernel_end);
+    
+    for &(start, end) in reserved {
+        frames.reserve_range(start, end);
+    }
+    
+    *FRAME_ALLOCATOR.lock() = Some(frames);
+    
+    unsafe {
+        paging::init();
+    }
+    
+    let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE | PageTableFlags::NO_EXECUTE;
+    paging::map_range(VirtAddr::new(HEAP_START), HEAP_SIZE as u64, flags)
+        .expect("فشل ربط صفحات الكومة");
+    
+    unsafe {
+        ALLOCATOR.lock().init(HEAP_START as *mut u8, HEAP_SIZE);
+    }
+    
+    info!("🧱 الكومة: {} كيلوبايت عند {:#x}", HEAP_SIZE / 1024, HEAP_START);
+}
+
+/// إطارات متجاورة مربوطة على عنوانها الفيزيائي المطابق
+pub fn alloc_dma(size: usize) -> Option<DmaRegion> {
+    let pages = size / FRAME_SIZE as usize;
+    let phys = FRAME_ALLOCATOR.lock().as_mut()?.allocate_contiguous(pages)?;
+    let flags = PageTableFlags::WRITABLE | PageTableFlags::NO_EXECUTE;
+    let virt = paging::map_physical(phys, size as u64, flags).ok()?;
+    Some(DmaRegion { phys, virt: virt.as_mut_ptr(), size })
+}
+
+/// الإطارات المحجوزة ناقص الجزء الحر من الكومة = الاستخدام الحقيقي
+pub fn usage_stats() -> MemoryStats {
+    let frames = FRAME_ALLOCATOR.lock();
+    let frames = match frames.as_ref() {
+        Some(frames) => frames,
+        None => return MemoryStats::default(),
+    };
+    
+    let total = frames.total_bytes() as usize;
+    let used = (frames.used_bytes() as usize).saturating_sub(ALLOCATOR.lock().free());
+    
+    MemoryStats {
+        total,
+        used,
+        free: total - used,
+        cached: 0,
+    }
+}
+
+pub fn emergency_cleanup() {
+    let stats = usage_stats();
+    log::warn!("💾 الذاكرة عند الذعر: {}/{} بايت", stats.used, stats.total);
+}
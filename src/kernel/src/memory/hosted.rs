@@ -0,0 +1,90 @@
+//! 🖥️ ذاكرة وهمية لوضع المحاكاة المستضافة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تعتمد على مخصص النظام في لينكس مع عدّ البايتات المخصصة، بحيث تعكس
+//! `MemoryStats` استخدام النواة الحقيقي داخل العملية.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use spin::Mutex;
+use std::alloc::{GlobalAlloc, Layout, System};
+
+use super::{DmaRegion, MemoryRegion, MemoryRegionKind, FRAME_SIZE};
+use crate::MemoryStats;
+
+/// حجم الذاكرة المحاكاة = مجموع المناطق القابلة للاستخدام في الخريطة
+static SIMULATED_TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        }
+        ptr
+    }
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+pub fn init(memory_map: &[MemoryRegion], _reserved: &[(u64, u64)]) {
+    let total: u64 = memory_map.iter()
+        .filter(|region| region.kind == MemoryRegionKind::Usable)
+        .map(|region| region.length)
+        .sum();
+    SIMULATED_TOTAL.store(total as usize, Ordering::Relaxed);
+    
+    log::debug!("🖥️ [محاكاة] الذاكرة: {} ميجابايت", total / 1024 / 1024);
+}
+
+pub fn usage_stats() -> MemoryStats {
+    let total = SIMULATED_TOTAL.load(Ordering::Relaxed);
+    let used = ALLOCATED.load(Ordering::Relaxed).min(total);
+    MemoryStats {
+        total,
+        used,
+        free: total - used,
+        cached: 0,
+    }
+}
+
+/// أول عنوان "فيزيائي" لذاكرة DMA المحاكاة (فوق أول 256 ميجابايت)
+const DMA_BASE: u64 = 0x1000_0000;
+
+static NEXT_DMA: AtomicU64 = AtomicU64::new(DMA_BASE);
+
+/// مناطق DMA: (العنوان الفيزيائي، المؤشر، الحجم)
+static DMA_REGIONS: Mutex<Vec<(u64, usize, usize)>> = Mutex::new(Vec::new());
+
+/// مؤشرات العملية لا تتسع لمسجلات الأجهزة ذات 32 بت (مثل PFN في virtio)،
+/// فتأخذ كل منطقة عنواناً فيزيائياً منخفضاً تترجمه الأجهزة المحاكاة عبر
+/// `dma_pointer`
+pub fn alloc_dma(size: usize) -> Option<DmaRegion> {
+    let layout = Layout::from_size_align(size, FRAME_SIZE as usize).ok()?;
+    let virt = unsafe { ALLOCATOR.alloc(layout) };
+    if virt.is_null() {
+        return None;
+    }
+    let phys = NEXT_DMA.fetch_add(size as u64, Ordering::Relaxed);
+    DMA_REGIONS.lock().push((phys, virt as usize, size));
+    Some(DmaRegion { phys, virt, size })
+}
+
+/// المؤشر المقابل لعنوان DMA فيزيائي
+pub fn dma_pointer(phys: u64) -> Option<*mut u8> {
+    DMA_REGIONS.lock().iter()
+        .find(|&&(start, _, size)| (start..start + size as u64).contains(&phys))
+        .map(|&(start, virt, _)| (virt + (phys - start) as usize) as *mut u8)
+}
+
+pub fn emergency_cleanup() {}
@@ -0,0 +1,103 @@
+//! 💾 إدارة الذاكرة لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! مخصص الإطارات الفيزيائية يُبنى من خريطة ذاكرة البرنامج الثابت، وجداول
+//! الصفحات رباعية المستويات تربط كومة النواة (`linked_list_allocator`) بإطارات
+//! حقيقية، فتعكس `MemoryStats` الاستخدام الفعلي.
+
+pub mod advanced;
+pub mod frame;
+
+#[cfg(not(feature = "hosted"))]
+pub mod paging;
+
+#[cfg(not(feature = "hosted"))]
+mod heap;
+#[cfg(not(feature = "hosted"))]
+use self::heap as backend;
+
+#[cfg(feature = "hosted")]
+mod hosted;
+#[cfg(feature = "hosted")]
+use self::hosted as backend;
+#[cfg(feature = "hosted")]
+pub use self::hosted::dma_pointer;
+
+use crate::MemoryStats;
+
+/// حجم الإطار/الصفحة
+pub const FRAME_SIZE: u64 = 4096;
+
+/// نوع منطقة في خريطة الذاكرة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRegionKind {
+    Usable,
+    Reserved,
+    AcpiReclaimable,
+    AcpiNvs,
+    BadMemory,
+}
+
+/// منطقة من خريطة ذاكرة البرنامج الثابت
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryRegion {
+    pub start: u64,
+    pub length: u64,
+    pub kind: MemoryRegionKind,
+}
+
+impl MemoryRegion {
+    pub const fn new(start: u64, length: u64, kind: MemoryRegionKind) -> Self {
+        Self { start, length, kind }
+    }
+    
+    pub fn end(&self) -> u64 {
+        self.start + self.length
+    }
+}
+
+/// خريطة احتياطية عند غياب معلومات Multiboot2 صالحة: 127 ميجابايت فوق
+/// أول ميجابايت (الحد الأدنى لذاكرة QEMU الافتراضية)
+pub const FALLBACK_MEMORY_MAP: [MemoryRegion; 2] = [
+    MemoryRegion::new(0, 0x10_0000, MemoryRegionKind::Reserved),
+    MemoryRegion::new(0x10_0000, 0x7F0_0000, MemoryRegionKind::Usable),
+];
+
+/// تهيئة الذاكرة الأساسية: مخصص الإطارات ثم جداول الصفحات والكومة
+/// (`reserved` مديات فيزيائية يجب ألا تُخصص، مثل معلومات الإقلاع و initrd)
+pub fn init(memory_map: &[MemoryRegion], reserved: &[(u64, u64)]) {
+    backend::init(memory_map, reserved);
+}
+
+/// إحصائيات استخدام الذاكرة الحالية
+pub(crate) fn get_usage_stats() -> MemoryStats {
+    backend::usage_stats()
+}
+
+/// ذاكرة متجاورة فيزيائياً لنقل الأجهزة المباشر (DMA)؛ لا تُحرر أبداً
+#[derive(Debug, Clone, Copy)]
+pub struct DmaRegion {
+    /// العنوان الذي يُعطى للجهاز
+    pub phys: u64,
+    /// العنوان الذي تستخدمه النواة
+    pub virt: *mut u8,
+    pub size: usize,
+}
+
+// الذاكرة ملك للتعريف الذي خصصها ولا يلمسها غيره
+unsafe impl Send for DmaRegion {}
+unsafe impl Sync for DmaRegion {}
+
+/// تخصيص ذاكرة DMA مصفرة بإطارات كاملة
+pub fn alloc_dma(size: usize) -> Option<DmaRegion> {
+    let size = (size.max(1) as u64).div_ceil(FRAME_SIZE) * FRAME_SIZE;
+    let region = backend::alloc_dma(size as usize)?;
+    unsafe { core::ptr::write_bytes(region.virt, 0, region.size) };
+    Some(region)
+}
+
+/// تنظيف الذاكرة بعد الذعر
+pub fn emergency_cleanup() {
+    log::warn!("🧹 تنظيف الذاكرة الطارئ...");
+    backend::emergency_cleanup();
+}
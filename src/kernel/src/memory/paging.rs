@@ -0,0 +1,115 @@
+//! 📄 إدارة جداول الصفحات رباعية المستويات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use lazy_static::lazy_static;
+use spin::Mutex;
+use x86_64::{PhysAddr, VirtAddr};
+use x86_64::registers::control::{Cr3, Efer, EferFlags};
+use x86_64::structures::paging::{
+    FrameAllocator, Mapper, OffsetPageTable, Page, PageTable, PageTableFlags, PhysFrame, Size4KiB, Translate,
+};
+use x86_64::structures::paging::mapper::{MapToError, UnmapError};
+
+use super::frame::FRAME_ALLOCATOR;
+
+/// إزاحة الذاكرة الفيزيائية: `boot_entry` يربط أول جيجابايت ربطاً مطابقاً
+pub const PHYSICAL_MEMORY_OFFSET: u64 = 0;
+
+lazy_static! {
+    static ref MAPPER: Mutex<Option<OffsetPageTable<'static>>> = Mutex::new(None);
+}
+
+/// أخطاء الربط
+#[derive(Debug)]
+pub enum PagingError {
+    NotInitialized,
+    Map(MapToError<Size4KiB>),
+    Unmap(UnmapError),
+}
+
+/// تهيئة مدير الجداول فوق جدول المستوى الرابع الحالي (CR3)
+///
+/// # Safety
+/// تُستدعى مرة واحدة، والذاكرة الفيزيائية كلها مربوطة عند `PHYSICAL_MEMORY_OFFSET`
+pub unsafe fn init() {
+    // السماح ببت NO_EXECUTE قبل استخدامه في أي مدخل
+    Efer::update(|flags| flags.insert(EferFlags::NO_EXECUTE_ENABLE));
+    
+    let (level_4_frame, _) = Cr3::read();
+    let virt = PHYSICAL_MEMORY_OFFSET + level_4_frame.start_address().as_u64();
+    let level_4_table = &mut *(virt as *mut PageTable);
+    
+    *MAPPER.lock() = Some(OffsetPageTable::new(level_4_table, VirtAddr::new(PHYSICAL_MEMORY_OFFSET)));
+}
+
+/// ربط مدى افتراضي بإطارات جديدة من مخصص الإطارات
+pub fn map_range(start: VirtAddr, size: u64, flags: PageTableFlags) -> Result<(), PagingError> {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().ok_or(PagingError::NotInitialized)?;
+    let mut frames = FRAME_ALLOCATOR.lock();
+    let frames = frames.as_mut().ok_or(PagingError::NotInitialized)?;
+    
+    let first = Page::<Size4KiB>::containing_address(start);
+    let last = Page::<Size4KiB>::containing_address(start + size - 1u64);
+    
+    for page in Page::range_inclusive(first, last) {
+        let frame = frames.allocate_frame().ok_or(PagingError::Map(MapToError::FrameAllocationFailed))?;
+        unsafe {
+            mapper.map_to(page, frame, flags, frames)
+                .map_err(PagingError::Map)?
+                .flush();
+        }
+    }
+    
+    Ok(())
+}
+
+/// ربط مدى فيزيائي (ذاكرة أجهزة، جداول ACPI) على عنوانه المطابق
+pub fn map_physical(phys: u64, size: u64, flags: PageTableFlags) -> Result<VirtAddr, PagingError> {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().ok_or(PagingError::NotInitialized)?;
+    let mut frames = FRAME_ALLOCATOR.lock();
+    let frames = frames.as_mut().ok_or(PagingError::NotInitialized)?;
+    
+    let first = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(phys));
+    let last = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(phys + size.max(1) - 1));
+    
+    for frame in PhysFrame::range_inclusive(first, last) {
+        let page = Page::<Size4KiB>::containing_address(VirtAddr::new(PHYSICAL_MEMORY_OFFSET + frame.start_address().as_u64()));
+        if mapper.translate_addr(page.start_address()).is_some() {
+            continue;
+        }
+        
+        unsafe {
+            mapper.map_to(page, frame, flags | PageTableFlags::PRESENT, frames)
+                .map_err(PagingError::Map)?
+                .flush();
+        }
+    }
+    
+    Ok(VirtAddr::new(PHYSICAL_MEMORY_OFFSET + phys))
+}
+
+/// فك ربط مدى وإعادة إطاراته إلى المخصص
+pub fn unmap_range(start: VirtAddr, size: u64) -> Result<(), PagingError> {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().ok_or(PagingError::NotInitialized)?;
+    let mut frames = FRAME_ALLOCATOR.lock();
+    let frames = frames.as_mut().ok_or(PagingError::NotInitialized)?;
+    
+    let first = Page::<Size4KiB>::containing_address(start);
+    let last = Page::<Size4KiB>::containing_address(start + size - 1u64);
+    
+    for page in Page::range_inclusive(first, last) {
+        let (frame, flush) = mapper.unmap(page).map_err(PagingError::Unmap)?;
+        flush.flush();
+        frames.deallocate(frame.start_address().as_u64());
+    }
+    
+    Ok(())
+}
+
+/// ترجمة عنوان افتراضي إلى فيزيائي
+pub fn translate(addr: VirtAddr) -> Option<PhysAddr> {
+    MAPPER.lock().as_ref()?.translate_addr(addr)
+}
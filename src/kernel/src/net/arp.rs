@@ -0,0 +1,99 @@
+//! 📇 ARP: من عنوان IPv4 إلى عنوان MAC
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الحزمة 28 بايت لإيثرنت و IPv4. كل طلب يصلنا يعلّمنا عنوان مرسله
+//! (RFC 826)، والمداخل تنتهي بعد `ENTRY_TTL_MS`.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use super::ethernet::{MacAddress, ETHERTYPE_IPV4};
+use super::ipv4::Ipv4Addr;
+
+/// طول الحزمة
+pub const PACKET_LEN: usize = 28;
+
+/// العمليات
+pub const REQUEST: u16 = 1;
+pub const REPLY: u16 = 2;
+
+/// عمر المدخل في الجدول
+pub const ENTRY_TTL_MS: u64 = 300_000;
+
+/// أقصى عدد للمداخل
+const MAX_ENTRIES: usize = 256;
+
+/// حزمة ARP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpPacket {
+    pub operation: u16,
+    pub sender_mac: MacAddress,
+    pub sender_ip: Ipv4Addr,
+    pub target_mac: MacAddress,
+    pub target_ip: Ipv4Addr,
+}
+
+impl ArpPacket {
+    /// قراءة حزمة إيثرنت/IPv4 فقط
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < PACKET_LEN || bytes[0..2] != [0, 1] || u16::from_be_bytes([bytes[2], bytes[3]]) != ETHERTYPE_IPV4 || bytes[4..6] != [6, 4] {
+            return None;
+        }
+        Some(Self {
+            operation: u16::from_be_bytes([bytes[6], bytes[7]]),
+            sender_mac: MacAddress(bytes[8..14].try_into().unwrap()),
+            sender_ip: Ipv4Addr(bytes[14..18].try_into().unwrap()),
+            target_mac: MacAddress(bytes[18..24].try_into().unwrap()),
+            target_ip: Ipv4Addr(bytes[24..28].try_into().unwrap()),
+        })
+    }
+    
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(PACKET_LEN);
+        bytes.extend_from_slice(&[0, 1]);
+        bytes.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        bytes.extend_from_slice(&[6, 4]);
+        bytes.extend_from_slice(&self.operation.to_be_bytes());
+        bytes.extend_from_slice(&self.sender_mac.0);
+        bytes.extend_from_slice(&self.sender_ip.0);
+        bytes.extend_from_slice(&self.target_mac.0);
+        bytes.extend_from_slice(&self.target_ip.0);
+        bytes
+    }
+}
+
+/// جدول ARP: العنوان ← (MAC، وقت الانتهاء)
+#[derive(Debug, Default)]
+pub struct ArpCache {
+    entries: BTreeMap<Ipv4Addr, (MacAddress, u64)>,
+}
+
+impl ArpCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn lookup(&self, ip: Ipv4Addr, now: u64) -> Option<MacAddress> {
+        self.entries.get(&ip).filter(|&&(_, expires)| expires > now).map(|&(mac, _)| mac)
+    }
+    
+    pub fn insert(&mut self, ip: Ipv4Addr, mac: MacAddress, now: u64) {
+        if self.entries.len() >= MAX_ENTRIES && !self.entries.contains_key(&ip) {
+            self.expire(now);
+            if self.entries.len() >= MAX_ENTRIES {
+                return;
+            }
+        }
+        self.entries.insert(ip, (mac, now + ENTRY_TTL_MS));
+    }
+    
+    /// إزالة المداخل المنتهية
+    pub fn expire(&mut self, now: u64) {
+        self.entries.retain(|_, &mut (_, expires)| expires > now);
+    }
+    
+    /// المداخل الصالحة
+    pub fn entries(&self, now: u64) -> Vec<(Ipv4Addr, MacAddress)> {
+        self.entries.iter().filter(|(_, &(_, expires))| expires > now).map(|(&ip, &(mac, _))| (ip, mac)).collect()
+    }
+}
@@ -0,0 +1,106 @@
+//! 🔁 خدمة الصدى (RFC 862) على المنفذ 7
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تعيد كل ما يصلها عبر TCP و UDP، لاختبار المكدس من المضيف:
+//!
+//! ```text
+//! qemu ... -nic user,model=virtio-net-pci,hostfwd=tcp::7007-:7,hostfwd=udp::7007-:7
+//! nc localhost 7007
+//! ```
+
+use alloc::vec::Vec;
+use log::{info, warn};
+use spin::Mutex;
+
+use super::{NetError, TcpListener, TcpStream, UdpSocket};
+use crate::process::timer;
+
+/// منفذ الخدمة
+pub const PORT: u16 = 7;
+
+/// فترة الخدمة
+const INTERVAL_MS: u64 = 10;
+
+/// أقصى عدد اتصالات متزامنة
+const MAX_CLIENTS: usize = 8;
+
+struct Client {
+    stream: TcpStream,
+    /// ما قُرئ ولم يتسع له مخزن الإرسال بعد
+    pending: Vec<u8>,
+}
+
+struct Service {
+    listener: TcpListener,
+    udp: UdpSocket,
+    clients: Vec<Client>,
+}
+
+static SERVICE: Mutex<Option<Service>> = Mutex::new(None);
+
+/// فتح المنفذ وتسجيل الخدمة الدورية
+pub fn start() {
+    let service = TcpListener::bind(PORT, MAX_CLIENTS).and_then(|listener| {
+        let udp = UdpSocket::bind(PORT)?;
+        Ok(Service { listener, udp, clients: Vec::new() })
+    });
+    match service {
+        Ok(service) => {
+            *SERVICE.lock() = Some(service);
+            timer::every("net-echo", INTERVAL_MS, serve);
+            info!("  🔁 خدمة الصدى على المنفذ {}", PORT);
+        }
+        Err(e) => warn!("⚠️ تعذر تشغيل خدمة الصدى: {:?}", e),
+    }
+}
+
+/// دورة واحدة: قبول الجديد ثم إعادة ما وصل
+pub fn serve() {
+    let mut guard = SERVICE.lock();
+    let service = match guard.as_mut() {
+        Some(service) => service,
+        None => return,
+    };
+    
+    while let Ok((from, payload)) = service.udp.recv_from() {
+        let _ = service.udp.send_to(from, &payload);
+    }
+    
+    while service.clients.len() < MAX_CLIENTS {
+        match service.listener.accept() {
+            Ok((stream, _)) => service.clients.push(Client { stream, pending: Vec::new() }),
+            Err(_) => break,
+        }
+    }
+    
+    // الاتصال الذي أغلقه الطرف الآخر يُغلق بعد إعادة ما بقي له
+    service.clients.retain_mut(|client| {
+        if !client.pending.is_empty() {
+            match client.stream.send(&client.pending) {
+                Ok(sent) => {
+                    client.pending.drain(..sent);
+                }
+                Err(NetError::WouldBlock) => return true,
+                Err(_) => return false,
+            }
+        }
+        
+        let mut buf = [0u8; 1024];
+        while client.pending.is_empty() {
+            match client.stream.recv(&mut buf) {
+                Ok(0) => {
+                    let _ = client.stream.close();
+                    return false;
+                }
+                Ok(count) => match client.stream.send(&buf[..count]) {
+                    Ok(sent) => client.pending.extend_from_slice(&buf[sent..count]),
+                    Err(NetError::WouldBlock) => client.pending.extend_from_slice(&buf[..count]),
+                    Err(_) => return false,
+                },
+                Err(NetError::WouldBlock) => break,
+                Err(_) => return false,
+            }
+        }
+        true
+    });
+}
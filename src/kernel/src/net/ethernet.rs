@@ -0,0 +1,83 @@
+//! 🔗 إطارات إيثرنت
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! ```text
+//! [الوجهة 6][المصدر 6][النوع 2][الحمولة 46-1500]
+//! ```
+//!
+//! بلا وسوم VLAN؛ الإطار الأقصر من 60 بايت يُكمل بأصفار (بطاقة الشبكة تضيف
+//! مجموع FCS).
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// طول الترويسة
+pub const HEADER_LEN: usize = 14;
+
+/// أقصى حمولة
+pub const MTU: usize = 1500;
+
+/// أقصر إطار بلا FCS
+pub const MIN_FRAME: usize = 60;
+
+/// أنواع الحمولة
+pub const ETHERTYPE_IPV4: u16 = 0x0800;
+pub const ETHERTYPE_ARP: u16 = 0x0806;
+
+/// عنوان MAC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MacAddress(pub [u8; 6]);
+
+impl MacAddress {
+    pub const BROADCAST: MacAddress = MacAddress([0xFF; 6]);
+    
+    pub fn is_broadcast(&self) -> bool {
+        *self == Self::BROADCAST
+    }
+    
+    /// عناوين البث المتعدد (والبث) بتها الأدنى في البايت الأول 1
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 1 != 0
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let b = &self.0;
+        write!(f, "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", b[0], b[1], b[2], b[3], b[4], b[5])
+    }
+}
+
+/// إطار مقروء
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame<'a> {
+    pub destination: MacAddress,
+    pub source: MacAddress,
+    pub ethertype: u16,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Frame<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < HEADER_LEN {
+            return None;
+        }
+        Some(Self {
+            destination: MacAddress(bytes[0..6].try_into().unwrap()),
+            source: MacAddress(bytes[6..12].try_into().unwrap()),
+            ethertype: u16::from_be_bytes([bytes[12], bytes[13]]),
+            payload: &bytes[HEADER_LEN..],
+        })
+    }
+}
+
+/// بناء إطار (مع الإكمال إلى الحد الأدنى)
+pub fn build(destination: MacAddress, source: MacAddress, ethertype: u16, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity((HEADER_LEN + payload.len()).max(MIN_FRAME));
+    frame.extend_from_slice(&destination.0);
+    frame.extend_from_slice(&source.0);
+    frame.extend_from_slice(&ethertype.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame.resize(frame.len().max(MIN_FRAME), 0);
+    frame
+}
@@ -0,0 +1,59 @@
+//! 📡 ICMP: الصدى (ping) ورسائل عدم الوصول
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use alloc::vec::Vec;
+
+use super::ipv4::checksum;
+
+/// الأنواع التي نفهمها
+pub const ECHO_REPLY: u8 = 0;
+pub const DESTINATION_UNREACHABLE: u8 = 3;
+pub const ECHO_REQUEST: u8 = 8;
+
+/// رمز "المنفذ غير متاح" تحت `DESTINATION_UNREACHABLE`
+pub const PORT_UNREACHABLE: u8 = 3;
+
+/// رسالة صدى
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Echo {
+    pub kind: u8,
+    pub identifier: u16,
+    pub sequence: u16,
+    pub data: Vec<u8>,
+}
+
+impl Echo {
+    /// قراءة طلب أو رد صدى بمجموع تحقق صحيح
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 8 || !matches!(bytes[0], ECHO_REQUEST | ECHO_REPLY) || bytes[1] != 0 || checksum(bytes) != 0 {
+            return None;
+        }
+        Some(Self {
+            kind: bytes[0],
+            identifier: u16::from_be_bytes([bytes[4], bytes[5]]),
+            sequence: u16::from_be_bytes([bytes[6], bytes[7]]),
+            data: bytes[8..].to_vec(),
+        })
+    }
+    
+    pub fn to_bytes(&self) -> Vec<u8> {
+        message(self.kind, 0, &[&self.identifier.to_be_bytes()[..], &self.sequence.to_be_bytes(), &self.data].concat())
+    }
+}
+
+/// "المنفذ غير متاح" ردّاً على `packet` (ترويسة IP وأول 8 بايت بعدها)
+pub fn port_unreachable(packet: &[u8]) -> Vec<u8> {
+    let header_len = (packet[0] & 0xF) as usize * 4;
+    let quoted = &packet[..packet.len().min(header_len + 8)];
+    message(DESTINATION_UNREACHABLE, PORT_UNREACHABLE, &[&[0u8; 4][..], quoted].concat())
+}
+
+/// رسالة بنوعها ورمزها وما بعد مجموع التحقق
+fn message(kind: u8, code: u8, rest: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(4 + rest.len());
+    bytes.extend_from_slice(&[kind, code, 0, 0]);
+    bytes.extend_from_slice(rest);
+    let sum = checksum(&bytes);
+    bytes[2..4].copy_from_slice(&sum.to_be_bytes());
+    bytes
+}
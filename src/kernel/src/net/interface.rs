@@ -0,0 +1,827 @@
+//! 🔗 الواجهة: عنوان واحد على بطاقة واحدة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الواجهة لا تعرف البطاقة: تأخذ الإطارات الواردة بـ `receive` وتضع الصادرة
+//! في صندوق يفرغه `transmit`، والوقت يُمرر إليها بالميلي ثانية. لذلك يمكن
+//! وصل واجهتين ببعضهما في الاختبارات بلا عتاد:
+//!
+//! ```text
+//! إطار ──receive──▶ ARP / IPv4 ──▶ ICMP (صدى)، UDP (طابور المقبس)، TCP (Tcb)
+//! poll ──▶ مؤقتات TCP وطلبات ARP ──▶ صندوق الصادر ──transmit──▶ البطاقة
+//! ```
+//!
+//! الحزم إلى عنوان لم يُعرف MAC له تنتظر رد ARP ثلاث ثوانٍ ثم تُسقط.
+
+use alloc::collections::{btree_map, BTreeMap, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use super::arp::{self, ArpCache, ArpPacket};
+use super::ethernet::{self, Frame, MacAddress, ETHERTYPE_ARP, ETHERTYPE_IPV4, MTU};
+use super::icmp::{self, Echo};
+use super::ipv4::{self, Ipv4Addr, Packet, SocketAddr, PROTOCOL_ICMP, PROTOCOL_TCP, PROTOCOL_UDP};
+use super::tcp::{self, Listener, Segment, Tcb, TcpState};
+use super::udp::{self, Datagram, UdpSocket};
+use super::{NetError, SocketHandle};
+use crate::arch;
+
+/// أول منفذ مؤقت وآخره (IANA)
+pub const EPHEMERAL_PORTS: core::ops::RangeInclusive<u16> = 49152..=65535;
+
+/// أكبر حمولة UDP في إطار واحد
+pub const MAX_DATAGRAM: usize = MTU - ipv4::HEADER_LEN - udp::HEADER_LEN;
+
+/// إعادة طلب ARP كل ثانية، والتخلي بعد ثلاث
+const ARP_RETRY_MS: u64 = 1000;
+const ARP_TIMEOUT_MS: u64 = 3000;
+
+/// أقصى عدد حزم تنتظر ARP
+const MAX_PENDING: usize = 64;
+
+/// معرّف طلبات الصدى التي نرسلها
+const PING_IDENTIFIER: u16 = 0x4948;
+
+/// ردود الصدى المحفوظة
+const MAX_PINGS: usize = 16;
+
+/// عنوان الواجهة وشبكتها
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub address: Ipv4Addr,
+    pub prefix: u8,
+    pub gateway: Option<Ipv4Addr>,
+}
+
+impl Config {
+    /// من "10.0.2.15/24" وبوابة اختيارية
+    pub fn parse(cidr: &str, gateway: Option<&str>) -> Option<Self> {
+        let (address, prefix) = match cidr.split_once('/') {
+            Some((address, prefix)) => (address, prefix.parse().ok().filter(|&prefix| prefix <= 32)?),
+            None => (cidr, 24),
+        };
+        let gateway = match gateway {
+            Some(gateway) => Some(gateway.parse().ok()?),
+            None => None,
+        };
+        Some(Self { address: address.parse().ok()?, prefix, gateway })
+    }
+    
+    pub fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::netmask(self.prefix)
+    }
+    
+    /// هل العنوان في شبكتنا المحلية؟
+    pub fn is_local(&self, ip: Ipv4Addr) -> bool {
+        let mask = self.netmask().to_u32();
+        ip.to_u32() & mask == self.address.to_u32() & mask
+    }
+    
+    /// عنوان البث في شبكتنا
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from_u32(self.address.to_u32() | !self.netmask().to_u32())
+    }
+}
+
+/// عدادات /proc/net/dev
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Statistics {
+    pub rx_packets: u64,
+    pub rx_bytes: u64,
+    pub rx_dropped: u64,
+    pub tx_packets: u64,
+    pub tx_bytes: u64,
+    pub tx_dropped: u64,
+}
+
+enum Socket {
+    Udp(UdpSocket),
+    Tcp(Tcb),
+    Listener(Listener),
+}
+
+struct Entry {
+    socket: Socket,
+    /// المستمع الذي ولّد الاتصال، حتى يأخذه `tcp_accept`
+    parent: Option<SocketHandle>,
+    /// حرره المستخدم: يُزال حين ينتهي إغلاقه
+    orphan: bool,
+}
+
+/// طلب ARP قائم: (وقت أول طلب، وقت آخر طلب)
+type ArpRequest = (u64, u64);
+
+/// الواجهة
+pub struct Interface {
+    mac: MacAddress,
+    config: Config,
+    arp: ArpCache,
+    arp_requests: BTreeMap<Ipv4Addr, ArpRequest>,
+    /// حزم IP تنتظر عنوان MAC للقفزة التالية
+    pending: Vec<(Ipv4Addr, Vec<u8>)>,
+    outbox: VecDeque<Vec<u8>>,
+    sockets: BTreeMap<SocketHandle, Entry>,
+    next_handle: u32,
+    next_port: u16,
+    ip_id: u16,
+    /// طلبات الصدى: الرقم ← (وقت الإرسال، زمن الذهاب والإياب)
+    pings: BTreeMap<u16, (u64, Option<u64>)>,
+    stats: Statistics,
+}
+
+impl Interface {
+    pub fn new(mac: MacAddress, config: Config) -> Self {
+        let seed = arch::cpu::random_u64();
+        Self {
+            mac,
+            config,
+            arp: ArpCache::new(),
+            arp_requests: BTreeMap::new(),
+            pending: Vec::new(),
+            outbox: VecDeque::new(),
+            sockets: BTreeMap::new(),
+            next_handle: 1,
+            next_port: EPHEMERAL_PORTS.start() + (seed % 16384) as u16,
+            ip_id: (seed >> 16) as u16,
+            pings: BTreeMap::new(),
+            stats: Statistics::default(),
+        }
+    }
+    
+    pub fn mac(&self) -> MacAddress {
+        self.mac
+    }
+    
+    /// بطاقة جديدة حلت محل القديمة؛ الجيران يتعلمون العنوان من طلبات ARP
+    pub fn set_mac(&mut self, mac: MacAddress) {
+        self.mac = mac;
+    }
+    
+    pub fn config(&self) -> Config {
+        self.config
+    }
+    
+    pub fn statistics(&self) -> Statistics {
+        self.stats
+    }
+    
+    pub fn arp_cache(&self) -> &ArpCache {
+        &self.arp
+    }
+    
+    /// الإطار التالي للإرسال
+    pub fn transmit(&mut self) -> Option<Vec<u8>> {
+        let frame = self.outbox.pop_front()?;
+        self.stats.tx_packets += 1;
+        self.stats.tx_bytes += frame.len() as u64;
+        Some(frame)
+    }
+    
+    /// إطار وصل من البطاقة
+    pub fn receive(&mut self, bytes: &[u8], now: u64) {
+        self.stats.rx_packets += 1;
+        self.stats.rx_bytes += bytes.len() as u64;
+        
+        let frame = match Frame::parse(bytes) {
+            Some(frame) if frame.destination == self.mac || frame.destination.is_broadcast() => frame,
+            _ => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        match frame.ethertype {
+            ETHERTYPE_ARP => self.receive_arp(frame.payload, now),
+            ETHERTYPE_IPV4 => self.receive_ipv4(frame.payload, now),
+            _ => self.stats.rx_dropped += 1,
+        }
+    }
+    
+    /// المؤقتات: إعادة طلبات ARP، ومؤقتات TCP وإرسال ما تراكم
+    pub fn poll(&mut self, now: u64) {
+        self.arp.expire(now);
+        
+        let mut expired = Vec::new();
+        let mut retry = Vec::new();
+        for (&ip, request) in self.arp_requests.iter_mut() {
+            if now.saturating_sub(request.0) >= ARP_TIMEOUT_MS {
+                expired.push(ip);
+            } else if now.saturating_sub(request.1) >= ARP_RETRY_MS {
+                request.1 = now;
+                retry.push(ip);
+            }
+        }
+        for ip in expired {
+            self.arp_requests.remove(&ip);
+            let before = self.pending.len();
+            self.pending.retain(|(hop, _)| *hop != ip);
+            self.stats.tx_dropped += (before - self.pending.len()) as u64;
+        }
+        for ip in retry {
+            self.send_arp(arp::REQUEST, MacAddress::BROADCAST, MacAddress([0; 6]), ip);
+        }
+        
+        let handles: Vec<SocketHandle> = self.sockets.keys().copied().collect();
+        for handle in handles {
+            if let Some(Entry { socket: Socket::Tcp(tcb), .. }) = self.sockets.get_mut(&handle) {
+                tcb.poll(now);
+            }
+            self.flush(handle, now);
+        }
+        self.reap();
+    }
+    
+    fn receive_arp(&mut self, payload: &[u8], now: u64) {
+        let packet = match ArpPacket::parse(payload) {
+            Some(packet) => packet,
+            None => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        
+        // تحديث مدخل معروف، أو تعلّم مرسل يخاطبنا (RFC 826)
+        let for_us = packet.target_ip == self.config.address;
+        if for_us || self.arp.lookup(packet.sender_ip, now).is_some() {
+            self.arp.insert(packet.sender_ip, packet.sender_mac, now);
+            self.release_pending(packet.sender_ip, packet.sender_mac);
+        }
+        if for_us && packet.operation == arp::REQUEST {
+            self.send_arp(arp::REPLY, packet.sender_mac, packet.sender_mac, packet.sender_ip);
+        }
+    }
+    
+    fn receive_ipv4(&mut self, payload: &[u8], now: u64) {
+        let packet = match Packet::parse(payload) {
+            Some(packet) if self.accepts(packet.destination) => packet,
+            _ => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        let broadcast = packet.destination != self.config.address;
+        
+        match packet.protocol {
+            PROTOCOL_ICMP => self.receive_icmp(&packet, broadcast, now),
+            PROTOCOL_UDP => self.receive_udp(&packet, payload, broadcast, now),
+            PROTOCOL_TCP if !broadcast => self.receive_tcp(&packet, now),
+            _ => self.stats.rx_dropped += 1,
+        }
+    }
+    
+    fn accepts(&self, destination: Ipv4Addr) -> bool {
+        destination == self.config.address || destination == Ipv4Addr::BROADCAST || destination == self.config.broadcast()
+    }
+    
+    fn receive_icmp(&mut self, packet: &Packet, broadcast: bool, now: u64) {
+        let echo = match Echo::parse(packet.payload) {
+            Some(echo) => echo,
+            None => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        match echo.kind {
+            icmp::ECHO_REQUEST if !broadcast => {
+                let reply = Echo { kind: icmp::ECHO_REPLY, ..echo };
+                let _ = self.send_ip(packet.source, PROTOCOL_ICMP, &reply.to_bytes(), now);
+            }
+            icmp::ECHO_REPLY if echo.identifier == PING_IDENTIFIER => {
+                if let Some((sent, rtt)) = self.pings.get_mut(&echo.sequence) {
+                    rtt.get_or_insert(now.saturating_sub(*sent));
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    fn receive_udp(&mut self, packet: &Packet, raw: &[u8], broadcast: bool, now: u64) {
+        let datagram = match Datagram::parse(packet.source, packet.destination, packet.payload) {
+            Some(datagram) => datagram,
+            None => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        let from = SocketAddr::new(packet.source, datagram.source_port);
+        
+        let socket = self.sockets.values_mut().find_map(|entry| match &mut entry.socket {
+            Socket::Udp(socket) if socket.port == datagram.destination_port => Some(socket),
+            _ => None,
+        });
+        match socket {
+            Some(socket) => socket.deliver(from, datagram.payload),
+            None if !broadcast => {
+                let header_len = (raw[0] & 0xF) as usize * 4;
+                let quoted = &raw[..header_len + packet.payload.len()];
+                let _ = self.send_ip(packet.source, PROTOCOL_ICMP, &icmp::port_unreachable(quoted), now);
+            }
+            None => {}
+        }
+    }
+    
+    fn receive_tcp(&mut self, packet: &Packet, now: u64) {
+        let segment = match Segment::parse(packet.source, packet.destination, packet.payload) {
+            Some(segment) => segment,
+            None => {
+                self.stats.rx_dropped += 1;
+                return;
+            }
+        };
+        let local = SocketAddr::new(packet.destination, segment.destination_port);
+        let remote = SocketAddr::new(packet.source, segment.source_port);
+        
+        let connection = self.sockets.iter_mut().find_map(|(&handle, entry)| match &mut entry.socket {
+            Socket::Tcp(tcb) if tcb.local == local && tcb.remote == remote && tcb.state() != TcpState::Closed => Some((handle, tcb, entry.parent)),
+            _ => None,
+        });
+        if let Some((handle, tcb, parent)) = connection {
+            let before = tcb.state();
+            tcb.process(&segment, now);
+            let established = before == TcpState::SynReceived && matches!(tcb.state(), TcpState::Established | TcpState::CloseWait);
+            
+            // اكتملت المصافحة: الاتصال جاهز لـ accept
+            if let (true, Some(parent)) = (established, parent) {
+                if let Some(Entry { socket: Socket::Listener(listener), .. }) = self.sockets.get_mut(&parent) {
+                    listener.ready.push_back(handle);
+                }
+            }
+            self.flush(handle, now);
+            return;
+        }
+        
+        let syn = segment.has(tcp::flags::SYN) && !segment.has(tcp::flags::ACK) && !segment.has(tcp::flags::RST);
+        let listener = self.sockets.iter().find_map(|(&handle, entry)| match &entry.socket {
+            Socket::Listener(listener) if listener.port == local.port => Some((handle, listener.backlog)),
+            _ => None,
+        });
+        match listener {
+            Some((parent, backlog)) if syn => {
+                // الممتلئ يُسقط SYN بصمت فيعيده الطرف الآخر لاحقاً
+                let waiting = self.sockets.values().filter(|entry| entry.parent == Some(parent)).count();
+                if waiting >= backlog {
+                    return;
+                }
+                let iss = arch::cpu::random_u64() as u32;
+                let handle = self.insert(Socket::Tcp(Tcb::accept(local, remote, &segment, iss)), Some(parent));
+                self.flush(handle, now);
+            }
+            Some(_) if !segment.has(tcp::flags::ACK) => {}
+            _ => {
+                if let Some(reset) = tcp::reset_for(&segment) {
+                    let _ = self.send_ip(remote.ip, PROTOCOL_TCP, &tcp::build(local, remote, &reset), now);
+                }
+            }
+        }
+    }
+    
+    fn send_arp(&mut self, operation: u16, destination: MacAddress, target_mac: MacAddress, target_ip: Ipv4Addr) {
+        let packet = ArpPacket { operation, sender_mac: self.mac, sender_ip: self.config.address, target_mac, target_ip };
+        self.outbox.push_back(ethernet::build(destination, self.mac, ETHERTYPE_ARP, &packet.to_bytes()));
+    }
+    
+    /// إرسال حزمة IP، أو تأجيلها حتى يجيب ARP عن القفزة التالية
+    fn send_ip(&mut self, destination: Ipv4Addr, protocol: u8, payload: &[u8], now: u64) -> Result<(), NetError> {
+        let packet = ipv4::build(self.config.address, destination, protocol, self.ip_id, payload);
+        self.ip_id = self.ip_id.wrapping_add(1);
+        
+        if destination == Ipv4Addr::BROADCAST || destination == self.config.broadcast() {
+            self.outbox.push_back(ethernet::build(MacAddress::BROADCAST, self.mac, ETHERTYPE_IPV4, &packet));
+            return Ok(());
+        }
+        let hop = if self.config.is_local(destination) {
+            destination
+        } else {
+            self.config.gateway.ok_or(NetError::Unreachable)?
+        };
+        
+        if let Some(mac) = self.arp.lookup(hop, now) {
+            self.outbox.push_back(ethernet::build(mac, self.mac, ETHERTYPE_IPV4, &packet));
+            return Ok(());
+        }
+        if self.pending.len() >= MAX_PENDING {
+            self.stats.tx_dropped += 1;
+            return Err(NetError::BufferFull);
+        }
+        self.pending.push((hop, packet));
+        if let btree_map::Entry::Vacant(request) = self.arp_requests.entry(hop) {
+            request.insert((now, now));
+            self.send_arp(arp::REQUEST, MacAddress::BROADCAST, MacAddress([0; 6]), hop);
+        }
+        Ok(())
+    }
+    
+    /// إرسال ما ينتظر عنواناً عرفناه للتو
+    fn release_pending(&mut self, ip: Ipv4Addr, mac: MacAddress) {
+        self.arp_requests.remove(&ip);
+        let pending = core::mem::take(&mut self.pending);
+        for (hop, packet) in pending {
+            if hop == ip {
+                self.outbox.push_back(ethernet::build(mac, self.mac, ETHERTYPE_IPV4, &packet));
+            } else {
+                self.pending.push((hop, packet));
+            }
+        }
+    }
+    
+    /// إرسال مقاطع اتصال TCP المستحقة
+    fn flush(&mut self, handle: SocketHandle, now: u64) {
+        let tcb = match self.sockets.get_mut(&handle) {
+            Some(Entry { socket: Socket::Tcp(tcb), .. }) => tcb,
+            _ => return,
+        };
+        let (local, remote) = (tcb.local, tcb.remote);
+        for segment in tcb.dispatch(now) {
+            // فشل الإرسال كفقد الحزمة: تعالجه الإعادة
+            let _ = self.send_ip(remote.ip, PROTOCOL_TCP, &tcp::build(local, remote, &segment), now);
+        }
+    }
+    
+    /// إزالة الاتصالات المنتهية التي لا يملكها أحد
+    fn reap(&mut self) {
+        let dead: Vec<SocketHandle> = self.sockets.iter()
+            .filter(|(_, entry)| matches!(&entry.socket, Socket::Tcp(tcb) if tcb.state() == TcpState::Closed) && (entry.orphan || entry.parent.is_some()))
+            .map(|(&handle, _)| handle)
+            .collect();
+        for handle in dead {
+            self.remove(handle);
+        }
+    }
+    
+    fn remove(&mut self, handle: SocketHandle) {
+        if let Some(Entry { parent: Some(parent), .. }) = self.sockets.remove(&handle) {
+            if let Some(Entry { socket: Socket::Listener(listener), .. }) = self.sockets.get_mut(&parent) {
+                listener.ready.retain(|&ready| ready != handle);
+            }
+        }
+    }
+    
+    fn insert(&mut self, socket: Socket, parent: Option<SocketHandle>) -> SocketHandle {
+        let handle = SocketHandle(self.next_handle);
+        self.next_handle = self.next_handle.wrapping_add(1).max(1);
+        self.sockets.insert(handle, Entry { socket, parent, orphan: false });
+        handle
+    }
+    
+    fn port_in_use(&self, port: u16, udp: bool) -> bool {
+        self.sockets.values().any(|entry| match &entry.socket {
+            Socket::Udp(socket) => udp && socket.port == port,
+            Socket::Tcp(tcb) => !udp && tcb.local.port == port,
+            Socket::Listener(listener) => !udp && listener.port == port,
+        })
+    }
+    
+    fn ephemeral_port(&mut self, udp: bool) -> Result<u16, NetError> {
+        let count = EPHEMERAL_PORTS.len();
+        for _ in 0..count {
+            let port = self.next_port;
+            self.next_port = if port == *EPHEMERAL_PORTS.end() { *EPHEMERAL_PORTS.start() } else { port + 1 };
+            if !self.port_in_use(port, udp) {
+                return Ok(port);
+            }
+        }
+        Err(NetError::AddressInUse)
+    }
+    
+    fn tcb(&mut self, handle: SocketHandle) -> Result<&mut Tcb, NetError> {
+        match self.sockets.get_mut(&handle) {
+            Some(Entry { socket: Socket::Tcp(tcb), .. }) => Ok(tcb),
+            _ => Err(NetError::InvalidHandle),
+        }
+    }
+    
+    /// ربط مقبس UDP؛ المنفذ صفر يختار منفذاً مؤقتاً
+    pub fn udp_bind(&mut self, port: u16) -> Result<SocketHandle, NetError> {
+        let port = match port {
+            0 => self.ephemeral_port(true)?,
+            port if self.port_in_use(port, true) => return Err(NetError::AddressInUse),
+            port => port,
+        };
+        Ok(self.insert(Socket::Udp(UdpSocket::new(port)), None))
+    }
+    
+    pub fn udp_send_to(&mut self, handle: SocketHandle, destination: SocketAddr, payload: &[u8], now: u64) -> Result<(), NetError> {
+        let port = match self.sockets.get(&handle) {
+            Some(Entry { socket: Socket::Udp(socket), .. }) => socket.port,
+            _ => return Err(NetError::InvalidHandle),
+        };
+        if payload.len() > MAX_DATAGRAM {
+            return Err(NetError::InvalidInput);
+        }
+        let datagram = udp::build(SocketAddr::new(self.config.address, port), destination, payload);
+        self.send_ip(destination.ip, PROTOCOL_UDP, &datagram, now)
+    }
+    
+    pub fn udp_recv_from(&mut self, handle: SocketHandle) -> Result<(SocketAddr, Vec<u8>), NetError> {
+        match self.sockets.get_mut(&handle) {
+            Some(Entry { socket: Socket::Udp(socket), .. }) => socket.take().ok_or(NetError::WouldBlock),
+            _ => Err(NetError::InvalidHandle),
+        }
+    }
+    
+    /// فتح اتصال؛ SYN يُرسل مع `poll` التالي
+    pub fn tcp_connect(&mut self, remote: SocketAddr) -> Result<SocketHandle, NetError> {
+        if remote.port == 0 || remote.ip == Ipv4Addr::UNSPECIFIED || remote.ip == Ipv4Addr::BROADCAST {
+            return Err(NetError::InvalidInput);
+        }
+        let local = SocketAddr::new(self.config.address, self.ephemeral_port(false)?);
+        let iss = arch::cpu::random_u64() as u32;
+        Ok(self.insert(Socket::Tcp(Tcb::connect(local, remote, iss)), None))
+    }
+    
+    /// الاستماع على منفذ؛ `backlog` أقصى عدد اتصالات تنتظر القبول
+    pub fn tcp_listen(&mut self, port: u16, backlog: usize) -> Result<SocketHandle, NetError> {
+        let taken = self.sockets.values().any(|entry| matches!(&entry.socket, Socket::Listener(listener) if listener.port == port));
+        if port == 0 || taken {
+            return Err(NetError::AddressInUse);
+        }
+        Ok(self.insert(Socket::Listener(Listener::new(port, backlog)), None))
+    }
+    
+    /// اتصال اكتملت مصافحته وعنوان طرفه الآخر
+    pub fn tcp_accept(&mut self, handle: SocketHandle) -> Result<(SocketHandle, SocketAddr), NetError> {
+        let accepted = match self.sockets.get_mut(&handle) {
+            Some(Entry { socket: Socket::Listener(listener), .. }) => listener.ready.pop_front().ok_or(NetError::WouldBlock)?,
+            _ => return Err(NetError::InvalidHandle),
+        };
+        let entry = self.sockets.get_mut(&accepted).ok_or(NetError::ConnectionReset)?;
+        entry.parent = None;
+        match &entry.socket {
+            Socket::Tcp(tcb) => Ok((accepted, tcb.remote)),
+            _ => Err(NetError::InvalidHandle),
+        }
+    }
+    
+    pub fn tcp_send(&mut self, handle: SocketHandle, data: &[u8]) -> Result<usize, NetError> {
+        self.tcb(handle)?.send(data)
+    }
+    
+    pub fn tcp_recv(&mut self, handle: SocketHandle, buf: &mut [u8]) -> Result<usize, NetError> {
+        self.tcb(handle)?.recv(buf)
+    }
+    
+    /// إغلاق جهة الإرسال (FIN بعد البيانات المعلقة)
+    pub fn tcp_close(&mut self, handle: SocketHandle) -> Result<(), NetError> {
+        self.tcb(handle)?.close();
+        Ok(())
+    }
+    
+    /// قطع الاتصال بـ RST
+    pub fn tcp_abort(&mut self, handle: SocketHandle, now: u64) -> Result<(), NetError> {
+        self.tcb(handle)?.abort();
+        self.flush(handle, now);
+        Ok(())
+    }
+    
+    pub fn tcp_state(&mut self, handle: SocketHandle) -> Result<TcpState, NetError> {
+        match self.sockets.get(&handle) {
+            Some(Entry { socket: Socket::Tcp(tcb), .. }) => Ok(tcb.state()),
+            Some(Entry { socket: Socket::Listener(_), .. }) => Ok(TcpState::Listen),
+            _ => Err(NetError::InvalidHandle),
+        }
+    }
+    
+    /// البايتات التي لم يؤكدها الطرف الآخر بعد
+    pub fn tcp_unacknowledged(&mut self, handle: SocketHandle) -> Result<usize, NetError> {
+        Ok(self.tcb(handle)?.unacknowledged())
+    }
+    
+    /// تحرير مقبس: الاتصال المفتوح يُغلق ويبقى حتى ينتهي إغلاقه، والمستمع
+    /// يقطع ما لم يُقبل من اتصالاته
+    pub fn release(&mut self, handle: SocketHandle, now: u64) {
+        let (listener, open) = match self.sockets.get(&handle) {
+            Some(Entry { socket: Socket::Listener(_), .. }) => (true, false),
+            Some(Entry { socket: Socket::Tcp(tcb), .. }) => (false, tcb.state() != TcpState::Closed),
+            Some(_) => (false, false),
+            None => return,
+        };
+        
+        if listener {
+            let children: Vec<SocketHandle> = self.sockets.iter().filter(|(_, entry)| entry.parent == Some(handle)).map(|(&child, _)| child).collect();
+            for child in children {
+                let _ = self.tcp_abort(child, now);
+                self.sockets.remove(&child);
+            }
+            self.sockets.remove(&handle);
+        } else if open {
+            if let Some(Entry { socket: Socket::Tcp(tcb), parent, orphan }) = self.sockets.get_mut(&handle) {
+                tcb.close();
+                *orphan = true;
+                *parent = None;
+            }
+            self.flush(handle, now);
+        } else {
+            self.remove(handle);
+        }
+    }
+    
+    /// قطع كل اتصالات TCP المفتوحة بـ RST (عند الإغلاق)؛ يعيد عددها
+    pub fn abort_all(&mut self, now: u64) -> usize {
+        let open: Vec<SocketHandle> = self.sockets.iter()
+            .filter(|(_, entry)| matches!(&entry.socket, Socket::Tcp(tcb) if tcb.state() != TcpState::Closed))
+            .map(|(&handle, _)| handle)
+            .collect();
+        for &handle in &open {
+            let _ = self.tcp_abort(handle, now);
+        }
+        self.reap();
+        open.len()
+    }
+    
+    /// إرسال طلب صدى برقم `sequence`
+    pub fn ping(&mut self, destination: Ipv4Addr, sequence: u16, now: u64) -> Result<(), NetError> {
+        let echo = Echo { kind: icmp::ECHO_REQUEST, identifier: PING_IDENTIFIER, sequence, data: b"Islam OS ping".to_vec() };
+        while self.pings.len() >= MAX_PINGS {
+            self.pings.pop_first();
+        }
+        self.pings.insert(sequence, (now, None));
+        self.send_ip(destination, PROTOCOL_ICMP, &echo.to_bytes(), now)
+    }
+    
+    /// زمن الذهاب والإياب للطلب `sequence` إن وصل رده
+    pub fn ping_reply(&self, sequence: u16) -> Option<u64> {
+        self.pings.get(&sequence).and_then(|&(_, rtt)| rtt)
+    }
+    
+    /// جدول /proc/net/tcp
+    pub fn tcp_table(&self) -> String {
+        let mut table = String::from("local\tremote\tstate\ttx_queue\trx_queue\n");
+        for entry in self.sockets.values() {
+            match &entry.socket {
+                Socket::Tcp(tcb) => {
+                    let (tx, rx) = tcb.queues();
+                    let _ = writeln!(table, "{}\t{}\t{}\t{}\t{}", tcb.local, tcb.remote, tcb.state(), tx, rx);
+                }
+                Socket::Listener(listener) => {
+                    let local = SocketAddr::new(self.config.address, listener.port);
+                    let _ = writeln!(table, "{}\t0.0.0.0:0\t{}\t0\t{}", local, TcpState::Listen, listener.ready.len());
+                }
+                Socket::Udp(_) => {}
+            }
+        }
+        table
+    }
+    
+    /// جدول /proc/net/udp
+    pub fn udp_table(&self) -> String {
+        let mut table = String::from("local\tqueued\tdropped\n");
+        for entry in self.sockets.values() {
+            if let Socket::Udp(socket) = &entry.socket {
+                let local = SocketAddr::new(self.config.address, socket.port);
+                let _ = writeln!(table, "{}\t{}\t{}", local, socket.queued(), socket.dropped);
+            }
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Config;
+    
+    const IP_A: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 1);
+    const IP_B: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 2);
+    
+    /// واجهتان على شبكة واحدة
+    fn pair() -> (Interface, Interface) {
+        let config = |cidr: &str| Config::parse(cidr, None).unwrap();
+        (
+            Interface::new(MacAddress([2, 0, 0, 0, 0, 1]), config("10.0.0.1/24")),
+            Interface::new(MacAddress([2, 0, 0, 0, 0, 2]), config("10.0.0.2/24")),
+        )
+    }
+    
+    /// نقل الإطارات بين الواجهتين حتى يهدأ الخط
+    fn exchange(a: &mut Interface, b: &mut Interface, now: u64) {
+        loop {
+            a.poll(now);
+            b.poll(now);
+            let mut idle = true;
+            while let Some(frame) = a.transmit() {
+                b.receive(&frame, now);
+                idle = false;
+            }
+            while let Some(frame) = b.transmit() {
+                a.receive(&frame, now);
+                idle = false;
+            }
+            if idle {
+                return;
+            }
+        }
+    }
+    
+    #[test_case]
+    fn test_arp_and_ping() {
+        let (mut a, mut b) = pair();
+        assert_eq!(Config::parse("10.0.2.15/33", None), None);
+        
+        // الصدى ينتظر رد ARP، والطرفان يتعلمان عنواني بعضهما
+        a.ping(IP_B, 1, 0).unwrap();
+        exchange(&mut a, &mut b, 5);
+        assert_eq!(a.ping_reply(1), Some(5));
+        assert_eq!(a.arp_cache().lookup(IP_B, 5), Some(b.mac()));
+        assert_eq!(b.arp_cache().lookup(IP_A, 5), Some(a.mac()));
+    }
+    
+    #[test_case]
+    fn test_udp_delivery_and_port_unreachable() {
+        let (mut a, mut b) = pair();
+        
+        // UDP إلى مقبس مربوط، و"المنفذ غير متاح" لمنفذ بلا مقبس
+        let server = b.udp_bind(5353).unwrap();
+        assert_eq!(b.udp_bind(5353), Err(NetError::AddressInUse));
+        let client = a.udp_bind(0).unwrap();
+        a.udp_send_to(client, SocketAddr::new(IP_B, 5353), b"salam", 10).unwrap();
+        exchange(&mut a, &mut b, 10);
+        let (from, payload) = b.udp_recv_from(server).unwrap();
+        assert_eq!((from.ip, payload.as_slice()), (IP_A, &b"salam"[..]));
+        assert_eq!(b.udp_recv_from(server), Err(NetError::WouldBlock));
+        
+        a.udp_send_to(client, SocketAddr::new(IP_B, 9), b"lost", 10).unwrap();
+        b.receive(&a.transmit().unwrap(), 10);
+        let reply = b.transmit().unwrap();
+        let packet = Packet::parse(Frame::parse(&reply).unwrap().payload).unwrap();
+        assert_eq!((packet.destination, &packet.payload[..2]), (IP_A, &[3u8, 3][..]));
+        a.receive(&reply, 10);
+    }
+    
+    #[test_case]
+    fn test_tcp_connection_lifecycle() {
+        let (mut a, mut b) = pair();
+        
+        // المصافحة، ثم بيانات أكبر من MSS
+        let listener = b.tcp_listen(8080, 4).unwrap();
+        let stream = a.tcp_connect(SocketAddr::new(IP_B, 8080)).unwrap();
+        assert_eq!(a.tcp_state(stream), Ok(TcpState::SynSent));
+        assert_eq!(b.tcp_accept(listener), Err(NetError::WouldBlock));
+        exchange(&mut a, &mut b, 20);
+        assert_eq!(a.tcp_state(stream), Ok(TcpState::Established));
+        let (accepted, peer) = b.tcp_accept(listener).unwrap();
+        assert_eq!((peer.ip, b.tcp_state(accepted)), (IP_A, Ok(TcpState::Established)));
+        
+        let data: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        assert_eq!(a.tcp_send(stream, &data), Ok(5000));
+        exchange(&mut a, &mut b, 30);
+        let mut buf = alloc::vec![0u8; 8192];
+        assert_eq!(b.tcp_recv(accepted, &mut buf), Ok(5000));
+        assert_eq!(&buf[..5000], &data[..]);
+        assert_eq!(a.tcp_unacknowledged(stream), Ok(0));
+        
+        // مقطع مفقود يُعاد بعد انتهاء مهلة الإعادة
+        b.tcp_send(accepted, b"reply").unwrap();
+        b.poll(40);
+        assert!(b.transmit().is_some() && b.transmit().is_none());
+        exchange(&mut a, &mut b, 41);
+        assert_eq!(a.tcp_recv(stream, &mut buf), Err(NetError::WouldBlock));
+        exchange(&mut a, &mut b, 40 + tcp::INITIAL_RTO_MS);
+        assert_eq!(a.tcp_recv(stream, &mut buf), Ok(5));
+        assert_eq!(&buf[..5], b"reply");
+        
+        // الإغلاق من الطرفين: FIN ثم نهاية البيانات ثم TimeWait
+        a.tcp_close(stream).unwrap();
+        assert_eq!(a.tcp_send(stream, b"late"), Err(NetError::NotConnected));
+        exchange(&mut a, &mut b, 2000);
+        assert_eq!((a.tcp_state(stream), b.tcp_state(accepted)), (Ok(TcpState::FinWait2), Ok(TcpState::CloseWait)));
+        assert_eq!(b.tcp_recv(accepted, &mut buf), Ok(0));
+        b.tcp_close(accepted).unwrap();
+        exchange(&mut a, &mut b, 2000);
+        assert_eq!((a.tcp_state(stream), b.tcp_state(accepted)), (Ok(TcpState::TimeWait), Ok(TcpState::Closed)));
+        exchange(&mut a, &mut b, 2000 + tcp::TIME_WAIT_MS);
+        assert_eq!(a.tcp_state(stream), Ok(TcpState::Closed));
+        a.release(stream, 2000 + tcp::TIME_WAIT_MS);
+        assert_eq!(a.tcp_state(stream), Err(NetError::InvalidHandle));
+    }
+    
+    #[test_case]
+    fn test_tcp_connection_refused() {
+        let (mut a, mut b) = pair();
+        
+        // منفذ بلا مستمع يرد بـ RST
+        let refused = a.tcp_connect(SocketAddr::new(IP_B, 81)).unwrap();
+        exchange(&mut a, &mut b, 100);
+        let mut buf = [0u8; 16];
+        assert_eq!(a.tcp_recv(refused, &mut buf), Err(NetError::ConnectionRefused));
+    }
+    
+    #[test_case]
+    fn test_abort_all_resets_peers() {
+        let (mut a, mut b) = pair();
+        
+        // الإغلاق يقطع المفتوح فقط، والطرف الآخر يرى RST
+        let listener = b.tcp_listen(22, 2).unwrap();
+        let first = a.tcp_connect(SocketAddr::new(IP_B, 22)).unwrap();
+        let second = a.tcp_connect(SocketAddr::new(IP_B, 22)).unwrap();
+        exchange(&mut a, &mut b, 30);
+        let (accepted, _) = b.tcp_accept(listener).unwrap();
+        a.tcp_abort(second, 30).unwrap();
+        exchange(&mut a, &mut b, 30);
+        
+        assert_eq!(a.abort_all(40), 1);
+        assert_eq!(a.tcp_state(first), Ok(TcpState::Closed));
+        exchange(&mut a, &mut b, 40);
+        let mut buf = [0u8; 16];
+        assert_eq!(b.tcp_recv(accepted, &mut buf), Err(NetError::ConnectionReset));
+        assert_eq!(a.abort_all(50), 0);
+    }
+}
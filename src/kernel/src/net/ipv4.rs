@@ -0,0 +1,178 @@
+//! 🌍 IPv4
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! ترويسة من 20 بايت بلا خيارات عند الإرسال، والخيارات تُتخطى عند
+//! الاستقبال. لا تجزئة: الحزم المجزأة تُسقط، والمرسلة لا تتجاوز MTU.
+//! مجموع التحقق هو مجموع متمم الواحد (RFC 1071) ويستخدمه ICMP و UDP و TCP.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// طول الترويسة بلا خيارات
+pub const HEADER_LEN: usize = 20;
+
+/// العمر الافتراضي للحزمة
+pub const DEFAULT_TTL: u8 = 64;
+
+/// البروتوكولات المحمولة
+pub const PROTOCOL_ICMP: u8 = 1;
+pub const PROTOCOL_TCP: u8 = 6;
+pub const PROTOCOL_UDP: u8 = 17;
+
+/// عنوان IPv4
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4Addr(pub [u8; 4]);
+
+impl Ipv4Addr {
+    pub const UNSPECIFIED: Ipv4Addr = Ipv4Addr([0; 4]);
+    pub const BROADCAST: Ipv4Addr = Ipv4Addr([255; 4]);
+    
+    pub const fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+        Self([a, b, c, d])
+    }
+    
+    pub fn to_u32(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+    
+    pub fn from_u32(value: u32) -> Self {
+        Self(value.to_be_bytes())
+    }
+    
+    /// قناع الشبكة لطول البادئة
+    pub fn netmask(prefix: u8) -> Self {
+        Self::from_u32(if prefix == 0 { 0 } else { u32::MAX << (32 - prefix.min(32) as u32) })
+    }
+}
+
+impl fmt::Display for Ipv4Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.0[0], self.0[1], self.0[2], self.0[3])
+    }
+}
+
+impl FromStr for Ipv4Addr {
+    type Err = ();
+    
+    fn from_str(text: &str) -> Result<Self, ()> {
+        let mut bytes = [0u8; 4];
+        let mut parts = text.split('.');
+        for byte in &mut bytes {
+            *byte = parts.next().ok_or(())?.parse().map_err(|_| ())?;
+        }
+        if parts.next().is_some() {
+            return Err(());
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// عنوان مقبس: IP ومنفذ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SocketAddr {
+    pub ip: Ipv4Addr,
+    pub port: u16,
+}
+
+impl SocketAddr {
+    pub const fn new(ip: Ipv4Addr, port: u16) -> Self {
+        Self { ip, port }
+    }
+}
+
+impl fmt::Display for SocketAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.ip, self.port)
+    }
+}
+
+/// إضافة بايتات إلى مجموع جزئي (كلمات 16 بت بترتيب الشبكة)
+pub fn checksum_add(mut sum: u32, data: &[u8]) -> u32 {
+    let mut words = data.chunks_exact(2);
+    for word in &mut words {
+        sum += u16::from_be_bytes([word[0], word[1]]) as u32;
+    }
+    if let [last] = words.remainder() {
+        sum += (*last as u32) << 8;
+    }
+    sum
+}
+
+/// طي المجموع وأخذ متممه
+pub fn checksum_finish(mut sum: u32) -> u16 {
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// مجموع التحقق لبيانات
+pub fn checksum(data: &[u8]) -> u16 {
+    checksum_finish(checksum_add(0, data))
+}
+
+/// مجموع تحقق UDP و TCP مع الترويسة الزائفة
+pub fn pseudo_checksum(source: Ipv4Addr, destination: Ipv4Addr, protocol: u8, segment: &[u8]) -> u16 {
+    let mut sum = checksum_add(0, &source.0);
+    sum = checksum_add(sum, &destination.0);
+    sum += protocol as u32 + segment.len() as u32;
+    checksum_finish(checksum_add(sum, segment))
+}
+
+/// حزمة مقروءة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet<'a> {
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub protocol: u8,
+    pub ttl: u8,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Packet<'a> {
+    /// قراءة حزمة كاملة غير مجزأة بمجموع تحقق صحيح
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < HEADER_LEN || bytes[0] >> 4 != 4 {
+            return None;
+        }
+        let header_len = (bytes[0] & 0xF) as usize * 4;
+        let total = u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+        if header_len < HEADER_LEN || total < header_len || total > bytes.len() || checksum(&bytes[..header_len]) != 0 {
+            return None;
+        }
+        
+        // MF أو إزاحة جزء: لا نعيد التجميع
+        let fragment = u16::from_be_bytes([bytes[6], bytes[7]]);
+        if fragment & 0x3FFF != 0 {
+            return None;
+        }
+        
+        Some(Self {
+            source: Ipv4Addr(bytes[12..16].try_into().unwrap()),
+            destination: Ipv4Addr(bytes[16..20].try_into().unwrap()),
+            protocol: bytes[9],
+            ttl: bytes[8],
+            // إيثرنت قد يضيف حشواً بعد الحزمة
+            payload: &bytes[header_len..total],
+        })
+    }
+}
+
+/// بناء حزمة بعلم "لا تجزئ"
+pub fn build(source: Ipv4Addr, destination: Ipv4Addr, protocol: u8, id: u16, payload: &[u8]) -> Vec<u8> {
+    let total = (HEADER_LEN + payload.len()) as u16;
+    let mut packet = Vec::with_capacity(total as usize);
+    packet.extend_from_slice(&[0x45, 0]);
+    packet.extend_from_slice(&total.to_be_bytes());
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x4000u16.to_be_bytes());
+    packet.extend_from_slice(&[DEFAULT_TTL, protocol, 0, 0]);
+    packet.extend_from_slice(&source.0);
+    packet.extend_from_slice(&destination.0);
+    
+    let sum = checksum(&packet);
+    packet[10..12].copy_from_slice(&sum.to_be_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
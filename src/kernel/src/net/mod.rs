@@ -0,0 +1,271 @@
+//! 🌐 نظام الشبكات لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! مكدس IPv4 على أول بطاقة يسجلها تعريف (`drivers::net`):
+//!
+//! ```text
+//! البطاقة ◀──▶ Interface (إيثرنت، ARP، IPv4) ◀──▶ ICMP، UDP، TCP ◀──▶ المقابس
+//! ```
+//!
+//! الاستطلاع الدوري (`poll`) يقرأ الإطارات الواردة ويشغل مؤقتات TCP ويرسل
+//! الصادر؛ المقابس لا تحجب وتعيد `WouldBlock` حين لا يوجد ما يُقرأ.
+//!
+//! المعاملات: `net=off` يعطل الشبكة، و `net.ip=10.0.2.15/24` و
+//! `net.gateway=10.0.2.2` (افتراضيا شبكة QEMU للمستخدم)، و `net.echo=1`
+//! يشغل خدمة الصدى على المنفذ 7. الحالة في /proc/net/dev و arp و tcp و udp.
+//!
+//! عند الإغلاق يقطع خطاف "network" اتصالات TCP بـ RST ويرسلها ثم يوقف البطاقة.
+
+pub mod arp;
+pub mod echo;
+pub mod ethernet;
+pub mod icmp;
+pub mod interface;
+pub mod ipv4;
+pub mod socket;
+pub mod tcp;
+pub mod udp;
+
+pub use self::interface::{Config, Interface};
+pub use self::ipv4::{Ipv4Addr, SocketAddr};
+pub use self::socket::{TcpListener, TcpStream, UdpSocket};
+pub use self::tcp::TcpState;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use log::{info, warn};
+use spin::Mutex;
+
+use crate::drivers::net::{self as devices, NetDevice};
+use crate::power::{self, HookContext};
+use crate::process::timer;
+
+/// أخطاء الشبكة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetError {
+    /// لا بطاقة شبكة
+    NoDevice,
+    /// المنفذ مستخدم
+    AddressInUse,
+    /// لا شيء الآن؛ يُعاد المحاولة لاحقاً
+    WouldBlock,
+    /// المقبس غير متصل أو أُغلق للإرسال
+    NotConnected,
+    /// رفض الطرف الآخر الاتصال (RST)
+    ConnectionRefused,
+    /// قطع الطرف الآخر الاتصال (RST)
+    ConnectionReset,
+    /// لم يرد الطرف الآخر بعد كل الإعادات
+    TimedOut,
+    /// مقبس غير موجود أو من نوع آخر
+    InvalidHandle,
+    /// لا طريق إلى العنوان
+    Unreachable,
+    /// الطابور ممتلئ
+    BufferFull,
+    /// معامل غير صالح
+    InvalidInput,
+    /// خطأ من البطاقة
+    Io,
+}
+
+/// معرف مقبس داخل الواجهة
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SocketHandle(pub u32);
+
+/// العنوان والبوابة الافتراضيان (شبكة QEMU للمستخدم)
+pub const DEFAULT_ADDRESS: &str = "10.0.2.15/24";
+pub const DEFAULT_GATEWAY: &str = "10.0.2.2";
+
+/// أقصى عدد إطارات تُقرأ من البطاقة في استطلاع واحد
+const RX_BUDGET: usize = 64;
+
+struct Stack {
+    interface: Interface,
+    device: Option<Arc<dyn NetDevice>>,
+}
+
+static STACK: Mutex<Option<Stack>> = Mutex::new(None);
+/// لا استطلاع: `net=off` أو بعد خطاف الإغلاق
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// تهيئة الشبكة على البطاقة التي ربطها تعريفها أثناء فحص PCI
+pub fn init() {
+    crate::fs::procfs::register("net/dev", proc_dev);
+    crate::fs::procfs::register("net/arp", proc_arp);
+    crate::fs::procfs::register("net/tcp", proc_tcp);
+    crate::fs::procfs::register("net/udp", proc_udp);
+    
+    let params = crate::boot::params();
+    if params.get("net") == Some("off") {
+        DISABLED.store(true, Ordering::SeqCst);
+        info!("  🌐 الشبكة معطلة (net=off)");
+        return;
+    }
+    power::register("network", power::priority::NETWORK, power::DEFAULT_BUDGET_TICKS, stop_on_shutdown);
+    
+    poll();
+    if STACK.lock().is_none() {
+        warn!("  ⚠️ لا بطاقة شبكة؛ الواجهة تعمل حين تظهر واحدة");
+    }
+    if params.flag("net.echo") {
+        echo::start();
+    }
+}
+
+/// خطاف الإغلاق: قطع الاتصالات وإرسال RST ما بقيت الميزانية، ثم إيقاف البطاقة
+fn stop_on_shutdown(context: &HookContext) -> Result<(), String> {
+    DISABLED.store(true, Ordering::SeqCst);
+    let mut guard = STACK.lock();
+    let Some(Stack { interface, device }) = guard.as_mut() else {
+        return Ok(());
+    };
+    let aborted = interface.abort_all(timer::uptime_ms());
+    let Some(device) = device.take() else {
+        return Ok(());
+    };
+    
+    let mut result = Ok(());
+    while let Some(frame) = interface.transmit() {
+        if context.expired() {
+            result = Err(alloc::format!("{}: انتهت الميزانية قبل إرسال RST", device.name()));
+            break;
+        }
+        if let Err(e) = device.transmit(&frame) {
+            result = Err(alloc::format!("{}: تعذر إرسال RST: {:?}", device.name(), e));
+            break;
+        }
+    }
+    device.stop();
+    info!("🌐 {}: أوقفت البطاقة بعد قطع {} اتصال", device.name(), aborted);
+    result
+}
+
+/// استئناف الاستطلاع كما يبدأ الإقلاع التالي (المحاكاة لا تُطفأ فعلاً)
+#[cfg(feature = "hosted")]
+pub(crate) fn reset() {
+    DISABLED.store(crate::boot::params().get("net") == Some("off"), Ordering::SeqCst);
+}
+
+/// العنوان من `net.ip` و `net.gateway`
+fn config() -> Config {
+    let params = crate::boot::params();
+    let cidr = params.get("net.ip").unwrap_or(DEFAULT_ADDRESS);
+    let gateway = params.get("net.gateway").unwrap_or(DEFAULT_GATEWAY);
+    Config::parse(cidr, Some(gateway)).unwrap_or_else(|| {
+        warn!("⚠️ عنوان غير صالح ({} عبر {})؛ الافتراضي {}", cidr, gateway, DEFAULT_ADDRESS);
+        Config::parse(DEFAULT_ADDRESS, Some(DEFAULT_GATEWAY)).unwrap()
+    })
+}
+
+/// ربط الواجهة بأول بطاقة مسجلة إن زالت بطاقتها أو لم تكن لها بطاقة
+fn bind(stack: &mut Option<Stack>) {
+    let current = stack.as_ref().and_then(|stack| stack.device.clone());
+    if let Some(device) = &current {
+        if devices::find(device.name()).is_some_and(|found| Arc::ptr_eq(&found, device)) {
+            return;
+        }
+    }
+    
+    let device = devices::devices().into_iter().next();
+    match (stack.as_mut(), device) {
+        (Some(stack), Some(device)) => {
+            info!("  🌐 {} ← {}", device.name(), stack.interface.config().address);
+            stack.interface.set_mac(device.mac());
+            stack.device = Some(device);
+        }
+        (Some(stack), None) => stack.device = None,
+        (None, Some(device)) => {
+            let config = config();
+            info!("  🌐 {}: {} بعنوان {}/{} والبوابة {}", device.name(), device.mac(), config.address, config.prefix,
+                config.gateway.unwrap_or(Ipv4Addr::UNSPECIFIED));
+            *stack = Some(Stack { interface: Interface::new(device.mac(), config), device: Some(device) });
+        }
+        (None, None) => {}
+    }
+}
+
+/// استطلاع البطاقة: الوارد، ثم المؤقتات، ثم الصادر
+pub fn poll() {
+    if DISABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let now = timer::uptime_ms();
+    let mut guard = STACK.lock();
+    bind(&mut guard);
+    
+    let connected = match guard.as_mut() {
+        Some(Stack { interface, device: Some(device) }) => {
+            for _ in 0..RX_BUDGET {
+                match device.receive() {
+                    Some(frame) => interface.receive(&frame, now),
+                    None => break,
+                }
+            }
+            interface.poll(now);
+            while let Some(frame) = interface.transmit() {
+                if let Err(e) = device.transmit(&frame) {
+                    warn!("⚠️ {}: تعذر الإرسال: {:?}", device.name(), e);
+                    break;
+                }
+            }
+            device.link_up()
+        }
+        _ => false,
+    };
+    drop(guard);
+    crate::SYSTEM_STATE.lock().network_connected = connected;
+}
+
+/// تنفيذ عملية على الواجهة بالوقت الحالي
+pub fn with_interface<R>(f: impl FnOnce(&mut Interface, u64) -> R) -> Result<R, NetError> {
+    match STACK.lock().as_mut() {
+        Some(stack) => Ok(f(&mut stack.interface, timer::uptime_ms())),
+        None => Err(NetError::NoDevice),
+    }
+}
+
+/// إرسال طلب صدى؛ الرد يُقرأ بـ `ping_reply`
+pub fn ping(destination: Ipv4Addr, sequence: u16) -> Result<(), NetError> {
+    with_interface(|interface, now| interface.ping(destination, sequence, now))?
+}
+
+/// زمن الذهاب والإياب لطلب صدى إن وصل رده
+pub fn ping_reply(sequence: u16) -> Option<u64> {
+    with_interface(|interface, _| interface.ping_reply(sequence)).ok().flatten()
+}
+
+/// /proc/net/dev
+fn proc_dev() -> String {
+    let mut table = String::from("iface\tmac\taddress\trx_packets\trx_bytes\trx_dropped\ttx_packets\ttx_bytes\ttx_dropped\tlink\n");
+    if let Some(Stack { interface, device: Some(device) }) = STACK.lock().as_ref() {
+        let (config, stats) = (interface.config(), interface.statistics());
+        table += &alloc::format!("{}\t{}\t{}/{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n", device.name(), interface.mac(), config.address, config.prefix,
+            stats.rx_packets, stats.rx_bytes, stats.rx_dropped, stats.tx_packets, stats.tx_bytes, stats.tx_dropped,
+            if device.link_up() { "up" } else { "down" });
+    }
+    table
+}
+
+/// /proc/net/arp
+fn proc_arp() -> String {
+    let mut table = String::from("address\tmac\n");
+    let now = timer::uptime_ms();
+    if let Some(stack) = STACK.lock().as_ref() {
+        for (ip, mac) in stack.interface.arp_cache().entries(now) {
+            table += &alloc::format!("{}\t{}\n", ip, mac);
+        }
+    }
+    table
+}
+
+/// /proc/net/tcp
+fn proc_tcp() -> String {
+    STACK.lock().as_ref().map_or_else(|| String::from("local\tremote\tstate\ttx_queue\trx_queue\n"), |stack| stack.interface.tcp_table())
+}
+
+/// /proc/net/udp
+fn proc_udp() -> String {
+    STACK.lock().as_ref().map_or_else(|| String::from("local\tqueued\tdropped\n"), |stack| stack.interface.udp_table())
+}
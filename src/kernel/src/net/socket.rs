@@ -0,0 +1,118 @@
+//! 🔌 المقابس
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! أغلفة فوق مقابس الواجهة العامة لا تحجب: ما ليس جاهزاً يعيد
+//! `NetError::WouldBlock`. تحرير الغلاف يغلق المقبس؛ الاتصال المفتوح يكمل
+//! إغلاقه المنظم في الخلفية.
+//!
+//! ```text
+//! let stream = TcpStream::connect(SocketAddr::new(Ipv4Addr::new(10, 0, 2, 2), 80))?;
+//! // ... poll حتى Established
+//! stream.send(b"GET / HTTP/1.0\r\n\r\n")?;
+//! ```
+
+use alloc::vec::Vec;
+
+use super::{with_interface, NetError, SocketAddr, SocketHandle, TcpState};
+
+/// اتصال TCP
+#[derive(Debug)]
+pub struct TcpStream {
+    handle: SocketHandle,
+}
+
+impl TcpStream {
+    /// بدء الاتصال؛ يصير `Established` بعد المصافحة
+    pub fn connect(remote: SocketAddr) -> Result<Self, NetError> {
+        let handle = with_interface(|interface, _| interface.tcp_connect(remote))??;
+        Ok(Self { handle })
+    }
+    
+    pub fn handle(&self) -> SocketHandle {
+        self.handle
+    }
+    
+    pub fn state(&self) -> Result<TcpState, NetError> {
+        with_interface(|interface, _| interface.tcp_state(self.handle))?
+    }
+    
+    pub fn is_connected(&self) -> bool {
+        matches!(self.state(), Ok(TcpState::Established))
+    }
+    
+    /// إضافة بيانات للإرسال؛ يعيد ما اتسع له المخزن
+    pub fn send(&self, data: &[u8]) -> Result<usize, NetError> {
+        with_interface(|interface, _| interface.tcp_send(self.handle, data))?
+    }
+    
+    /// قراءة ما وصل؛ الصفر نهاية البيانات
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        with_interface(|interface, _| interface.tcp_recv(self.handle, buf))?
+    }
+    
+    /// إغلاق جهة الإرسال؛ القراءة تبقى ممكنة حتى يغلق الطرف الآخر
+    pub fn close(&self) -> Result<(), NetError> {
+        with_interface(|interface, _| interface.tcp_close(self.handle))?
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        let _ = with_interface(|interface, now| interface.release(self.handle, now));
+    }
+}
+
+/// منفذ TCP مستمع
+#[derive(Debug)]
+pub struct TcpListener {
+    handle: SocketHandle,
+}
+
+impl TcpListener {
+    /// الاستماع على `port`؛ حتى `backlog` اتصالاً ينتظر القبول
+    pub fn bind(port: u16, backlog: usize) -> Result<Self, NetError> {
+        let handle = with_interface(|interface, _| interface.tcp_listen(port, backlog))??;
+        Ok(Self { handle })
+    }
+    
+    /// اتصال اكتملت مصافحته
+    pub fn accept(&self) -> Result<(TcpStream, SocketAddr), NetError> {
+        let (handle, remote) = with_interface(|interface, _| interface.tcp_accept(self.handle))??;
+        Ok((TcpStream { handle }, remote))
+    }
+}
+
+impl Drop for TcpListener {
+    fn drop(&mut self) {
+        let _ = with_interface(|interface, now| interface.release(self.handle, now));
+    }
+}
+
+/// مقبس UDP
+#[derive(Debug)]
+pub struct UdpSocket {
+    handle: SocketHandle,
+}
+
+impl UdpSocket {
+    /// الربط بـ `port`؛ الصفر يختار منفذاً مؤقتاً
+    pub fn bind(port: u16) -> Result<Self, NetError> {
+        let handle = with_interface(|interface, _| interface.udp_bind(port))??;
+        Ok(Self { handle })
+    }
+    
+    pub fn send_to(&self, destination: SocketAddr, payload: &[u8]) -> Result<(), NetError> {
+        with_interface(|interface, now| interface.udp_send_to(self.handle, destination, payload, now))?
+    }
+    
+    /// الرسالة التالية ومرسلها
+    pub fn recv_from(&self) -> Result<(SocketAddr, Vec<u8>), NetError> {
+        with_interface(|interface, _| interface.udp_recv_from(self.handle))?
+    }
+}
+
+impl Drop for UdpSocket {
+    fn drop(&mut self) {
+        let _ = with_interface(|interface, now| interface.release(self.handle, now));
+    }
+}
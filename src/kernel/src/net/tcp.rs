@@ -0,0 +1,696 @@
+//! 🔁 TCP
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! كتلة تحكم (`Tcb`) لكل اتصال تنفذ آلة حالات RFC 793:
+//!
+//! ```text
+//! connect ──SYN──▶ SynSent ──SYN+ACK──▶ Established ◀──ACK── SynReceived ◀──SYN── listen
+//! close: Established ──FIN──▶ FinWait1 ──▶ FinWait2 ──FIN──▶ TimeWait ──▶ Closed
+//!        CloseWait ──FIN──▶ LastAck ──ACK──▶ Closed
+//! ```
+//!
+//! المقاطع الواصلة خارج الترتيب تُسقط مع ACK مكرر، فيعيد المرسل من أول ما
+//! لم يُؤكد. الإعادة بمهلة RTO تُقدّر من زمن الذهاب والإياب (RFC 6298)
+//! وتتضاعف مع كل محاولة، ونافذة الإرسال هي ما يعلنه الطرف الآخر بلا تحجيم.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::ipv4::{pseudo_checksum, Ipv4Addr, SocketAddr, PROTOCOL_TCP};
+use super::{NetError, SocketHandle};
+
+/// أعلام الترويسة
+pub mod flags {
+    pub const FIN: u8 = 0x01;
+    pub const SYN: u8 = 0x02;
+    pub const RST: u8 = 0x04;
+    pub const PSH: u8 = 0x08;
+    pub const ACK: u8 = 0x10;
+}
+
+/// طول الترويسة بلا خيارات
+pub const HEADER_LEN: usize = 20;
+
+/// أكبر مقطع نرسله ونعلنه (MTU إيثرنت ناقص ترويستي IP و TCP)
+pub const MSS: u16 = 1460;
+
+/// ما يُفترض إن لم يعلن الطرف الآخر MSS
+pub const DEFAULT_MSS: u16 = 536;
+
+/// سعة مخزني الإرسال والاستقبال (أقصى نافذة بلا تحجيم)
+pub const BUFFER_SIZE: usize = 65535;
+
+/// مهلة الإعادة الأولى وحداها
+pub const INITIAL_RTO_MS: u64 = 1000;
+pub const MIN_RTO_MS: u64 = 200;
+pub const MAX_RTO_MS: u64 = 60_000;
+
+/// محاولات الإعادة قبل قطع الاتصال
+pub const MAX_RETRIES: u32 = 8;
+
+/// مدة TimeWait (ضعف أقصى عمر للمقطع)
+pub const TIME_WAIT_MS: u64 = 60_000;
+
+fn seq_lt(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) < 0
+}
+
+fn seq_le(a: u32, b: u32) -> bool {
+    !seq_lt(b, a)
+}
+
+/// مقطع مقروء
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment<'a> {
+    pub source_port: u16,
+    pub destination_port: u16,
+    pub sequence: u32,
+    pub acknowledgment: u32,
+    pub flags: u8,
+    pub window: u16,
+    /// خيار MSS (في SYN فقط)
+    pub mss: Option<u16>,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Segment<'a> {
+    pub fn parse(source: Ipv4Addr, destination: Ipv4Addr, bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < HEADER_LEN || pseudo_checksum(source, destination, PROTOCOL_TCP, bytes) != 0 {
+            return None;
+        }
+        let offset = (bytes[12] >> 4) as usize * 4;
+        if offset < HEADER_LEN || offset > bytes.len() {
+            return None;
+        }
+        
+        // الخيارات: النهاية 0، والحشو 1، وغيرهما (نوع، طول، قيمة)
+        let mut mss = None;
+        let mut options = &bytes[HEADER_LEN..offset];
+        while let [kind, rest @ ..] = options {
+            match (*kind, rest) {
+                (0, _) => break,
+                (1, _) => options = rest,
+                (_, [len, ..]) if *len >= 2 && *len as usize <= options.len() => {
+                    if *kind == 2 && *len == 4 {
+                        mss = Some(u16::from_be_bytes([rest[1], rest[2]]));
+                    }
+                    options = &options[*len as usize..];
+                }
+                _ => break,
+            }
+        }
+        
+        Some(Self {
+            source_port: u16::from_be_bytes([bytes[0], bytes[1]]),
+            destination_port: u16::from_be_bytes([bytes[2], bytes[3]]),
+            sequence: u32::from_be_bytes(bytes[4..8].try_into().unwrap()),
+            acknowledgment: u32::from_be_bytes(bytes[8..12].try_into().unwrap()),
+            flags: bytes[13],
+            window: u16::from_be_bytes([bytes[14], bytes[15]]),
+            mss,
+            payload: &bytes[offset..],
+        })
+    }
+    
+    pub fn has(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+    
+    /// الطول في فضاء الترقيم (SYN و FIN يأخذ كل منهما رقماً)
+    pub fn sequence_len(&self) -> u32 {
+        self.payload.len() as u32 + self.has(flags::SYN) as u32 + self.has(flags::FIN) as u32
+    }
+}
+
+/// مقطع صادر (المنافذ من الاتصال)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outgoing {
+    pub sequence: u32,
+    pub acknowledgment: u32,
+    pub flags: u8,
+    pub window: u16,
+    pub mss: Option<u16>,
+    pub payload: Vec<u8>,
+}
+
+/// بناء مقطع بمجموع تحقق
+pub fn build(source: SocketAddr, destination: SocketAddr, segment: &Outgoing) -> Vec<u8> {
+    let header_len = HEADER_LEN + if segment.mss.is_some() { 4 } else { 0 };
+    let mut bytes = Vec::with_capacity(header_len + segment.payload.len());
+    bytes.extend_from_slice(&source.port.to_be_bytes());
+    bytes.extend_from_slice(&destination.port.to_be_bytes());
+    bytes.extend_from_slice(&segment.sequence.to_be_bytes());
+    bytes.extend_from_slice(&segment.acknowledgment.to_be_bytes());
+    bytes.extend_from_slice(&[(header_len as u8 / 4) << 4, segment.flags]);
+    bytes.extend_from_slice(&segment.window.to_be_bytes());
+    bytes.extend_from_slice(&[0, 0, 0, 0]);
+    if let Some(mss) = segment.mss {
+        bytes.extend_from_slice(&[2, 4]);
+        bytes.extend_from_slice(&mss.to_be_bytes());
+    }
+    bytes.extend_from_slice(&segment.payload);
+    
+    let sum = pseudo_checksum(source.ip, destination.ip, PROTOCOL_TCP, &bytes);
+    bytes[16..18].copy_from_slice(&sum.to_be_bytes());
+    bytes
+}
+
+/// RST لمقطع لا اتصال له (RFC 793، "Reset Generation")
+pub fn reset_for(segment: &Segment) -> Option<Outgoing> {
+    if segment.has(flags::RST) {
+        return None;
+    }
+    let reset = if segment.has(flags::ACK) {
+        Outgoing { sequence: segment.acknowledgment, acknowledgment: 0, flags: flags::RST, window: 0, mss: None, payload: Vec::new() }
+    } else {
+        let acknowledgment = segment.sequence.wrapping_add(segment.sequence_len());
+        Outgoing { sequence: 0, acknowledgment, flags: flags::RST | flags::ACK, window: 0, mss: None, payload: Vec::new() }
+    };
+    Some(reset)
+}
+
+/// حالات الاتصال
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpState {
+    Closed,
+    Listen,
+    SynSent,
+    SynReceived,
+    Established,
+    FinWait1,
+    FinWait2,
+    CloseWait,
+    Closing,
+    LastAck,
+    TimeWait,
+}
+
+impl fmt::Display for TcpState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TcpState::Closed => "CLOSED",
+            TcpState::Listen => "LISTEN",
+            TcpState::SynSent => "SYN_SENT",
+            TcpState::SynReceived => "SYN_RECV",
+            TcpState::Established => "ESTABLISHED",
+            TcpState::FinWait1 => "FIN_WAIT1",
+            TcpState::FinWait2 => "FIN_WAIT2",
+            TcpState::CloseWait => "CLOSE_WAIT",
+            TcpState::Closing => "CLOSING",
+            TcpState::LastAck => "LAST_ACK",
+            TcpState::TimeWait => "TIME_WAIT",
+        };
+        f.write_str(name)
+    }
+}
+
+/// كتلة التحكم في اتصال
+#[derive(Debug)]
+pub struct Tcb {
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    state: TcpState,
+    error: Option<NetError>,
+    
+    iss: u32,
+    /// أول رقم لم يؤكده الطرف الآخر
+    snd_una: u32,
+    /// الرقم التالي الذي سنرسله
+    snd_nxt: u32,
+    /// النافذة التي أعلنها الطرف الآخر
+    snd_wnd: u32,
+    remote_mss: u16,
+    
+    irs: u32,
+    /// الرقم التالي المتوقع من الطرف الآخر
+    rcv_nxt: u32,
+    
+    /// بيانات لم تُؤكد بعد، أولها عند الرقم `tx_seq`
+    tx: VecDeque<u8>,
+    tx_seq: u32,
+    rx: VecDeque<u8>,
+    
+    /// أغلق المستخدم جهة الإرسال (FIN بعد آخر البيانات)
+    fin_queued: bool,
+    ack_pending: bool,
+    rst_pending: Option<Outgoing>,
+    /// إرسال بايت واحد رغم النافذة الصفرية
+    probe: bool,
+    
+    /// مهلة الإعادة (أو فحص النافذة الصفرية)
+    timer: Option<u64>,
+    rto: u64,
+    srtt: Option<u64>,
+    rttvar: u64,
+    /// (آخر رقم في العينة، وقت الإرسال) لقياس زمن الذهاب والإياب
+    rtt_sample: Option<(u32, u64)>,
+    retries: u32,
+    time_wait_until: u64,
+}
+
+impl Tcb {
+    fn new(local: SocketAddr, remote: SocketAddr, state: TcpState, iss: u32) -> Self {
+        Self {
+            local,
+            remote,
+            state,
+            error: None,
+            iss,
+            snd_una: iss,
+            snd_nxt: iss,
+            snd_wnd: 0,
+            remote_mss: DEFAULT_MSS,
+            irs: 0,
+            rcv_nxt: 0,
+            tx: VecDeque::new(),
+            tx_seq: iss.wrapping_add(1),
+            rx: VecDeque::new(),
+            fin_queued: false,
+            ack_pending: false,
+            rst_pending: None,
+            probe: false,
+            timer: None,
+            rto: INITIAL_RTO_MS,
+            srtt: None,
+            rttvar: 0,
+            rtt_sample: None,
+            retries: 0,
+            time_wait_until: 0,
+        }
+    }
+    
+    /// فتح نشط: SYN يُرسل في أول `dispatch`
+    pub fn connect(local: SocketAddr, remote: SocketAddr, iss: u32) -> Self {
+        Self::new(local, remote, TcpState::SynSent, iss)
+    }
+    
+    /// فتح سلبي من SYN وصل إلى منفذ مستمع
+    pub fn accept(local: SocketAddr, remote: SocketAddr, syn: &Segment, iss: u32) -> Self {
+        let mut tcb = Self::new(local, remote, TcpState::SynReceived, iss);
+        tcb.irs = syn.sequence;
+        tcb.rcv_nxt = syn.sequence.wrapping_add(1);
+        tcb.snd_wnd = syn.window as u32;
+        tcb.remote_mss = syn.mss.unwrap_or(DEFAULT_MSS);
+        tcb
+    }
+    
+    pub fn state(&self) -> TcpState {
+        self.state
+    }
+    
+    /// سبب الإغلاق غير الطبيعي
+    pub fn error(&self) -> Option<NetError> {
+        self.error
+    }
+    
+    /// بايتات تنتظر القراءة
+    pub fn readable(&self) -> usize {
+        self.rx.len()
+    }
+    
+    /// بايتات لم يؤكدها الطرف الآخر بعد
+    pub fn unacknowledged(&self) -> usize {
+        self.tx.len()
+    }
+    
+    fn tx_end(&self) -> u32 {
+        self.tx_seq.wrapping_add(self.tx.len() as u32)
+    }
+    
+    fn rx_window(&self) -> u32 {
+        (BUFFER_SIZE - self.rx.len()) as u32
+    }
+    
+    fn fail(&mut self, error: NetError) {
+        self.state = TcpState::Closed;
+        self.error = Some(error);
+        self.tx.clear();
+        self.timer = None;
+    }
+    
+    /// إضافة بيانات للإرسال؛ يعيد ما اتسع له المخزن
+    pub fn send(&mut self, data: &[u8]) -> Result<usize, NetError> {
+        match self.state {
+            TcpState::SynSent | TcpState::SynReceived | TcpState::Established | TcpState::CloseWait if !self.fin_queued => {}
+            TcpState::Closed => return Err(self.error.unwrap_or(NetError::NotConnected)),
+            _ => return Err(NetError::NotConnected),
+        }
+        let count = data.len().min(BUFFER_SIZE - self.tx.len());
+        if count == 0 && !data.is_empty() {
+            return Err(NetError::WouldBlock);
+        }
+        self.tx.extend(&data[..count]);
+        Ok(count)
+    }
+    
+    /// قراءة ما وصل؛ الصفر نهاية البيانات بعد FIN الطرف الآخر
+    pub fn recv(&mut self, buf: &mut [u8]) -> Result<usize, NetError> {
+        if self.rx.is_empty() {
+            return match self.state {
+                TcpState::CloseWait | TcpState::LastAck | TcpState::Closing | TcpState::TimeWait => Ok(0),
+                TcpState::Closed => self.error.map_or(Ok(0), Err),
+                _ => Err(NetError::WouldBlock),
+            };
+        }
+        
+        let before = self.rx_window();
+        let count = buf.len().min(self.rx.len());
+        for (slot, byte) in buf.iter_mut().zip(self.rx.drain(..count)) {
+            *slot = byte;
+        }
+        // تحديث النافذة حين تنفتح بعد أن ضاقت عن مقطع
+        if before < MSS as u32 && self.rx_window() >= MSS as u32 {
+            self.ack_pending = true;
+        }
+        Ok(count)
+    }
+    
+    /// إغلاق جهة الإرسال بعد إرسال ما في المخزن
+    pub fn close(&mut self) {
+        match self.state {
+            TcpState::SynSent => self.state = TcpState::Closed,
+            TcpState::SynReceived | TcpState::Established | TcpState::CloseWait => self.fin_queued = true,
+            _ => {}
+        }
+    }
+    
+    /// قطع فوري بـ RST
+    pub fn abort(&mut self) {
+        if !matches!(self.state, TcpState::Closed | TcpState::Listen | TcpState::SynSent | TcpState::TimeWait) {
+            self.rst_pending = Some(Outgoing { sequence: self.snd_nxt, acknowledgment: 0, flags: flags::RST, window: 0, mss: None, payload: Vec::new() });
+        }
+        self.state = TcpState::Closed;
+        self.tx.clear();
+        self.timer = None;
+    }
+    
+    /// معالجة مقطع وصل لهذا الاتصال
+    pub fn process(&mut self, segment: &Segment, now: u64) {
+        use flags::*;
+        
+        match self.state {
+            TcpState::Closed | TcpState::Listen => return,
+            TcpState::SynSent => return self.process_syn_sent(segment, now),
+            _ => {}
+        }
+        
+        // SYN مكرر: فُقد ردنا
+        if self.state == TcpState::SynReceived && segment.has(SYN) && segment.sequence == self.irs {
+            self.snd_nxt = self.iss;
+            return;
+        }
+        
+        if !self.acceptable(segment) {
+            if !segment.has(RST) {
+                self.ack_pending = true;
+            }
+            return;
+        }
+        
+        if segment.has(RST) {
+            // RST في غير موضعه قد يكون مزوراً: نطلب تأكيداً بـ ACK
+            if segment.sequence == self.rcv_nxt {
+                self.fail(NetError::ConnectionReset);
+            } else {
+                self.ack_pending = true;
+            }
+            return;
+        }
+        if segment.has(SYN) || !segment.has(ACK) {
+            self.ack_pending |= segment.has(SYN);
+            return;
+        }
+        
+        let ack = segment.acknowledgment;
+        if self.state == TcpState::SynReceived {
+            if seq_lt(self.snd_una, ack) && seq_le(ack, self.snd_nxt) {
+                self.state = TcpState::Established;
+            } else {
+                self.rst_pending = Some(Outgoing { sequence: ack, acknowledgment: 0, flags: RST, window: 0, mss: None, payload: Vec::new() });
+                return;
+            }
+        }
+        
+        if seq_lt(self.snd_nxt, ack) {
+            self.ack_pending = true;
+            return;
+        }
+        if seq_lt(self.snd_una, ack) {
+            self.acknowledge(ack, now);
+        }
+        if seq_le(self.snd_una, ack) {
+            self.snd_wnd = segment.window as u32;
+        }
+        
+        let fin_acked = self.fin_queued && self.snd_una == self.tx_end().wrapping_add(1);
+        match self.state {
+            TcpState::FinWait1 if fin_acked => self.state = TcpState::FinWait2,
+            TcpState::Closing if fin_acked => self.enter_time_wait(now),
+            TcpState::LastAck if fin_acked => {
+                self.state = TcpState::Closed;
+                return;
+            }
+            _ => {}
+        }
+        
+        if !segment.payload.is_empty() && matches!(self.state, TcpState::Established | TcpState::FinWait1 | TcpState::FinWait2) {
+            // ما وصل سابقاً من مقطع متداخل يُتخطى
+            let skip = (self.rcv_nxt.wrapping_sub(segment.sequence) as usize).min(segment.payload.len());
+            let data = &segment.payload[skip..];
+            let count = data.len().min(self.rx_window() as usize);
+            self.rx.extend(&data[..count]);
+            self.rcv_nxt = self.rcv_nxt.wrapping_add(count as u32);
+            self.ack_pending = true;
+            if count < data.len() {
+                return;
+            }
+        }
+        
+        let fin_sequence = segment.sequence.wrapping_add(segment.payload.len() as u32);
+        if segment.has(FIN) && fin_sequence == self.rcv_nxt {
+            self.rcv_nxt = self.rcv_nxt.wrapping_add(1);
+            self.ack_pending = true;
+            match self.state {
+                TcpState::SynReceived | TcpState::Established => self.state = TcpState::CloseWait,
+                TcpState::FinWait1 if fin_acked => self.enter_time_wait(now),
+                TcpState::FinWait1 => self.state = TcpState::Closing,
+                TcpState::FinWait2 => self.enter_time_wait(now),
+                _ => {}
+            }
+        }
+    }
+    
+    fn process_syn_sent(&mut self, segment: &Segment, now: u64) {
+        use flags::*;
+        
+        let ack = segment.acknowledgment;
+        let ack_ok = seq_lt(self.iss, ack) && seq_le(ack, self.snd_nxt);
+        if segment.has(ACK) && !ack_ok {
+            if !segment.has(RST) {
+                self.rst_pending = Some(Outgoing { sequence: ack, acknowledgment: 0, flags: RST, window: 0, mss: None, payload: Vec::new() });
+            }
+            return;
+        }
+        if segment.has(RST) {
+            if segment.has(ACK) {
+                self.fail(NetError::ConnectionRefused);
+            }
+            return;
+        }
+        // الفتح المتزامن (SYN بلا ACK) غير مدعوم
+        if !segment.has(SYN) || !segment.has(ACK) {
+            return;
+        }
+        
+        self.irs = segment.sequence;
+        self.rcv_nxt = segment.sequence.wrapping_add(1);
+        self.remote_mss = segment.mss.unwrap_or(DEFAULT_MSS);
+        self.acknowledge(ack, now);
+        self.snd_wnd = segment.window as u32;
+        self.state = TcpState::Established;
+        self.ack_pending = true;
+    }
+    
+    fn acceptable(&self, segment: &Segment) -> bool {
+        let window = self.rx_window();
+        let in_window = |sequence: u32| seq_le(self.rcv_nxt, sequence) && seq_lt(sequence, self.rcv_nxt.wrapping_add(window));
+        match (segment.sequence_len(), window) {
+            (0, 0) => segment.sequence == self.rcv_nxt,
+            (0, _) => in_window(segment.sequence),
+            (_, 0) => false,
+            (len, _) => in_window(segment.sequence) || in_window(segment.sequence.wrapping_add(len - 1)),
+        }
+    }
+    
+    /// تقدم `snd_una` إلى `ack`: تحرير المؤكد وقياس زمن الذهاب والإياب
+    fn acknowledge(&mut self, ack: u32, now: u64) {
+        if seq_lt(self.tx_seq, ack) {
+            let count = (ack.wrapping_sub(self.tx_seq) as usize).min(self.tx.len());
+            self.tx.drain(..count);
+            self.tx_seq = self.tx_seq.wrapping_add(count as u32);
+        }
+        self.snd_una = ack;
+        self.retries = 0;
+        
+        if let Some((end, sent)) = self.rtt_sample {
+            if seq_le(end, ack) {
+                self.rtt_sample = None;
+                self.update_rto(now.saturating_sub(sent));
+            }
+        }
+        self.timer = if self.snd_una == self.snd_nxt { None } else { Some(now + self.rto) };
+    }
+    
+    fn update_rto(&mut self, rtt: u64) {
+        let srtt = match self.srtt {
+            None => {
+                self.rttvar = rtt / 2;
+                rtt
+            }
+            Some(srtt) => {
+                self.rttvar = (3 * self.rttvar + srtt.abs_diff(rtt)) / 4;
+                (7 * srtt + rtt) / 8
+            }
+        };
+        self.srtt = Some(srtt);
+        self.rto = (srtt + (4 * self.rttvar).max(1)).clamp(MIN_RTO_MS, MAX_RTO_MS);
+    }
+    
+    fn enter_time_wait(&mut self, now: u64) {
+        self.state = TcpState::TimeWait;
+        self.time_wait_until = now + TIME_WAIT_MS;
+        self.timer = None;
+    }
+    
+    /// المؤقتات: الإعادة، وفحص النافذة الصفرية، ونهاية TimeWait
+    pub fn poll(&mut self, now: u64) {
+        if self.state == TcpState::TimeWait && now >= self.time_wait_until {
+            self.state = TcpState::Closed;
+        }
+        let unsent = !matches!(self.state, TcpState::SynSent | TcpState::SynReceived)
+            && (self.snd_nxt.wrapping_sub(self.tx_seq) as usize) < self.tx.len();
+        
+        match self.timer {
+            Some(deadline) if now >= deadline => {
+                self.timer = None;
+                if self.snd_una != self.snd_nxt {
+                    self.retries += 1;
+                    if self.retries > MAX_RETRIES {
+                        self.fail(NetError::TimedOut);
+                        return;
+                    }
+                    // الإعادة من أول ما لم يُؤكد (يشمل SYN و FIN)
+                    self.snd_nxt = self.snd_una;
+                    self.rto = (self.rto * 2).min(MAX_RTO_MS);
+                    self.rtt_sample = None;
+                } else if self.snd_wnd == 0 && unsent {
+                    self.probe = true;
+                    self.rto = (self.rto * 2).min(MAX_RTO_MS);
+                }
+            }
+            None if self.snd_wnd == 0 && unsent && self.snd_una == self.snd_nxt => self.timer = Some(now + self.rto),
+            _ => {}
+        }
+    }
+    
+    fn segment(&self, sequence: u32, flags: u8, payload: Vec<u8>) -> Outgoing {
+        let acknowledgment = if flags & flags::ACK != 0 { self.rcv_nxt } else { 0 };
+        let mss = (flags & flags::SYN != 0).then_some(MSS);
+        Outgoing { sequence, acknowledgment, flags, window: self.rx_window().min(u16::MAX as u32) as u16, mss, payload }
+    }
+    
+    fn arm(&mut self, now: u64) {
+        if self.timer.is_none() {
+            self.timer = Some(now + self.rto);
+        }
+    }
+    
+    /// المقاطع الواجب إرسالها الآن
+    pub fn dispatch(&mut self, now: u64) -> Vec<Outgoing> {
+        use flags::*;
+        
+        let mut out = Vec::new();
+        out.extend(self.rst_pending.take());
+        
+        match self.state {
+            TcpState::Closed | TcpState::Listen => {}
+            TcpState::SynSent | TcpState::SynReceived => {
+                if self.snd_nxt == self.iss {
+                    let flags = if self.state == TcpState::SynSent { SYN } else { SYN | ACK };
+                    out.push(self.segment(self.iss, flags, Vec::new()));
+                    self.snd_nxt = self.iss.wrapping_add(1);
+                    self.rtt_sample.get_or_insert((self.snd_nxt, now));
+                    self.arm(now);
+                } else if self.ack_pending && self.state == TcpState::SynReceived {
+                    out.push(self.segment(self.snd_nxt, ACK, Vec::new()));
+                }
+            }
+            _ => self.dispatch_data(now, &mut out),
+        }
+        
+        if out.is_empty() && self.ack_pending && self.state != TcpState::Closed {
+            out.push(self.segment(self.snd_nxt, ACK, Vec::new()));
+        }
+        self.ack_pending = false;
+        out
+    }
+    
+    fn dispatch_data(&mut self, now: u64, out: &mut Vec<Outgoing>) {
+        use flags::*;
+        
+        let mss = self.remote_mss.min(MSS) as usize;
+        loop {
+            let offset = self.snd_nxt.wrapping_sub(self.tx_seq) as usize;
+            if offset >= self.tx.len() {
+                break;
+            }
+            let window = if self.probe { self.snd_wnd.max(1) } else { self.snd_wnd };
+            let allowed = window.saturating_sub(self.snd_nxt.wrapping_sub(self.snd_una)) as usize;
+            if allowed == 0 {
+                break;
+            }
+            
+            let len = mss.min(self.tx.len() - offset).min(allowed);
+            let payload: Vec<u8> = self.tx.range(offset..offset + len).copied().collect();
+            let flags = if offset + len == self.tx.len() { ACK | PSH } else { ACK };
+            out.push(self.segment(self.snd_nxt, flags, payload));
+            self.snd_nxt = self.snd_nxt.wrapping_add(len as u32);
+            self.rtt_sample.get_or_insert((self.snd_nxt, now));
+            self.probe = false;
+            self.arm(now);
+        }
+        
+        let fin_state = matches!(self.state, TcpState::Established | TcpState::CloseWait | TcpState::FinWait1 | TcpState::Closing | TcpState::LastAck);
+        if self.fin_queued && fin_state && self.snd_nxt == self.tx_end() {
+            out.push(self.segment(self.snd_nxt, FIN | ACK, Vec::new()));
+            self.snd_nxt = self.snd_nxt.wrapping_add(1);
+            self.arm(now);
+            self.state = match self.state {
+                TcpState::Established => TcpState::FinWait1,
+                TcpState::CloseWait => TcpState::LastAck,
+                state => state,
+            };
+        }
+    }
+    
+    /// أرقام الحالة لـ /proc/net/tcp: (المرسل غير المؤكد، المستقبل غير المقروء)
+    pub fn queues(&self) -> (usize, usize) {
+        (self.tx.len(), self.rx.len())
+    }
+}
+
+/// مستمع: منفذ وطابور الاتصالات المكتملة
+#[derive(Debug)]
+pub struct Listener {
+    pub port: u16,
+    pub backlog: usize,
+    /// الاتصالات التي اكتملت مصافحتها وتنتظر `accept`
+    pub ready: VecDeque<SocketHandle>,
+}
+
+impl Listener {
+    pub fn new(port: u16, backlog: usize) -> Self {
+        Self { port, backlog: backlog.max(1), ready: VecDeque::new() }
+    }
+}
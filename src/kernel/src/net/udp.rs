@@ -0,0 +1,95 @@
+//! 📨 UDP
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! المقبس طابور رسائل واردة محدود؛ الزائد يُسقط كما في أي مقبس UDP.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use super::ipv4::{pseudo_checksum, Ipv4Addr, SocketAddr, PROTOCOL_UDP};
+
+/// طول الترويسة
+pub const HEADER_LEN: usize = 8;
+
+/// أقصى عدد رسائل تنتظر القراءة
+pub const MAX_QUEUED: usize = 64;
+
+/// رسالة مقروءة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Datagram<'a> {
+    pub source_port: u16,
+    pub destination_port: u16,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Datagram<'a> {
+    /// قراءة رسالة؛ مجموع التحقق الصفري يعني أن المرسل لم يحسبه
+    pub fn parse(source: Ipv4Addr, destination: Ipv4Addr, bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < HEADER_LEN {
+            return None;
+        }
+        let length = u16::from_be_bytes([bytes[4], bytes[5]]) as usize;
+        if length < HEADER_LEN || length > bytes.len() {
+            return None;
+        }
+        let bytes = &bytes[..length];
+        if bytes[6..8] != [0, 0] && pseudo_checksum(source, destination, PROTOCOL_UDP, bytes) != 0 {
+            return None;
+        }
+        
+        Some(Self {
+            source_port: u16::from_be_bytes([bytes[0], bytes[1]]),
+            destination_port: u16::from_be_bytes([bytes[2], bytes[3]]),
+            payload: &bytes[HEADER_LEN..],
+        })
+    }
+}
+
+/// بناء رسالة بمجموع تحقق
+pub fn build(source: SocketAddr, destination: SocketAddr, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&source.port.to_be_bytes());
+    bytes.extend_from_slice(&destination.port.to_be_bytes());
+    bytes.extend_from_slice(&((HEADER_LEN + payload.len()) as u16).to_be_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes.extend_from_slice(payload);
+    
+    // الصفر محجوز لـ"بلا مجموع"
+    let sum = match pseudo_checksum(source.ip, destination.ip, PROTOCOL_UDP, &bytes) {
+        0 => 0xFFFF,
+        sum => sum,
+    };
+    bytes[6..8].copy_from_slice(&sum.to_be_bytes());
+    bytes
+}
+
+/// مقبس UDP مربوط بمنفذ
+#[derive(Debug, Default)]
+pub struct UdpSocket {
+    pub port: u16,
+    queue: VecDeque<(SocketAddr, Vec<u8>)>,
+    /// الرسائل التي أُسقطت لامتلاء الطابور
+    pub dropped: u64,
+}
+
+impl UdpSocket {
+    pub fn new(port: u16) -> Self {
+        Self { port, ..Self::default() }
+    }
+    
+    pub fn deliver(&mut self, from: SocketAddr, payload: &[u8]) {
+        if self.queue.len() >= MAX_QUEUED {
+            self.dropped += 1;
+            return;
+        }
+        self.queue.push_back((from, payload.to_vec()));
+    }
+    
+    pub fn take(&mut self) -> Option<(SocketAddr, Vec<u8>)> {
+        self.queue.pop_front()
+    }
+    
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+}
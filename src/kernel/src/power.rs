@@ -0,0 +1,250 @@
+//! 🔌 الإغلاق وإعادة التشغيل المنظمان
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تسجل الأنظمة الفرعية خطافات إغلاق عند تهيئتها، وتُنفذ بترتيب الأولوية
+//! (الأصغر أولاً) قبل إيقاف المهام وإطفاء الجهاز أو إعادة تشغيله:
+//!
+//! ```text
+//! الذكاء الاصطناعي → الدفع → الشبكة → الجلسات → أنظمة الملفات → التعريفات
+//! ```
+//!
+//! فشل خطاف أو تجاوزه ميزانيته لا يوقف التسلسل؛ يُسجل في التقرير وسجل
+//! التدقيق ثم يُكمل الإغلاق. الميزانية بتكات المؤقت وهي للتقرير فقط: لا
+//! يُقطع خطاف أثناء عمله لأنه قد يمسك أقفالاً، فعلى الخطاف الذي قد يطول
+//! (انتظار الشبكة أو القرص) أن يسأل `HookContext::expired` ويتوقف بنفسه.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use log::{error, info, warn};
+use spin::Mutex;
+
+use crate::{accounts, arch, audit, process};
+use crate::accounts::{AuthError, Privilege, SessionId};
+
+/// أولويات الخطافات المعتادة
+pub mod priority {
+    pub const AI: u8 = 10;
+    pub const PAYMENT: u8 = 20;
+    pub const NETWORK: u8 = 30;
+    pub const SESSIONS: u8 = 40;
+    pub const FILESYSTEMS: u8 = 60;
+    pub const DRIVERS: u8 = 80;
+}
+
+/// ميزانية الخطاف الافتراضية بالتكات
+pub const DEFAULT_BUDGET_TICKS: u64 = 500;
+
+/// ما يحدث بعد تنفيذ الخطافات
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerAction {
+    PowerOff,
+    Reboot,
+}
+
+impl PowerAction {
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerAction::PowerOff => "poweroff",
+            PowerAction::Reboot => "reboot",
+        }
+    }
+}
+
+/// ما يراه الخطاف أثناء تنفيذه
+pub struct HookContext {
+    pub action: PowerAction,
+    deadline: u64,
+}
+
+impl HookContext {
+    /// هل تجاوز الخطاف ميزانيته؟ الخطاف الطويل يتوقف حين تصبح صحيحة
+    pub fn expired(&self) -> bool {
+        process::scheduler::try_ticks().is_some_and(|now| now > self.deadline)
+    }
+}
+
+/// دالة الخطاف: رسالة الخطأ تظهر في التقرير
+pub type HookFn = fn(&HookContext) -> Result<(), String>;
+
+/// خطاف مسجل
+#[derive(Clone)]
+pub struct ShutdownHook {
+    pub name: &'static str,
+    pub priority: u8,
+    /// ما يُتوقع أن يستغرقه الخطاف؛ تجاوزه يُبلغ ولا يقطعه
+    pub budget_ticks: u64,
+    pub run: HookFn,
+}
+
+/// نتيجة خطاف واحد
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookOutcome {
+    Completed,
+    Failed(String),
+    /// اكتمل بعد انتهاء ميزانيته
+    OverBudget,
+}
+
+/// سطر في تقرير الإغلاق
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookReport {
+    pub name: &'static str,
+    pub priority: u8,
+    pub elapsed: u64,
+    pub outcome: HookOutcome,
+}
+
+/// قائمة خطافات مرتبة
+#[derive(Default)]
+pub struct ShutdownSequence {
+    hooks: Vec<ShutdownHook>,
+}
+
+impl ShutdownSequence {
+    pub const fn new() -> Self {
+        Self { hooks: Vec::new() }
+    }
+    
+    /// إضافة خطاف؛ التسجيل بالاسم نفسه يستبدل السابق
+    pub fn register(&mut self, hook: ShutdownHook) {
+        self.hooks.retain(|existing| existing.name != hook.name);
+        let position = self.hooks.iter().position(|existing| existing.priority > hook.priority).unwrap_or(self.hooks.len());
+        self.hooks.insert(position, hook);
+    }
+    
+    /// تنفيذ كل الخطافات بالترتيب وإعادة التقرير
+    pub fn run(&self, action: PowerAction) -> Vec<HookReport> {
+        self.hooks.iter().map(|hook| run_hook(hook, action)).collect()
+    }
+}
+
+fn run_hook(hook: &ShutdownHook, action: PowerAction) -> HookReport {
+    let start = process::scheduler::try_ticks().unwrap_or(0);
+    let context = HookContext { action, deadline: start.saturating_add(hook.budget_ticks) };
+    
+    let result = (hook.run)(&context);
+    let elapsed = process::scheduler::try_ticks().unwrap_or(start).saturating_sub(start);
+    
+    let outcome = match result {
+        Err(message) => HookOutcome::Failed(message),
+        Ok(()) if elapsed > hook.budget_ticks => HookOutcome::OverBudget,
+        Ok(()) => HookOutcome::Completed,
+    };
+    HookReport { name: hook.name, priority: hook.priority, elapsed, outcome }
+}
+
+static SEQUENCE: Mutex<ShutdownSequence> = Mutex::new(ShutdownSequence::new());
+static LAST_REPORT: Mutex<Vec<HookReport>> = Mutex::new(Vec::new());
+static IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// تسجيل خطاف في تسلسل النظام
+pub fn register(name: &'static str, priority: u8, budget_ticks: u64, run: HookFn) {
+    SEQUENCE.lock().register(ShutdownHook { name, priority, budget_ticks, run });
+}
+
+/// هل بدأ الإغلاق؟
+pub fn in_progress() -> bool {
+    IN_PROGRESS.load(Ordering::SeqCst)
+}
+
+/// تقرير آخر إغلاق (يبقى في وضع المحاكاة حيث لا يُطفأ الجهاز فعلاً)
+pub fn last_report() -> Vec<HookReport> {
+    LAST_REPORT.lock().clone()
+}
+
+/// نسيان الإغلاق السابق كما يبدأ الإقلاع التالي (المحاكاة لا تُطفأ فعلاً)
+#[cfg(feature = "hosted")]
+pub(crate) fn reset() {
+    LAST_REPORT.lock().clear();
+    IN_PROGRESS.store(false, Ordering::SeqCst);
+}
+
+/// إغلاق يطلبه مستخدم (يتطلب صلاحية `Power`)
+pub fn shutdown(session: SessionId, action: PowerAction, reason: &str) -> Result<(), AuthError> {
+    let user = accounts::authorize(session, Privilege::Power)?;
+    kernel_shutdown(action, &alloc::format!("{} ({})", reason, user.name));
+    Ok(())
+}
+
+/// تنفيذ الخطافات ثم إيقاف المهام والإطفاء أو إعادة التشغيل؛ للإغلاق الذي
+/// تقرره النواة نفسها (زر الطاقة، نفاد الرصيد) فلا جلسة تُسأل
+pub(crate) fn kernel_shutdown(action: PowerAction, reason: &str) {
+    if IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        warn!("🔌 الإغلاق جارٍ بالفعل، تجاهل طلب {} ({})", action.name(), reason);
+        return;
+    }
+    
+    error!("🛑 بدء {}: {}", action.name(), reason);
+    audit::record("power", format_args!("{}: {}", action.name(), reason));
+    
+    // نسخة من القائمة: الخطاف قد يسجل خطافاً أو يطلب إغلاقاً
+    let hooks = SEQUENCE.lock().hooks.clone();
+    let mut report = Vec::with_capacity(hooks.len());
+    for hook in &hooks {
+        info!("🔌 خطاف الإغلاق {} (أولوية {})", hook.name, hook.priority);
+        let result = run_hook(hook, action);
+        match &result.outcome {
+            HookOutcome::Completed => {}
+            HookOutcome::Failed(message) => {
+                error!("❌ فشل خطاف {}: {}", hook.name, message);
+                audit::record("power", format_args!("فشل خطاف {}: {}", hook.name, message));
+            }
+            HookOutcome::OverBudget => {
+                error!("⏱️ تجاوز خطاف {} ميزانيته ({} من {} تكة)", hook.name, result.elapsed, hook.budget_ticks);
+                audit::record("power", format_args!("تجاوز خطاف {} ميزانيته ({} تكة)", hook.name, result.elapsed));
+            }
+        }
+        report.push(result);
+    }
+    
+    let failed = report.iter().filter(|entry| entry.outcome != HookOutcome::Completed).count();
+    info!("🔌 اكتملت خطافات الإغلاق: {} من {} بنجاح", report.len() - failed, report.len());
+    *LAST_REPORT.lock() = report;
+    
+    process::scheduler::emergency_stop();
+    match action {
+        PowerAction::PowerOff => arch::cpu::shutdown(),
+        PowerAction::Reboot => arch::cpu::reboot(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[cfg(feature = "hosted")]
+    #[test_case]
+    fn test_shutdown_sequence_order_and_failures() {
+        fn ok(_: &HookContext) -> Result<(), String> {
+            Ok(())
+        }
+        fn failing(_: &HookContext) -> Result<(), String> {
+            Err(String::from("القرص لا يستجيب"))
+        }
+        fn slow(context: &HookContext) -> Result<(), String> {
+            while !context.expired() {
+                arch::interrupts::wait_for_interrupt();
+            }
+            Ok(())
+        }
+        
+        crate::hosted::boot();
+        let hook = |name, priority, budget_ticks, run: HookFn| ShutdownHook { name, priority, budget_ticks, run };
+        let mut sequence = ShutdownSequence::new();
+        sequence.register(hook("drivers", priority::DRIVERS, 10, ok));
+        sequence.register(hook("ai", priority::AI, 3, slow));
+        sequence.register(hook("network", priority::NETWORK, 10, failing));
+        sequence.register(hook("payment", priority::PAYMENT, 10, failing));
+        sequence.register(hook("payment", priority::PAYMENT, 10, ok));
+        
+        let report = sequence.run(PowerAction::Reboot);
+        let names: Vec<_> = report.iter().map(|entry| entry.name).collect();
+        assert_eq!(names, ["ai", "payment", "network", "drivers"]);
+        assert_eq!(report[0].outcome, HookOutcome::OverBudget);
+        assert!(report[0].elapsed > 3);
+        assert_eq!(report[1].outcome, HookOutcome::Completed);
+        assert_eq!(report[2].outcome, HookOutcome::Failed(String::from("القرص لا يستجيب")));
+        assert_eq!(report[3].outcome, HookOutcome::Completed);
+    }
+}
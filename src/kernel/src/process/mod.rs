@@ -0,0 +1,8 @@
+//! 🔄 إدارة العمليات لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+pub mod scheduler;
+pub mod task;
+pub mod timer;
+
+pub use task::{Priority, TaskId, TaskState};
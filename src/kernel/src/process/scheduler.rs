@@ -0,0 +1,575 @@
+//! ⏱️ جدولة العمليات الاستباقية بالأولويات
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تُستدعى `on_timer_tick` من مقاطعة المؤقت: تُحاسب المهمة الحالية،
+//! وتوقظ النائمين، وتستبق المهمة عند انتهاء شريحتها أو ظهور مهمة أعلى أولوية.
+//! داخل كل فئة أولوية يكون التناوب دائرياً (Round Robin).
+//!
+//! لكل معالج طابور تشغيل بقفل مستقل ومهمة خمول خاصة. تُوضع المهمة عند
+//! إنشائها على أقل المعالجات حملاً وتبقى عليه، فلا يلمس معالج مكدس مهمة قد
+//! يكون معالج آخر في منتصف حفظه. معالج الإقلاع يعد التكات ويشغل المؤقتات.
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use lazy_static::lazy_static;
+use log::{info, warn};
+use spin::Mutex;
+
+use super::task::{Priority, Task, TaskId, TaskState};
+use crate::arch;
+
+lazy_static! {
+    /// طابور تشغيل لكل معالج
+    static ref RUN_QUEUES: Vec<Mutex<Scheduler>> = (0..arch::MAX_CPUS)
+        .map(|cpu| Mutex::new(Scheduler::new(cpu)))
+        .collect();
+}
+
+/// معرف المهمة التالية (فريد عبر المعالجات)
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// تكات المؤقت منذ تهيئة الجدولة
+static TICKS: AtomicU64 = AtomicU64::new(0);
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// طول نافذة محاسبة استخدام المعالج بالتكات
+const ACCOUNTING_WINDOW: u64 = 100;
+
+/// معرف مهمة النواة الرئيسية (سياق الإقلاع)
+pub const KERNEL_TASK: TaskId = TaskId(0);
+
+/// لقطة من حالة مهمة للعرض
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub name: String,
+    pub priority: Priority,
+    pub state: TaskState,
+    /// المعالج الذي تعمل عليه
+    pub cpu: usize,
+    pub cpu_ticks: u64,
+    /// نسبة استخدام المعالج في آخر نافذة محاسبة
+    pub cpu_percent: f32,
+}
+
+/// تبديل سياق معلق: (مكان حفظ المكدس الحالي، المكدس الجديد)
+type PendingSwitch = (*mut u64, u64);
+
+/// طابور تشغيل معالج واحد
+struct Scheduler {
+    cpu: usize,
+    tasks: BTreeMap<TaskId, Box<Task>>,
+    ready: [VecDeque<TaskId>; Priority::COUNT],
+    current: TaskId,
+    idle: Option<TaskId>,
+    /// تكات هذا المعالج (لنافذة المحاسبة)
+    ticks: u64,
+    window_start: u64,
+    window_busy: u64,
+    last_window: u64,
+    last_usage: f32,
+    need_resched: bool,
+    is_initialized: bool,
+}
+
+impl Scheduler {
+    fn new(cpu: usize) -> Self {
+        Self {
+            cpu,
+            tasks: BTreeMap::new(),
+            ready: Default::default(),
+            current: KERNEL_TASK,
+            idle: None,
+            ticks: 0,
+            window_start: 0,
+            window_busy: 0,
+            last_window: ACCOUNTING_WINDOW,
+            last_usage: 0.0,
+            need_resched: false,
+            is_initialized: false,
+        }
+    }
+    
+    fn spawn(&mut self, name: &str, priority: Priority, entry: fn()) -> TaskId {
+        let id = TaskId(NEXT_ID.fetch_add(1, Ordering::SeqCst));
+        let task = Task::new(id, name, priority, entry, TICKS.load(Ordering::SeqCst));
+        self.tasks.insert(id, Box::new(task));
+        self.make_ready(id);
+        id
+    }
+    
+    fn current_priority(&self) -> Priority {
+        self.tasks.get(&self.current)
+            .map(|task| task.priority)
+            .unwrap_or(Priority::Idle)
+    }
+    
+    fn make_ready(&mut self, id: TaskId) {
+        let priority = match self.tasks.get_mut(&id) {
+            Some(task) if task.state != TaskState::Dead => {
+                task.state = TaskState::Ready;
+                task.priority
+            }
+            _ => return,
+        };
+        
+        self.ready[priority.index()].push_back(id);
+        
+        if priority > self.current_priority() {
+            self.need_resched = true;
+        }
+    }
+    
+    fn highest_ready_priority(&self) -> Option<Priority> {
+        Priority::DESCENDING.iter()
+            .copied()
+            .find(|p| !self.ready[p.index()].is_empty())
+    }
+    
+    /// المهام الحية عدا الخمول
+    fn load(&self) -> usize {
+        self.tasks.values()
+            .filter(|task| task.is_alive() && Some(task.id) != self.idle)
+            .count()
+    }
+    
+    /// محاسبة تكة مؤقت واحدة
+    fn tick(&mut self) {
+        self.ticks += 1;
+        let now = TICKS.load(Ordering::SeqCst);
+        let is_idle = Some(self.current) == self.idle;
+        
+        if let Some(task) = self.tasks.get_mut(&self.current) {
+            task.cpu_ticks += 1;
+            task.window_ticks += 1;
+            task.remaining_slice = task.remaining_slice.saturating_sub(1);
+            if task.remaining_slice == 0 {
+                self.need_resched = true;
+            }
+        }
+        
+        if !is_idle {
+            self.window_busy += 1;
+        }
+        
+        // إيقاظ المهام التي انتهت مدة نومها
+        let woken: Vec<TaskId> = self.tasks.values()
+            .filter(|task| matches!(task.state, TaskState::Sleeping { until } if until <= now))
+            .map(|task| task.id)
+            .collect();
+        for id in woken {
+            self.make_ready(id);
+        }
+        
+        // إغلاق نافذة المحاسبة
+        let window = self.ticks - self.window_start;
+        if window >= ACCOUNTING_WINDOW {
+            self.last_usage = self.window_busy as f32 * 100.0 / window as f32;
+            self.last_window = window;
+            self.window_busy = 0;
+            self.window_start = self.ticks;
+            for task in self.tasks.values_mut() {
+                task.window_ticks = 0;
+            }
+        }
+    }
+    
+    /// اختيار المهمة التالية؛ `yielding` يعني التنازل لمهمة من نفس الأولوية
+    fn pick_next(&mut self, yielding: bool) -> Option<PendingSwitch> {
+        self.need_resched = false;
+        
+        let current_id = self.current;
+        let (current_running, current_priority, slice_left) = match self.tasks.get(&current_id) {
+            Some(task) => (task.state == TaskState::Running, task.priority, task.remaining_slice),
+            None => (false, Priority::Idle, 0),
+        };
+        
+        let next_priority = match self.highest_ready_priority() {
+            Some(priority) => priority,
+            None => {
+                self.refill_slice(current_id);
+                return None;
+            }
+        };
+        
+        if current_running {
+            let keep = next_priority < current_priority
+                || (next_priority == current_priority && slice_left > 0 && !yielding);
+            if keep {
+                self.refill_slice(current_id);
+                return None;
+            }
+        }
+        
+        let next_id = self.ready[next_priority.index()].pop_front()?;
+        
+        if current_running {
+            if let Some(task) = self.tasks.get_mut(&current_id) {
+                task.state = TaskState::Ready;
+            }
+            self.ready[current_priority.index()].push_back(current_id);
+        }
+        self.refill_slice(current_id);
+        
+        let next_rsp = {
+            let next = self.tasks.get_mut(&next_id)?;
+            next.state = TaskState::Running;
+            next.remaining_slice = next.priority.time_slice();
+            next.saved_rsp
+        };
+        self.current = next_id;
+        
+        let current = self.tasks.get_mut(&current_id)?;
+        Some((&mut current.saved_rsp as *mut u64, next_rsp))
+    }
+    
+    fn refill_slice(&mut self, id: TaskId) {
+        if let Some(task) = self.tasks.get_mut(&id) {
+            if task.remaining_slice == 0 {
+                task.remaining_slice = task.priority.time_slice();
+            }
+        }
+    }
+    
+    /// تحرير المهام المنتهية (عدا الحالية لأنها ما زالت على مكدسها)
+    fn reap(&mut self) {
+        let current = self.current;
+        self.tasks.retain(|id, task| task.is_alive() || *id == current);
+    }
+    
+    fn should_yield(&self) -> bool {
+        if self.need_resched {
+            return true;
+        }
+        
+        match self.highest_ready_priority() {
+            Some(priority) => priority > Priority::Idle && priority >= self.current_priority(),
+            None => false,
+        }
+    }
+}
+
+/// تنفيذ تبديل السياق بعد تحرير القفل
+fn perform_switch(switch: Option<PendingSwitch>) {
+    if let Some((old_rsp, new_rsp)) = switch {
+        unsafe {
+            arch::context::switch(old_rsp, new_rsp);
+        }
+    }
+}
+
+/// مهمة الخمول: تعمل فقط عندما لا توجد مهمة جاهزة
+fn idle_loop() {
+    loop {
+        arch::cpu::halt();
+    }
+}
+
+/// طابور المعالج المنفذ
+fn local() -> &'static Mutex<Scheduler> {
+    &RUN_QUEUES[arch::smp::current()]
+}
+
+/// طوابير المعالجات العاملة
+fn online_queues() -> impl Iterator<Item = &'static Mutex<Scheduler>> {
+    RUN_QUEUES.iter().take(arch::smp::online())
+}
+
+/// نقطة بداية كل مهمة جديدة على مكدسها الخاص
+pub extern "C" fn task_trampoline() -> ! {
+    let entry = {
+        let scheduler = local().lock();
+        scheduler.tasks.get(&scheduler.current).and_then(|task| task.entry)
+    };
+    
+    // وصلنا إلى هنا من داخل مقاطعة المؤقت أو من تنازل بمقاطعات معطلة
+    unsafe {
+        arch::interrupts::enable();
+    }
+    
+    if let Some(entry) = entry {
+        entry();
+    }
+    
+    exit();
+}
+
+/// تهيئة الجدولة: سياق الإقلاع يصبح مهمة النواة الرئيسية على المعالج 0
+pub fn init() {
+    arch::interrupts::without_interrupts(|| {
+        let mut scheduler = RUN_QUEUES[0].lock();
+        if scheduler.is_initialized {
+            return;
+        }
+        
+        let kernel = Task::boot(KERNEL_TASK, "kernel", Priority::Normal);
+        scheduler.tasks.insert(KERNEL_TASK, Box::new(kernel));
+        scheduler.current = KERNEL_TASK;
+        
+        let idle = scheduler.spawn("idle", Priority::Idle, idle_loop);
+        scheduler.idle = Some(idle);
+        scheduler.is_initialized = true;
+        INITIALIZED.store(true, Ordering::SeqCst);
+    });
+    
+    crate::fs::procfs::register("tasks", proc_tasks);
+    info!("⏱️ الجدولة الاستباقية جاهزة ({} فئات أولوية)", Priority::COUNT);
+}
+
+/// تهيئة طابور معالج تطبيقي: سياق بدئه يصبح مهمة الخمول عليه
+pub fn init_ap(cpu: usize) {
+    arch::interrupts::without_interrupts(|| {
+        let mut scheduler = RUN_QUEUES[cpu].lock();
+        let id = TaskId(NEXT_ID.fetch_add(1, Ordering::SeqCst));
+        let idle = Task::boot(id, &format!("idle/{}", cpu), Priority::Idle);
+        scheduler.tasks.insert(id, Box::new(idle));
+        scheduler.current = id;
+        scheduler.idle = Some(id);
+        scheduler.is_initialized = true;
+    });
+}
+
+/// /proc/tasks: جدول المهام بسطر عناوين
+fn proc_tasks() -> String {
+    use core::fmt::Write;
+    
+    let mut table = String::from("id\tname\tpriority\tstate\tcpu\tcpu_ticks\tcpu_percent\n");
+    for task in task_list() {
+        let state = match task.state {
+            TaskState::Ready => "ready",
+            TaskState::Running => "running",
+            TaskState::Sleeping { .. } => "sleeping",
+            TaskState::Blocked => "blocked",
+            TaskState::Dead => "dead",
+        };
+        let _ = writeln!(table, "{}\t{}\t{:?}\t{}\t{}\t{}\t{:.1}", task.id, task.name, task.priority, state, task.cpu, task.cpu_ticks, task.cpu_percent);
+    }
+    table
+}
+
+/// إنشاء مهمة نواة جديدة على أقل المعالجات حملاً
+pub fn spawn(name: &str, priority: Priority, entry: fn()) -> TaskId {
+    let cpu = arch::interrupts::without_interrupts(|| {
+        (0..arch::smp::online())
+            .min_by_key(|&cpu| RUN_QUEUES[cpu].lock().load())
+            .unwrap_or(0)
+    });
+    spawn_on(cpu, name, priority, entry)
+}
+
+/// إنشاء مهمة على معالج بعينه (المعالج 0 إن لم يكن عاملاً)
+pub fn spawn_on(cpu: usize, name: &str, priority: Priority, entry: fn()) -> TaskId {
+    let cpu = if cpu < arch::smp::online() { cpu } else { 0 };
+    let id = arch::interrupts::without_interrupts(|| {
+        RUN_QUEUES[cpu].lock().spawn(name, priority, entry)
+    });
+    info!("🆕 مهمة جديدة: {} ({}) بأولوية {:?} على المعالج {}", name, id, priority, cpu);
+    id
+}
+
+/// يُستدعى من معالج مقاطعة المؤقت أو تكة معالج الإقلاع (المقاطعات معطلة)
+pub fn on_timer_tick() {
+    let cpu = arch::smp::current();
+    if cpu == 0 {
+        // المؤقتات والمراقب تعمل قبل تهيئة الجدولة أيضاً
+        super::timer::on_tick();
+        crate::health::watchdog_tick();
+        if INITIALIZED.load(Ordering::SeqCst) {
+            TICKS.fetch_add(1, Ordering::SeqCst);
+        }
+        arch::smp::broadcast_tick();
+    }
+    
+    let switch = {
+        let mut scheduler = RUN_QUEUES[cpu].lock();
+        if !scheduler.is_initialized {
+            return;
+        }
+        
+        scheduler.tick();
+        if scheduler.need_resched {
+            scheduler.pick_next(false)
+        } else {
+            None
+        }
+    };
+    
+    perform_switch(switch);
+}
+
+/// نقطة جدولة من الحلقة الرئيسية: تحرير المنتهي والتنازل عند الحاجة
+pub fn run() {
+    let should_yield = arch::interrupts::without_interrupts(|| {
+        let mut scheduler = local().lock();
+        scheduler.reap();
+        scheduler.should_yield()
+    });
+    
+    if should_yield {
+        yield_now();
+    }
+}
+
+/// التنازل عن المعالج طوعاً
+pub fn yield_now() {
+    arch::interrupts::without_interrupts(|| {
+        let switch = local().lock().pick_next(true);
+        perform_switch(switch);
+    });
+}
+
+/// نوم المهمة الحالية لعدد من التكات
+pub fn sleep(ticks: u64) {
+    arch::interrupts::without_interrupts(|| {
+        let switch = {
+            let mut scheduler = local().lock();
+            let until = TICKS.load(Ordering::SeqCst) + ticks.max(1);
+            let current = scheduler.current;
+            if let Some(task) = scheduler.tasks.get_mut(&current) {
+                task.state = TaskState::Sleeping { until };
+            }
+            scheduler.pick_next(true)
+        };
+        perform_switch(switch);
+    });
+}
+
+/// حجب المهمة الحالية حتى تُستدعى `wake`
+pub fn block_current() {
+    arch::interrupts::without_interrupts(|| {
+        let switch = {
+            let mut scheduler = local().lock();
+            let current = scheduler.current;
+            if let Some(task) = scheduler.tasks.get_mut(&current) {
+                task.state = TaskState::Blocked;
+            }
+            scheduler.pick_next(true)
+        };
+        perform_switch(switch);
+    });
+}
+
+/// إيقاظ مهمة نائمة أو محجوبة على أي معالج
+pub fn wake(id: TaskId) -> bool {
+    arch::interrupts::without_interrupts(|| {
+        for queue in online_queues() {
+            let mut scheduler = queue.lock();
+            let Some(state) = scheduler.tasks.get(&id).map(|task| task.state) else {
+                continue;
+            };
+            let waiting = matches!(state, TaskState::Sleeping { .. } | TaskState::Blocked);
+            if waiting {
+                scheduler.make_ready(id);
+            }
+            return waiting;
+        }
+        false
+    })
+}
+
+/// إنهاء المهمة الحالية
+pub fn exit() -> ! {
+    arch::interrupts::without_interrupts(|| {
+        let switch = {
+            let mut scheduler = local().lock();
+            let current = scheduler.current;
+            if let Some(task) = scheduler.tasks.get_mut(&current) {
+                task.state = TaskState::Dead;
+            }
+            scheduler.pick_next(true)
+        };
+        perform_switch(switch);
+    });
+    
+    // لا نعود إلى مهمة منتهية
+    loop {
+        arch::cpu::halt();
+    }
+}
+
+/// معرف المهمة الحالية على هذا المعالج
+pub fn current_id() -> TaskId {
+    arch::interrupts::without_interrupts(|| local().lock().current)
+}
+
+/// عدد المهام الحية على كل المعالجات (بدون مهام الخمول)
+pub fn get_active_count() -> u32 {
+    arch::interrupts::without_interrupts(|| {
+        online_queues().map(|queue| queue.lock().load()).sum::<usize>() as u32
+    })
+}
+
+/// متوسط استخدام المعالجات في آخر نافذة محاسبة
+pub fn cpu_usage() -> f32 {
+    let cores = core_usage();
+    cores.iter().sum::<f32>() / cores.len().max(1) as f32
+}
+
+/// استخدام كل معالج عامل في آخر نافذة محاسبة، بترتيب أرقامها
+pub fn core_usage() -> Vec<f32> {
+    arch::interrupts::without_interrupts(|| {
+        online_queues().map(|queue| queue.lock().last_usage).collect()
+    })
+}
+
+/// عدد تكات المؤقت منذ تهيئة الجدولة
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::SeqCst)
+}
+
+/// عدد التكات دون أقفال (للسجل ومعالج الذعر)؛ لا شيء قبل تهيئة الجدولة
+pub fn try_ticks() -> Option<u64> {
+    INITIALIZED.load(Ordering::SeqCst).then(ticks)
+}
+
+/// قائمة المهام الحالية على كل المعالجات
+pub fn task_list() -> Vec<TaskInfo> {
+    arch::interrupts::without_interrupts(|| {
+        let mut tasks = Vec::new();
+        for queue in online_queues() {
+            let scheduler = queue.lock();
+            let window = (scheduler.ticks - scheduler.window_start).max(1);
+            tasks.extend(scheduler.tasks.values().map(|task| TaskInfo {
+                id: task.id,
+                name: task.name.clone(),
+                priority: task.priority,
+                state: task.state,
+                cpu: scheduler.cpu,
+                cpu_ticks: task.cpu_ticks,
+                cpu_percent: task.window_ticks as f32 * 100.0 / window as f32,
+            }));
+        }
+        tasks.sort_by_key(|task| task.id);
+        tasks
+    })
+}
+
+/// إيقاف جميع المهام عدا النواة والحالية ومهام الخمول
+pub fn emergency_stop() {
+    let stopped = arch::interrupts::without_interrupts(|| {
+        let mut stopped = 0;
+        for queue in online_queues() {
+            let mut scheduler = queue.lock();
+            let keep = [Some(scheduler.current), Some(KERNEL_TASK), scheduler.idle];
+            
+            for task in scheduler.tasks.values_mut() {
+                if !keep.contains(&Some(task.id)) && task.is_alive() {
+                    task.state = TaskState::Dead;
+                    stopped += 1;
+                }
+            }
+            
+            let idle = scheduler.idle;
+            for ready in scheduler.ready.iter_mut() {
+                ready.retain(|id| Some(*id) == idle || *id == KERNEL_TASK);
+            }
+        }
+        stopped
+    });
+    
+    warn!("🛑 تم إيقاف {} مهمة", stopped);
+}
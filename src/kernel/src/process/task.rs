@@ -0,0 +1,137 @@
+//! 📋 كتلة التحكم في المهمة (TCB)
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt;
+
+use crate::arch;
+
+/// حجم مكدس النواة لكل مهمة
+pub const KERNEL_STACK_SIZE: usize = 16 * 1024; // 16 كيلوبايت
+
+/// معرف المهمة
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TaskId(pub u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// فئات الأولوية (الأعلى يسبق دائماً)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Idle = 0,
+    Background = 1,
+    Normal = 2,
+    High = 3,
+    Realtime = 4,
+}
+
+impl Priority {
+    /// عدد فئات الأولوية
+    pub const COUNT: usize = 5;
+    
+    /// جميع الفئات من الأعلى إلى الأدنى
+    pub const DESCENDING: [Priority; Priority::COUNT] = [
+        Priority::Realtime,
+        Priority::High,
+        Priority::Normal,
+        Priority::Background,
+        Priority::Idle,
+    ];
+    
+    pub fn index(self) -> usize {
+        self as usize
+    }
+    
+    /// الشريحة الزمنية بالتكات قبل الاستباق
+    pub fn time_slice(self) -> u32 {
+        match self {
+            Priority::Realtime => 20,
+            Priority::High => 10,
+            Priority::Normal => 5,
+            Priority::Background => 3,
+            Priority::Idle => 1,
+        }
+    }
+}
+
+/// حالة المهمة
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    Ready,
+    Running,
+    Sleeping { until: u64 },
+    Blocked,
+    Dead,
+}
+
+/// كتلة التحكم في المهمة
+pub struct Task {
+    pub id: TaskId,
+    pub name: String,
+    pub priority: Priority,
+    pub state: TaskState,
+    /// مؤشر المكدس المحفوظ عند تبديل السياق
+    pub saved_rsp: u64,
+    /// دالة الدخول (لا توجد لمهمة النواة الرئيسية)
+    pub entry: Option<fn()>,
+    /// عدد التكات التي قضتها المهمة على المعالج
+    pub cpu_ticks: u64,
+    /// تكات المعالج منذ آخر نافذة محاسبة
+    pub window_ticks: u64,
+    pub remaining_slice: u32,
+    pub created_at: u64,
+    stack: Option<Box<[u8]>>,
+}
+
+impl Task {
+    /// مهمة جديدة بمكدس خاص تبدأ من `entry`
+    pub fn new(id: TaskId, name: &str, priority: Priority, entry: fn(), now: u64) -> Self {
+        let mut stack = vec![0u8; KERNEL_STACK_SIZE].into_boxed_slice();
+        let saved_rsp = arch::context::prepare_stack(&mut stack, super::scheduler::task_trampoline);
+        
+        Self {
+            id,
+            name: name.to_string(),
+            priority,
+            state: TaskState::Ready,
+            saved_rsp,
+            entry: Some(entry),
+            cpu_ticks: 0,
+            window_ticks: 0,
+            remaining_slice: priority.time_slice(),
+            created_at: now,
+            stack: Some(stack),
+        }
+    }
+    
+    /// مهمة تمثل سياق الإقلاع الحالي (تستخدم مكدس الإقلاع)
+    pub fn boot(id: TaskId, name: &str, priority: Priority) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            priority,
+            state: TaskState::Running,
+            saved_rsp: 0,
+            entry: None,
+            cpu_ticks: 0,
+            window_ticks: 0,
+            remaining_slice: priority.time_slice(),
+            created_at: 0,
+            stack: None,
+        }
+    }
+    
+    pub fn is_alive(&self) -> bool {
+        self.state != TaskState::Dead
+    }
+    
+    pub fn stack_size(&self) -> usize {
+        self.stack.as_ref().map(|s| s.len()).unwrap_or(0)
+    }
+}
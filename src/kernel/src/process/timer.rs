@@ -0,0 +1,238 @@
+//! ⏰ مؤقتات النواة وطابور العمل المؤجل
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تسجل الأنظمة الفرعية مهاماً دورية أو لمرة واحدة بالميلي ثانية، وتُحفظ في
+//! عجلة مؤقتات مجزأة تتقدم خانة واحدة مع كل مقاطعة مؤقت:
+//!
+//! ```text
+//! IRQ 0 ──▶ on_tick ──▶ عجلة المؤقتات ──انتهت──▶ طابور العمل ──▶ run_pending (الحلقة الرئيسية)
+//! ```
+//!
+//! لا تُنفذ المهام داخل المقاطعة؛ تُنقل إلى طابور العمل المؤجل وتنفذها
+//! الحلقة الرئيسية والمقاطعات مفعلة، فيمكنها أخذ الأقفال والكتابة إلى القرص.
+//! المهمة الدورية التي لم تُنفذ بعد لا تُضاف مرة ثانية.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+use crate::arch;
+
+/// عدد خانات العجلة
+pub const SLOTS: usize = 256;
+
+/// تحويل ميلي ثوانٍ إلى تكات (تكة واحدة على الأقل)
+pub fn ms_to_ticks(ms: u64) -> u64 {
+    (ms * arch::pit::HZ as u64).div_ceil(1000).max(1)
+}
+
+/// تحويل تكات إلى ميلي ثوانٍ
+pub fn ticks_to_ms(ticks: u64) -> u64 {
+    ticks * 1000 / arch::pit::HZ as u64
+}
+
+/// معرف مؤقت مسجل
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+/// دالة المهمة
+pub type Job = fn();
+
+struct Timer {
+    id: TimerId,
+    name: &'static str,
+    deadline: u64,
+    period: Option<u64>,
+    job: Job,
+}
+
+/// مؤقت انتهت مهلته في التكة الحالية
+#[derive(Debug, Clone, Copy)]
+pub struct Expired {
+    pub id: TimerId,
+    pub name: &'static str,
+    pub job: Job,
+}
+
+/// عجلة مؤقتات مجزأة: المؤقت في الخانة `deadline % SLOTS`
+pub struct TimerWheel {
+    slots: [Vec<Timer>; SLOTS],
+    now: u64,
+    next_id: u64,
+}
+
+impl TimerWheel {
+    pub const fn new() -> Self {
+        const EMPTY: Vec<Timer> = Vec::new();
+        Self { slots: [EMPTY; SLOTS], now: 0, next_id: 1 }
+    }
+    
+    /// التكة الحالية للعجلة
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+    
+    /// تسجيل مؤقت ينتهي بعد `delay` تكة، ويتكرر كل `period` إن وُجدت
+    pub fn schedule(&mut self, name: &'static str, delay: u64, period: Option<u64>, job: Job) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.insert(Timer { id, name, deadline: self.now + delay.max(1), period: period.map(|p| p.max(1)), job });
+        id
+    }
+    
+    fn insert(&mut self, timer: Timer) {
+        self.slots[(timer.deadline % SLOTS as u64) as usize].push(timer);
+    }
+    
+    /// إلغاء مؤقت؛ false إن لم يكن مسجلاً
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        for slot in self.slots.iter_mut() {
+            if let Some(position) = slot.iter().position(|timer| timer.id == id) {
+                slot.swap_remove(position);
+                return true;
+            }
+        }
+        false
+    }
+    
+    /// عدد المؤقتات المسجلة
+    pub fn pending(&self) -> usize {
+        self.slots.iter().map(Vec::len).sum()
+    }
+    
+    /// التقدم تكة واحدة وإعادة المؤقتات المنتهية بترتيب تسجيلها
+    pub fn advance(&mut self) -> Vec<Expired> {
+        self.now += 1;
+        let now = self.now;
+        
+        let slot = &mut self.slots[(now % SLOTS as u64) as usize];
+        let (due, waiting): (Vec<Timer>, Vec<Timer>) = slot.drain(..).partition(|timer| timer.deadline <= now);
+        *slot = waiting;
+        
+        let mut expired: Vec<Expired> = Vec::with_capacity(due.len());
+        for mut timer in due {
+            expired.push(Expired { id: timer.id, name: timer.name, job: timer.job });
+            if let Some(period) = timer.period {
+                timer.deadline = now + period;
+                self.insert(timer);
+            }
+        }
+        expired.sort_by_key(|timer| timer.id);
+        expired
+    }
+}
+
+impl Default for TimerWheel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// عمل مؤجل ينتظر الحلقة الرئيسية
+#[derive(Debug, Clone, Copy)]
+struct Work {
+    name: &'static str,
+    job: Job,
+    timer: Option<TimerId>,
+}
+
+static WHEEL: Mutex<TimerWheel> = Mutex::new(TimerWheel::new());
+static WORK: Mutex<VecDeque<Work>> = Mutex::new(VecDeque::new());
+
+/// مهمة دورية كل `ms` ميلي ثانية (أول تنفيذ بعد فترة كاملة)
+pub fn every(name: &'static str, ms: u64, job: Job) -> TimerId {
+    let ticks = ms_to_ticks(ms);
+    arch::interrupts::without_interrupts(|| WHEEL.lock().schedule(name, ticks, Some(ticks), job))
+}
+
+/// مهمة لمرة واحدة بعد `ms` ميلي ثانية
+pub fn after(name: &'static str, ms: u64, job: Job) -> TimerId {
+    let ticks = ms_to_ticks(ms);
+    arch::interrupts::without_interrupts(|| WHEEL.lock().schedule(name, ticks, None, job))
+}
+
+/// إلغاء مؤقت مسجل
+pub fn cancel(id: TimerId) -> bool {
+    arch::interrupts::without_interrupts(|| WHEEL.lock().cancel(id))
+}
+
+/// تكات المؤقت منذ الإقلاع
+pub fn ticks() -> u64 {
+    arch::interrupts::without_interrupts(|| WHEEL.lock().now())
+}
+
+/// الزمن منذ تهيئة المؤقت بالميلي ثانية
+pub fn uptime_ms() -> u64 {
+    ticks_to_ms(ticks())
+}
+
+/// تأجيل عمل إلى الحلقة الرئيسية (آمن من داخل المقاطعات)
+pub fn defer(name: &'static str, job: Job) {
+    arch::interrupts::without_interrupts(|| WORK.lock().push_back(Work { name, job, timer: None }));
+}
+
+/// عدد الأعمال المؤجلة التي تنتظر التنفيذ
+pub fn queued() -> usize {
+    arch::interrupts::without_interrupts(|| WORK.lock().len())
+}
+
+/// يُستدعى من معالج مقاطعة المؤقت (المقاطعات معطلة)
+pub fn on_tick() {
+    let expired = WHEEL.lock().advance();
+    if expired.is_empty() {
+        return;
+    }
+    
+    let mut work = WORK.lock();
+    for timer in expired {
+        if !work.iter().any(|queued| queued.timer == Some(timer.id)) {
+            work.push_back(Work { name: timer.name, job: timer.job, timer: Some(timer.id) });
+        }
+    }
+}
+
+/// تنفيذ الأعمال المؤجلة خارج المقاطعة؛ يعيد عدد ما نُفذ
+pub fn run_pending() -> usize {
+    let mut count = 0;
+    // عمل واحد في كل مرة: العمل قد يؤجل عملاً آخر أو يسجل مؤقتاً
+    while let Some(work) = arch::interrupts::without_interrupts(|| WORK.lock().pop_front()) {
+        log::trace!("⏰ تنفيذ عمل مؤجل: {}", work.name);
+        (work.job)();
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_timer_wheel() {
+        fn job() {}
+        
+        assert_eq!(ms_to_ticks(0), 1);
+        assert_eq!(ticks_to_ms(ms_to_ticks(250)), 250);
+        
+        let mut wheel = TimerWheel::new();
+        let periodic = wheel.schedule("periodic", 3, Some(3), job);
+        let once = wheel.schedule("once", 5, None, job);
+        // مهلة أطول من دورة العجلة تنتظر دورتها في الخانة نفسها
+        let far = wheel.schedule("far", SLOTS as u64 + 1, None, job);
+        
+        let mut fired = Vec::new();
+        for _ in 0..SLOTS + 1 {
+            for expired in wheel.advance() {
+                fired.push((wheel.now(), expired.name));
+            }
+        }
+        assert_eq!(&fired[..3], &[(3, "periodic"), (5, "once"), (6, "periodic")]);
+        assert_eq!(fired.iter().filter(|(_, name)| *name == "once").count(), 1);
+        assert_eq!(fired.last(), Some(&(SLOTS as u64 + 1, "far")));
+        
+        assert!(wheel.cancel(periodic));
+        assert!(!wheel.cancel(once));
+        assert!(!wheel.cancel(far));
+        assert_eq!(wheel.pending(), 0);
+    }
+}
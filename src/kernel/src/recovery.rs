@@ -0,0 +1,275 @@
+//! 🧰 وحدة تحكم الاسترداد
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تعمل عند الإقلاع بـ `recovery=1`: لا تطبيقات ولا ذكاء اصطناعي ولا دفع،
+//! أنظمة الملفات للقراءة فقط، وأوامر تشخيص بسيطة عبر المنفذ التسلسلي أو
+//! لوحة المفاتيح (الحروف اللاتينية فقط). إعادة التشغيل والإيقاف تتطلبان
+//! جلسة بصلاحية `Power` تُفتح بأمر `login`.
+
+use alloc::string::String;
+use core::fmt::Write;
+use log::info;
+
+use crate::accounts::{self, AuthError, SessionId};
+use crate::drivers::{keyboard, serial, vga};
+use crate::input::{self, InputEvent, Key};
+use crate::power::{self, PowerAction};
+use crate::{arch, boot, process};
+
+/// أقصى طول لسطر الأوامر
+const MAX_LINE: usize = 256;
+
+const PROMPT: &str = "recovery# ";
+
+/// عدد أسطر السجل التي يعرضها أمر dmesg
+const DMESG_LINES: usize = 32;
+
+/// نتيجة تنفيذ أمر
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleAction {
+    Continue,
+    Reboot,
+    Halt,
+}
+
+/// طباعة إلى المنفذ التسلسلي وشاشة VGA معاً
+macro_rules! console_print {
+    ($($arg:tt)*) => {{
+        serial::write_fmt(format_args!($($arg)*));
+        let _ = vga::WRITER.lock().write_fmt(format_args!($($arg)*));
+    }};
+}
+
+/// محرر أسطر بسيط فوق المنفذ التسلسلي
+pub struct Console {
+    line: String,
+    /// جلسة `login` المفتوحة
+    session: Option<SessionId>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self { line: String::new(), session: None }
+    }
+    
+    pub fn session(&self) -> Option<SessionId> {
+        self.session
+    }
+    
+    /// معالجة بايت مدخل؛ يعيد إجراءً عند اكتمال سطر
+    pub fn feed(&mut self, byte: u8) -> Option<ConsoleAction> {
+        match byte {
+            b'\r' | b'\n' => {
+                console_print!("\n");
+                let line = core::mem::take(&mut self.line);
+                let action = self.execute(line.trim());
+                if action == ConsoleAction::Continue {
+                    console_print!("{}", PROMPT);
+                }
+                Some(action)
+            }
+            // Backspace و DEL
+            0x08 | 0x7F => {
+                if self.line.pop().is_some() {
+                    console_print!("\x08 \x08");
+                }
+                None
+            }
+            byte if byte.is_ascii() && !byte.is_ascii_control() && self.line.len() < MAX_LINE => {
+                self.line.push(byte as char);
+                console_print!("{}", byte as char);
+                None
+            }
+            _ => None,
+        }
+    }
+    
+    /// تنفيذ أمر واحد
+    pub fn execute(&mut self, command: &str) -> ConsoleAction {
+        match command {
+            "" => {}
+            "help" => {
+                console_print!("الأوامر المتاحة:\n");
+                console_print!("  help     - عرض هذه القائمة\n");
+                console_print!("  status   - حالة النظام\n");
+                console_print!("  mem      - استخدام الذاكرة\n");
+                console_print!("  tasks    - قائمة المهام\n");
+                console_print!("  tokens   - رصيد التوكنات\n");
+                console_print!("  users    - الحسابات والجلسات\n");
+                console_print!("  audit    - آخر أحداث سجل التدقيق\n");
+                console_print!("  dmesg    - آخر أسطر سجل النواة (dmesg clear للمسح)\n");
+                console_print!("  cmdline  - سطر أوامر النواة\n");
+                console_print!("  crash    - سجل العطل من الإقلاع السابق (crash clear للمسح)\n");
+                console_print!("  login    - فتح جلسة: login <اسم> <كلمة المرور>\n");
+                console_print!("  logout   - إغلاق الجلسة\n");
+                console_print!("  reboot   - إعادة التشغيل\n");
+                console_print!("  halt     - إيقاف التشغيل\n");
+            }
+            "status" => {
+                let state = crate::SYSTEM_STATE.lock();
+                console_print!("{} {}\n", crate::SYSTEM_NAME, crate::SYSTEM_VERSION);
+                console_print!("وقت التشغيل: {} تكت\n", state.uptime_ticks);
+                console_print!("العمليات النشطة: {}\n", state.active_processes);
+                console_print!("أنظمة الملفات: {}\n", if crate::fs::is_read_only() { "للقراءة فقط" } else { "قراءة وكتابة" });
+            }
+            "mem" => {
+                let stats = crate::memory::get_usage_stats();
+                console_print!("الذاكرة: {}/{} بايت ({:.1}%)\n", stats.used, stats.total, stats.percent());
+            }
+            "tasks" => {
+                for task in process::scheduler::task_list() {
+                    console_print!("{:>4} {:<16} cpu{} {:?} {:?}\n", task.id, task.name, task.cpu, task.priority, task.state);
+                }
+            }
+            "tokens" => {
+                let tokens = crate::TOKEN_MANAGER.lock();
+                console_print!("{}: {} (الحد الأدنى: {})\n", crate::TOKEN_NAME, tokens.current_tokens, tokens.min_tokens);
+                console_print!("السجل: {} (المحفظة {})\n", tokens.ledger.name(), tokens.wallet);
+                console_print!("المرحلة: {}\n", tokens.policy.state().name());
+            }
+            "users" => {
+                for user in crate::accounts::users() {
+                    console_print!("{:>6} {:<16} {:?} ({} {})\n", user.id, user.name, user.role, user.token_balance, crate::TOKEN_NAME);
+                }
+                for session in crate::accounts::sessions() {
+                    console_print!("جلسة {}: {} منذ التكة {}\n", session.id, session.name, session.started);
+                }
+            }
+            "audit" => {
+                for entry in crate::audit::recent(16) {
+                    console_print!("{:>8} [{}] {}\n", entry.tick, entry.category, entry.message);
+                }
+            }
+            "dmesg" => {
+                for line in crate::dmesg::recent(DMESG_LINES) {
+                    console_print!("[{:>8}] {:<5} {}\n", line.tick, line.level, line.text);
+                }
+            }
+            "dmesg clear" => crate::dmesg::clear(),
+            "cmdline" => {
+                console_print!("{}\n", boot::params().raw());
+            }
+            "crash" => match crate::crash::last() {
+                Some(record) => {
+                    for (key, value) in &record.fields {
+                        console_print!("{} = {}\n", key, value);
+                    }
+                    for frame in &record.backtrace {
+                        console_print!("  {}\n", frame);
+                    }
+                    for line in &record.log {
+                        console_print!("  {}\n", line);
+                    }
+                }
+                None => console_print!("لا يوجد سجل عطل محفوظ\n"),
+            },
+            "crash clear" => match crate::crash::clear() {
+                Ok(()) => console_print!("تم مسح سجل العطل\n"),
+                Err(e) => console_print!("فشل مسح سجل العطل: {:?}\n", e),
+            },
+            "logout" => match self.session.take() {
+                Some(session) => {
+                    let _ = accounts::logout(session);
+                    console_print!("تم إغلاق الجلسة\n");
+                }
+                None => console_print!("لا جلسة مفتوحة\n"),
+            },
+            "reboot" => return ConsoleAction::Reboot,
+            "halt" => return ConsoleAction::Halt,
+            unknown => match unknown.strip_prefix("login ") {
+                Some(credentials) => self.login(credentials),
+                None => console_print!("أمر غير معروف: {} (اكتب help)\n", unknown),
+            },
+        }
+        
+        ConsoleAction::Continue
+    }
+    
+    /// فتح جلسة تحل محل السابقة
+    fn login(&mut self, credentials: &str) {
+        let (name, password) = credentials.trim().split_once(' ').unwrap_or((credentials.trim(), ""));
+        match accounts::login(name, password) {
+            Ok(session) => {
+                if let Some(previous) = self.session.replace(session) {
+                    let _ = accounts::logout(previous);
+                }
+                console_print!("مرحباً {}\n", name);
+            }
+            Err(e) => console_print!("فشل الدخول: {:?}\n", e),
+        }
+    }
+    
+    /// إعادة التشغيل أو الإيقاف باسم الجلسة المفتوحة
+    pub fn power(&self, action: PowerAction, reason: &str) -> Result<(), AuthError> {
+        let result = match self.session {
+            Some(session) => power::shutdown(session, action, reason),
+            None => Err(AuthError::NoSession),
+        };
+        if let Err(e) = result {
+            console_print!("⛔ رُفض {}: {:?} (يتطلب login بحساب مدير)\n{}", action.name(), e, PROMPT);
+        }
+        result
+    }
+}
+
+/// حلقة وحدة تحكم الاسترداد (تحل محل `main_loop`)
+pub fn run() -> ! {
+    info!("🧰 بدء وحدة تحكم الاسترداد على المنفذ التسلسلي");
+    
+    console_print!("\n🧰 {} - وضع الاسترداد\n", crate::SYSTEM_NAME);
+    console_print!("اكتب help لعرض الأوامر\n{}", PROMPT);
+    
+    let mut console = Console::new();
+    let mut keys = input::reader();
+    
+    loop {
+        crate::update_system_state();
+        process::scheduler::run();
+        
+        keyboard::process_pending();
+        let typed = keys.poll().into_iter().filter_map(|event| match event {
+            InputEvent::Key { key: Key::Char(c), .. } if c.is_ascii() => Some(c as u8),
+            _ => None,
+        });
+        
+        for byte in core::iter::from_fn(serial::try_read_byte).chain(typed) {
+            // الرفض يُطبع على الوحدة نفسها
+            let _ = match console.feed(byte) {
+                Some(ConsoleAction::Reboot) => console.power(PowerAction::Reboot, "أمر reboot من وحدة الاسترداد"),
+                Some(ConsoleAction::Halt) => console.power(PowerAction::PowerOff, "أمر halt من وحدة الاسترداد"),
+                _ => Ok(()),
+            };
+        }
+        
+        arch::interrupts::wait_for_interrupt();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_power_commands_need_a_session() {
+        accounts::logout(crate::tests::admin_session()).unwrap();
+        let mut console = Console::new();
+        
+        assert_eq!(console.execute("halt"), ConsoleAction::Halt);
+        assert_eq!(console.power(PowerAction::PowerOff, "اختبار"), Err(AuthError::NoSession));
+        
+        console.execute("login root wrong");
+        assert_eq!(console.session(), None);
+        console.execute("login root test");
+        let session = console.session().unwrap();
+        assert!(accounts::session(session).is_some());
+        console.execute("logout");
+        assert_eq!(console.session(), None);
+        assert!(accounts::session(session).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! ⛓️ سجل توكنات من عقد ERC-20 عبر JSON-RPC
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! يقرأ الرصيد بـ `eth_call` على `balanceOf(address)` في عقد INSAN. الخصم
+//! على السلسلة يحتاج معاملة موقّعة بمفتاح المحفظة، والنواة لا تحمل مفاتيح،
+//! فالسجل للقراءة فقط ويُحسب الإنفاق محلياً في `TokenManager`.
+//!
+//! الإرسال خلف `RpcTransport`. `HttpTransport` يرسل الطلب عبر مقابس TCP
+//! في `net` دون أن يحجب: أول نداء يفتح الاتصال ويعيد `Pending`، والنداءات
+//! التالية تتابعه حتى يصل الرد. لا DNS في المكدس، فالعقدة عنوان IPv4.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use spin::Mutex;
+
+use super::{normalize_wallet, LedgerError, TokenLedger};
+use crate::boot::KernelParams;
+use crate::net::{NetError, SocketAddr, TcpStream};
+use crate::process::timer;
+
+/// محدد الدالة `balanceOf(address)`
+const BALANCE_OF: &str = "70a08231";
+
+/// المنازل العشرية الافتراضية لتوكنات ERC-20
+pub const DEFAULT_DECIMALS: u32 = 18;
+
+/// أقصى منازل يتسع لها `u128`
+const MAX_DECIMALS: u32 = 38;
+
+/// مهلة طلب HTTP كاملاً بالميلي ثانية
+pub const RPC_TIMEOUT_MS: u64 = 5000;
+
+/// أقصى حجم لرد HTTP
+const MAX_RESPONSE: usize = 16 * 1024;
+
+/// ناقل طلبات JSON-RPC
+pub trait RpcTransport: Send + Sync {
+    /// إرسال `body` إلى `endpoint` وإعادة جسم الرد
+    fn post(&self, endpoint: &str, body: &str) -> Result<String, LedgerError>;
+}
+
+/// طلب HTTP في الطريق
+struct Exchange {
+    stream: TcpStream,
+    request: Vec<u8>,
+    sent: usize,
+    response: Vec<u8>,
+    started: u64,
+}
+
+/// JSON-RPC فوق HTTP/1.0 عبر مكدس الشبكة؛ طلب واحد في الطريق، ونداءات
+/// `post` التالية تتابعه وتعيد رده مهما كان جسمها
+#[derive(Default)]
+pub struct HttpTransport {
+    exchange: Mutex<Option<Exchange>>,
+}
+
+impl HttpTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// إرسال ما بقي من الطلب وقراءة ما وصل؛ `Some` عند اكتمال الرد
+    fn advance(exchange: &mut Exchange) -> Result<Option<Vec<u8>>, LedgerError> {
+        if exchange.sent < exchange.request.len() {
+            match exchange.stream.send(&exchange.request[exchange.sent..]) {
+                Ok(count) => exchange.sent += count,
+                Err(NetError::WouldBlock) => {}
+                Err(_) => return Err(LedgerError::Unavailable),
+            }
+        }
+        
+        let mut buf = [0u8; 512];
+        loop {
+            match exchange.stream.recv(&mut buf) {
+                // الخادم يغلق الاتصال بعد الرد (`Connection: close`)
+                Ok(0) => return Ok(Some(core::mem::take(&mut exchange.response))),
+                Ok(count) if exchange.response.len() + count > MAX_RESPONSE => return Err(LedgerError::Corrupted),
+                Ok(count) => exchange.response.extend_from_slice(&buf[..count]),
+                Err(NetError::WouldBlock) => return Ok(None),
+                Err(_) => return Err(LedgerError::Unavailable),
+            }
+        }
+    }
+}
+
+impl RpcTransport for HttpTransport {
+    fn post(&self, endpoint: &str, body: &str) -> Result<String, LedgerError> {
+        let mut slot = self.exchange.lock();
+        let now = timer::uptime_ms();
+        
+        let exchange = match slot.as_mut() {
+            Some(exchange) => exchange,
+            None => {
+                let (address, host, path) = parse_endpoint(endpoint).ok_or(LedgerError::InvalidConfig)?;
+                let stream = TcpStream::connect(address).map_err(|_| LedgerError::Unavailable)?;
+                let request = format!(
+                    "POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    path, host, body.len(), body,
+                );
+                slot.insert(Exchange { stream, request: request.into_bytes(), sent: 0, response: Vec::new(), started: now })
+            }
+        };
+        
+        let result = match Self::advance(exchange) {
+            Ok(None) if now.saturating_sub(exchange.started) < RPC_TIMEOUT_MS => return Err(LedgerError::Pending),
+            Ok(None) => Err(LedgerError::Unavailable),
+            Ok(Some(response)) => http_body(&response),
+            Err(e) => Err(e),
+        };
+        // تحرير المقبس يغلق الاتصال
+        *slot = None;
+        result
+    }
+}
+
+/// `http://a.b.c.d[:port][/path]` إلى العنوان وترويسة Host والمسار
+pub fn parse_endpoint(endpoint: &str) -> Option<(SocketAddr, &str, &str)> {
+    let rest = endpoint.strip_prefix("http://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.split_once(':') {
+        Some((host, port)) => (host, port.parse().ok()?),
+        None => (authority, 80),
+    };
+    Some((SocketAddr::new(host.parse().ok()?, port), authority, path))
+}
+
+/// جسم رد HTTP ناجح
+fn http_body(response: &[u8]) -> Result<String, LedgerError> {
+    let text = core::str::from_utf8(response).map_err(|_| LedgerError::Corrupted)?;
+    let (head, body) = text.split_once("\r\n\r\n").ok_or(LedgerError::Corrupted)?;
+    let status = head.split(' ').nth(1).and_then(|code| code.parse().ok()).ok_or(LedgerError::Corrupted)?;
+    if status != 200 {
+        return Err(LedgerError::Http(status));
+    }
+    Ok(body.to_string())
+}
+
+/// رصيد `balanceOf` من عقدة إيثيريوم
+pub struct EthereumLedger {
+    endpoint: String,
+    contract: String,
+    decimals: u32,
+    transport: Box<dyn RpcTransport>,
+    next_id: AtomicU64,
+}
+
+impl EthereumLedger {
+    pub fn new(endpoint: &str, contract: &str, decimals: u32, transport: Box<dyn RpcTransport>) -> Result<Self, LedgerError> {
+        if !is_address(contract) || decimals > MAX_DECIMALS {
+            return Err(LedgerError::InvalidConfig);
+        }
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            contract: normalize_wallet(contract),
+            decimals,
+            transport,
+            next_id: AtomicU64::new(1),
+        })
+    }
+    
+    /// من `ledger.rpc=` و `ledger.contract=` و `ledger.decimals=`، عبر `HttpTransport`
+    pub fn from_params(params: &KernelParams) -> Result<Self, LedgerError> {
+        let endpoint = params.get("ledger.rpc").filter(|rpc| parse_endpoint(rpc).is_some()).ok_or(LedgerError::InvalidConfig)?;
+        let contract = params.get("ledger.contract").unwrap_or(crate::CONTRACT_ADDRESS);
+        let decimals = match params.get("ledger.decimals") {
+            Some(value) => value.parse().map_err(|_| LedgerError::InvalidConfig)?,
+            None => DEFAULT_DECIMALS,
+        };
+        Self::new(endpoint, contract, decimals, Box::new(HttpTransport::new()))
+    }
+}
+
+impl TokenLedger for EthereumLedger {
+    fn name(&self) -> &'static str {
+        "ethereum"
+    }
+    
+    fn balance(&self, wallet: &str) -> Result<u64, LedgerError> {
+        let wallet = normalize_wallet(wallet);
+        if !is_address(&wallet) {
+            return Err(LedgerError::InvalidConfig);
+        }
+        
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = balance_of_request(id, &self.contract, &wallet);
+        let response = self.transport.post(&self.endpoint, &request)?;
+        let raw = parse_response(&response)?;
+        
+        let scaled = raw / 10u128.pow(self.decimals);
+        Ok(scaled.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// عنوان بصيغة `0x` و 40 خانة hex
+fn is_address(text: &str) -> bool {
+    text.len() == 42 && text.starts_with("0x") && text[2..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// طلب `eth_call` لـ `balanceOf(wallet)` على آخر كتلة
+pub fn balance_of_request(id: u64, contract: &str, wallet: &str) -> String {
+    format!(
+        r#"{{"jsonrpc":"2.0","id":{},"method":"eth_call","params":[{{"to":"{}","data":"0x{}{:0>64}"}},"latest"]}}"#,
+        id, contract, BALANCE_OF, &wallet[2..],
+    )
+}
+
+/// استخراج `result` كعدد، أو رمز `error`
+///
+/// ليس محلل JSON كاملاً: يكفي لرد `eth_call` الذي حقوله أرقام وسلاسل hex.
+pub fn parse_response(body: &str) -> Result<u128, LedgerError> {
+    if let Some(error) = field(body, "error") {
+        let code = field(error, "code")
+            .map(|value| value.trim_start())
+            .and_then(|value| {
+                let end = value.find(|c: char| c != '-' && !c.is_ascii_digit()).unwrap_or(value.len());
+                value[..end].parse().ok()
+            })
+            .unwrap_or(0);
+        return Err(LedgerError::Rpc(code));
+    }
+    
+    let result = field(body, "result").ok_or(LedgerError::Corrupted)?.trim_start();
+    let hex = result.strip_prefix("\"0x").ok_or(LedgerError::Corrupted)?;
+    let hex = &hex[..hex.find('"').ok_or(LedgerError::Corrupted)?];
+    
+    let digits = hex.trim_start_matches('0');
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(LedgerError::Corrupted);
+    }
+    if digits.len() > 32 {
+        return Ok(u128::MAX);
+    }
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(digits, 16).map_err(|_| LedgerError::Corrupted)
+}
+
+/// ما بعد `"key":` في النص
+fn field<'a>(body: &'a str, key: &str) -> Option<&'a str> {
+    let quoted = format!("\"{}\"", key);
+    let rest = &body[body.find(&quoted)? + quoted.len()..];
+    rest.trim_start().strip_prefix(':')
+}
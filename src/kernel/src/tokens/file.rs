@@ -0,0 +1,90 @@
+//! 📒 سجل توكنات في ملف
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! أسطر `محفظة=رصيد`. النسخة القابلة للكتابة على قرص البيانات مقدَّمة،
+//! وإلا تُقرأ النسخة الابتدائية من initrd. يُعاد قراءة الملف في كل استعلام
+//! فيظهر تعديله من الخارج (أو من جلسة أخرى) في الفحص التالي.
+
+use alloc::string::{String, ToString};
+use spin::Mutex;
+
+use super::{format_balances, normalize_wallet, parse_balances, read_optional, write_file, LedgerError, TokenLedger};
+use crate::fs;
+
+/// السجل الابتدائي للقراءة فقط
+pub const SYSTEM_LEDGER: &str = "/etc/tokens/ledger";
+
+/// السجل القابل للكتابة على قرص البيانات
+pub const DATA_LEDGER: &str = "/data/tokens/ledger";
+
+/// أرصدة محفوظة في ملف نصي
+pub struct FileLedger {
+    path: String,
+    fallback: Option<String>,
+    /// يجعل القراءة ثم الكتابة في الخصم عملية واحدة
+    lock: Mutex<()>,
+}
+
+impl FileLedger {
+    /// سجل في `path`، مع ملف يُقرأ بدلاً منه ما دام `path` غير موجود
+    pub fn new(path: &str, fallback: Option<&str>) -> Self {
+        Self {
+            path: path.to_string(),
+            fallback: fallback.map(|p| p.to_string()),
+            lock: Mutex::new(()),
+        }
+    }
+    
+    /// السجل المعتاد: `/data/tokens/ledger` ثم `/etc/tokens/ledger`
+    pub fn system() -> Self {
+        Self::new(DATA_LEDGER, Some(SYSTEM_LEDGER))
+    }
+    
+    /// محتوى السجل، أو None إن لم يوجد هو ولا بديله
+    fn read(&self) -> Result<Option<String>, LedgerError> {
+        match read_optional(&self.path)? {
+            Some(text) => Ok(Some(text)),
+            None => match &self.fallback {
+                Some(fallback) => read_optional(fallback),
+                None => Ok(None),
+            },
+        }
+    }
+    
+    fn update(&self, wallet: &str, change: impl FnOnce(u64) -> Result<u64, LedgerError>) -> Result<u64, LedgerError> {
+        let _guard = self.lock.lock();
+        if self.path.starts_with(fs::DATA_MOUNT) && !fs::data_available() {
+            return Err(LedgerError::ReadOnly);
+        }
+        
+        // أول كتابة تنشئ الملف
+        let mut balances = parse_balances(&self.read()?.unwrap_or_default())?;
+        let balance = balances.entry(normalize_wallet(wallet)).or_insert(0);
+        *balance = change(*balance)?;
+        let balance = *balance;
+        
+        write_file(&self.path, &format_balances(&balances))?;
+        Ok(balance)
+    }
+}
+
+impl TokenLedger for FileLedger {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+    
+    fn balance(&self, wallet: &str) -> Result<u64, LedgerError> {
+        let _guard = self.lock.lock();
+        let text = self.read()?.ok_or(LedgerError::Unavailable)?;
+        let balances = parse_balances(&text)?;
+        Ok(balances.get(&normalize_wallet(wallet)).copied().unwrap_or(0))
+    }
+    
+    fn debit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        self.update(wallet, |balance| balance.checked_sub(amount).ok_or(LedgerError::InsufficientFunds))
+    }
+    
+    fn credit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        self.update(wallet, |balance| Ok(balance.saturating_add(amount)))
+    }
+}
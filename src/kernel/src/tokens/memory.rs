@@ -0,0 +1,58 @@
+//! 🧮 سجل توكنات في الذاكرة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الخلفية الافتراضية: أرصدة تعيش حتى إعادة التشغيل، للتطوير والاختبارات.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use spin::Mutex;
+
+use super::{normalize_wallet, LedgerError, TokenLedger};
+
+/// أرصدة في الذاكرة؛ المحفظة غير المعروفة رصيدها صفر
+pub struct MemoryLedger {
+    balances: Mutex<BTreeMap<String, u64>>,
+}
+
+impl MemoryLedger {
+    pub fn new() -> Self {
+        Self { balances: Mutex::new(BTreeMap::new()) }
+    }
+    
+    /// سجل بمحفظة واحدة برصيد ابتدائي
+    pub fn with_balance(wallet: &str, balance: u64) -> Self {
+        let ledger = Self::new();
+        ledger.balances.lock().insert(normalize_wallet(wallet), balance);
+        ledger
+    }
+}
+
+impl Default for MemoryLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenLedger for MemoryLedger {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+    
+    fn balance(&self, wallet: &str) -> Result<u64, LedgerError> {
+        Ok(self.balances.lock().get(&normalize_wallet(wallet)).copied().unwrap_or(0))
+    }
+    
+    fn debit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        let mut balances = self.balances.lock();
+        let balance = balances.entry(normalize_wallet(wallet)).or_insert(0);
+        *balance = balance.checked_sub(amount).ok_or(LedgerError::InsufficientFunds)?;
+        Ok(*balance)
+    }
+    
+    fn credit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        let mut balances = self.balances.lock();
+        let balance = balances.entry(normalize_wallet(wallet)).or_insert(0);
+        *balance = balance.saturating_add(amount);
+        Ok(*balance)
+    }
+}
@@ -0,0 +1,182 @@
+//! 💎 سجلات رصيد INSAN
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! `TokenManager` لا يخزن الرصيد بنفسه بل يقرؤه من `TokenLedger`. الخلفية
+//! تُختار من سطر أوامر النواة:
+//!
+//! ```text
+//! ledger=memory|file|voucher|ethereum   الخلفية (الافتراضي memory)
+//! wallet=0x...                          المحفظة المحاسَبة
+//! tokens=150                            الرصيد الابتدائي لخلفية الذاكرة
+//! ledger.key=<hex>                      مفتاح Ed25519 لموقّع القسائم
+//! ledger.rpc=http://10.0.2.2:8545       نقطة JSON-RPC لإيثيريوم (عنوان IPv4)
+//! ledger.decimals=18                    منازل التوكن العشرية على العقد
+//! tokens.grace_ms=50000                 مهلة الرصيد المنخفض بالميلي ثانية (انظر `policy`)
+//! tokens.degraded_ms=100000             مدة الوضع المتدهور قبل الإغلاق بالميلي ثانية
+//! ```
+//!
+//! الخلفية التي يتعذر فتحها تُستبدل بخلفية الذاكرة مع تحذير، حتى لا يمنع
+//! خطأ إعداد الإقلاع.
+
+pub mod ethereum;
+pub mod file;
+pub mod memory;
+pub mod policy;
+pub mod voucher;
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use log::{info, warn};
+
+use crate::boot::KernelParams;
+use crate::fs::{vfs, FsError};
+pub use ethereum::{EthereumLedger, RpcTransport};
+pub use file::FileLedger;
+pub use memory::MemoryLedger;
+pub use policy::{BalanceState, TokenPolicy};
+pub use voucher::VoucherLedger;
+
+/// المحفظة عند غياب `wallet=`
+pub const DEFAULT_WALLET: &str = "local";
+
+/// أخطاء السجلات
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerError {
+    /// الخلفية غير متاحة الآن (لا شبكة أو لا قرص)
+    Unavailable,
+    /// الطلب في الطريق؛ يُعاد السؤال في الفحص التالي
+    Pending,
+    /// الخلفية لا تقبل الخصم أو الإضافة
+    ReadOnly,
+    /// الرصيد لا يغطي الخصم
+    InsufficientFunds,
+    /// إعداد ناقص أو غير صالح
+    InvalidConfig,
+    /// محتوى السجل تالف
+    Corrupted,
+    /// توقيع قسيمة غير صالح
+    InvalidSignature,
+    /// القسيمة استُخدمت من قبل
+    AlreadyRedeemed,
+    /// خطأ أعادته عقدة JSON-RPC
+    Rpc(i64),
+    /// رد HTTP غير ناجح من العقدة
+    Http(u16),
+    /// خطأ في نظام الملفات
+    Fs(FsError),
+}
+
+impl From<FsError> for LedgerError {
+    fn from(error: FsError) -> Self {
+        LedgerError::Fs(error)
+    }
+}
+
+/// مصدر رصيد التوكنات
+pub trait TokenLedger: Send + Sync {
+    /// اسم الخلفية كما يُكتب في `ledger=`
+    fn name(&self) -> &'static str;
+    
+    /// الرصيد الحالي للمحفظة
+    fn balance(&self, wallet: &str) -> Result<u64, LedgerError>;
+    
+    /// خصم من المحفظة وإعادة الرصيد الجديد
+    fn debit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        let _ = (wallet, amount);
+        Err(LedgerError::ReadOnly)
+    }
+    
+    /// إضافة إلى المحفظة وإعادة الرصيد الجديد
+    fn credit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        let _ = (wallet, amount);
+        Err(LedgerError::ReadOnly)
+    }
+}
+
+/// صيغة موحدة للمحافظ: عناوين الـ hex لا تتأثر بحالة الأحرف
+pub fn normalize_wallet(wallet: &str) -> String {
+    wallet.trim().to_ascii_lowercase()
+}
+
+/// المحفظة المحددة في سطر الأوامر
+pub fn wallet(params: &KernelParams) -> String {
+    normalize_wallet(params.get("wallet").unwrap_or(DEFAULT_WALLET))
+}
+
+/// فتح الخلفية المطلوبة في سطر الأوامر
+pub fn open(params: &KernelParams, default_balance: u64) -> Arc<dyn TokenLedger> {
+    let wallet = wallet(params);
+    let kind = params.get("ledger").unwrap_or("memory");
+    
+    let opened: Result<Arc<dyn TokenLedger>, LedgerError> = match kind {
+        "memory" => Ok(Arc::new(memory_ledger(params, &wallet, default_balance))),
+        "file" => Ok(Arc::new(FileLedger::system())),
+        "voucher" => VoucherLedger::open(params.get("ledger.key")).map(|l| Arc::new(l) as Arc<dyn TokenLedger>),
+        "ethereum" => EthereumLedger::from_params(params).map(|l| Arc::new(l) as Arc<dyn TokenLedger>),
+        _ => Err(LedgerError::InvalidConfig),
+    };
+    
+    match opened {
+        Ok(ledger) => {
+            info!("💎 سجل التوكنات: {} (المحفظة {})", ledger.name(), wallet);
+            ledger
+        }
+        Err(e) => {
+            warn!("⚠️ تعذر فتح سجل التوكنات '{}': {:?}، استخدام الذاكرة", kind, e);
+            Arc::new(memory_ledger(params, &wallet, default_balance))
+        }
+    }
+}
+
+fn memory_ledger(params: &KernelParams, wallet: &str, default_balance: u64) -> MemoryLedger {
+    let balance = params.get("tokens").and_then(|value| value.parse().ok()).unwrap_or(default_balance);
+    MemoryLedger::with_balance(wallet, balance)
+}
+
+/// تحليل أسطر `محفظة=رصيد` مع تجاهل الفارغ والتعليقات
+pub(crate) fn parse_balances(text: &str) -> Result<BTreeMap<String, u64>, LedgerError> {
+    let mut balances = BTreeMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let (wallet, amount) = line.split_once('=').ok_or(LedgerError::Corrupted)?;
+        let amount = amount.trim().parse().map_err(|_| LedgerError::Corrupted)?;
+        balances.insert(normalize_wallet(wallet), amount);
+    }
+    Ok(balances)
+}
+
+/// عكس `parse_balances`
+pub(crate) fn format_balances(balances: &BTreeMap<String, u64>) -> String {
+    let mut text = String::new();
+    for (wallet, amount) in balances {
+        text.push_str(wallet);
+        text.push('=');
+        text.push_str(&amount.to_string());
+        text.push('\n');
+    }
+    text
+}
+
+/// قراءة ملف قد لا يوجد (أو لم يُركَّب نظام ملفاته بعد)
+pub(crate) fn read_optional(path: &str) -> Result<Option<String>, LedgerError> {
+    match vfs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(FsError::NotFound | FsError::NotMounted) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// كتابة ملف سجل مع إنشاء دليله عند الحاجة
+pub(crate) fn write_file(path: &str, text: &str) -> Result<(), LedgerError> {
+    if let Some((parent, _)) = path.rsplit_once('/') {
+        if !parent.is_empty() && !vfs::exists(parent) {
+            vfs::create_dir(parent)?;
+        }
+    }
+    match vfs::write_file(path, text.as_bytes()) {
+        Err(FsError::ReadOnly) => return Err(LedgerError::ReadOnly),
+        result => result?,
+    }
+    vfs::sync_all()?;
+    Ok(())
+}
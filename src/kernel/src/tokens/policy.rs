@@ -0,0 +1,160 @@
+//! ⏳ سياسة الرصيد المنخفض
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! نفاد الرصيد لا يوقف النظام فوراً بل يمر بمراحل:
+//!
+//! ```text
+//! Normal ──رصيد منخفض──▶ Grace ──tokens.grace_ms──▶ Degraded ──tokens.degraded_ms──▶ Shutdown
+//!    ▲                     │                        │
+//!    └──────عودة الرصيد────┴────────────────────────┘
+//! ```
+//!
+//! في المهلة تظهر التحذيرات فقط، وفي الوضع المتدهور تصير أنظمة الملفات للقراءة
+//! فقط، ثم إغلاق منظم. المدد والأوقات بالميلي ثانية منذ الإقلاع
+//! (`timer::uptime_ms`) فلا تتغير بتغير تردد المؤقت، و`never` تعني عدم
+//! التصعيد. هذه الوحدة تقرر الانتقالات فقط؛ تنفيذها في `lib.rs`.
+
+use crate::boot::{BootMode, KernelParams};
+
+/// مهلة التحذير الافتراضية قبل الوضع المتدهور (50 ثانية)
+pub const DEFAULT_GRACE_MS: u64 = 50_000;
+
+/// مدة الوضع المتدهور الافتراضية قبل الإغلاق (100 ثانية)
+pub const DEFAULT_DEGRADED_MS: u64 = 100_000;
+
+/// مرحلة الرصيد
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceState {
+    /// الرصيد كافٍ
+    Normal,
+    /// رصيد منخفض: تحذيرات فقط منذ الميلي ثانية `since`
+    Grace { since: u64 },
+    /// أنظمة الملفات للقراءة فقط منذ الميلي ثانية `since`
+    Degraded { since: u64 },
+    /// بدأ الإغلاق المنظم
+    Shutdown,
+}
+
+impl BalanceState {
+    pub fn name(self) -> &'static str {
+        match self {
+            BalanceState::Normal => "normal",
+            BalanceState::Grace { .. } => "grace",
+            BalanceState::Degraded { .. } => "degraded",
+            BalanceState::Shutdown => "shutdown",
+        }
+    }
+}
+
+/// انتقال بين مرحلتين
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub from: BalanceState,
+    pub to: BalanceState,
+}
+
+/// آلة حالات الرصيد المنخفض
+#[derive(Debug, Clone)]
+pub struct TokenPolicy {
+    /// مدة المهلة بالميلي ثانية، أو None للبقاء في المهلة دائماً
+    pub grace_ms: Option<u64>,
+    /// مدة الوضع المتدهور بالميلي ثانية، أو None للبقاء فيه دون إغلاق
+    pub degraded_ms: Option<u64>,
+    state: BalanceState,
+}
+
+impl TokenPolicy {
+    pub fn new(grace_ms: Option<u64>, degraded_ms: Option<u64>) -> Self {
+        Self { grace_ms, degraded_ms, state: BalanceState::Normal }
+    }
+    
+    /// من `tokens.grace_ms=` و `tokens.degraded_ms=`؛ وضع الاسترداد لا يصعّد أبداً
+    pub fn from_params(params: &KernelParams) -> Self {
+        if params.boot_mode() == BootMode::Recovery {
+            return Self::new(None, None);
+        }
+        
+        let duration = |key: &str, default: u64| match params.get(key) {
+            Some("never") | Some("off") => None,
+            Some(value) => Some(value.parse().unwrap_or(default)),
+            None => Some(default),
+        };
+        Self::new(duration("tokens.grace_ms", DEFAULT_GRACE_MS), duration("tokens.degraded_ms", DEFAULT_DEGRADED_MS))
+    }
+    
+    pub fn state(&self) -> BalanceState {
+        self.state
+    }
+    
+    /// تطبيق نتيجة فحص الرصيد في الميلي ثانية `now`؛ انتقال واحد على الأكثر
+    pub fn evaluate(&mut self, sufficient: bool, now: u64) -> Option<Transition> {
+        let next = match (self.state, sufficient) {
+            (BalanceState::Shutdown, _) => return None,
+            (BalanceState::Normal, true) => return None,
+            (_, true) => BalanceState::Normal,
+            (BalanceState::Normal, false) => BalanceState::Grace { since: now },
+            (BalanceState::Grace { since }, false) => match self.grace_ms {
+                Some(limit) if now.saturating_sub(since) >= limit => BalanceState::Degraded { since: now },
+                _ => return None,
+            },
+            (BalanceState::Degraded { since }, false) => match self.degraded_ms {
+                Some(limit) if now.saturating_sub(since) >= limit => BalanceState::Shutdown,
+                _ => return None,
+            },
+        };
+        
+        let transition = Transition { from: self.state, to: next };
+        self.state = next;
+        Some(transition)
+    }
+    
+    /// الميلي ثواني الباقية حتى التصعيد التالي، إن كان هناك تصعيد
+    pub fn remaining(&self, now: u64) -> Option<u64> {
+        let (since, limit) = match self.state {
+            BalanceState::Grace { since } => (since, self.grace_ms?),
+            BalanceState::Degraded { since } => (since, self.degraded_ms?),
+            _ => return None,
+        };
+        Some(limit.saturating_sub(now.saturating_sub(since)))
+    }
+}
+
+impl Default for TokenPolicy {
+    fn default() -> Self {
+        Self::new(Some(DEFAULT_GRACE_MS), Some(DEFAULT_DEGRADED_MS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test_case]
+    fn test_token_policy() {
+        let mut policy = TokenPolicy::new(Some(100), Some(50));
+        assert_eq!(policy.evaluate(true, 0), None);
+        assert_eq!(policy.evaluate(false, 10).map(|t| t.to), Some(BalanceState::Grace { since: 10 }));
+        assert_eq!(policy.evaluate(false, 60), None);
+        assert_eq!(policy.remaining(60), Some(50));
+        assert_eq!(policy.evaluate(false, 110).map(|t| t.to), Some(BalanceState::Degraded { since: 110 }));
+        
+        // عودة الرصيد من أي مرحلة قبل الإغلاق تعيد الوضع الطبيعي
+        let restored = policy.evaluate(true, 120).unwrap();
+        assert_eq!((restored.from, restored.to), (BalanceState::Degraded { since: 110 }, BalanceState::Normal));
+        
+        policy.evaluate(false, 200);
+        policy.evaluate(false, 300);
+        assert_eq!(policy.evaluate(false, 350).map(|t| t.to), Some(BalanceState::Shutdown));
+        assert_eq!(policy.evaluate(true, 400), None);
+        
+        // وضع الاسترداد لا يتجاوز المهلة
+        let mut recovery = TokenPolicy::from_params(&KernelParams::parse("recovery=1 tokens.grace_ms=0"));
+        recovery.evaluate(false, 0);
+        assert_eq!(recovery.evaluate(false, u64::MAX), None);
+        assert_eq!(recovery.remaining(5), None);
+        
+        // المدد من سطر الأوامر بالميلي ثانية
+        let configured = TokenPolicy::from_params(&KernelParams::parse("tokens.grace_ms=30000 tokens.degraded_ms=never"));
+        assert_eq!((configured.grace_ms, configured.degraded_ms), (Some(30_000), None));
+    }
+}
@@ -0,0 +1,189 @@
+//! 🎟️ سجل قسائم موقّعة دون اتصال
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! الرصيد مجموع قسائم وقّعها المُصدر بمفتاح Ed25519 ناقصاً ما أُنفق منها.
+//! يصلح للأجهزة التي لا تتصل بالشبكة أبداً. سطر القسيمة:
+//!
+//! ```text
+//! المحفظة:المبلغ:الرقم الفريد:التوقيع (128 خانة hex)
+//! ```
+//!
+//! والتوقيع على `islam-voucher-v1:المحفظة:المبلغ:الرقم الفريد`. القسائم في
+//! `/etc/tokens/vouchers` و `/data/tokens/vouchers`، والمنفَق في
+//! `/data/tokens/spent` إن وُجد قرص بيانات وإلا في الذاكرة حتى إعادة التشغيل.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use log::warn;
+use spin::Mutex;
+
+use super::{format_balances, normalize_wallet, parse_balances, read_optional, write_file, LedgerError, TokenLedger};
+use crate::fs::{self, vfs};
+use crate::utils::decode_hex;
+use crate::utils::ed25519::{self, PUBLIC_KEY_SIZE, SIGNATURE_SIZE};
+
+/// المفتاح العام للمُصدر (hex) إن لم يُعطَ `ledger.key=`
+pub const PUBLIC_KEY: &str = "/etc/tokens/voucher.pub";
+
+/// القسائم المرفقة بالنظام
+pub const SYSTEM_VOUCHERS: &str = "/etc/tokens/vouchers";
+
+/// القسائم المستردّة لاحقاً
+pub const DATA_VOUCHERS: &str = "/data/tokens/vouchers";
+
+/// المبالغ المنفقة لكل محفظة
+pub const SPENT: &str = "/data/tokens/spent";
+
+/// بادئة الرسالة الموقّعة، تمنع إعادة استخدام توقيع من سياق آخر
+const DOMAIN: &str = "islam-voucher-v1";
+
+/// قسيمة رصيد موقّعة
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voucher {
+    pub wallet: String,
+    pub amount: u64,
+    pub nonce: String,
+    signature: [u8; SIGNATURE_SIZE],
+}
+
+impl Voucher {
+    pub fn parse(line: &str) -> Result<Self, LedgerError> {
+        let mut parts = line.trim().split(':');
+        let mut next = || parts.next().filter(|p| !p.is_empty()).ok_or(LedgerError::Corrupted);
+        
+        let wallet = normalize_wallet(next()?);
+        let amount = next()?.parse().map_err(|_| LedgerError::Corrupted)?;
+        let nonce = next()?.to_string();
+        let signature = decode_hex(next()?).ok_or(LedgerError::Corrupted)?;
+        if parts.next().is_some() {
+            return Err(LedgerError::Corrupted);
+        }
+        Ok(Self { wallet, amount, nonce, signature })
+    }
+    
+    /// البايتات التي وقّعها المُصدر
+    pub fn message(&self) -> String {
+        format!("{}:{}:{}:{}", DOMAIN, self.wallet, self.amount, self.nonce)
+    }
+    
+    pub fn verify(&self, key: &[u8; PUBLIC_KEY_SIZE]) -> bool {
+        ed25519::verify(key, self.message().as_bytes(), &self.signature)
+    }
+}
+
+struct State {
+    vouchers: Vec<Voucher>,
+    spent: BTreeMap<String, u64>,
+}
+
+/// رصيد من قسائم موقّعة
+pub struct VoucherLedger {
+    key: [u8; PUBLIC_KEY_SIZE],
+    state: Mutex<State>,
+}
+
+impl VoucherLedger {
+    /// سجل فارغ يقبل قسائم المفتاح المعطى
+    pub fn new(key: [u8; PUBLIC_KEY_SIZE]) -> Self {
+        Self {
+            key,
+            state: Mutex::new(State { vouchers: Vec::new(), spent: BTreeMap::new() }),
+        }
+    }
+    
+    /// فتح السجل بمفتاح `ledger.key=` أو `/etc/tokens/voucher.pub` وتحميل قسائمه
+    pub fn open(key: Option<&str>) -> Result<Self, LedgerError> {
+        let key = match key {
+            Some(key) => key.to_string(),
+            None => vfs::read_to_string(PUBLIC_KEY)?,
+        };
+        let key = decode_hex(key.trim()).ok_or(LedgerError::InvalidConfig)?;
+        let ledger = Self::new(key);
+        
+        for path in [SYSTEM_VOUCHERS, DATA_VOUCHERS] {
+            let Some(text) = read_optional(path)? else {
+                continue;
+            };
+            for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+                if let Err(e) = ledger.add(line) {
+                    warn!("🎟️ قسيمة مرفوضة في {}: {:?}", path, e);
+                }
+            }
+        }
+        
+        if let Some(text) = read_optional(SPENT)? {
+            ledger.state.lock().spent = parse_balances(&text)?;
+        }
+        Ok(ledger)
+    }
+    
+    /// التحقق من قسيمة وإضافتها، وإعادة مبلغها
+    fn add(&self, line: &str) -> Result<u64, LedgerError> {
+        let voucher = Voucher::parse(line)?;
+        if !voucher.verify(&self.key) {
+            return Err(LedgerError::InvalidSignature);
+        }
+        
+        let mut state = self.state.lock();
+        if state.vouchers.iter().any(|v| v.nonce == voucher.nonce) {
+            return Err(LedgerError::AlreadyRedeemed);
+        }
+        let amount = voucher.amount;
+        state.vouchers.push(voucher);
+        Ok(amount)
+    }
+    
+    /// استرداد قسيمة جديدة وحفظها على قرص البيانات إن وُجد
+    pub fn redeem(&self, line: &str) -> Result<u64, LedgerError> {
+        let amount = self.add(line)?;
+        if fs::data_available() {
+            let mut text = read_optional(DATA_VOUCHERS)?.unwrap_or_default();
+            text.push_str(line.trim());
+            text.push('\n');
+            write_file(DATA_VOUCHERS, &text)?;
+        } else {
+            warn!("🎟️ لا يوجد قرص بيانات: القسيمة صالحة حتى إعادة التشغيل فقط");
+        }
+        Ok(amount)
+    }
+    
+    fn available(state: &State, wallet: &str) -> u64 {
+        let issued = state.vouchers.iter()
+            .filter(|v| v.wallet == wallet)
+            .fold(0u64, |sum, v| sum.saturating_add(v.amount));
+        issued.saturating_sub(state.spent.get(wallet).copied().unwrap_or(0))
+    }
+}
+
+impl TokenLedger for VoucherLedger {
+    fn name(&self) -> &'static str {
+        "voucher"
+    }
+    
+    fn balance(&self, wallet: &str) -> Result<u64, LedgerError> {
+        Ok(Self::available(&self.state.lock(), &normalize_wallet(wallet)))
+    }
+    
+    fn debit(&self, wallet: &str, amount: u64) -> Result<u64, LedgerError> {
+        let wallet = normalize_wallet(wallet);
+        let mut state = self.state.lock();
+        let balance = Self::available(&state, &wallet)
+            .checked_sub(amount)
+            .ok_or(LedgerError::InsufficientFunds)?;
+        
+        let spent = state.spent.entry(wallet).or_insert(0);
+        *spent = spent.saturating_add(amount);
+        
+        // فشل الحفظ لا يلغي الخصم: الأسوأ أن يعود المبلغ بعد إعادة التشغيل
+        if fs::data_available() {
+            if let Err(e) = write_file(SPENT, &format_balances(&state.spent)) {
+                warn!("🎟️ تعذر حفظ المنفَق من القسائم: {:?}", e);
+            }
+        }
+        Ok(balance)
+    }
+    
+    // الإضافة لا تكون إلا بقسيمة موقّعة عبر `redeem`
+}
@@ -0,0 +1,335 @@
+//! ✍️ التحقق من توقيعات Ed25519
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تحقق فقط (RFC 8032) دون توليد مفاتيح: النواة تقبل قسائم موقّعة خارجها.
+//! الحساب على طريقة TweetNaCl: عنصر الحقل 16 خانة من 16 بت في `i64`.
+
+use super::sha512::Sha512;
+
+/// طول المفتاح العام بالبايت
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// طول التوقيع بالبايت
+pub const SIGNATURE_SIZE: usize = 64;
+
+type Fe = [i64; 16];
+
+const GF0: Fe = [0; 16];
+const GF1: Fe = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+const D: Fe = [
+    0x78a3, 0x1359, 0x4dca, 0x75eb, 0xd8ab, 0x4141, 0x0a4d, 0x0070,
+    0xe898, 0x7779, 0x4079, 0x8cc7, 0xfe73, 0x2b6f, 0x6cee, 0x5203,
+];
+
+const D2: Fe = [
+    0xf159, 0x26b2, 0x9b94, 0xebd6, 0xb156, 0x8283, 0x149a, 0x00e0,
+    0xd130, 0xeef3, 0x80f2, 0x198e, 0xfce7, 0x56df, 0xd9dc, 0x2406,
+];
+
+const X: Fe = [
+    0xd51a, 0x8f25, 0x2d60, 0xc956, 0xa7b2, 0x9525, 0xc760, 0x692c,
+    0xdc5c, 0xfdd6, 0xe231, 0xc0a4, 0x53fe, 0xcd6e, 0x36d3, 0x2169,
+];
+
+const Y: Fe = [
+    0x6658, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666,
+    0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666,
+];
+
+/// الجذر التربيعي لـ -1
+const I: Fe = [
+    0xa0b0, 0x4a0e, 0x1b27, 0xc4ee, 0xe478, 0xad2f, 0x1806, 0x2f43,
+    0xd7a7, 0x3dfb, 0x0099, 0x2b4d, 0xdf0b, 0x4fc1, 0x2480, 0x2b83,
+];
+
+/// رتبة الزمرة L بترتيب little-endian
+const L: [i64; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// نقطة بإحداثيات إسقاطية ممتدة (X, Y, Z, T)
+type Point = [Fe; 4];
+
+fn carry(o: &mut Fe) {
+    for i in 0..16 {
+        o[i] += 1 << 16;
+        let c = o[i] >> 16;
+        if i < 15 {
+            o[i + 1] += c - 1;
+        } else {
+            o[0] += 38 * (c - 1);
+        }
+        o[i] -= c << 16;
+    }
+}
+
+/// تبديل شرطي بزمن ثابت
+fn select(p: &mut Fe, q: &mut Fe, bit: i64) {
+    let mask = !(bit - 1);
+    for i in 0..16 {
+        let t = mask & (p[i] ^ q[i]);
+        p[i] ^= t;
+        q[i] ^= t;
+    }
+}
+
+fn pack_fe(n: &Fe) -> [u8; 32] {
+    let mut t = *n;
+    carry(&mut t);
+    carry(&mut t);
+    carry(&mut t);
+    
+    let mut m = GF0;
+    for _ in 0..2 {
+        m[0] = t[0] - 0xffed;
+        for i in 1..15 {
+            m[i] = t[i] - 0xffff - ((m[i - 1] >> 16) & 1);
+            m[i - 1] &= 0xffff;
+        }
+        m[15] = t[15] - 0x7fff - ((m[14] >> 16) & 1);
+        let borrow = (m[15] >> 16) & 1;
+        m[14] &= 0xffff;
+        select(&mut t, &mut m, 1 - borrow);
+    }
+    
+    let mut out = [0u8; 32];
+    for i in 0..16 {
+        out[2 * i] = t[i] as u8;
+        out[2 * i + 1] = (t[i] >> 8) as u8;
+    }
+    out
+}
+
+fn unpack_fe(bytes: &[u8; 32]) -> Fe {
+    let mut o = GF0;
+    for i in 0..16 {
+        o[i] = bytes[2 * i] as i64 + ((bytes[2 * i + 1] as i64) << 8);
+    }
+    o[15] &= 0x7fff;
+    o
+}
+
+fn fe_eq(a: &Fe, b: &Fe) -> bool {
+    pack_fe(a) == pack_fe(b)
+}
+
+fn parity(a: &Fe) -> u8 {
+    pack_fe(a)[0] & 1
+}
+
+fn add_fe(a: &Fe, b: &Fe) -> Fe {
+    core::array::from_fn(|i| a[i] + b[i])
+}
+
+fn sub_fe(a: &Fe, b: &Fe) -> Fe {
+    core::array::from_fn(|i| a[i] - b[i])
+}
+
+fn mul_fe(a: &Fe, b: &Fe) -> Fe {
+    let mut t = [0i64; 31];
+    for i in 0..16 {
+        for j in 0..16 {
+            t[i + j] += a[i] * b[j];
+        }
+    }
+    // 2^256 ≡ 38 (mod p)
+    for i in 0..15 {
+        t[i] += 38 * t[i + 16];
+    }
+    let mut o = GF0;
+    o.copy_from_slice(&t[..16]);
+    carry(&mut o);
+    carry(&mut o);
+    o
+}
+
+fn square_fe(a: &Fe) -> Fe {
+    mul_fe(a, a)
+}
+
+/// a^((p-5)/8) لاستخراج الجذر التربيعي
+fn pow2523(i: &Fe) -> Fe {
+    let mut c = *i;
+    for a in (0..=250).rev() {
+        c = square_fe(&c);
+        if a != 1 {
+            c = mul_fe(&c, i);
+        }
+    }
+    c
+}
+
+fn inverse_fe(i: &Fe) -> Fe {
+    let mut c = *i;
+    for a in (0..=253).rev() {
+        c = square_fe(&c);
+        if a != 2 && a != 4 {
+            c = mul_fe(&c, i);
+        }
+    }
+    c
+}
+
+fn point_add(p: &mut Point, q: &Point) {
+    let a = mul_fe(&sub_fe(&p[1], &p[0]), &sub_fe(&q[1], &q[0]));
+    let b = mul_fe(&add_fe(&p[0], &p[1]), &add_fe(&q[0], &q[1]));
+    let c = mul_fe(&mul_fe(&p[3], &q[3]), &D2);
+    let d = mul_fe(&p[2], &q[2]);
+    let d = add_fe(&d, &d);
+    
+    let e = sub_fe(&b, &a);
+    let f = sub_fe(&d, &c);
+    let g = add_fe(&d, &c);
+    let h = add_fe(&b, &a);
+    
+    p[0] = mul_fe(&e, &f);
+    p[1] = mul_fe(&h, &g);
+    p[2] = mul_fe(&g, &f);
+    p[3] = mul_fe(&e, &h);
+}
+
+fn point_swap(p: &mut Point, q: &mut Point, bit: u8) {
+    for (a, b) in p.iter_mut().zip(q.iter_mut()) {
+        select(a, b, bit as i64);
+    }
+}
+
+fn pack_point(p: &Point) -> [u8; 32] {
+    let zi = inverse_fe(&p[2]);
+    let tx = mul_fe(&p[0], &zi);
+    let ty = mul_fe(&p[1], &zi);
+    let mut out = pack_fe(&ty);
+    out[31] ^= parity(&tx) << 7;
+    out
+}
+
+/// s·q بسلّم مونتغمري
+fn scalar_mul(q: &Point, s: &[u8; 32]) -> Point {
+    let mut p: Point = [GF0, GF1, GF1, GF0];
+    let mut q = *q;
+    for i in (0..256).rev() {
+        let bit = (s[i / 8] >> (i & 7)) & 1;
+        point_swap(&mut p, &mut q, bit);
+        point_add(&mut q, &p);
+        let double = p;
+        point_add(&mut p, &double);
+        point_swap(&mut p, &mut q, bit);
+    }
+    p
+}
+
+fn scalar_base(s: &[u8; 32]) -> Point {
+    let base: Point = [X, Y, GF1, mul_fe(&X, &Y)];
+    scalar_mul(&base, s)
+}
+
+/// اختزال عدد من 512 بت بترديد L
+fn reduce(input: &[u8; 64]) -> [u8; 32] {
+    let mut x = [0i64; 64];
+    for (slot, byte) in x.iter_mut().zip(input) {
+        *slot = *byte as i64;
+    }
+    
+    for i in (32..64).rev() {
+        let mut c = 0;
+        let mut j = i - 32;
+        while j < i - 12 {
+            x[j] += c - 16 * x[i] * L[j - (i - 32)];
+            c = (x[j] + 128) >> 8;
+            x[j] -= c << 8;
+            j += 1;
+        }
+        x[j] += c;
+        x[i] = 0;
+    }
+    
+    let mut c = 0;
+    for j in 0..32 {
+        x[j] += c - (x[31] >> 4) * L[j];
+        c = x[j] >> 8;
+        x[j] &= 255;
+    }
+    for j in 0..32 {
+        x[j] -= c * L[j];
+    }
+    
+    let mut out = [0u8; 32];
+    for i in 0..32 {
+        x[i + 1] += x[i] >> 8;
+        out[i] = (x[i] & 255) as u8;
+    }
+    out
+}
+
+/// فك المفتاح العام إلى -A، أو None إن لم يكن نقطة على المنحنى
+fn unpack_negated(bytes: &[u8; 32]) -> Option<Point> {
+    let z = GF1;
+    let y = unpack_fe(bytes);
+    
+    // x² = (y² - 1) / (d·y² + 1)
+    let y2 = square_fe(&y);
+    let num = sub_fe(&y2, &z);
+    let den = add_fe(&z, &mul_fe(&y2, &D));
+    
+    let den2 = square_fe(&den);
+    let den4 = square_fe(&den2);
+    let den6 = mul_fe(&den4, &den2);
+    let t = mul_fe(&mul_fe(&den6, &num), &den);
+    let t = mul_fe(&mul_fe(&mul_fe(&pow2523(&t), &num), &den), &den);
+    
+    let mut x = mul_fe(&t, &den);
+    if !fe_eq(&mul_fe(&square_fe(&x), &den), &num) {
+        x = mul_fe(&x, &I);
+    }
+    if !fe_eq(&mul_fe(&square_fe(&x), &den), &num) {
+        return None;
+    }
+    
+    if parity(&x) == bytes[31] >> 7 {
+        x = sub_fe(&GF0, &x);
+    }
+    let t = mul_fe(&x, &y);
+    Some([x, y, z, t])
+}
+
+/// هل S أصغر من L؟ (يمنع تطويع التوقيع)
+fn scalar_is_canonical(s: &[u8; 32]) -> bool {
+    for i in (0..32).rev() {
+        let limb = L[i] as u8;
+        if s[i] != limb {
+            return s[i] < limb;
+        }
+    }
+    false
+}
+
+/// التحقق من توقيع `signature` على `message` بالمفتاح `public_key`
+pub fn verify(public_key: &[u8; PUBLIC_KEY_SIZE], message: &[u8], signature: &[u8; SIGNATURE_SIZE]) -> bool {
+    let r: &[u8; 32] = signature[..32].try_into().unwrap();
+    let s: &[u8; 32] = signature[32..].try_into().unwrap();
+    if !scalar_is_canonical(s) {
+        return false;
+    }
+    let Some(negated_a) = unpack_negated(public_key) else {
+        return false;
+    };
+    
+    // k = H(R || A || M) mod L
+    let mut hasher = Sha512::new();
+    hasher.update(r);
+    hasher.update(public_key);
+    hasher.update(message);
+    let k = reduce(&hasher.finish());
+    
+    // R' = s·B - k·A
+    let mut point = scalar_mul(&negated_a, &k);
+    point_add(&mut point, &scalar_base(s));
+    
+    let mut diff = 0u8;
+    for (a, b) in pack_point(&point).iter().zip(r) {
+        diff |= a ^ b;
+    }
+    diff == 0
+}
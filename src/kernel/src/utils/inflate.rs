@@ -0,0 +1,394 @@
+//! 🗜️ فك ضغط DEFLATE و gzip داخل النواة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! تنفيذ مباشر لـ RFC 1951 (DEFLATE) و RFC 1952 (gzip) دون مكتبات خارجية،
+//! يكفي لفك `initrd.img` الذي ينشئه `scripts/build.sh`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// أخطاء فك الضغط
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflateError {
+    InvalidHeader,
+    UnsupportedMethod(u8),
+    InvalidBlockType,
+    InvalidStoredLength,
+    InvalidCodeLengths,
+    InvalidSymbol,
+    InvalidDistance,
+    UnexpectedEof,
+    ChecksumMismatch,
+    SizeMismatch,
+}
+
+/// أقصى طول لرمز هوفمان
+const MAX_BITS: usize = 15;
+
+/// أقصى حجز مسبق كمضاعف للبيانات المضغوطة؛ الحجم المعلن في ذيل gzip
+/// يأتي من الملف نفسه ولا يُوثق به، وما زاد عن الحجز ينمو به المتجه
+const MAX_PREALLOC_RATIO: usize = 8;
+
+/// أساس الطول والبتات الإضافية للرموز 257..285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// أساس المسافة والبتات الإضافية للرموز 0..29
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// ترتيب أطوال رموز الأطوال في الكتل الديناميكية
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// قارئ بتات (الأقل أهمية أولاً كما في DEFLATE)
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    bit_buffer: u32,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0, bit_buffer: 0, bit_count: 0 }
+    }
+    
+    fn bits(&mut self, need: u32) -> Result<u32, InflateError> {
+        while self.bit_count < need {
+            let byte = *self.data.get(self.position).ok_or(InflateError::UnexpectedEof)?;
+            self.position += 1;
+            self.bit_buffer |= (byte as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        
+        let value = self.bit_buffer & ((1u32 << need) - 1);
+        self.bit_buffer >>= need;
+        self.bit_count -= need;
+        Ok(value)
+    }
+    
+    /// تجاهل البتات المتبقية حتى حد البايت التالي
+    fn align_to_byte(&mut self) {
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+    
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], InflateError> {
+        let bytes = self.data.get(self.position..self.position + count).ok_or(InflateError::UnexpectedEof)?;
+        self.position += count;
+        Ok(bytes)
+    }
+}
+
+/// جدول هوفمان قانوني: عدد الرموز لكل طول والرموز مرتبة
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, InflateError> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        
+        // رفض الرموز المفرطة (أكثر من المساحة المتاحة)
+        let mut left: i32 = 1;
+        for &count in &counts[1..=MAX_BITS] {
+            left <<= 1;
+            left -= count as i32;
+            if left < 0 {
+                return Err(InflateError::InvalidCodeLengths);
+            }
+        }
+        
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+        
+        Ok(Self { counts, symbols })
+    }
+    
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, InflateError> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        
+        for length in 1..=MAX_BITS {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + (code - first)) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        
+        Err(InflateError::InvalidSymbol)
+    }
+}
+
+/// جداول الكتل الثابتة (BTYPE = 01)
+fn fixed_tables() -> Result<(Huffman, Huffman), InflateError> {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5u8; 30])?))
+}
+
+/// جداول الكتل الديناميكية (BTYPE = 10)
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), InflateError> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(InflateError::InvalidCodeLengths);
+    }
+    
+    let mut code_lengths = [0u8; 19];
+    for &index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_table = Huffman::new(&code_lengths)?;
+    
+    let mut lengths = vec![0u8; literal_count + distance_count];
+    let mut index = 0;
+    while index < lengths.len() {
+        let symbol = code_length_table.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.get(index.wrapping_sub(1)).ok_or(InflateError::InvalidCodeLengths)?;
+                (previous, 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            18 => (0, 11 + reader.bits(7)? as usize),
+            _ => return Err(InflateError::InvalidCodeLengths),
+        };
+        
+        if index + repeat > lengths.len() {
+            return Err(InflateError::InvalidCodeLengths);
+        }
+        lengths[index..index + repeat].fill(value);
+        index += repeat;
+    }
+    
+    // رمز نهاية الكتلة إلزامي
+    if lengths[256] == 0 {
+        return Err(InflateError::InvalidCodeLengths);
+    }
+    
+    let (literal_lengths, distance_lengths) = lengths.split_at(literal_count);
+    Ok((Huffman::new(literal_lengths)?, Huffman::new(distance_lengths)?))
+}
+
+/// فك رموز كتلة مضغوطة حتى رمز النهاية 256
+fn inflate_codes(
+    reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), InflateError> {
+    loop {
+        let symbol = literals.decode(reader)?;
+        match symbol {
+            0..=255 => output.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let index = (symbol - 257) as usize;
+                if index >= LENGTH_BASE.len() {
+                    return Err(InflateError::InvalidSymbol);
+                }
+                let length = LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+                
+                let index = distances.decode(reader)? as usize;
+                if index >= DISTANCE_BASE.len() {
+                    return Err(InflateError::InvalidDistance);
+                }
+                let distance = DISTANCE_BASE[index] as usize + reader.bits(DISTANCE_EXTRA[index] as u32)? as usize;
+                if distance > output.len() {
+                    return Err(InflateError::InvalidDistance);
+                }
+                
+                // قد يتداخل المصدر مع الهدف، لذا النسخ بايتاً بايتاً
+                let start = output.len() - distance;
+                for i in 0..length {
+                    let byte = output[start + i];
+                    output.push(byte);
+                }
+            }
+        }
+    }
+}
+
+/// فك تدفق DEFLATE خام؛ يعيد البيانات وعدد البايتات المستهلكة
+fn inflate_stream(data: &[u8], size_hint: usize) -> Result<(Vec<u8>, usize), InflateError> {
+    let mut reader = BitReader::new(data);
+    let mut output = Vec::with_capacity(size_hint.min(data.len().saturating_mul(MAX_PREALLOC_RATIO)));
+    
+    loop {
+        let is_final = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let header = reader.read_bytes(4)?;
+                let length = u16::from_le_bytes([header[0], header[1]]);
+                let complement = u16::from_le_bytes([header[2], header[3]]);
+                if length != !complement {
+                    return Err(InflateError::InvalidStoredLength);
+                }
+                output.extend_from_slice(reader.read_bytes(length as usize)?);
+            }
+            1 => {
+                let (literals, distances) = fixed_tables()?;
+                inflate_codes(&mut reader, &mut output, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_tables(&mut reader)?;
+                inflate_codes(&mut reader, &mut output, &literals, &distances)?;
+            }
+            _ => return Err(InflateError::InvalidBlockType),
+        }
+        
+        if is_final {
+            break;
+        }
+    }
+    
+    Ok((output, reader.position))
+}
+
+/// فك تدفق DEFLATE خام
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, InflateError> {
+    inflate_stream(data, data.len() * 4).map(|(output, _)| output)
+}
+
+/// هل تبدأ البيانات بتوقيع gzip؟
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.len() >= 2 && data[0] == 0x1f && data[1] == 0x8b
+}
+
+/// فك ملف gzip مع التحقق من CRC32 والحجم
+pub fn gunzip(data: &[u8]) -> Result<Vec<u8>, InflateError> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+    
+    if data.len() < 18 || !is_gzip(data) {
+        return Err(InflateError::InvalidHeader);
+    }
+    if data[2] != 8 {
+        return Err(InflateError::UnsupportedMethod(data[2]));
+    }
+    
+    let flags = data[3];
+    let mut offset = 10;
+    
+    if flags & FEXTRA != 0 {
+        let extra = data.get(offset..offset + 2).ok_or(InflateError::UnexpectedEof)?;
+        offset += 2 + u16::from_le_bytes([extra[0], extra[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = data.get(offset..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or(InflateError::UnexpectedEof)?;
+            offset += end + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        offset += 2;
+    }
+    
+    let body = data.get(offset..data.len() - 8).ok_or(InflateError::UnexpectedEof)?;
+    let trailer = &data[data.len() - 8..];
+    let expected_crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let expected_size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    
+    let (output, _) = inflate_stream(body, expected_size as usize)?;
+    
+    if output.len() as u32 != expected_size {
+        return Err(InflateError::SizeMismatch);
+    }
+    if crc32(&output) != expected_crc {
+        return Err(InflateError::ChecksumMismatch);
+    }
+    
+    Ok(output)
+}
+
+/// جدول CRC32 (متعدد الحدود 0xEDB88320)
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// حساب CRC32 كما في gzip
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// كتلة مخزنة واحدة تحوي "abc" مع ذيل يعلن `size`
+    fn stored_gzip(size: u32) -> Vec<u8> {
+        let mut image = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+        image.extend_from_slice(&[1, 3, 0, 0xfc, 0xff]);
+        image.extend_from_slice(b"abc");
+        image.extend_from_slice(&crc32(b"abc").to_le_bytes());
+        image.extend_from_slice(&size.to_le_bytes());
+        image
+    }
+    
+    #[test_case]
+    fn test_gunzip_ignores_forged_size_hint() {
+        assert_eq!(gunzip(&stored_gzip(3)).unwrap(), b"abc");
+        // 4 GiB معلنة لثلاثة بايتات: تُرفض دون محاولة حجزها
+        assert_eq!(gunzip(&stored_gzip(u32::MAX)), Err(InflateError::SizeMismatch));
+    }
+}
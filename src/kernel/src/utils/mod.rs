@@ -0,0 +1,19 @@
+//! 🧰 أدوات مساعدة للنواة
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+
+pub mod ed25519;
+pub mod inflate;
+pub mod sha256;
+pub mod sha512;
+
+/// فك سلسلة hex بطول 2N بالضبط إلى N بايت
+pub fn decode_hex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    if text.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(text.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
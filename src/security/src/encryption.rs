@@ -0,0 +1,63 @@
+//! 🔐 مفاتيح محرك التشفير
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! `EncryptionEngine` في `haris_core`؛ هنا البيانات المشفرة والمفاتيح ومصدر
+//! العشوائية. المصدر الوحيد RDRAND: بدونه لا مفتاح ولا تشفير، ولا بديل
+//! مصنوع من عداد أو ساعة.
+
+use alloc::vec::Vec;
+
+use crate::haris_core::HARIS_SYSTEM;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionAlgorithm {
+    Aes256Gcm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionStatus {
+    Strong,
+    /// لا مفتاح نشط (لا مصدر عشوائية في المعالج)
+    NoKey,
+}
+
+/// نص مشفر ومعرف مفتاحه؛ `iv` جديد لكل تشفير
+#[derive(Debug, Clone)]
+pub struct EncryptedData {
+    pub ciphertext: Vec<u8>,
+    pub key_id: u64,
+    pub algorithm: EncryptionAlgorithm,
+    pub iv: Vec<u8>,
+}
+
+pub struct EncryptionKey {
+    pub id: u64,
+    pub value: Vec<u8>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// ملء `buf` من RDRAND؛ `false` إن لم يتوفر أو لم يُرجع قيمة
+pub fn fill_random(buf: &mut [u8]) -> bool {
+    use core::arch::x86_64::{__cpuid, _rdrand64_step};
+    
+    // CPUID.1:ECX[30]
+    if __cpuid(1).ecx & (1 << 30) == 0 {
+        return false;
+    }
+    
+    for chunk in buf.chunks_mut(8) {
+        let mut value = 0;
+        // التوصية: إعادة المحاولة عشر مرات قبل عدّ الفشل
+        if !(0..10).any(|_| unsafe { _rdrand64_step(&mut value) } == 1) {
+            return false;
+        }
+        chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+    }
+    true
+}
+
+/// التدوير الدوري: مفتاح جديد حين تنقضي مدة المفتاح الحالي
+pub fn rotate_keys() {
+    HARIS_SYSTEM.lock().encryption_engine.rotate_if_due(crate::time::current_timestamp());
+}
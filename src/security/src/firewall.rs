@@ -0,0 +1,93 @@
+//! 🔥 قواعد جدار حماية إسلام وحزمه
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! `Firewall` نفسه في `haris_core`؛ هنا القاعدة والحزمة التي تُطابق عليها.
+
+use alloc::string::String;
+
+/// ما تفعله القاعدة بالحزمة أو التحويل المطابق
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    Allow,
+    Block,
+    /// قفل النظام (رصيد التوكنات)
+    BlockSystem,
+    RequireApproval,
+    BlockAndAlert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Any,
+    TCP,
+    UDP,
+    ICMP,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// مستوى تسجيل الحزم المرفوضة
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirewallStatus {
+    Active,
+    Inactive,
+}
+
+/// طرف اتصال IPv4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoint {
+    pub address: [u8; 4],
+    pub port: u16,
+}
+
+impl Endpoint {
+    pub fn ip(&self) -> String {
+        let [a, b, c, d] = self.address;
+        alloc::format!("{}.{}.{}.{}", a, b, c, d)
+    }
+}
+
+/// حزمة كما يراها جدار الحماية
+#[derive(Debug, Clone)]
+pub struct NetworkPacket {
+    pub source: Endpoint,
+    pub destination: Endpoint,
+    pub protocol: Protocol,
+    pub direction: Direction,
+}
+
+pub struct FirewallRule {
+    pub name: String,
+    pub action: RuleAction,
+    pub protocol: Protocol,
+    /// منافذ الوجهة (شاملة)
+    pub port_range: Option<(u16, u16)>,
+    /// عناوين المصدر (شاملة)
+    pub ip_range: Option<([u8; 4], [u8; 4])>,
+    pub direction: Direction,
+}
+
+impl FirewallRule {
+    pub fn matches(&self, packet: &NetworkPacket) -> bool {
+        let port = packet.destination.port;
+        let source = packet.source.address;
+        self.direction == packet.direction
+            && (self.protocol == Protocol::Any || self.protocol == packet.protocol)
+            && self.port_range.is_none_or(|(first, last)| (first..=last).contains(&port))
+            && self.ip_range.is_none_or(|(first, last)| (first..=last).contains(&source))
+    }
+}
+
+/// التحديث الدوري (القواعد ثابتة بعد، فلا عمل له)
+pub fn update() {}
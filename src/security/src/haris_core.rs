@@ -5,14 +5,21 @@ use alloc::collections::{BTreeSet, BTreeMap};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use spin::Mutex;
-use sha2::{Sha256, Sha512, Digest};
 use aes_gcm::{Aes256Gcm, KeyInit, aead::{Aead, Key, Nonce}};
-use rand_core::{RngCore, OsRng};
+
+use crate::encryption::{fill_random, EncryptedData, EncryptionAlgorithm, EncryptionKey, EncryptionStatus};
+use crate::firewall::{Direction, FirewallRule, FirewallStatus, LogLevel, NetworkPacket, Protocol, RuleAction};
+use crate::ids::{IntrusionAlert, IntrusionDetectionSystem};
 
 lazy_static! {
     pub static ref HARIS_SYSTEM: Mutex<HarisSecurity> = Mutex::new(HarisSecurity::new());
 }
 
+/// تفعيل الحارس عند الإقلاع (الدفاعات وجدار الحماية والمفاتيح الأولى)
+pub fn activate() {
+    lazy_static::initialize(&HARIS_SYSTEM);
+}
+
 pub struct HarisSecurity {
     pub name: String,
     pub version: String,
@@ -25,6 +32,12 @@ pub struct HarisSecurity {
     pub token_protection: TokenProtection,
 }
 
+impl Default for HarisSecurity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HarisSecurity {
     pub fn new() -> Self {
         let mut system = Self {
@@ -103,6 +116,18 @@ impl HarisSecurity {
         true
     }
     
+    /// التهديدات النشطة من تنبيهات كشف التسلل
+    fn detect_active_threats(&self) -> Vec<ActiveThreat> {
+        self.intrusion_detection.get_alerts().into_iter()
+            .map(|alert| ActiveThreat { source: alert.source })
+            .collect()
+    }
+    
+    fn send_alert(&mut self, packet: &NetworkPacket) {
+        log::error!("🚨 إنذار أمني: حظر {} بعد محاولة تسلل", packet.source.ip());
+        self.audit_log.record(alloc::format!("intrusion\t{}", packet.source.ip()));
+    }
+    
     fn respond_to_intrusion(&mut self, packet: &NetworkPacket) {
         log::warn!("🚨 تم اكتشاف محاولة تسلل من: {:?}", packet.source);
         
@@ -116,6 +141,12 @@ impl HarisSecurity {
         self.send_alert(packet);
     }
     
+    /// الدفاعات الدائمة
+    fn activate_defenses(&mut self) {
+        self.active_defenses.push(ActiveDefense::ProtocolValidation);
+        self.active_defenses.push(ActiveDefense::RateLimiting);
+    }
+    
     fn activate_emergency_defenses(&mut self) {
         self.threat_level = ThreatLevel::Critical;
         
@@ -130,6 +161,16 @@ impl HarisSecurity {
     pub fn protect_token_transaction(&self, transaction: &TokenTransaction) -> bool {
         self.token_protection.validate_transaction(transaction)
     }
+    
+    /// بلاغ من مراقب صحة النواة: الحالة الحرجة ترفع مستوى التهديد إلى High
+    pub fn report_health_alert(&mut self, check: &str, critical: bool, message: &str) {
+        log::warn!("🛡️ بلاغ صحة من النواة [{}]: {}", check, message);
+        
+        if critical && matches!(self.threat_level, ThreatLevel::Low | ThreatLevel::Medium) {
+            self.threat_level = ThreatLevel::High;
+            log::warn!("🛡️ رفع مستوى التهديد إلى {:?}", self.threat_level);
+        }
+    }
 }
 
 pub struct Firewall {
@@ -140,6 +181,12 @@ pub struct Firewall {
     log_level: LogLevel,
 }
 
+impl Default for Firewall {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Firewall {
     pub fn new() -> Self {
         Self {
@@ -234,9 +281,16 @@ impl Firewall {
         }
         
         // الافتراضي: رفض
+        if self.log_level >= LogLevel::Medium {
+            log::debug!("🔥 رفض {:?} من {} (لا قاعدة مطابقة)", packet.protocol, packet.source.ip());
+        }
         false
     }
     
+    pub fn get_status(&self) -> FirewallStatus {
+        if self.is_active { FirewallStatus::Active } else { FirewallStatus::Inactive }
+    }
+    
     pub fn block_ip(&mut self, ip: String) {
         self.blocked_ips.insert(ip);
     }
@@ -244,19 +298,45 @@ impl Firewall {
     pub fn allow_ip(&mut self, ip: String) {
         self.allowed_ips.insert(ip);
     }
+    
+    /// القواعد والقوائم بترتيب التطبيق، سطر لكل منها
+    pub fn ruleset(&self) -> String {
+        let mut out = alloc::format!("active={}\n", self.is_active);
+        for ip in &self.blocked_ips {
+            out.push_str(&alloc::format!("block\tip={}\n", ip));
+        }
+        for ip in &self.allowed_ips {
+            out.push_str(&alloc::format!("allow\tip={}\n", ip));
+        }
+        for rule in &self.rules {
+            let ports = match rule.port_range {
+                Some((first, last)) => alloc::format!("{}-{}", first, last),
+                None => "*".to_string(),
+            };
+            out.push_str(&alloc::format!(
+                "{:?}\t{:?}\t{:?}\tports={}\t{}\n",
+                rule.action, rule.direction, rule.protocol, ports, rule.name,
+            ));
+        }
+        out
+    }
 }
 
 pub struct EncryptionEngine {
-    master_key: [u8; 32],
     key_rotation_interval: u64,
     last_rotation: u64,
     active_keys: BTreeMap<u64, EncryptionKey>,
 }
 
+impl Default for EncryptionEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EncryptionEngine {
     pub fn new() -> Self {
         Self {
-            master_key: [0; 32],
             key_rotation_interval: 86400, // يوم واحد بالثواني
             last_rotation: 0,
             active_keys: BTreeMap::new(),
@@ -264,14 +344,13 @@ impl EncryptionEngine {
     }
     
     pub fn initialize(&mut self) {
-        // توليد مفتاح رئيسي عشوائي
-        let mut rng = OsRng;
-        rng.fill_bytes(&mut self.master_key);
-        
         // توليد المفاتيح الأولية
         self.generate_new_key();
         
-        log::info!("🔐 تم تهيئة محرك التشفير الإسلامي");
+        match self.get_status() {
+            EncryptionStatus::Strong => log::info!("🔐 تم تهيئة محرك التشفير الإسلامي"),
+            EncryptionStatus::NoKey => log::warn!("🔐 لا RDRAND في المعالج: التشفير معطل"),
+        }
     }
     
     pub fn encrypt(&self, data: &[u8]) -> Result<EncryptedData, SecurityError> {
@@ -280,38 +359,75 @@ impl EncryptionEngine {
             .ok_or(SecurityError::KeyNotFound)?;
         
         let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key.value));
-        let nonce = Nonce::from_slice(&key.nonce);
         
-        let ciphertext = cipher.encrypt(nonce, data)
+        // GCM لا يحتمل تكرار nonce مع المفتاح نفسه
+        let mut iv = [0u8; 12];
+        if !fill_random(&mut iv) {
+            return Err(SecurityError::EncryptionFailed);
+        }
+        
+        let ciphertext = cipher.encrypt(Nonce::<Aes256Gcm>::from_slice(&iv), data)
             .map_err(|_| SecurityError::EncryptionFailed)?;
         
         Ok(EncryptedData {
             ciphertext,
             key_id,
             algorithm: EncryptionAlgorithm::Aes256Gcm,
-            iv: key.nonce.to_vec(),
+            iv: iv.to_vec(),
         })
     }
     
+    pub fn decrypt(&self, encrypted: &EncryptedData) -> Result<Vec<u8>, SecurityError> {
+        let key = self.active_keys.get(&encrypted.key_id)
+            .ok_or(SecurityError::KeyNotFound)?;
+        if encrypted.iv.len() != 12 {
+            return Err(SecurityError::DecryptionFailed);
+        }
+        
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key.value));
+        cipher.decrypt(Nonce::<Aes256Gcm>::from_slice(&encrypted.iv), encrypted.ciphertext.as_slice())
+            .map_err(|_| SecurityError::DecryptionFailed)
+    }
+    
+    /// أحدث مفتاح
+    fn get_current_key_id(&self) -> u64 {
+        self.active_keys.keys().next_back().copied().unwrap_or(0)
+    }
+    
+    pub fn get_status(&self) -> EncryptionStatus {
+        if self.active_keys.is_empty() { EncryptionStatus::NoKey } else { EncryptionStatus::Strong }
+    }
+    
+    /// مفتاح جديد بعد انقضاء المدة؛ المفتاح السابق يبقى مدة أخرى لفك ما شُفر به
+    pub fn rotate_if_due(&mut self, now: u64) {
+        if now < self.last_rotation + self.key_rotation_interval {
+            return;
+        }
+        self.generate_new_key();
+        self.active_keys.retain(|_, key| key.expires_at + self.key_rotation_interval > now);
+    }
+    
+    /// المفتاح من RDRAND وحده؛ بدونه لا يُضاف مفتاح
     fn generate_new_key(&mut self) {
-        let mut rng = OsRng;
         let mut key_value = [0u8; 32];
-        let mut nonce = [0u8; 12];
-        
-        rng.fill_bytes(&mut key_value);
-        rng.fill_bytes(&mut nonce);
+        if !fill_random(&mut key_value) {
+            return;
+        }
         
-        let key_id = crate::time::current_timestamp();
+        let now = crate::time::current_timestamp();
+        let key_id = match self.active_keys.keys().next_back() {
+            Some(&last) => now.max(last + 1),
+            None => now,
+        };
         let key = EncryptionKey {
             id: key_id,
             value: key_value.to_vec(),
-            nonce: nonce.to_vec(),
-            created_at: key_id,
-            expires_at: key_id + self.key_rotation_interval,
+            created_at: now,
+            expires_at: now + self.key_rotation_interval,
         };
         
-        self.active_keys.insert(key_id, key);
-        self.last_rotation = key_id;
+        self.active_keys.insert(key.id, key);
+        self.last_rotation = now;
     }
 }
 
@@ -322,6 +438,12 @@ pub struct TokenProtection {
     transaction_monitor: TransactionMonitor,
 }
 
+impl Default for TokenProtection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenProtection {
     pub fn new() -> Self {
         Self {
@@ -334,7 +456,11 @@ impl TokenProtection {
     
     pub fn activate(&mut self) {
         self.add_security_rules();
-        log::info!("💰 تم تفعيل حماية توكنات {} الإسلامي", crate::TOKEN_NAME);
+        log::info!("💰 تم تفعيل حماية توكنات {} الإسلامي ({})", crate::TOKEN_NAME, self.contract_address);
+    }
+    
+    pub fn check_security(&self) -> TokenSecurityStatus {
+        if self.security_rules.is_empty() { TokenSecurityStatus::Unprotected } else { TokenSecurityStatus::Secure }
     }
     
     fn add_security_rules(&mut self) {
@@ -372,7 +498,7 @@ impl TokenProtection {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThreatLevel {
     Low,
     Medium,
@@ -399,6 +525,12 @@ pub struct SecurityReport {
     pub recommendations: Vec<Recommendation>,
 }
 
+impl Default for SecurityReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SecurityReport {
     pub fn new() -> Self {
         Self {
@@ -421,4 +553,109 @@ pub enum SecurityError {
     InvalidSignature,
     RuleViolation,
     TokenInsufficient,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSecurityStatus {
+    Secure,
+    /// القواعد لم تُفعّل بعد
+    Unprotected,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActiveThreat {
+    pub source: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub text: String,
+}
+
+/// سجل تدقيق الأمن (آخر `MAX_AUDIT_ENTRIES` حدثاً)
+#[derive(Default)]
+pub struct AuditLog {
+    entries: Vec<String>,
+}
+
+const MAX_AUDIT_ENTRIES: usize = 256;
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn record(&mut self, entry: String) {
+        if self.entries.len() == MAX_AUDIT_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+    }
+    
+    pub fn log_blocked_packet(&mut self, packet: &NetworkPacket) {
+        self.record(alloc::format!("blocked\t{}\t{:?}\t{}", packet.source.ip(), packet.protocol, packet.destination.port));
+    }
+    
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+}
+
+/// تحويل توكنات يُعرض على قواعد الحماية
+#[derive(Debug, Clone)]
+pub struct TokenTransaction {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    /// رصيد المرسل قبل التحويل
+    pub balance: u64,
+    /// وافق المستخدم على التحويل الكبير
+    pub approved: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RuleCondition {
+    /// الرصيد بعد التحويل أقل من الحد
+    BalanceLessThan(u32),
+    TransferGreaterThan(u64),
+    /// تحويل صفري أو إلى المرسل نفسه
+    SuspiciousPattern,
+}
+
+pub struct TokenRule {
+    pub name: String,
+    pub condition: RuleCondition,
+    pub action: RuleAction,
+}
+
+impl TokenRule {
+    /// `false` إن طابق الشرط وكان الإجراء يمنع التحويل
+    pub fn validate(&self, transaction: &TokenTransaction) -> bool {
+        let triggered = match self.condition {
+            RuleCondition::BalanceLessThan(min) => transaction.balance.saturating_sub(transaction.amount) < u64::from(min),
+            RuleCondition::TransferGreaterThan(limit) => transaction.amount > limit,
+            RuleCondition::SuspiciousPattern => transaction.amount == 0 || transaction.from == transaction.to,
+        };
+        
+        match self.action {
+            _ if !triggered => true,
+            RuleAction::Allow => true,
+            RuleAction::RequireApproval => transaction.approved,
+            RuleAction::Block | RuleAction::BlockSystem | RuleAction::BlockAndAlert => false,
+        }
+    }
+}
+
+/// تسجيل التحويلات المقبولة
+#[derive(Default)]
+pub struct TransactionMonitor;
+
+impl TransactionMonitor {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    pub fn monitor(&self, transaction: &TokenTransaction) {
+        log::debug!("💰 تحويل: {} ← {} {}", transaction.to, transaction.from, transaction.amount);
+    }
+}
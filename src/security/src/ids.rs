@@ -0,0 +1,42 @@
+//! 👁️ كشف التسلل
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! هيكل فقط: لا قواعد كشف بعد، فكل حزمة تمر ولا تُسجل تنبيهات.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::firewall::NetworkPacket;
+
+#[derive(Debug, Clone)]
+pub struct IntrusionAlert {
+    pub source: String,
+    pub timestamp: u64,
+}
+
+#[derive(Default)]
+pub struct IntrusionDetectionSystem {
+    alerts: Vec<IntrusionAlert>,
+}
+
+impl IntrusionDetectionSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn start(&mut self) {
+        log::info!("👁️ كشف التسلل: لا قواعد بعد");
+    }
+    
+    /// `true` إن كانت الحزمة محاولة تسلل
+    pub fn analyze_packet(&mut self, _packet: &NetworkPacket) -> bool {
+        false
+    }
+    
+    pub fn get_alerts(&self) -> Vec<IntrusionAlert> {
+        self.alerts.clone()
+    }
+}
+
+/// الفحص الدوري (لا عمل له بعد)
+pub fn scan() {}
@@ -0,0 +1,29 @@
+//! 🛡️ حارس إسلام (Haris Islam) - نظام الأمن لنظام تشغيل إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! مكتبة `no_std` تربطها النواة وتستدعي مهامها الدورية:
+//!
+//! ```text
+//! haris_core::activate()        عند الإقلاع
+//! firewall::update() و ids::scan() و encryption::rotate_keys()
+//! ```
+//!
+//! `firewall` و `ids` و `encryption` و `time` هياكل دنيا تكفي لبناء
+//! `haris_core`؛ لا كشف تسلل ولا قواعد ديناميكية فيها بعد.
+
+#![no_std]
+
+extern crate alloc;
+#[macro_use]
+extern crate lazy_static;
+
+pub mod encryption;
+pub mod firewall;
+pub mod haris_core;
+pub mod ids;
+pub mod time;
+
+/// عقد توكن INSAN ورصيده الأدنى (كما في النواة)
+pub const CONTRACT_ADDRESS: &str = "0xa23D57f128Df2517517CA0c195C5159d81324711";
+pub const TOKEN_NAME: &str = "INSAN";
+pub const MIN_TOKENS: u32 = 100;
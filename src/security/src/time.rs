@@ -0,0 +1,19 @@
+//! 🕒 ساعة حارس إسلام
+//! المبرمج والمطور: إسلام بن الحسن - Islam Bin El-Hassan (I-H)
+//!
+//! المكتبة لا تملك مؤقتاً؛ النواة تضبط الساعة بالثواني منذ الإقلاع، ومنها
+//! تُحسب أعمار المفاتيح.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static NOW: AtomicU64 = AtomicU64::new(0);
+
+/// الثواني منذ الإقلاع كما ضبطتها النواة آخر مرة
+pub fn current_timestamp() -> u64 {
+    NOW.load(Ordering::Relaxed)
+}
+
+/// ضبط الساعة (لا تعود إلى الوراء)
+pub fn set_current_timestamp(seconds: u64) {
+    NOW.fetch_max(seconds, Ordering::Relaxed);
+}